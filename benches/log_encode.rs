@@ -0,0 +1,131 @@
+//! raft日志内容编码对比：json与MessagePack的日志大小、编码及解码(应用日志)耗时
+//!
+//! cargo bench --bench log_encode
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use async_raft_ext::raft::{Entry, EntryNormal, EntryPayload};
+use ratchjob::job::model::actor_model::JobManagerRaftReq;
+use ratchjob::raft::store::model::LogRecordDto;
+use ratchjob::raft::store::{ClientRequest, LogValueFormat, StoreUtils};
+use ratchjob::schedule::model::actor_model::ScheduleManagerRaftReq;
+use ratchjob::task::model::enum_type::TaskStatusType;
+use ratchjob::task::model::task::{JobTaskInfo, TaskCallBackParam, TaskTryLog};
+
+const ENTRY_COUNT: u64 = 20000;
+const BATCH_SIZE: u64 = 20;
+
+fn build_task(task_id: u64) -> Arc<JobTaskInfo> {
+    let addr = Arc::new(format!("192.168.1.{}:9999", task_id % 200));
+    Arc::new(JobTaskInfo {
+        task_id,
+        job_id: task_id % 500,
+        trigger_time: 1735660800 + task_id as u32,
+        instance_addr: addr.clone(),
+        trigger_message: Arc::new("trigger by cron".to_owned()),
+        status: TaskStatusType::Running,
+        trigger_from: Arc::new("SYSTEM".to_owned()),
+        try_times: 1,
+        try_logs: vec![TaskTryLog {
+            execution_time: 1735660800 + task_id as u32,
+            addr,
+        }],
+        timeout_second: 60,
+        namespace: Arc::new("dev".to_owned()),
+        app_name: Arc::new("xxl-job-executor-sample".to_owned()),
+        ..Default::default()
+    })
+}
+
+fn build_entries() -> Vec<Entry<ClientRequest>> {
+    (0..ENTRY_COUNT)
+        .map(|index| {
+            let start = index * BATCH_SIZE;
+            let data = if index % 2 == 0 {
+                ClientRequest::JobReq {
+                    req: JobManagerRaftReq::UpdateTaskList(
+                        (start..start + BATCH_SIZE).map(build_task).collect(),
+                    ),
+                }
+            } else {
+                ClientRequest::ScheduleReq {
+                    req: ScheduleManagerRaftReq::TaskCallBacks(
+                        (start..start + BATCH_SIZE)
+                            .map(|task_id| TaskCallBackParam {
+                                task_id,
+                                task_date_time: 1735660800000 + task_id as i64,
+                                success: true,
+                                handle_msg: Some(Arc::new("ok".to_owned())),
+                                result: None,
+                                instance_addr: None,
+                            })
+                            .collect(),
+                    ),
+                }
+            };
+            Entry {
+                term: 1,
+                index: index + 1,
+                payload: EntryPayload::Normal(EntryNormal { data }),
+            }
+        })
+        .collect()
+}
+
+fn to_json_record(entry: &Entry<ClientRequest>) -> LogRecordDto {
+    LogRecordDto {
+        index: entry.index,
+        term: entry.term,
+        value: serde_json::to_vec(&entry.payload).unwrap(),
+    }
+}
+
+fn run<T>(f: impl Fn() -> T) -> (T, Duration) {
+    let start = Instant::now();
+    let v = f();
+    (v, start.elapsed())
+}
+
+fn print_line(name: &str, bytes: usize, encode: Duration, decode: Duration) {
+    let count = ENTRY_COUNT as f64;
+    println!(
+        "{:<8} bytes:{:>10} avg:{:>6}B encode:{:>10.0}/s decode(apply):{:>10.0}/s",
+        name,
+        bytes,
+        bytes / ENTRY_COUNT as usize,
+        count / encode.as_secs_f64(),
+        count / decode.as_secs_f64(),
+    );
+}
+
+fn main() {
+    let entries = build_entries();
+    let (json_records, json_encode) =
+        run(|| entries.iter().map(to_json_record).collect::<Vec<_>>());
+    let (records, encode) = run(|| {
+        entries
+            .iter()
+            .map(|e| StoreUtils::entry_to_record(e, LogValueFormat::MsgpackV1).unwrap())
+            .collect::<Vec<_>>()
+    });
+    let json_bytes: usize = json_records.iter().map(|e| e.value.len()).sum();
+    let bytes: usize = records.iter().map(|e| e.value.len()).sum();
+    // 旧的json日志同样通过log_record_to_entry读取
+    let (_, json_decode) = run(|| {
+        for record in json_records.clone() {
+            StoreUtils::log_record_to_entry(record).unwrap();
+        }
+    });
+    let (_, decode) = run(|| {
+        for record in records.clone() {
+            StoreUtils::log_record_to_entry(record).unwrap();
+        }
+    });
+    println!("entries:{}, {} tasks per entry", ENTRY_COUNT, BATCH_SIZE);
+    print_line("json", json_bytes, json_encode, json_decode);
+    print_line("msgpack", bytes, encode, decode);
+    println!(
+        "log bytes reduced:{:.1}%",
+        (1.0 - bytes as f64 / json_bytes as f64) * 100.0
+    );
+}
@@ -0,0 +1,95 @@
+use crate::agent::model::{AgentConfig, REGISTRY_GROUP, XXL_ACCESS_TOKEN_HEADER};
+use crate::common::get_app_version;
+use crate::common::http_utils::HttpUtils;
+use crate::openapi::xxljob::model::server_request::{CallbackParam, RegistryParam};
+use crate::openapi::xxljob::model::XxlApiResult;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// 调用调度中心xxl-job接口,多个地址时依次尝试
+pub struct AdminClient {
+    client: reqwest::Client,
+    headers: HashMap<String, String>,
+    config: Arc<AgentConfig>,
+}
+
+impl AdminClient {
+    pub fn new(config: Arc<AgentConfig>) -> Self {
+        let mut headers = HashMap::new();
+        headers.insert("Content-Type".to_string(), "application/json".to_string());
+        headers.insert(
+            "User-Agent".to_owned(),
+            format!("ratch-job-agent/{}", get_app_version()),
+        );
+        if !config.access_token.is_empty() {
+            headers.insert(
+                XXL_ACCESS_TOKEN_HEADER.to_owned(),
+                config.access_token.clone(),
+            );
+        }
+        Self {
+            client: reqwest::Client::new(),
+            headers,
+            config,
+        }
+    }
+
+    fn build_registry_param(&self) -> RegistryParam {
+        RegistryParam {
+            registry_group: Arc::new(REGISTRY_GROUP.to_owned()),
+            registry_key: Arc::new(self.config.app_name.clone()),
+            registry_value: Arc::new(self.config.address.clone()),
+        }
+    }
+
+    pub async fn registry(&self) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(&self.build_registry_param())?;
+        self.request("/api/registry", body).await
+    }
+
+    pub async fn registry_remove(&self) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(&self.build_registry_param())?;
+        self.request("/api/registryRemove", body).await
+    }
+
+    pub async fn callback(&self, params: Vec<CallbackParam>) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(&params)?;
+        self.request("/api/callback", body).await
+    }
+
+    async fn request(&self, sub_url: &str, body: Vec<u8>) -> anyhow::Result<()> {
+        let mut last_err = anyhow::anyhow!("admin addresses is empty");
+        for addr in &self.config.admin_addresses {
+            let url = format!("{}{}", addr.trim_end_matches('/'), sub_url);
+            match HttpUtils::request(
+                &self.client,
+                "POST",
+                &url,
+                body.clone(),
+                Some(&self.headers),
+                Some(3000),
+            )
+            .await
+            {
+                Ok(resp) => {
+                    let result: anyhow::Result<XxlApiResult<String>> =
+                        serde_json::from_slice(&resp.body).map_err(|e| e.into());
+                    match result {
+                        Ok(v) if v.is_success() => return Ok(()),
+                        _ => {
+                            last_err = anyhow::anyhow!(
+                                "call admin error,url:{},resp:{}",
+                                &url,
+                                resp.get_lossy_string_body()
+                            );
+                        }
+                    }
+                }
+                Err(err) => {
+                    last_err = err;
+                }
+            }
+        }
+        Err(last_err)
+    }
+}
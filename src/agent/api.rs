@@ -0,0 +1,131 @@
+use crate::agent::core::AgentTaskManager;
+use crate::agent::model::{
+    AgentConfig, AgentTaskReq, AgentTaskResult, IdleBeatParam, KillParam, LogParam,
+    XXL_ACCESS_TOKEN_HEADER,
+};
+use crate::agent::task_log::TaskLog;
+use crate::openapi::xxljob::model::{xxl_api_empty_success, XxlApiResult};
+use crate::task::model::request_model::JobRunParam;
+use actix::Addr;
+use actix_web::web::{Data, ServiceConfig};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub struct AgentShareData {
+    pub config: Arc<AgentConfig>,
+    pub task_manager: Addr<AgentTaskManager>,
+}
+
+pub fn agent_api_config(config: &mut ServiceConfig) {
+    config
+        .service(web::resource("/beat").route(web::post().to(beat)))
+        .service(web::resource("/idleBeat").route(web::post().to(idle_beat)))
+        .service(web::resource("/run").route(web::post().to(run)))
+        .service(web::resource("/kill").route(web::post().to(kill)))
+        .service(web::resource("/log").route(web::post().to(log)));
+}
+
+fn check_token(req: &HttpRequest, config: &AgentConfig) -> Option<HttpResponse> {
+    if config.access_token.is_empty() {
+        return None;
+    }
+    let token = req
+        .headers()
+        .get(XXL_ACCESS_TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .unwrap_or_default();
+    if token == config.access_token {
+        None
+    } else {
+        Some(HttpResponse::Ok().json(XxlApiResult::<()>::fail(Some(
+            "The access token is wrong.".to_owned(),
+        ))))
+    }
+}
+
+fn fail_response(msg: String) -> HttpResponse {
+    HttpResponse::Ok().json(XxlApiResult::<()>::fail(Some(msg)))
+}
+
+pub(crate) async fn beat(req: HttpRequest, data: Data<Arc<AgentShareData>>) -> impl Responder {
+    if let Some(resp) = check_token(&req, &data.config) {
+        return resp;
+    }
+    HttpResponse::Ok().json(xxl_api_empty_success())
+}
+
+pub(crate) async fn idle_beat(
+    req: HttpRequest,
+    data: Data<Arc<AgentShareData>>,
+    web::Json(param): web::Json<IdleBeatParam>,
+) -> impl Responder {
+    if let Some(resp) = check_token(&req, &data.config) {
+        return resp;
+    }
+    match data
+        .task_manager
+        .send(AgentTaskReq::IdleBeat(param.job_id))
+        .await
+    {
+        Ok(Ok(AgentTaskResult::Running(false))) => HttpResponse::Ok().json(xxl_api_empty_success()),
+        Ok(Ok(_)) => fail_response("job thread is running or has trigger queue.".to_owned()),
+        _ => fail_response("idle beat error".to_owned()),
+    }
+}
+
+pub(crate) async fn run(
+    req: HttpRequest,
+    data: Data<Arc<AgentShareData>>,
+    web::Json(param): web::Json<JobRunParam>,
+) -> impl Responder {
+    if let Some(resp) = check_token(&req, &data.config) {
+        return resp;
+    }
+    match data.task_manager.send(AgentTaskReq::Run(param)).await {
+        Ok(Ok(_)) => HttpResponse::Ok().json(xxl_api_empty_success()),
+        Ok(Err(err)) => fail_response(err.to_string()),
+        Err(err) => fail_response(err.to_string()),
+    }
+}
+
+pub(crate) async fn kill(
+    req: HttpRequest,
+    data: Data<Arc<AgentShareData>>,
+    web::Json(param): web::Json<KillParam>,
+) -> impl Responder {
+    if let Some(resp) = check_token(&req, &data.config) {
+        return resp;
+    }
+    match data
+        .task_manager
+        .send(AgentTaskReq::Kill(param.job_id))
+        .await
+    {
+        Ok(Ok(_)) => HttpResponse::Ok().json(xxl_api_empty_success()),
+        Ok(Err(err)) => fail_response(err.to_string()),
+        Err(err) => fail_response(err.to_string()),
+    }
+}
+
+pub(crate) async fn log(
+    req: HttpRequest,
+    data: Data<Arc<AgentShareData>>,
+    web::Json(param): web::Json<LogParam>,
+) -> impl Responder {
+    if let Some(resp) = check_token(&req, &data.config) {
+        return resp;
+    }
+    let running = matches!(
+        data.task_manager
+            .send(AgentTaskReq::IsRunning(param.log_id))
+            .await,
+        Ok(Ok(AgentTaskResult::Running(true)))
+    );
+    let path = TaskLog::log_path(&data.config, param.log_date_time, param.log_id);
+    let from_line_num = param.from_line_num;
+    match web::block(move || TaskLog::read(&path, from_line_num, !running)).await {
+        Ok(Ok(result)) => HttpResponse::Ok().json(XxlApiResult::success(Some(result))),
+        Ok(Err(err)) => fail_response(err.to_string()),
+        Err(err) => fail_response(err.to_string()),
+    }
+}
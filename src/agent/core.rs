@@ -0,0 +1,363 @@
+use crate::agent::admin_client::AdminClient;
+use crate::agent::model::{AgentConfig, AgentTaskReq, AgentTaskResult, ScriptType, TaskRunResult};
+use crate::agent::task_log::TaskLog;
+use crate::common::datetime_utils::now_millis_i64;
+use crate::openapi::xxljob::model::server_request::CallbackParam;
+use crate::openapi::xxljob::model::{FAIL_CODE, SUCCESS_CODE};
+use crate::task::model::request_model::JobRunParam;
+use actix::prelude::*;
+use std::collections::{HashMap, VecDeque};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+const BLOCK_DISCARD_LATER: &str = "DISCARD_LATER";
+const BLOCK_COVER_EARLY: &str = "COVER_EARLY";
+const CALLBACK_RETRY_TIMES: u32 = 3;
+
+struct RunningTask {
+    log_id: u64,
+    kill_sender: Option<oneshot::Sender<String>>,
+}
+
+impl RunningTask {
+    fn kill(&mut self, reason: String) {
+        if let Some(sender) = self.kill_sender.take() {
+            sender.send(reason).ok();
+        }
+    }
+}
+
+#[derive(Default)]
+struct JobTaskQueue {
+    running: Option<RunningTask>,
+    pending: VecDeque<JobRunParam>,
+}
+
+/// 执行器代理的任务管理;同一任务按阻塞策略串行、丢弃或覆盖执行
+pub struct AgentTaskManager {
+    config: Arc<AgentConfig>,
+    admin_client: Arc<AdminClient>,
+    job_map: HashMap<u64, JobTaskQueue>,
+}
+
+impl AgentTaskManager {
+    pub fn new(config: Arc<AgentConfig>, admin_client: Arc<AdminClient>) -> Self {
+        Self {
+            config,
+            admin_client,
+            job_map: HashMap::new(),
+        }
+    }
+
+    fn run(&mut self, param: JobRunParam, ctx: &mut Context<Self>) -> anyhow::Result<()> {
+        let glue_type = param.glue_type.clone().unwrap_or_default();
+        if ScriptType::from_glue_type(&glue_type).is_none() {
+            return Err(anyhow::anyhow!("glue type not support:{}", &glue_type));
+        }
+        let queue = self.job_map.entry(param.job_id).or_default();
+        if let Some(running) = queue.running.as_mut() {
+            match param.executor_block_strategy.as_deref() {
+                Some(BLOCK_DISCARD_LATER) => {
+                    return Err(anyhow::anyhow!("block strategy effect:Discard Later"));
+                }
+                Some(BLOCK_COVER_EARLY) => {
+                    running.kill("block strategy effect:Cover Early".to_owned());
+                    for item in queue.pending.drain(..) {
+                        Self::callback(
+                            &self.admin_client,
+                            item.log_id,
+                            TaskRunResult {
+                                success: false,
+                                message: "block strategy effect:Cover Early".to_owned(),
+                                result: None,
+                            },
+                        );
+                    }
+                }
+                _ => {
+                    queue.pending.push_back(param);
+                    return Ok(());
+                }
+            }
+        }
+        self.start(param, ctx);
+        Ok(())
+    }
+
+    fn start(&mut self, param: JobRunParam, ctx: &mut Context<Self>) {
+        let (kill_sender, kill_receiver) = oneshot::channel();
+        let queue = self.job_map.entry(param.job_id).or_default();
+        queue.running = Some(RunningTask {
+            log_id: param.log_id,
+            kill_sender: Some(kill_sender),
+        });
+        let job_id = param.job_id;
+        let log_id = param.log_id;
+        let config = self.config.clone();
+        let addr = ctx.address();
+        tokio::spawn(async move {
+            let result = Self::run_script(config, param, kill_receiver).await;
+            addr.do_send(AgentTaskReq::Finish(job_id, log_id, result));
+        });
+    }
+
+    fn finish(&mut self, job_id: u64, log_id: u64, result: TaskRunResult, ctx: &mut Context<Self>) {
+        Self::callback(&self.admin_client, log_id, result);
+        let next = if let Some(queue) = self.job_map.get_mut(&job_id) {
+            if queue.running.as_ref().map(|v| v.log_id) != Some(log_id) {
+                // 被覆盖的任务,当前运行的是新任务
+                return;
+            }
+            queue.running = None;
+            queue.pending.pop_front()
+        } else {
+            None
+        };
+        if let Some(param) = next {
+            self.start(param, ctx);
+        } else {
+            self.job_map.remove(&job_id);
+        }
+    }
+
+    fn kill(&mut self, job_id: u64) {
+        if let Some(queue) = self.job_map.get_mut(&job_id) {
+            for item in queue.pending.drain(..) {
+                Self::callback(
+                    &self.admin_client,
+                    item.log_id,
+                    TaskRunResult {
+                        success: false,
+                        message: "job killed".to_owned(),
+                        result: None,
+                    },
+                );
+            }
+            if let Some(running) = queue.running.as_mut() {
+                running.kill("job killed".to_owned());
+            }
+        }
+    }
+
+    fn is_running(&self, log_id: u64) -> bool {
+        self.job_map.values().any(|queue| {
+            queue.running.as_ref().map(|v| v.log_id) == Some(log_id)
+                || queue.pending.iter().any(|v| v.log_id == log_id)
+        })
+    }
+
+    fn callback(admin_client: &Arc<AdminClient>, log_id: u64, result: TaskRunResult) {
+        let admin_client = admin_client.clone();
+        let param = CallbackParam {
+            log_id,
+            log_date_time: now_millis_i64(),
+            handle_code: if result.success {
+                SUCCESS_CODE
+            } else {
+                FAIL_CODE
+            },
+            handle_msg: Some(Arc::new(result.message)),
+            handle_result: result.result.map(Arc::new),
+        };
+        let fut = async move {
+            for i in 0..CALLBACK_RETRY_TIMES {
+                match admin_client.callback(vec![param.clone()]).await {
+                    Ok(_) => return,
+                    Err(err) => {
+                        log::warn!("agent callback error,log_id:{},{}", log_id, err);
+                        tokio::time::sleep(Duration::from_secs(2u64.pow(i + 1))).await;
+                    }
+                }
+            }
+            log::error!("agent callback failed,log_id:{}", log_id);
+        };
+        tokio::spawn(fut);
+    }
+
+    async fn run_script(
+        config: Arc<AgentConfig>,
+        param: JobRunParam,
+        kill_receiver: oneshot::Receiver<String>,
+    ) -> TaskRunResult {
+        let log_date_time = param.log_date_time.unwrap_or_default() as i64;
+        let log_path = TaskLog::log_path(&config, log_date_time, param.log_id);
+        let result = match Self::do_run_script(&config, &param, &log_path, kill_receiver).await {
+            Ok(v) => v,
+            Err(err) => TaskRunResult {
+                success: false,
+                message: err.to_string(),
+                result: None,
+            },
+        };
+        TaskLog::append(
+            &log_path,
+            &format!(
+                "----------- ratch-job agent finish, success:{}, message:{}",
+                result.success, &result.message
+            ),
+        )
+        .ok();
+        result
+    }
+
+    async fn do_run_script(
+        config: &AgentConfig,
+        param: &JobRunParam,
+        log_path: &Path,
+        kill_receiver: oneshot::Receiver<String>,
+    ) -> anyhow::Result<TaskRunResult> {
+        let glue_type = param.glue_type.clone().unwrap_or_default();
+        let script_type = ScriptType::from_glue_type(&glue_type)
+            .ok_or_else(|| anyhow::anyhow!("glue type not support:{}", &glue_type))?;
+        let script_path = Self::write_script(config, param, script_type)?;
+        TaskLog::append(
+            log_path,
+            &format!(
+                "----------- ratch-job agent start, logId:{}, script:{}",
+                param.log_id,
+                script_path.display()
+            ),
+        )?;
+        let log_file = std::fs::OpenOptions::new().append(true).open(log_path)?;
+        // 脚本可把json结果写入RATCH_RESULT_FILE,下次运行时通过RATCH_LAST_RESULT读取
+        let result_path = log_path.with_extension("result");
+        let interpreter = match script_type {
+            ScriptType::Shell => &config.shell,
+            ScriptType::Python => &config.python,
+        };
+        // 参数与xxl-job脚本任务一致: 任务参数 分片序号 分片总数
+        let mut child = tokio::process::Command::new(interpreter)
+            .arg(&script_path)
+            .arg(param.executor_params.as_deref().map_or("", |v| v.as_str()))
+            .arg(param.broadcast_index.unwrap_or_default().to_string())
+            .arg(param.broadcast_total.unwrap_or(1).to_string())
+            .current_dir(config.get_work_dir())
+            .envs(&config.envs)
+            .env("RATCH_JOB_ID", param.job_id.to_string())
+            .env("RATCH_TASK_ID", param.log_id.to_string())
+            .env("RATCH_RESULT_FILE", &result_path)
+            .env(
+                "RATCH_LAST_RESULT",
+                param.last_result.as_deref().map_or("", |v| v.as_str()),
+            )
+            .stdout(log_file.try_clone()?)
+            .stderr(log_file)
+            .kill_on_drop(true)
+            .spawn()?;
+        let timeout = param.executor_timeout.unwrap_or_default();
+        let timeout_fut = async {
+            if timeout > 0 {
+                tokio::time::sleep(Duration::from_secs(timeout as u64)).await;
+            } else {
+                std::future::pending::<()>().await;
+            }
+        };
+        let result = tokio::select! {
+            status = child.wait() => {
+                let status = status?;
+                if status.success() {
+                    TaskRunResult {
+                        success: true,
+                        message: String::new(),
+                        result: Self::read_result(&result_path),
+                    }
+                } else {
+                    TaskRunResult {
+                        success: false,
+                        message: format!("script exit code:{}", status.code().unwrap_or(-1)),
+                        result: None,
+                    }
+                }
+            }
+            Ok(reason) = kill_receiver => {
+                child.kill().await.ok();
+                TaskRunResult { success: false, message: reason, result: None }
+            }
+            _ = timeout_fut => {
+                child.kill().await.ok();
+                TaskRunResult { success: false, message: format!("job timeout:{}s", timeout), result: None }
+            }
+        };
+        Ok(result)
+    }
+
+    fn read_result(path: &Path) -> Option<String> {
+        std::fs::read_to_string(path)
+            .ok()
+            .map(|v| v.trim().to_owned())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// 脚本按任务id与脚本更新时间保存,脚本未变更时复用
+    fn write_script(
+        config: &AgentConfig,
+        param: &JobRunParam,
+        script_type: ScriptType,
+    ) -> anyhow::Result<PathBuf> {
+        let glue_source = param
+            .glue_source
+            .as_ref()
+            .filter(|v| !v.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("glue source is empty"))?;
+        let path = config.script_dir().join(format!(
+            "{}_{}.{}",
+            param.job_id,
+            param.glue_update_time.unwrap_or_default(),
+            script_type.suffix()
+        ));
+        if !path.exists() {
+            std::fs::create_dir_all(config.script_dir())?;
+            std::fs::write(&path, glue_source.as_bytes())?;
+        }
+        Ok(path)
+    }
+
+    fn heartbeat(&mut self, ctx: &mut Context<Self>) {
+        ctx.run_later(Duration::from_secs(3600), |act, ctx| {
+            if let Err(err) = TaskLog::clean_expired(&act.config) {
+                log::warn!("agent clean expired log error:{}", err);
+            }
+            act.heartbeat(ctx);
+        });
+    }
+}
+
+impl Actor for AgentTaskManager {
+    type Context = Context<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        log::info!("AgentTaskManager started");
+        if let Err(err) = TaskLog::clean_expired(&self.config) {
+            log::warn!("agent clean expired log error:{}", err);
+        }
+        self.heartbeat(ctx);
+    }
+}
+
+impl Handler<AgentTaskReq> for AgentTaskManager {
+    type Result = anyhow::Result<AgentTaskResult>;
+
+    fn handle(&mut self, msg: AgentTaskReq, ctx: &mut Context<Self>) -> Self::Result {
+        match msg {
+            AgentTaskReq::Run(param) => {
+                self.run(param, ctx)?;
+            }
+            AgentTaskReq::Kill(job_id) => {
+                self.kill(job_id);
+            }
+            AgentTaskReq::IdleBeat(job_id) => {
+                let running = self.job_map.contains_key(&job_id);
+                return Ok(AgentTaskResult::Running(running));
+            }
+            AgentTaskReq::IsRunning(log_id) => {
+                return Ok(AgentTaskResult::Running(self.is_running(log_id)));
+            }
+            AgentTaskReq::Finish(job_id, log_id, result) => {
+                self.finish(job_id, log_id, result, ctx);
+            }
+        }
+        Ok(AgentTaskResult::None)
+    }
+}
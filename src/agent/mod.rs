@@ -0,0 +1,73 @@
+use crate::agent::admin_client::AdminClient;
+use crate::agent::api::{agent_api_config, AgentShareData};
+use crate::agent::core::AgentTaskManager;
+use crate::agent::model::AgentConfig;
+use actix::Actor;
+use actix_web::web::Data;
+use actix_web::{middleware, App, HttpServer};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub mod admin_client;
+pub mod api;
+pub mod core;
+pub mod model;
+pub mod task_log;
+
+const REGISTRY_INTERVAL_SECONDS: u64 = 30;
+
+/// 以xxl-job执行器协议运行的命令执行代理
+pub async fn run_agent(config: AgentConfig) -> anyhow::Result<()> {
+    std::fs::create_dir_all(config.script_dir())?;
+    std::fs::create_dir_all(config.log_dir())?;
+    std::fs::create_dir_all(config.get_work_dir())?;
+    let config = Arc::new(config);
+    log::info!(
+        "agent start,app_name:{},address:{},data dir:{}",
+        &config.app_name,
+        &config.address,
+        config.data_dir.display()
+    );
+    let admin_client = Arc::new(AdminClient::new(config.clone()));
+    let task_manager = AgentTaskManager::new(config.clone(), admin_client.clone()).start();
+    let registry_client = admin_client.clone();
+    let registry_handle = tokio::spawn(async move {
+        loop {
+            if let Err(err) = registry_client.registry().await {
+                log::error!("agent registry error:{}", err);
+            }
+            tokio::time::sleep(Duration::from_secs(REGISTRY_INTERVAL_SECONDS)).await;
+        }
+    });
+    let share_data = Arc::new(AgentShareData {
+        config: config.clone(),
+        task_manager,
+    });
+    HttpServer::new(move || {
+        App::new()
+            .app_data(Data::new(share_data.clone()))
+            .wrap(middleware::Logger::default())
+            .configure(agent_api_config)
+    })
+    .workers(2)
+    .bind(("0.0.0.0", config.port))?
+    .run()
+    .await?;
+    registry_handle.abort();
+    if let Err(err) = admin_client.registry_remove().await {
+        log::error!("agent registry remove error:{}", err);
+    }
+    Ok(())
+}
+
+/// 获取本机第一个非回环的ipv4地址
+pub fn get_local_ip() -> String {
+    if let Ok(list) = if_addrs::get_if_addrs() {
+        for item in list {
+            if !item.is_loopback() && item.ip().is_ipv4() {
+                return item.ip().to_string();
+            }
+        }
+    }
+    "127.0.0.1".to_owned()
+}
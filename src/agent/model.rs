@@ -0,0 +1,151 @@
+use crate::task::model::request_model::JobRunParam;
+use actix::Message;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+pub const REGISTRY_GROUP: &str = "EXECUTOR";
+pub const XXL_ACCESS_TOKEN_HEADER: &str = "XXL-JOB-ACCESS-TOKEN";
+
+#[derive(Debug, Clone, Default)]
+pub struct AgentConfig {
+    pub admin_addresses: Vec<String>,
+    pub app_name: String,
+    pub access_token: String,
+    pub port: u16,
+    /// 注册到调度中心的执行器地址
+    pub address: String,
+    pub data_dir: PathBuf,
+    pub work_dir: Option<PathBuf>,
+    pub envs: HashMap<String, String>,
+    pub log_retention_days: u32,
+    pub shell: String,
+    pub python: String,
+}
+
+impl AgentConfig {
+    pub fn script_dir(&self) -> PathBuf {
+        self.data_dir.join("scripts")
+    }
+
+    pub fn log_dir(&self) -> PathBuf {
+        self.data_dir.join("logs")
+    }
+
+    pub fn get_work_dir(&self) -> PathBuf {
+        self.work_dir.clone().unwrap_or(self.data_dir.clone())
+    }
+
+    /// 解析 `KEY=VALUE` 格式的环境变量
+    pub fn parse_envs(list: &[String]) -> anyhow::Result<HashMap<String, String>> {
+        let mut envs = HashMap::new();
+        for item in list {
+            let (key, value) = item
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("env format is invalid:{}", item))?;
+            if key.is_empty() {
+                return Err(anyhow::anyhow!("env format is invalid:{}", item));
+            }
+            envs.insert(key.to_owned(), value.to_owned());
+        }
+        Ok(envs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScriptType {
+    Shell,
+    Python,
+}
+
+impl ScriptType {
+    pub fn from_glue_type(glue_type: &str) -> Option<Self> {
+        match glue_type {
+            "GLUE_SHELL" => Some(ScriptType::Shell),
+            "GLUE_PYTHON" => Some(ScriptType::Python),
+            _ => None,
+        }
+    }
+
+    pub fn suffix(&self) -> &'static str {
+        match self {
+            ScriptType::Shell => "sh",
+            ScriptType::Python => "py",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct KillParam {
+    pub job_id: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct IdleBeatParam {
+    pub job_id: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogParam {
+    #[serde(rename(serialize = "logDateTim", deserialize = "logDateTim"))]
+    pub log_date_time: i64,
+    pub log_id: u64,
+    pub from_line_num: usize,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct LogResult {
+    pub from_line_num: usize,
+    pub to_line_num: usize,
+    pub log_content: String,
+    pub is_end: bool,
+}
+
+/// 任务执行结果
+#[derive(Debug, Clone)]
+pub struct TaskRunResult {
+    pub success: bool,
+    pub message: String,
+    pub result: Option<String>,
+}
+
+#[derive(Message)]
+#[rtype(result = "anyhow::Result<AgentTaskResult>")]
+pub enum AgentTaskReq {
+    Run(JobRunParam),
+    Kill(u64),
+    IdleBeat(u64),
+    IsRunning(u64),
+    Finish(u64, u64, TaskRunResult),
+}
+
+pub enum AgentTaskResult {
+    Running(bool),
+    None,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agent_parse_envs() {
+        let envs =
+            AgentConfig::parse_envs(&["A=1".to_owned(), "B=x=y".to_owned(), "C=".to_owned()])
+                .unwrap();
+        assert_eq!(envs.get("A").unwrap(), "1");
+        assert_eq!(envs.get("B").unwrap(), "x=y");
+        assert_eq!(envs.get("C").unwrap(), "");
+        assert!(AgentConfig::parse_envs(&["A".to_owned()]).is_err());
+        assert!(AgentConfig::parse_envs(&["=1".to_owned()]).is_err());
+        assert_eq!(
+            ScriptType::from_glue_type("GLUE_PYTHON"),
+            Some(ScriptType::Python)
+        );
+        assert_eq!(ScriptType::from_glue_type("GLUE_NODEJS"), None);
+    }
+}
@@ -0,0 +1,81 @@
+use crate::agent::model::{AgentConfig, LogResult};
+use crate::common::datetime_utils::{get_datetime_millis, get_local_offset};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+const LOG_DATE_FMT: &str = "%Y-%m-%d";
+
+/// 任务日志按天分目录保存: `{data_dir}/logs/{yyyy-MM-dd}/{logId}.log`
+pub struct TaskLog;
+
+impl TaskLog {
+    pub fn log_path(config: &AgentConfig, log_date_time: i64, log_id: u64) -> PathBuf {
+        let date = get_datetime_millis(log_date_time, &get_local_offset())
+            .map(|v| v.format(LOG_DATE_FMT).to_string())
+            .unwrap_or_default();
+        config.log_dir().join(date).join(format!("{}.log", log_id))
+    }
+
+    pub fn append(path: &Path, content: &str) -> anyhow::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)?;
+        writeln!(file, "{}", content)?;
+        Ok(())
+    }
+
+    /// 从指定行(从1开始)读取日志
+    pub fn read(path: &Path, from_line_num: usize, is_end: bool) -> anyhow::Result<LogResult> {
+        let from_line_num = from_line_num.max(1);
+        let file = match std::fs::File::open(path) {
+            Ok(file) => file,
+            Err(_) => {
+                return Ok(LogResult {
+                    from_line_num,
+                    to_line_num: from_line_num - 1,
+                    log_content: String::new(),
+                    is_end,
+                })
+            }
+        };
+        let mut log_content = String::new();
+        let mut to_line_num = from_line_num - 1;
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line_num = i + 1;
+            if line_num < from_line_num {
+                continue;
+            }
+            log_content.push_str(&line?);
+            log_content.push('\n');
+            to_line_num = line_num;
+        }
+        Ok(LogResult {
+            from_line_num,
+            to_line_num,
+            log_content,
+            is_end,
+        })
+    }
+
+    /// 清理超过保留天数的日志目录
+    pub fn clean_expired(config: &AgentConfig) -> anyhow::Result<()> {
+        let expire_date = (chrono::Local::now()
+            - chrono::Duration::days(config.log_retention_days as i64))
+        .format(LOG_DATE_FMT)
+        .to_string();
+        for entry in std::fs::read_dir(config.log_dir())? {
+            let entry = entry?;
+            let name = entry.file_name().to_string_lossy().to_string();
+            // 日期格式的目录名可直接按字符串比较
+            if name.len() == expire_date.len() && name < expire_date && entry.path().is_dir() {
+                log::info!("agent remove expired log dir:{}", &name);
+                std::fs::remove_dir_all(entry.path())?;
+            }
+        }
+        Ok(())
+    }
+}
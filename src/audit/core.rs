@@ -0,0 +1,259 @@
+use crate::audit::model::actor_model::{
+    AuditManagerRaftReq, AuditManagerRaftResult, AuditManagerReq, AuditManagerResult,
+};
+use crate::audit::model::audit::{AuditQueryParam, AuditRecord};
+use crate::common::app_config::AppConfig;
+use crate::common::byte_utils::id_to_bin;
+use crate::common::constant::AUDIT_TABLE_NAME;
+use crate::common::pb::data_object::AuditRecordDo;
+use crate::raft::store::model::SnapshotRecordDto;
+use crate::raft::store::raftapply::{RaftApplyDataRequest, RaftApplyDataResponse};
+use crate::raft::store::raftsnapshot::{SnapshotWriterActor, SnapshotWriterRequest};
+use actix::prelude::*;
+use bean_factory::{bean, BeanFactory, FactoryData, Inject};
+use quick_protobuf::{BytesReader, Writer};
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+const DAY_MILLIS: u64 = 24 * 60 * 60 * 1000;
+
+/// 审计日志,按id递增顺序保存
+#[bean(inject)]
+pub struct AuditManager {
+    records: VecDeque<Arc<AuditRecord>>,
+    next_id: u64,
+    retain_millis: u64,
+    max_count: usize,
+    data_load_completed: bool,
+}
+
+impl AuditManager {
+    pub fn new(app_config: Arc<AppConfig>) -> Self {
+        Self::new_with_retention(app_config.audit_retain_days, app_config.audit_max_count)
+    }
+
+    fn new_with_retention(retain_days: u64, max_count: usize) -> Self {
+        AuditManager {
+            records: VecDeque::new(),
+            next_id: 1,
+            retain_millis: retain_days * DAY_MILLIS,
+            max_count,
+            data_load_completed: false,
+        }
+    }
+
+    fn add_record(&mut self, record: Arc<AuditRecord>) {
+        let mut record = record;
+        // id由状态机按日志顺序分配,各节点一致
+        Arc::make_mut(&mut record).id = self.next_id;
+        self.next_id += 1;
+        let now = record.create_time;
+        self.records.push_back(record);
+        self.clear_expired(now);
+    }
+
+    /// 以最新记录的时间为准清理,保证各节点结果一致
+    fn clear_expired(&mut self, now: u64) {
+        while self.records.len() > self.max_count {
+            self.records.pop_front();
+        }
+        if self.retain_millis == 0 {
+            return;
+        }
+        let min_time = now.saturating_sub(self.retain_millis);
+        while let Some(first) = self.records.front() {
+            if first.create_time >= min_time {
+                break;
+            }
+            self.records.pop_front();
+        }
+    }
+
+    fn query(&self, param: &AuditQueryParam) -> (usize, Vec<Arc<AuditRecord>>) {
+        let mut total = 0;
+        let mut list = vec![];
+        for record in self.records.iter().rev() {
+            if !param.match_record(record) {
+                continue;
+            }
+            if total >= param.offset && list.len() < param.limit {
+                list.push(record.clone());
+            }
+            total += 1;
+        }
+        (total, list)
+    }
+
+    fn build_snapshot(&self, writer: Addr<SnapshotWriterActor>) -> anyhow::Result<()> {
+        for record in self.records.iter() {
+            let mut buf = Vec::new();
+            {
+                let mut pb_writer = Writer::new(&mut buf);
+                let value_do = record.to_do();
+                pb_writer.write_message(&value_do)?;
+            }
+            let record = SnapshotRecordDto {
+                tree: AUDIT_TABLE_NAME.clone(),
+                key: id_to_bin(record.id),
+                value: buf,
+                op_type: 0,
+            };
+            writer.do_send(SnapshotWriterRequest::Record(record));
+        }
+        Ok(())
+    }
+
+    fn load_snapshot_record(&mut self, record: SnapshotRecordDto) -> anyhow::Result<()> {
+        // 运行中安装快照时先清空已有记录，避免重复
+        if self.data_load_completed {
+            self.records.clear();
+            self.next_id = 1;
+            self.data_load_completed = false;
+        }
+        let mut reader = BytesReader::from_bytes(&record.value);
+        let value_do: AuditRecordDo = reader.read_message(&record.value)?;
+        let record: AuditRecord = value_do.into();
+        if record.id >= self.next_id {
+            self.next_id = record.id + 1;
+        }
+        self.records.push_back(Arc::new(record));
+        Ok(())
+    }
+
+    fn load_completed(&mut self, _ctx: &mut Context<Self>) -> anyhow::Result<()> {
+        self.records.make_contiguous().sort_by_key(|r| r.id);
+        if let Some(last) = self.records.back() {
+            let now = last.create_time;
+            self.clear_expired(now);
+        }
+        self.data_load_completed = true;
+        log::info!(
+            "AuditManager load completed,record count:{}",
+            self.records.len()
+        );
+        Ok(())
+    }
+}
+
+impl Actor for AuditManager {
+    type Context = Context<Self>;
+
+    fn started(&mut self, _ctx: &mut Self::Context) {
+        log::info!("AuditManager started");
+    }
+}
+
+impl Inject for AuditManager {
+    type Context = Context<Self>;
+
+    fn inject(
+        &mut self,
+        _factory_data: FactoryData,
+        _factory: BeanFactory,
+        _ctx: &mut Self::Context,
+    ) {
+    }
+}
+
+impl Handler<AuditManagerRaftReq> for AuditManager {
+    type Result = anyhow::Result<AuditManagerRaftResult>;
+
+    fn handle(&mut self, msg: AuditManagerRaftReq, _ctx: &mut Self::Context) -> Self::Result {
+        match msg {
+            AuditManagerRaftReq::Add(record) => {
+                self.add_record(record);
+                Ok(AuditManagerRaftResult::None)
+            }
+        }
+    }
+}
+
+impl Handler<AuditManagerReq> for AuditManager {
+    type Result = anyhow::Result<AuditManagerResult>;
+
+    fn handle(&mut self, msg: AuditManagerReq, _ctx: &mut Self::Context) -> Self::Result {
+        match msg {
+            AuditManagerReq::Query(param) => {
+                let (total, list) = self.query(&param);
+                Ok(AuditManagerResult::PageInfo(total, list))
+            }
+        }
+    }
+}
+
+impl Handler<RaftApplyDataRequest> for AuditManager {
+    type Result = anyhow::Result<RaftApplyDataResponse>;
+
+    fn handle(&mut self, msg: RaftApplyDataRequest, ctx: &mut Self::Context) -> Self::Result {
+        match msg {
+            RaftApplyDataRequest::BuildSnapshot(writer) => {
+                self.build_snapshot(writer)?;
+            }
+            RaftApplyDataRequest::LoadSnapshotRecord(record) => {
+                self.load_snapshot_record(record)?;
+            }
+            RaftApplyDataRequest::LoadCompleted => {
+                self.load_completed(ctx)?;
+            }
+        }
+        Ok(RaftApplyDataResponse::None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::model::audit::{AUDIT_ACTION_UPDATE, AUDIT_ACTOR_CONSOLE, AUDIT_TARGET_JOB};
+
+    fn build_record(create_time: u64) -> Arc<AuditRecord> {
+        let mut record = AuditRecord::new(
+            Arc::new("admin".to_owned()),
+            AUDIT_ACTOR_CONSOLE,
+            AUDIT_ACTION_UPDATE,
+            AUDIT_TARGET_JOB,
+            "1".to_owned(),
+        );
+        record.create_time = create_time;
+        Arc::new(record)
+    }
+
+    #[test]
+    fn audit_retention() {
+        let mut manager = AuditManager::new_with_retention(1, 3);
+        manager.add_record(build_record(1000));
+        manager.add_record(build_record(2000));
+        manager.add_record(build_record(3000));
+        manager.add_record(build_record(4000));
+        assert_eq!(manager.records.len(), 3);
+        assert_eq!(manager.records.front().unwrap().id, 2);
+
+        manager.add_record(build_record(DAY_MILLIS + 3500));
+        assert_eq!(manager.records.len(), 2);
+        assert_eq!(manager.records.front().unwrap().id, 4);
+
+        let param = AuditQueryParam {
+            limit: 1,
+            ..Default::default()
+        };
+        let (total, list) = manager.query(&param);
+        assert_eq!(total, 2);
+        assert_eq!(list[0].id, 5);
+
+        // 运行中安装快照后只保留快照中的记录
+        manager.data_load_completed = true;
+        let mut buf = Vec::new();
+        Writer::new(&mut buf)
+            .write_message(&manager.records[0].to_do())
+            .unwrap();
+        manager
+            .load_snapshot_record(SnapshotRecordDto {
+                tree: AUDIT_TABLE_NAME.clone(),
+                key: id_to_bin(4),
+                value: buf,
+                op_type: 0,
+            })
+            .unwrap();
+        assert_eq!(manager.records.len(), 1);
+        assert_eq!(manager.next_id, 5);
+    }
+}
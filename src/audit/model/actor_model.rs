@@ -0,0 +1,26 @@
+use crate::audit::model::audit::{AuditQueryParam, AuditRecord};
+use actix::Message;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Message, Deserialize, Serialize)]
+#[rtype(result = "anyhow::Result<AuditManagerRaftResult>")]
+pub enum AuditManagerRaftReq {
+    Add(Arc<AuditRecord>),
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub enum AuditManagerRaftResult {
+    None,
+}
+
+#[derive(Debug, Message)]
+#[rtype(result = "anyhow::Result<AuditManagerResult>")]
+pub enum AuditManagerReq {
+    Query(AuditQueryParam),
+}
+
+#[derive(Debug, Clone)]
+pub enum AuditManagerResult {
+    PageInfo(usize, Vec<Arc<AuditRecord>>),
+}
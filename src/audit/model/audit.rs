@@ -0,0 +1,228 @@
+use crate::common::constant::EMPTY_ARC_STR;
+use crate::common::pb::data_object::AuditRecordDo;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::sync::Arc;
+
+pub const AUDIT_ACTOR_CONSOLE: &str = "CONSOLE";
+pub const AUDIT_ACTOR_OPENAPI: &str = "OPENAPI";
+
+pub const AUDIT_TARGET_JOB: &str = "JOB";
+pub const AUDIT_TARGET_APP: &str = "APP";
+pub const AUDIT_TARGET_NAMESPACE: &str = "NAMESPACE";
+pub const AUDIT_TARGET_USER: &str = "USER";
+pub const AUDIT_TARGET_SCHEDULE: &str = "SCHEDULE";
+
+pub const AUDIT_ACTION_CREATE: &str = "CREATE";
+pub const AUDIT_ACTION_UPDATE: &str = "UPDATE";
+pub const AUDIT_ACTION_REMOVE: &str = "REMOVE";
+pub const AUDIT_ACTION_TRIGGER: &str = "TRIGGER";
+pub const AUDIT_ACTION_RESET_PASSWORD: &str = "RESET_PASSWORD";
+pub const AUDIT_ACTION_ROLLBACK: &str = "ROLLBACK";
+pub const AUDIT_ACTION_CANCEL: &str = "CANCEL";
+pub const AUDIT_ACTION_PAUSE: &str = "PAUSE";
+pub const AUDIT_ACTION_RESUME: &str = "RESUME";
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecord {
+    /// 由状态机写入时分配
+    pub id: u64,
+    pub actor: Arc<String>,
+    pub actor_type: Arc<String>,
+    pub action: Arc<String>,
+    pub target_type: Arc<String>,
+    pub target_id: Arc<String>,
+    pub namespace: Arc<String>,
+    /// 变更前的json,为空表示不存在
+    pub before: Arc<String>,
+    /// 变更后的json,为空表示已删除
+    pub after: Arc<String>,
+    pub source_ip: Arc<String>,
+    pub create_time: u64,
+    pub detail: Arc<String>,
+}
+
+impl AuditRecord {
+    pub fn new(
+        actor: Arc<String>,
+        actor_type: &str,
+        action: &str,
+        target_type: &str,
+        target_id: String,
+    ) -> Self {
+        AuditRecord {
+            actor,
+            actor_type: Arc::new(actor_type.to_owned()),
+            action: Arc::new(action.to_owned()),
+            target_type: Arc::new(target_type.to_owned()),
+            target_id: Arc::new(target_id),
+            namespace: EMPTY_ARC_STR.clone(),
+            before: EMPTY_ARC_STR.clone(),
+            after: EMPTY_ARC_STR.clone(),
+            source_ip: EMPTY_ARC_STR.clone(),
+            detail: EMPTY_ARC_STR.clone(),
+            ..Default::default()
+        }
+    }
+
+    pub fn set_before<T: Serialize>(&mut self, value: Option<&T>) {
+        self.before = Self::to_json(value);
+    }
+
+    pub fn set_after<T: Serialize>(&mut self, value: Option<&T>) {
+        self.after = Self::to_json(value);
+    }
+
+    fn to_json<T: Serialize>(value: Option<&T>) -> Arc<String> {
+        value
+            .and_then(|v| serde_json::to_string(v).ok())
+            .map(Arc::new)
+            .unwrap_or_else(|| EMPTY_ARC_STR.clone())
+    }
+
+    pub fn to_do(&self) -> AuditRecordDo<'_> {
+        AuditRecordDo {
+            id: self.id,
+            actor: Cow::Borrowed(&self.actor),
+            actor_type: Cow::Borrowed(&self.actor_type),
+            action: Cow::Borrowed(&self.action),
+            target_type: Cow::Borrowed(&self.target_type),
+            target_id: Cow::Borrowed(&self.target_id),
+            namespace: Cow::Borrowed(&self.namespace),
+            before: Cow::Borrowed(&self.before),
+            after: Cow::Borrowed(&self.after),
+            source_ip: Cow::Borrowed(&self.source_ip),
+            create_time: self.create_time,
+            detail: Cow::Borrowed(&self.detail),
+        }
+    }
+}
+
+impl<'a> From<AuditRecordDo<'a>> for AuditRecord {
+    fn from(record: AuditRecordDo<'a>) -> Self {
+        AuditRecord {
+            id: record.id,
+            actor: Arc::new(record.actor.to_string()),
+            actor_type: Arc::new(record.actor_type.to_string()),
+            action: Arc::new(record.action.to_string()),
+            target_type: Arc::new(record.target_type.to_string()),
+            target_id: Arc::new(record.target_id.to_string()),
+            namespace: Arc::new(record.namespace.to_string()),
+            before: Arc::new(record.before.to_string()),
+            after: Arc::new(record.after.to_string()),
+            source_ip: Arc::new(record.source_ip.to_string()),
+            create_time: record.create_time,
+            detail: Arc::new(record.detail.to_string()),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct AuditQueryParam {
+    pub actor: Option<Arc<String>>,
+    pub action: Option<Arc<String>>,
+    pub target_type: Option<Arc<String>>,
+    pub target_id: Option<Arc<String>>,
+    pub namespace: Option<Arc<String>>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub offset: usize,
+    pub limit: usize,
+}
+
+impl AuditQueryParam {
+    pub fn match_record(&self, record: &AuditRecord) -> bool {
+        if let Some(actor) = &self.actor {
+            if !record.actor.contains(actor.as_str()) {
+                return false;
+            }
+        }
+        if !Self::match_value(&self.action, &record.action)
+            || !Self::match_value(&self.target_type, &record.target_type)
+            || !Self::match_value(&self.target_id, &record.target_id)
+            || !Self::match_value(&self.namespace, &record.namespace)
+        {
+            return false;
+        }
+        if let Some(start_time) = self.start_time {
+            if record.create_time < start_time {
+                return false;
+            }
+        }
+        if let Some(end_time) = self.end_time {
+            if record.create_time > end_time {
+                return false;
+            }
+        }
+        true
+    }
+
+    fn match_value(expect: &Option<Arc<String>>, value: &Arc<String>) -> bool {
+        match expect {
+            Some(v) if !v.is_empty() => v.as_str() == value.as_str(),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditFieldChange {
+    pub field: String,
+    pub before: Value,
+    pub after: Value,
+}
+
+/// 按顶层字段比较变更前后的json
+pub fn diff_json(before: &str, after: &str) -> Vec<AuditFieldChange> {
+    let before = parse_object(before);
+    let after = parse_object(after);
+    let mut keys = BTreeSet::new();
+    keys.extend(before.keys().cloned());
+    keys.extend(after.keys().cloned());
+    let mut changes = vec![];
+    for key in keys {
+        let before_value = before.get(&key).cloned().unwrap_or(Value::Null);
+        let after_value = after.get(&key).cloned().unwrap_or(Value::Null);
+        if before_value != after_value {
+            changes.push(AuditFieldChange {
+                field: key,
+                before: before_value,
+                after: after_value,
+            });
+        }
+    }
+    changes
+}
+
+fn parse_object(value: &str) -> serde_json::Map<String, Value> {
+    if value.is_empty() {
+        return serde_json::Map::new();
+    }
+    match serde_json::from_str(value) {
+        Ok(Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn diff_job_update() {
+        let before = r#"{"id":1,"cronValue":"0 * * * * *","enable":true}"#;
+        let after = r#"{"id":1,"cronValue":"0 0 * * * *","enable":true}"#;
+        let changes = diff_json(before, after);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "cronValue");
+        assert_eq!(changes[0].after, Value::String("0 0 * * * *".to_owned()));
+
+        let changes = diff_json("", after);
+        assert_eq!(changes.len(), 3);
+        assert!(changes.iter().all(|c| c.before.is_null()));
+    }
+}
@@ -0,0 +1,2 @@
+pub mod actor_model;
+pub mod audit;
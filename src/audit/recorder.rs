@@ -0,0 +1,108 @@
+use crate::audit::model::actor_model::AuditManagerRaftReq;
+use crate::audit::model::audit::{AuditRecord, AUDIT_ACTOR_CONSOLE, AUDIT_ACTOR_OPENAPI};
+use crate::common::datetime_utils::now_millis;
+use crate::common::model::UserSession;
+use crate::common::share_data::ShareData;
+use crate::raft::store::ClientRequest;
+use actix_http::HttpMessage;
+use actix_web::web::Data;
+use actix_web::HttpRequest;
+use std::sync::Arc;
+
+/// open-api调用方可通过该请求头标识自己
+pub const AUDIT_CLIENT_HEADER: &str = "Ratch-Client";
+
+pub struct AuditRecorder;
+
+impl AuditRecorder {
+    /// 控制台操作,操作人取登录会话
+    pub fn console(
+        req: &HttpRequest,
+        action: &str,
+        target_type: &str,
+        target_id: String,
+    ) -> AuditRecord {
+        let actor = req
+            .extensions()
+            .get::<Arc<UserSession>>()
+            .map(|session| session.username.clone())
+            .unwrap_or_default();
+        Self::build(
+            req,
+            actor,
+            AUDIT_ACTOR_CONSOLE,
+            action,
+            target_type,
+            target_id,
+        )
+    }
+
+    /// open-api操作,操作人取请求头中的客户端标识
+    pub fn openapi(
+        req: &HttpRequest,
+        action: &str,
+        target_type: &str,
+        target_id: String,
+    ) -> AuditRecord {
+        Self::build(
+            req,
+            Self::openapi_actor(req),
+            AUDIT_ACTOR_OPENAPI,
+            action,
+            target_type,
+            target_id,
+        )
+    }
+
+    /// open-api调用方标识,未设置时为openapi
+    pub fn openapi_actor(req: &HttpRequest) -> Arc<String> {
+        let actor = req
+            .headers()
+            .get(AUDIT_CLIENT_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .filter(|v| !v.is_empty())
+            .unwrap_or("openapi");
+        Arc::new(actor.to_owned())
+    }
+
+    fn build(
+        req: &HttpRequest,
+        actor: Arc<String>,
+        actor_type: &str,
+        action: &str,
+        target_type: &str,
+        target_id: String,
+    ) -> AuditRecord {
+        let mut record = AuditRecord::new(actor, actor_type, action, target_type, target_id);
+        if let Some(ip) = Self::source_ip(req) {
+            record.source_ip = Arc::new(ip);
+        }
+        record.create_time = now_millis();
+        record
+    }
+
+    /// 来源ip取连接地址，只有连接来自配置的可信代理时才取转发请求头中的地址
+    fn source_ip(req: &HttpRequest) -> Option<String> {
+        let peer_ip = req.peer_addr().map(|v| v.ip().to_string());
+        let is_trusted_proxy = match (&peer_ip, req.app_data::<Data<Arc<ShareData>>>()) {
+            (Some(ip), Some(app)) => app.app_config.audit_trusted_proxies.contains(ip),
+            _ => false,
+        };
+        if is_trusted_proxy {
+            if let Some(ip) = req.connection_info().realip_remote_addr() {
+                return Some(ip.to_owned());
+            }
+        }
+        peer_ip
+    }
+
+    /// 通过raft写入审计日志,失败只记录日志不影响业务结果
+    pub async fn submit(app: &ShareData, record: AuditRecord) {
+        let req = ClientRequest::AuditReq {
+            req: AuditManagerRaftReq::Add(Arc::new(record)),
+        };
+        if let Err(e) = app.raft_request_route.request(req).await {
+            log::error!("submit audit record error,{}", e);
+        }
+    }
+}
@@ -15,4 +15,144 @@ pub struct Cli {
 #[derive(Debug, Subcommand)]
 pub enum Commands {
     About,
+    /// backup the data dir, the server must be stopped
+    Backup {
+        /// backup file path
+        #[arg(short, long)]
+        output: String,
+    },
+    /// restore a single node cluster from backup file to an empty data dir
+    Restore {
+        /// backup file path
+        #[arg(short, long)]
+        input: String,
+        /// restore raft log to this index
+        #[arg(long)]
+        end_index: Option<u64>,
+        /// clear the data dir before restore when it is not empty
+        #[arg(long)]
+        force: bool,
+    },
+    /// inspect raft data files read-only, better run on a copied data dir
+    Inspect {
+        #[command(subcommand)]
+        command: InspectCommands,
+    },
+    /// run as a command execution agent, register to ratch-job by xxl-job executor api
+    Agent {
+        /// app name, support `ns://{namespace}/{appName}`
+        #[arg(long)]
+        app_name: String,
+        /// admin addresses, split by ','
+        #[arg(long, default_value = "http://127.0.0.1:8725/xxl-job-admin")]
+        admin_addresses: String,
+        /// xxl-job access token
+        #[arg(long, default_value = "default_token")]
+        access_token: String,
+        /// agent http port
+        #[arg(short, long, default_value_t = 9999)]
+        port: u16,
+        /// agent ip used for registry, default is the local ip
+        #[arg(long)]
+        ip: Option<String>,
+        /// agent address used for registry, default is `http://{ip}:{port}/`
+        #[arg(long)]
+        address: Option<String>,
+        /// data dir for scripts and logs
+        #[arg(long, default_value = "ratch_agent_data")]
+        data_dir: String,
+        /// working dir of the scripts, default is the data dir
+        #[arg(long)]
+        work_dir: Option<String>,
+        /// env of the scripts, format `KEY=VALUE`
+        #[arg(long = "env")]
+        envs: Vec<String>,
+        /// log retention days
+        #[arg(long, default_value_t = 7)]
+        log_retention_days: u32,
+        /// shell interpreter for GLUE_SHELL
+        #[arg(long, default_value = "bash")]
+        shell: String,
+        /// python interpreter for GLUE_PYTHON
+        #[arg(long, default_value = "python3")]
+        python: String,
+    },
+    /// sync jobs from a declarative manifest file (yaml or json)
+    Sync {
+        /// manifest file path
+        #[arg(short, long)]
+        file: String,
+        /// ratch-job http api address
+        #[arg(long, default_value = "http://127.0.0.1:8725")]
+        server: String,
+        /// only print the plan, do not apply
+        #[arg(long)]
+        dry_run: bool,
+        /// delete the jobs of the manifest apps which are not in the manifest
+        #[arg(long)]
+        prune: bool,
+    },
+    /// import executors and jobs from a xxl-job admin database dump, repeatable
+    ImportXxl {
+        /// mysqldump sql file, contains the inserts of xxl_job_group, xxl_job_info and xxl_job_log
+        #[arg(long)]
+        sql: Option<String>,
+        /// csv export of xxl_job_group, with header
+        #[arg(long)]
+        group_csv: Option<String>,
+        /// csv export of xxl_job_info, with header
+        #[arg(long)]
+        job_csv: Option<String>,
+        /// csv export of xxl_job_log, with header
+        #[arg(long)]
+        log_csv: Option<String>,
+        /// target namespace, default is `xxl`
+        #[arg(long)]
+        namespace: Option<String>,
+        /// ratch-job http api address
+        #[arg(long, default_value = "http://127.0.0.1:8725")]
+        server: String,
+        /// only print the plan, do not apply
+        #[arg(long)]
+        dry_run: bool,
+        /// write the converted manifest to this file instead of importing
+        #[arg(short, long)]
+        output: Option<String>,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum InspectCommands {
+    /// print the raft index of data dir
+    Index {
+        /// data dir path
+        data_dir: String,
+    },
+    /// print snapshot header and records as json lines
+    Snapshot {
+        /// snapshot file path
+        path: String,
+        /// max record count
+        #[arg(short, long)]
+        limit: Option<u64>,
+    },
+    /// print raft log entries as json lines
+    Logs {
+        /// data dir path
+        data_dir: String,
+        /// start log index
+        #[arg(short, long)]
+        start: Option<u64>,
+        /// max entry count
+        #[arg(short, long, default_value_t = 100)]
+        limit: u64,
+        /// print the last entries
+        #[arg(short, long)]
+        tail: bool,
+    },
+    /// verify index, snapshot and log files
+    Verify {
+        /// data dir path
+        data_dir: String,
+    },
 }
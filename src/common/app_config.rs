@@ -10,6 +10,10 @@ pub struct AppConfig {
     pub http_api_port: u16,
     pub xxl_job_prefix_path: String,
     pub xxl_default_access_token: String,
+    /// 是否开启xxl-job admin管理接口兼容(jobinfo、joblog)
+    pub xxl_admin_api_enable: bool,
+    /// xxl-job执行器分组id到应用的映射,如 `1:app-a,2:ns://dev/app-b`
+    pub xxl_admin_group_mapping: String,
     pub app_instance_health_timeout: u32,
     pub http_console_port: u16,
     pub http_workers: Option<usize>,
@@ -21,17 +25,53 @@ pub struct AppConfig {
     pub raft_auto_init: bool,
     pub raft_join_addr: String,
     pub raft_snapshot_log_size: u64,
+    pub raft_log_retain_size: u64,
+    pub raft_log_compress: String,
+    /// raft日志内容是否使用MessagePack编码，集群全部节点升级后才能开启
+    pub raft_log_msgpack_enable: bool,
     pub cluster_token: Arc<String>,
     pub metrics_enable: bool,
     pub metrics_collect_interval_second: u64,
     pub metrics_log_interval_second: u64,
     pub metrics_log_enable: bool,
     pub task_request_parallel: usize,
+    /// HTTP任务同步等待响应，单独限制并行数，避免占满执行器任务的下发
+    pub task_http_request_parallel: usize,
     pub console_captcha_enable: bool,
     pub console_login_timeout: i32,
     pub init_admin_username: String,
     pub init_admin_password: String,
     pub job_task_log_limit: usize,
+    pub job_version_limit: usize,
+    pub job_glue_version_limit: usize,
+    pub schedule_partition_enable: bool,
+    pub backup_dir: String,
+    pub oidc_issuer: String,
+    pub oidc_client_id: String,
+    pub oidc_client_secret: String,
+    pub oidc_redirect_uri: String,
+    pub oidc_scopes: String,
+    pub oidc_username_claim: String,
+    pub oidc_role_claim: String,
+    pub oidc_role_mapping: String,
+    pub oidc_default_role: String,
+    pub oidc_namespace_claim: String,
+    pub ldap_url: String,
+    pub ldap_bind_dn: String,
+    pub ldap_bind_password: String,
+    pub ldap_base_dn: String,
+    pub ldap_user_filter: String,
+    pub ldap_username_attr: String,
+    pub ldap_nickname_attr: String,
+    pub ldap_group_attr: String,
+    pub ldap_role_mapping: String,
+    pub ldap_namespace_mapping: String,
+    pub ldap_default_role: String,
+    pub ldap_sync_interval_second: u64,
+    pub audit_retain_days: u64,
+    pub audit_max_count: usize,
+    /// 可信的反向代理ip，只有来自这些地址的请求才使用X-Forwarded-For等请求头中的来源ip
+    pub audit_trusted_proxies: Vec<String>,
 }
 
 impl AppConfig {
@@ -43,6 +83,12 @@ impl AppConfig {
         let xxl_job_prefix_path = Self::get_xxl_job_prefix_path();
         let xxl_default_access_token =
             std::env::var("RATCH_XXL_DEFAULT_ACCESS_TOKEN").unwrap_or("default_token".to_string());
+        let xxl_admin_api_enable = std::env::var("RATCH_XXL_ADMIN_API_ENABLE")
+            .unwrap_or("false".to_owned())
+            .parse()
+            .unwrap_or(false);
+        let xxl_admin_group_mapping =
+            std::env::var("RATCH_XXL_ADMIN_GROUP_MAPPING").unwrap_or_default();
         let http_api_port = std::env::var("RATCH_HTTP_API_PORT")
             .unwrap_or_default()
             .parse()
@@ -85,6 +131,15 @@ impl AppConfig {
             .unwrap_or("10000".to_owned())
             .parse()
             .unwrap_or(10000);
+        let raft_log_retain_size = std::env::var("RATCH_RAFT_LOG_RETAIN_SIZE")
+            .unwrap_or("".to_owned())
+            .parse()
+            .unwrap_or(raft_snapshot_log_size);
+        let raft_log_compress = std::env::var("RATCH_RAFT_LOG_COMPRESS").unwrap_or_default();
+        let raft_log_msgpack_enable = std::env::var("RATCH_RAFT_LOG_MSGPACK_ENABLE")
+            .unwrap_or("false".to_owned())
+            .parse()
+            .unwrap_or(false);
         let metrics_log_enable = std::env::var("RATCH_METRICS_ENABLE_LOG")
             .unwrap_or("false".to_owned())
             .parse()
@@ -113,6 +168,10 @@ impl AppConfig {
             .unwrap_or("20".to_owned())
             .parse()
             .unwrap_or(20);
+        let task_http_request_parallel = std::env::var("RATCH_TASK_HTTP_REQUEST_PARALLEL")
+            .unwrap_or("20".to_owned())
+            .parse()
+            .unwrap_or(20);
         let console_captcha_enable = std::env::var("RATCH_CONSOLE_ENABLE_CAPTCHA")
             .unwrap_or("true".to_owned())
             .parse()
@@ -134,11 +193,96 @@ impl AppConfig {
         if job_task_log_limit < 20 {
             job_task_log_limit = 20;
         }
+        let mut job_version_limit = std::env::var("RATCH_JOB_VERSION_LIMIT")
+            .unwrap_or("20".to_owned())
+            .parse()
+            .unwrap_or(20);
+        if job_version_limit < 1 {
+            job_version_limit = 1;
+        }
+        let mut job_glue_version_limit = std::env::var("RATCH_JOB_GLUE_VERSION_LIMIT")
+            .unwrap_or("30".to_owned())
+            .parse()
+            .unwrap_or(30);
+        if job_glue_version_limit < 1 {
+            job_glue_version_limit = 1;
+        }
+        let schedule_partition_enable = std::env::var("RATCH_SCHEDULE_PARTITION_ENABLE")
+            .unwrap_or("false".to_owned())
+            .parse()
+            .unwrap_or(false);
+        let backup_dir = std::env::var("RATCH_BACKUP_DIR")
+            .unwrap_or_else(|_| format!("{}_backup", &local_db_dir));
+        let oidc_issuer = std::env::var("RATCH_OIDC_ISSUER").unwrap_or_default();
+        let oidc_client_id = std::env::var("RATCH_OIDC_CLIENT_ID").unwrap_or_default();
+        let oidc_client_secret = std::env::var("RATCH_OIDC_CLIENT_SECRET").unwrap_or_default();
+        let oidc_redirect_uri = std::env::var("RATCH_OIDC_REDIRECT_URI").unwrap_or_else(|_| {
+            format!(
+                "http://127.0.0.1:{}/ratchjob/api/console/v1/login/oidc/callback",
+                &http_console_port
+            )
+        });
+        let oidc_scopes = StringUtils::map_not_empty(std::env::var("RATCH_OIDC_SCOPES").ok())
+            .unwrap_or("openid profile email".to_owned());
+        let oidc_username_claim =
+            StringUtils::map_not_empty(std::env::var("RATCH_OIDC_USERNAME_CLAIM").ok())
+                .unwrap_or("preferred_username".to_owned());
+        let oidc_role_claim =
+            StringUtils::map_not_empty(std::env::var("RATCH_OIDC_ROLE_CLAIM").ok())
+                .unwrap_or("groups".to_owned());
+        let oidc_role_mapping = std::env::var("RATCH_OIDC_ROLE_MAPPING").unwrap_or_default();
+        let oidc_default_role =
+            std::env::var("RATCH_OIDC_DEFAULT_ROLE").unwrap_or("visitor".to_owned());
+        let oidc_namespace_claim = std::env::var("RATCH_OIDC_NAMESPACE_CLAIM").unwrap_or_default();
+        let ldap_url = std::env::var("RATCH_LDAP_URL").unwrap_or_default();
+        let ldap_bind_dn = std::env::var("RATCH_LDAP_BIND_DN").unwrap_or_default();
+        let ldap_bind_password = std::env::var("RATCH_LDAP_BIND_PASSWORD").unwrap_or_default();
+        let ldap_base_dn = std::env::var("RATCH_LDAP_BASE_DN").unwrap_or_default();
+        let ldap_user_filter =
+            StringUtils::map_not_empty(std::env::var("RATCH_LDAP_USER_FILTER").ok())
+                .unwrap_or("(uid={username})".to_owned());
+        let ldap_username_attr =
+            StringUtils::map_not_empty(std::env::var("RATCH_LDAP_USERNAME_ATTR").ok())
+                .unwrap_or("uid".to_owned());
+        let ldap_nickname_attr =
+            StringUtils::map_not_empty(std::env::var("RATCH_LDAP_NICKNAME_ATTR").ok())
+                .unwrap_or("cn".to_owned());
+        let ldap_group_attr =
+            StringUtils::map_not_empty(std::env::var("RATCH_LDAP_GROUP_ATTR").ok())
+                .unwrap_or("memberOf".to_owned());
+        let ldap_role_mapping = std::env::var("RATCH_LDAP_ROLE_MAPPING").unwrap_or_default();
+        let ldap_namespace_mapping =
+            std::env::var("RATCH_LDAP_NAMESPACE_MAPPING").unwrap_or_default();
+        let ldap_default_role =
+            std::env::var("RATCH_LDAP_DEFAULT_ROLE").unwrap_or("visitor".to_owned());
+        let ldap_sync_interval_second = std::env::var("RATCH_LDAP_SYNC_INTERVAL_SECOND")
+            .unwrap_or("600".to_owned())
+            .parse()
+            .unwrap_or(600);
+        let audit_retain_days = std::env::var("RATCH_AUDIT_RETAIN_DAYS")
+            .unwrap_or("90".to_owned())
+            .parse()
+            .unwrap_or(90);
+        let mut audit_max_count = std::env::var("RATCH_AUDIT_MAX_COUNT")
+            .unwrap_or("100000".to_owned())
+            .parse()
+            .unwrap_or(100000);
+        if audit_max_count < 100 {
+            audit_max_count = 100;
+        }
+        let audit_trusted_proxies = std::env::var("RATCH_AUDIT_TRUSTED_PROXIES")
+            .unwrap_or_default()
+            .split(',')
+            .map(|v| v.trim().to_owned())
+            .filter(|v| !v.is_empty())
+            .collect();
         Self {
             local_db_dir,
             http_api_port,
             xxl_job_prefix_path,
             xxl_default_access_token,
+            xxl_admin_api_enable,
+            xxl_admin_group_mapping,
             app_instance_health_timeout,
             http_console_port,
             http_workers,
@@ -151,16 +295,49 @@ impl AppConfig {
             raft_auto_init,
             raft_join_addr,
             raft_snapshot_log_size,
+            raft_log_retain_size,
+            raft_log_compress,
+            raft_log_msgpack_enable,
             metrics_enable,
             metrics_log_enable,
             metrics_collect_interval_second,
             metrics_log_interval_second,
             task_request_parallel,
+            task_http_request_parallel,
             console_captcha_enable,
             console_login_timeout,
             init_admin_username,
             init_admin_password,
             job_task_log_limit,
+            job_version_limit,
+            job_glue_version_limit,
+            schedule_partition_enable,
+            backup_dir,
+            oidc_issuer,
+            oidc_client_id,
+            oidc_client_secret,
+            oidc_redirect_uri,
+            oidc_scopes,
+            oidc_username_claim,
+            oidc_role_claim,
+            oidc_role_mapping,
+            oidc_default_role,
+            oidc_namespace_claim,
+            ldap_url,
+            ldap_bind_dn,
+            ldap_bind_password,
+            ldap_base_dn,
+            ldap_user_filter,
+            ldap_username_attr,
+            ldap_nickname_attr,
+            ldap_group_attr,
+            ldap_role_mapping,
+            ldap_namespace_mapping,
+            ldap_default_role,
+            ldap_sync_interval_second,
+            audit_retain_days,
+            audit_max_count,
+            audit_trusted_proxies,
         }
     }
 
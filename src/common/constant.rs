@@ -24,6 +24,11 @@ lazy_static::lazy_static! {
     pub static ref CACHE_TABLE_NAME: Arc<String> =  Arc::new("T_CACHE".to_string());
     pub static ref USER_TABLE_NAME: Arc<String> =  Arc::new("T_USER".to_string());
     pub static ref NAMESPACE_TABLE_NAME: Arc<String> =  Arc::new("T_NAMESPACE".to_string());
+    pub static ref AUDIT_TABLE_NAME: Arc<String> =  Arc::new("T_AUDIT".to_string());
+    pub static ref JOB_VERSION_TABLE_NAME: Arc<String> =  Arc::new("T_JOB_VERSION".to_string());
+    pub static ref JOB_GLUE_TABLE_NAME: Arc<String> =  Arc::new("T_JOB_GLUE".to_string());
+    pub static ref SCHEDULE_PARTITION_TABLE_NAME: Arc<String> =  Arc::new("T_SCHEDULE_PARTITION".to_string());
+    pub static ref SCHEDULE_PAUSE_TABLE_NAME: Arc<String> =  Arc::new("T_SCHEDULE_PAUSE".to_string());
 
     pub static ref SEQ_JOB_ID: Arc<String> =  Arc::new("job_id".to_string());
     pub static ref SEQ_TASK_ID: Arc<String> =  Arc::new("task_id".to_string());
@@ -33,4 +38,6 @@ lazy_static::lazy_static! {
     pub static ref ERR_MSG_NOT_FOUND_APP_INSTANCE_ADDR: Arc<String> =  Arc::new("Not found the application instance address".to_string());
     pub static ref ERR_MSG_JOB_DISABLE: Arc<String> =  Arc::new("Job is disabled or not found".to_string());
     pub static ref ERR_MSG_TASK_TIMEOUT: Arc<String> =  Arc::new("Task timed out".to_string());
+    pub static ref ERR_MSG_TASK_CANCELLED: Arc<String> =  Arc::new("Task cancelled".to_string());
+    pub static ref ERR_MSG_HTTP_CONFIG_EMPTY: Arc<String> =  Arc::new("The http job config is empty".to_string());
 }
@@ -1,7 +1,116 @@
 use chrono::{DateTime, FixedOffset, TimeZone, Utc};
 use cron::Schedule;
+use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
+pub(crate) const CRON_SHORTHANDS: [&str; 5] =
+    ["@yearly", "@monthly", "@weekly", "@daily", "@hourly"];
+
+///
+/// cron表达式的字段定义: 秒 分 时 日 月 周 [年]
+/// 周取值1为周日,7为周六
+pub(crate) struct CronFieldDef {
+    pub name: &'static str,
+    pub min: u32,
+    pub max: u32,
+}
+
+pub(crate) const CRON_FIELDS: [CronFieldDef; 7] = [
+    CronFieldDef {
+        name: "second",
+        min: 0,
+        max: 59,
+    },
+    CronFieldDef {
+        name: "minute",
+        min: 0,
+        max: 59,
+    },
+    CronFieldDef {
+        name: "hour",
+        min: 0,
+        max: 23,
+    },
+    CronFieldDef {
+        name: "day-of-month",
+        min: 1,
+        max: 31,
+    },
+    CronFieldDef {
+        name: "month",
+        min: 1,
+        max: 12,
+    },
+    CronFieldDef {
+        name: "day-of-week",
+        min: 1,
+        max: 7,
+    },
+    CronFieldDef {
+        name: "year",
+        min: 1970,
+        max: 2100,
+    },
+];
+
+///
+/// cron表达式校验错误
+/// position为出错位置(从1开始的字符列),field为出错字段名,整体错误时为空
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CronValueError {
+    pub position: usize,
+    pub field: String,
+    pub value: String,
+    pub message: String,
+}
+
+impl CronValueError {
+    fn new(position: usize, field: &str, value: &str, message: String) -> Self {
+        Self {
+            position,
+            field: field.to_owned(),
+            value: value.to_owned(),
+            message,
+        }
+    }
+}
+
+impl std::fmt::Display for CronValueError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.field.is_empty() {
+            write!(f, "{} (column {})", self.message, self.position)
+        } else {
+            write!(
+                f,
+                "{} field '{}' at column {}: {}",
+                self.field, self.value, self.position, self.message
+            )
+        }
+    }
+}
+
+impl std::error::Error for CronValueError {}
+
+/// 按空白拆分,返回(起始列,内容),列从1开始
+fn split_with_position(value: &str, separator: fn(char) -> bool) -> Vec<(usize, &str)> {
+    let mut items = Vec::new();
+    let mut start: Option<(usize, usize)> = None;
+    for (column, (index, c)) in value.char_indices().enumerate() {
+        if separator(c) {
+            if let Some((start_column, start_index)) = start.take() {
+                items.push((start_column + 1, &value[start_index..index]));
+            }
+        } else if start.is_none() {
+            start = Some((column, index));
+        }
+    }
+    if let Some((start_column, start_index)) = start {
+        items.push((start_column + 1, &value[start_index..]));
+    }
+    items
+}
+
 pub struct CronUtil;
 
 impl CronUtil {
@@ -9,6 +118,152 @@ impl CronUtil {
         Schedule::from_str(cron_value).is_ok()
     }
 
+    ///
+    /// 校验cron表达式,是否合法以cron库解析结果为准
+    /// 解析失败时逐个字段定位出错位置
+    pub fn check_cron_value(cron_value: &str) -> Result<Schedule, CronValueError> {
+        match Schedule::from_str(cron_value) {
+            Ok(v) => Ok(v),
+            Err(e) => Err(Self::locate_cron_error(cron_value)
+                .unwrap_or_else(|| CronValueError::new(1, "", cron_value, e.to_string()))),
+        }
+    }
+
+    fn locate_cron_error(cron_value: &str) -> Option<CronValueError> {
+        let fields = split_with_position(cron_value, char::is_whitespace);
+        if fields.is_empty() {
+            return Some(CronValueError::new(
+                1,
+                "",
+                cron_value,
+                "cron_value is empty".to_owned(),
+            ));
+        }
+        let (position, first) = fields[0];
+        if first.starts_with('@') {
+            return Some(CronValueError::new(
+                position,
+                "",
+                first,
+                format!(
+                    "unknown shorthand, supported: {}",
+                    CRON_SHORTHANDS.join(",")
+                ),
+            ));
+        }
+        if fields.len() < 6 {
+            let end = cron_value.trim_end().chars().count() + 1;
+            let message = if fields.len() == 5 {
+                format!(
+                    "expected 6 or 7 fields (second minute hour day-of-month month day-of-week [year]), found 5; 5-field unix cron is not supported, add the second field, e.g. '0 {}'",
+                    cron_value.trim()
+                )
+            } else {
+                format!(
+                    "expected 6 or 7 fields (second minute hour day-of-month month day-of-week [year]), found {}",
+                    fields.len()
+                )
+            };
+            return Some(CronValueError::new(end, "", cron_value, message));
+        }
+        if fields.len() > 7 {
+            let (position, value) = fields[7];
+            return Some(CronValueError::new(
+                position,
+                "",
+                value,
+                format!(
+                    "expected 6 or 7 fields (second minute hour day-of-month month day-of-week [year]), found {}",
+                    fields.len()
+                ),
+            ));
+        }
+        for (index, (position, value)) in fields.iter().enumerate() {
+            if let Some(e) = Self::check_cron_field(index, *position, value) {
+                return Some(e);
+            }
+        }
+        None
+    }
+
+    /// 其它字段使用`*`,单独解析该字段
+    fn parse_single_field(index: usize, value: &str) -> bool {
+        let expression: Vec<&str> = CRON_FIELDS[..std::cmp::max(index + 1, 6)]
+            .iter()
+            .enumerate()
+            .map(|(i, _)| if i == index { value } else { "*" })
+            .collect();
+        Schedule::from_str(&expression.join(" ")).is_ok()
+    }
+
+    fn check_cron_field(index: usize, position: usize, value: &str) -> Option<CronValueError> {
+        if Self::parse_single_field(index, value) {
+            return None;
+        }
+        let def = &CRON_FIELDS[index];
+        for (item_position, item) in split_with_position(value, |c| c == ',') {
+            if !Self::parse_single_field(index, item) {
+                let position = position + item_position - 1;
+                let message = Self::explain_item_error(def, item);
+                return Some(CronValueError::new(position, def.name, item, message));
+            }
+        }
+        // 存在空的列表项,如`1,,2`
+        let empty_offset = if value.starts_with(',') {
+            Some(0)
+        } else if value.ends_with(',') {
+            Some(value.len())
+        } else {
+            value.find(",,").map(|v| v + 1)
+        };
+        let (position, message) = match empty_offset {
+            Some(offset) => (
+                position + value[..offset].chars().count(),
+                "list item is empty".to_owned(),
+            ),
+            None => (
+                position,
+                format!("'{}' is not a valid {} expression", value, def.name),
+            ),
+        };
+        Some(CronValueError::new(position, def.name, value, message))
+    }
+
+    fn explain_item_error(def: &CronFieldDef, item: &str) -> String {
+        if item.contains(['L', 'W', '#']) {
+            return "'L', 'W' and '#' are not supported".to_owned();
+        }
+        if item.contains('?') && def.name != "day-of-month" && def.name != "day-of-week" {
+            return "'?' is only allowed in day-of-month and day-of-week".to_owned();
+        }
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (range, Some(step)),
+            None => (item, None),
+        };
+        if let Some(step) = step {
+            match step.parse::<u32>() {
+                Ok(0) => return "step must be greater than 0".to_owned(),
+                Err(_) => return format!("step '{}' is not a number", step),
+                _ => {}
+            }
+        }
+        for v in range.split('-') {
+            if let Ok(v) = v.parse::<u32>() {
+                if v < def.min || v > def.max {
+                    return format!("value {} is out of range {}-{}", v, def.min, def.max);
+                }
+            }
+        }
+        if let Some((start, end)) = range.split_once('-') {
+            if let (Ok(start), Ok(end)) = (start.parse::<u32>(), end.parse::<u32>()) {
+                if start > end {
+                    return format!("range start {} is greater than end {}", start, end);
+                }
+            }
+        }
+        format!("'{}' is not a valid {} expression", item, def.name)
+    }
+
     pub fn next_cron_time_by_timestamp(
         cron_schedule: &Schedule,
         fixed_offset: &FixedOffset,
@@ -64,4 +319,51 @@ mod tests {
         println!("next_time:{}", next_time);
         Ok(())
     }
+
+    #[test]
+    fn test_check_cron_value() {
+        assert!(CronUtil::check_cron_value("0 0/30 9-17 * * MON-FRI").is_ok());
+        assert!(CronUtil::check_cron_value("@daily").is_ok());
+        let e = CronUtil::check_cron_value("0 0 25 * * *").unwrap_err();
+        assert_eq!((e.position, e.field.as_str()), (5, "hour"));
+        let e = CronUtil::check_cron_value("0 1,2,61 * * * *").unwrap_err();
+        assert_eq!((e.position, e.value.as_str()), (7, "61"));
+        let e = CronUtil::check_cron_value("*/5 * * * *").unwrap_err();
+        assert_eq!((e.position, e.field.as_str()), (12, ""));
+        let e = CronUtil::check_cron_value("0 0 12 ? * 1#2").unwrap_err();
+        assert_eq!((e.position, e.field.as_str()), (12, "day-of-week"));
+        let e = CronUtil::check_cron_value("0 0 1,,2 * * *").unwrap_err();
+        assert_eq!(e.position, 7);
+    }
+
+    #[test]
+    fn test_check_cron_value_same_as_cron() {
+        // 校验结果需与cron库解析一致,不能比cron库更严格
+        for cron_value in [
+            "* * * * * *",
+            "0 0/30 9-17 * * MON-FRI",
+            "@daily",
+            "@hourly",
+            "0 0 12 ? * *",
+            "0 0 12 1 JAN-MAR *",
+            "0 15 10 * * Mon,Wed 2030",
+            "0 0 3 * * * 2020",
+            "0 30 9 * * *",
+            "0 0/20 * * * *",
+            "0 0 25 * * *",
+            "0 1,2,61 * * * *",
+            "*/5 * * * *",
+            "0 0 12 ? * 1#2",
+            "0 0 1,,2 * * *",
+            "0 0 12 * * FOO",
+            "",
+        ] {
+            assert_eq!(
+                CronUtil::check_cron_value(cron_value).is_ok(),
+                CronUtil::check_cron_valid(cron_value),
+                "cron_value:{}",
+                cron_value
+            );
+        }
+    }
 }
@@ -51,3 +51,64 @@ pub fn get_datetime_millis(
 ) -> Option<DateTime<FixedOffset>> {
     DateTime::<Utc>::from_timestamp_millis(mills).map(|v| v.with_timezone(fixed_offset))
 }
+
+///
+/// 解析时区偏移,支持`+08:00`、`+0800`、`+8`、`UTC+8`、`GMT-05:30`、`Z`、`UTC`
+/// 不依赖时区库,不支持`Asia/Shanghai`这类时区名称
+pub fn parse_fixed_offset(value: &str) -> anyhow::Result<FixedOffset> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "time zone '{}' is invalid, use offset like +08:00 or UTC+8",
+            value
+        )
+    };
+    let mut v = value.trim();
+    for prefix in ["UTC", "GMT"] {
+        if v.get(..3).is_some_and(|p| p.eq_ignore_ascii_case(prefix)) {
+            v = &v[3..];
+            break;
+        }
+    }
+    if v.is_empty() || v == "Z" {
+        return Ok(FixedOffset::east_opt(0).unwrap());
+    }
+    let sign = match v.chars().next() {
+        Some('+') => 1,
+        Some('-') => -1,
+        _ => return Err(invalid()),
+    };
+    let v = &v[1..];
+    if !v.chars().all(|c| c.is_ascii_digit() || c == ':') {
+        return Err(invalid());
+    }
+    let (hour, minute) = if let Some((hour, minute)) = v.split_once(':') {
+        (hour, minute)
+    } else if v.len() == 4 {
+        v.split_at(2)
+    } else {
+        (v, "0")
+    };
+    match (hour.parse::<i32>(), minute.parse::<i32>()) {
+        (Ok(hour), Ok(minute)) if hour <= 14 && minute < 60 => {
+            FixedOffset::east_opt(sign * (hour * 3600 + minute * 60)).ok_or_else(invalid)
+        }
+        _ => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fixed_offset() {
+        let offset = |v: &str| parse_fixed_offset(v).map(|v| v.local_minus_utc()).ok();
+        assert_eq!(offset("+08:00"), Some(8 * 3600));
+        assert_eq!(offset("UTC+8"), Some(8 * 3600));
+        assert_eq!(offset("gmt-0530"), Some(-(5 * 3600 + 30 * 60)));
+        assert_eq!(offset("Z"), Some(0));
+        assert_eq!(offset("UTC"), Some(0));
+        assert!(offset("Asia/Shanghai").is_none());
+        assert!(offset("+25").is_none());
+    }
+}
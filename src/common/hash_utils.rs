@@ -8,3 +8,12 @@ pub fn get_hash_value<T: Hash>(v: &T) -> u64 {
     v.hash(&mut hasher);
     hasher.finish()
 }
+
+/// 跨版本稳定的哈希值(crc32),用于需要各节点计算结果一致的场景
+pub fn get_stable_hash_value(values: &[u64]) -> u64 {
+    let mut hasher = crc32fast::Hasher::new();
+    for v in values {
+        hasher.update(&v.to_le_bytes());
+    }
+    hasher.finalize() as u64
+}
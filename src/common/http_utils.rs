@@ -54,6 +54,29 @@ impl HttpUtils {
         })
     }
 
+    /// 只读取前max_body_len字节的响应内容，其余部分丢弃
+    async fn get_limit_response_wrap(
+        mut resp: reqwest::Response,
+        max_body_len: usize,
+    ) -> anyhow::Result<ResponseWrap> {
+        let status = resp.status().as_u16();
+        let mut body = Vec::new();
+        while body.len() < max_body_len {
+            match resp.chunk().await? {
+                Some(chunk) => {
+                    let len = std::cmp::min(chunk.len(), max_body_len - body.len());
+                    body.extend_from_slice(&chunk[..len]);
+                }
+                None => break,
+            }
+        }
+        Ok(ResponseWrap {
+            status,
+            headers: vec![],
+            body,
+        })
+    }
+
     pub async fn request(
         client: &reqwest::Client,
         method_name: &str,
@@ -62,11 +85,43 @@ impl HttpUtils {
         headers: Option<&HashMap<String, String>>,
         timeout_millis: Option<u64>,
     ) -> anyhow::Result<ResponseWrap> {
+        let req_builer =
+            Self::build_request(client, method_name, url, body, headers, timeout_millis);
+        let res = req_builer.send().await?;
+        Self::get_response_wrap(res).await
+    }
+
+    /// 发起请求，响应内容最多读取max_body_len字节，不返回响应头
+    pub async fn request_limit_body(
+        client: &reqwest::Client,
+        method_name: &str,
+        url: &str,
+        body: Vec<u8>,
+        headers: Option<&HashMap<String, String>>,
+        timeout_millis: Option<u64>,
+        max_body_len: usize,
+    ) -> anyhow::Result<ResponseWrap> {
+        let req_builer =
+            Self::build_request(client, method_name, url, body, headers, timeout_millis);
+        let res = req_builer.send().await?;
+        Self::get_limit_response_wrap(res, max_body_len).await
+    }
+
+    fn build_request(
+        client: &reqwest::Client,
+        method_name: &str,
+        url: &str,
+        body: Vec<u8>,
+        headers: Option<&HashMap<String, String>>,
+        timeout_millis: Option<u64>,
+    ) -> reqwest::RequestBuilder {
         let mut req_builer = match method_name {
             "GET" => client.get(url),
             "POST" => client.post(url),
             "PUT" => client.put(url),
             "DELETE" => client.delete(url),
+            "PATCH" => client.patch(url),
+            "HEAD" => client.head(url),
             _ => client.post(url),
         };
         if let Some(headers) = headers {
@@ -80,7 +135,6 @@ impl HttpUtils {
         if !body.is_empty() {
             req_builer = req_builer.body(body);
         }
-        let res = req_builer.send().await?;
-        Self::get_response_wrap(res).await
+        req_builer
     }
 }
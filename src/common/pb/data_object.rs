@@ -17,7 +17,7 @@ use quick_protobuf::sizeofs::*;
 use super::*;
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
 pub struct JobDo<'a> {
     pub id: u64,
     pub enable: bool,
@@ -41,6 +41,13 @@ pub struct JobDo<'a> {
     pub create_time: u64,
     pub retry_interval: u32,
     pub job_key: Cow<'a, str>,
+    pub glue_source: Cow<'a, str>,
+    pub glue_update_time: u64,
+    pub http_config: Option<data_object::JobHttpConfigDo<'a>>,
+    pub retry_policy: Option<data_object::JobRetryPolicyDo<'a>>,
+    pub labels: KVMap<Cow<'a, str>, Cow<'a, str>>,
+    pub allow_time_window: Cow<'a, str>,
+    pub forbid_time_window: Cow<'a, str>,
 }
 
 impl<'a> MessageRead<'a> for JobDo<'a> {
@@ -70,6 +77,16 @@ impl<'a> MessageRead<'a> for JobDo<'a> {
                 Ok(160) => msg.create_time = r.read_uint64(bytes)?,
                 Ok(168) => msg.retry_interval = r.read_uint32(bytes)?,
                 Ok(178) => msg.job_key = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(186) => msg.glue_source = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(192) => msg.glue_update_time = r.read_uint64(bytes)?,
+                Ok(202) => msg.http_config = Some(r.read_message::<data_object::JobHttpConfigDo>(bytes)?),
+                Ok(210) => msg.retry_policy = Some(r.read_message::<data_object::JobRetryPolicyDo>(bytes)?),
+                Ok(218) => {
+                    let (key, value) = r.read_map(bytes, |r, bytes| Ok(r.read_string(bytes).map(Cow::Borrowed)?), |r, bytes| Ok(r.read_string(bytes).map(Cow::Borrowed)?))?;
+                    msg.labels.insert(key, value);
+                }
+                Ok(226) => msg.allow_time_window = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(234) => msg.forbid_time_window = r.read_string(bytes).map(Cow::Borrowed)?,
                 Ok(t) => { r.read_unknown(bytes, t)?; }
                 Err(e) => return Err(e),
             }
@@ -103,6 +120,13 @@ impl<'a> MessageWrite for JobDo<'a> {
         + if self.create_time == 0u64 { 0 } else { 2 + sizeof_varint(*(&self.create_time) as u64) }
         + if self.retry_interval == 0u32 { 0 } else { 2 + sizeof_varint(*(&self.retry_interval) as u64) }
         + if self.job_key == "" { 0 } else { 2 + sizeof_len((&self.job_key).len()) }
+        + if self.glue_source == "" { 0 } else { 2 + sizeof_len((&self.glue_source).len()) }
+        + if self.glue_update_time == 0u64 { 0 } else { 2 + sizeof_varint(*(&self.glue_update_time) as u64) }
+        + self.http_config.as_ref().map_or(0, |m| 2 + sizeof_len((m).get_size()))
+        + self.retry_policy.as_ref().map_or(0, |m| 2 + sizeof_len((m).get_size()))
+        + self.labels.iter().map(|(k, v)| 2 + sizeof_len(2 + sizeof_len((k).len()) + sizeof_len((v).len()))).sum::<usize>()
+        + if self.allow_time_window == "" { 0 } else { 2 + sizeof_len((&self.allow_time_window).len()) }
+        + if self.forbid_time_window == "" { 0 } else { 2 + sizeof_len((&self.forbid_time_window).len()) }
     }
 
     fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
@@ -128,12 +152,155 @@ impl<'a> MessageWrite for JobDo<'a> {
         if self.create_time != 0u64 { w.write_with_tag(160, |w| w.write_uint64(*&self.create_time))?; }
         if self.retry_interval != 0u32 { w.write_with_tag(168, |w| w.write_uint32(*&self.retry_interval))?; }
         if self.job_key != "" { w.write_with_tag(178, |w| w.write_string(&**&self.job_key))?; }
+        if self.glue_source != "" { w.write_with_tag(186, |w| w.write_string(&**&self.glue_source))?; }
+        if self.glue_update_time != 0u64 { w.write_with_tag(192, |w| w.write_uint64(*&self.glue_update_time))?; }
+        if let Some(ref s) = self.http_config { w.write_with_tag(202, |w| w.write_message(s))?; }
+        if let Some(ref s) = self.retry_policy { w.write_with_tag(210, |w| w.write_message(s))?; }
+        for (k, v) in self.labels.iter() { w.write_with_tag(218, |w| w.write_map(2 + sizeof_len((k).len()) + sizeof_len((v).len()), 10, |w| w.write_string(&**k), 18, |w| w.write_string(&**v)))?; }
+        if self.allow_time_window != "" { w.write_with_tag(226, |w| w.write_string(&**&self.allow_time_window))?; }
+        if self.forbid_time_window != "" { w.write_with_tag(234, |w| w.write_string(&**&self.forbid_time_window))?; }
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
+pub struct JobHttpHeaderDo<'a> {
+    pub name: Cow<'a, str>,
+    pub value: Cow<'a, str>,
+}
+
+impl<'a> MessageRead<'a> for JobHttpHeaderDo<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.name = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(18) => msg.value = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { r.read_unknown(bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for JobHttpHeaderDo<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + if self.name == "" { 0 } else { 1 + sizeof_len((&self.name).len()) }
+        + if self.value == "" { 0 } else { 1 + sizeof_len((&self.value).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.name != "" { w.write_with_tag(10, |w| w.write_string(&**&self.name))?; }
+        if self.value != "" { w.write_with_tag(18, |w| w.write_string(&**&self.value))?; }
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
+pub struct JobHttpConfigDo<'a> {
+    pub method: Cow<'a, str>,
+    pub url: Cow<'a, str>,
+    pub headers: Vec<data_object::JobHttpHeaderDo<'a>>,
+    pub body: Cow<'a, str>,
+    pub expected_status: Cow<'a, str>,
+    pub timeout_second: u32,
+}
+
+impl<'a> MessageRead<'a> for JobHttpConfigDo<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.method = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(18) => msg.url = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(26) => msg.headers.push(r.read_message::<data_object::JobHttpHeaderDo>(bytes)?),
+                Ok(34) => msg.body = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(42) => msg.expected_status = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(48) => msg.timeout_second = r.read_uint32(bytes)?,
+                Ok(t) => { r.read_unknown(bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for JobHttpConfigDo<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + if self.method == "" { 0 } else { 1 + sizeof_len((&self.method).len()) }
+        + if self.url == "" { 0 } else { 1 + sizeof_len((&self.url).len()) }
+        + self.headers.iter().map(|s| 1 + sizeof_len((s).get_size())).sum::<usize>()
+        + if self.body == "" { 0 } else { 1 + sizeof_len((&self.body).len()) }
+        + if self.expected_status == "" { 0 } else { 1 + sizeof_len((&self.expected_status).len()) }
+        + if self.timeout_second == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.timeout_second) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.method != "" { w.write_with_tag(10, |w| w.write_string(&**&self.method))?; }
+        if self.url != "" { w.write_with_tag(18, |w| w.write_string(&**&self.url))?; }
+        for s in &self.headers { w.write_with_tag(26, |w| w.write_message(s))?; }
+        if self.body != "" { w.write_with_tag(34, |w| w.write_string(&**&self.body))?; }
+        if self.expected_status != "" { w.write_with_tag(42, |w| w.write_string(&**&self.expected_status))?; }
+        if self.timeout_second != 0u32 { w.write_with_tag(48, |w| w.write_uint32(*&self.timeout_second))?; }
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
+pub struct JobRetryPolicyDo<'a> {
+    pub backoff: Cow<'a, str>,
+    pub max_interval: u32,
+    pub jitter_percent: u32,
+    pub exclude_tried_instance: bool,
+    pub retry_on: Vec<Cow<'a, str>>,
+}
+
+impl<'a> MessageRead<'a> for JobRetryPolicyDo<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.backoff = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(16) => msg.max_interval = r.read_uint32(bytes)?,
+                Ok(24) => msg.jitter_percent = r.read_uint32(bytes)?,
+                Ok(32) => msg.exclude_tried_instance = r.read_bool(bytes)?,
+                Ok(42) => msg.retry_on.push(r.read_string(bytes).map(Cow::Borrowed)?),
+                Ok(t) => { r.read_unknown(bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for JobRetryPolicyDo<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + if self.backoff == "" { 0 } else { 1 + sizeof_len((&self.backoff).len()) }
+        + if self.max_interval == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.max_interval) as u64) }
+        + if self.jitter_percent == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.jitter_percent) as u64) }
+        + if self.exclude_tried_instance == false { 0 } else { 1 + sizeof_varint(*(&self.exclude_tried_instance) as u64) }
+        + self.retry_on.iter().map(|s| 1 + sizeof_len((s).len())).sum::<usize>()
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.backoff != "" { w.write_with_tag(10, |w| w.write_string(&**&self.backoff))?; }
+        if self.max_interval != 0u32 { w.write_with_tag(16, |w| w.write_uint32(*&self.max_interval))?; }
+        if self.jitter_percent != 0u32 { w.write_with_tag(24, |w| w.write_uint32(*&self.jitter_percent))?; }
+        if self.exclude_tried_instance != false { w.write_with_tag(32, |w| w.write_bool(*&self.exclude_tried_instance))?; }
+        for s in &self.retry_on { w.write_with_tag(42, |w| w.write_string(&**s))?; }
         Ok(())
     }
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
 pub struct TaskTryLogDo<'a> {
     pub execution_time: u32,
     pub addr: Cow<'a, str>,
@@ -169,7 +336,7 @@ impl<'a> MessageWrite for TaskTryLogDo<'a> {
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
 pub struct JobTaskDo<'a> {
     pub task_id: u64,
     pub job_id: u64,
@@ -190,6 +357,8 @@ pub struct JobTaskDo<'a> {
     pub trigger_user: Cow<'a, str>,
     pub namespace: Cow<'a, str>,
     pub app_name: Cow<'a, str>,
+    pub result: Cow<'a, str>,
+    pub fail_type: Cow<'a, str>,
 }
 
 impl<'a> MessageRead<'a> for JobTaskDo<'a> {
@@ -216,6 +385,8 @@ impl<'a> MessageRead<'a> for JobTaskDo<'a> {
                 Ok(138) => msg.trigger_user = r.read_string(bytes).map(Cow::Borrowed)?,
                 Ok(146) => msg.namespace = r.read_string(bytes).map(Cow::Borrowed)?,
                 Ok(154) => msg.app_name = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(162) => msg.result = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(170) => msg.fail_type = r.read_string(bytes).map(Cow::Borrowed)?,
                 Ok(t) => { r.read_unknown(bytes, t)?; }
                 Err(e) => return Err(e),
             }
@@ -246,6 +417,8 @@ impl<'a> MessageWrite for JobTaskDo<'a> {
         + if self.trigger_user == "" { 0 } else { 2 + sizeof_len((&self.trigger_user).len()) }
         + if self.namespace == "" { 0 } else { 2 + sizeof_len((&self.namespace).len()) }
         + if self.app_name == "" { 0 } else { 2 + sizeof_len((&self.app_name).len()) }
+        + if self.result == "" { 0 } else { 2 + sizeof_len((&self.result).len()) }
+        + if self.fail_type == "" { 0 } else { 2 + sizeof_len((&self.fail_type).len()) }
     }
 
     fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
@@ -268,12 +441,14 @@ impl<'a> MessageWrite for JobTaskDo<'a> {
         if self.trigger_user != "" { w.write_with_tag(138, |w| w.write_string(&**&self.trigger_user))?; }
         if self.namespace != "" { w.write_with_tag(146, |w| w.write_string(&**&self.namespace))?; }
         if self.app_name != "" { w.write_with_tag(154, |w| w.write_string(&**&self.app_name))?; }
+        if self.result != "" { w.write_with_tag(162, |w| w.write_string(&**&self.result))?; }
+        if self.fail_type != "" { w.write_with_tag(170, |w| w.write_string(&**&self.fail_type))?; }
         Ok(())
     }
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
 pub struct AppInstanceDo<'a> {
     pub addr: Cow<'a, str>,
     pub last_modified_time: u32,
@@ -313,7 +488,7 @@ impl<'a> MessageWrite for AppInstanceDo<'a> {
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
 pub struct AppInfoDo<'a> {
     pub app_name: Cow<'a, str>,
     pub namespace: Cow<'a, str>,
@@ -365,7 +540,7 @@ impl<'a> MessageWrite for AppInfoDo<'a> {
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
 pub struct CacheItemDo<'a> {
     pub cache_type: u32,
     pub key: Cow<'a, str>,
@@ -409,7 +584,7 @@ impl<'a> MessageWrite for CacheItemDo<'a> {
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
 pub struct PrivilegeGroupDo<'a> {
     pub enabled: bool,
     pub whitelist_is_all: bool,
@@ -457,7 +632,7 @@ impl<'a> MessageWrite for PrivilegeGroupDo<'a> {
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
 pub struct UserInfoDo<'a> {
     pub username: Cow<'a, str>,
     pub nickname: Cow<'a, str>,
@@ -469,6 +644,7 @@ pub struct UserInfoDo<'a> {
     pub extend_info: KVMap<Cow<'a, str>, Cow<'a, str>>,
     pub namespace_privilege: Option<data_object::PrivilegeGroupDo<'a>>,
     pub app_privilege: Option<data_object::PrivilegeGroupDo<'a>>,
+    pub source: Cow<'a, str>,
 }
 
 impl<'a> MessageRead<'a> for UserInfoDo<'a> {
@@ -489,6 +665,7 @@ impl<'a> MessageRead<'a> for UserInfoDo<'a> {
                 }
                 Ok(82) => msg.namespace_privilege = Some(r.read_message::<data_object::PrivilegeGroupDo>(bytes)?),
                 Ok(90) => msg.app_privilege = Some(r.read_message::<data_object::PrivilegeGroupDo>(bytes)?),
+                Ok(98) => msg.source = r.read_string(bytes).map(Cow::Borrowed)?,
                 Ok(t) => { r.read_unknown(bytes, t)?; }
                 Err(e) => return Err(e),
             }
@@ -510,6 +687,7 @@ impl<'a> MessageWrite for UserInfoDo<'a> {
         + self.extend_info.iter().map(|(k, v)| 1 + sizeof_len(2 + sizeof_len((k).len()) + sizeof_len((v).len()))).sum::<usize>()
         + self.namespace_privilege.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
         + self.app_privilege.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+        + if self.source == "" { 0 } else { 1 + sizeof_len((&self.source).len()) }
     }
 
     fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
@@ -523,12 +701,13 @@ impl<'a> MessageWrite for UserInfoDo<'a> {
         for (k, v) in self.extend_info.iter() { w.write_with_tag(74, |w| w.write_map(2 + sizeof_len((k).len()) + sizeof_len((v).len()), 10, |w| w.write_string(&**k), 18, |w| w.write_string(&**v)))?; }
         if let Some(ref s) = self.namespace_privilege { w.write_with_tag(82, |w| w.write_message(s))?; }
         if let Some(ref s) = self.app_privilege { w.write_with_tag(90, |w| w.write_message(s))?; }
+        if self.source != "" { w.write_with_tag(98, |w| w.write_string(&**&self.source))?; }
         Ok(())
     }
 }
 
 #[allow(clippy::derive_partial_eq_without_eq)]
-#[derive(Debug, Default, PartialEq, Clone)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
 pub struct NamespaceDo<'a> {
     pub id: Cow<'a, str>,
     pub name: Cow<'a, str>,
@@ -567,3 +746,275 @@ impl<'a> MessageWrite for NamespaceDo<'a> {
     }
 }
 
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
+pub struct SchedulePartitionDo {
+    pub version: u64,
+    pub node_ids: Vec<u64>,
+}
+
+impl<'a> MessageRead<'a> for SchedulePartitionDo {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.version = r.read_uint64(bytes)?,
+                Ok(18) => msg.node_ids = r.read_packed(bytes, |r, bytes| Ok(r.read_uint64(bytes)?))?,
+                Ok(t) => { r.read_unknown(bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl MessageWrite for SchedulePartitionDo {
+    fn get_size(&self) -> usize {
+        0
+        + if self.version == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.version) as u64) }
+        + if self.node_ids.is_empty() { 0 } else { 1 + sizeof_len(self.node_ids.iter().map(|s| sizeof_varint(*(s) as u64)).sum::<usize>()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.version != 0u64 { w.write_with_tag(8, |w| w.write_uint64(*&self.version))?; }
+        w.write_packed_with_tag(18, &self.node_ids, |w, m| w.write_uint64(*m), &|m| sizeof_varint(*(m) as u64))?;
+        Ok(())
+    }
+}
+
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
+pub struct SchedulePauseDo<'a> {
+    pub namespace: Cow<'a, str>,
+    pub app_name: Cow<'a, str>,
+    pub resume_time: u32,
+    pub past_due_strategy: Cow<'a, str>,
+    pub reason: Cow<'a, str>,
+    pub operator: Cow<'a, str>,
+    pub update_time: u64,
+    pub start_time: u32,
+}
+
+impl<'a> MessageRead<'a> for SchedulePauseDo<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(10) => msg.namespace = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(18) => msg.app_name = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(24) => msg.resume_time = r.read_uint32(bytes)?,
+                Ok(34) => msg.past_due_strategy = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(42) => msg.reason = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(50) => msg.operator = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(56) => msg.update_time = r.read_uint64(bytes)?,
+                Ok(64) => msg.start_time = r.read_uint32(bytes)?,
+                Ok(t) => { r.read_unknown(bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for SchedulePauseDo<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + if self.namespace == "" { 0 } else { 1 + sizeof_len((&self.namespace).len()) }
+        + if self.app_name == "" { 0 } else { 1 + sizeof_len((&self.app_name).len()) }
+        + if self.resume_time == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.resume_time) as u64) }
+        + if self.past_due_strategy == "" { 0 } else { 1 + sizeof_len((&self.past_due_strategy).len()) }
+        + if self.reason == "" { 0 } else { 1 + sizeof_len((&self.reason).len()) }
+        + if self.operator == "" { 0 } else { 1 + sizeof_len((&self.operator).len()) }
+        + if self.update_time == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.update_time) as u64) }
+        + if self.start_time == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.start_time) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.namespace != "" { w.write_with_tag(10, |w| w.write_string(&**&self.namespace))?; }
+        if self.app_name != "" { w.write_with_tag(18, |w| w.write_string(&**&self.app_name))?; }
+        if self.resume_time != 0u32 { w.write_with_tag(24, |w| w.write_uint32(*&self.resume_time))?; }
+        if self.past_due_strategy != "" { w.write_with_tag(34, |w| w.write_string(&**&self.past_due_strategy))?; }
+        if self.reason != "" { w.write_with_tag(42, |w| w.write_string(&**&self.reason))?; }
+        if self.operator != "" { w.write_with_tag(50, |w| w.write_string(&**&self.operator))?; }
+        if self.update_time != 0u64 { w.write_with_tag(56, |w| w.write_uint64(*&self.update_time))?; }
+        if self.start_time != 0u32 { w.write_with_tag(64, |w| w.write_uint32(*&self.start_time))?; }
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
+pub struct AuditRecordDo<'a> {
+    pub id: u64,
+    pub actor: Cow<'a, str>,
+    pub actor_type: Cow<'a, str>,
+    pub action: Cow<'a, str>,
+    pub target_type: Cow<'a, str>,
+    pub target_id: Cow<'a, str>,
+    pub namespace: Cow<'a, str>,
+    pub before: Cow<'a, str>,
+    pub after: Cow<'a, str>,
+    pub source_ip: Cow<'a, str>,
+    pub create_time: u64,
+    pub detail: Cow<'a, str>,
+}
+
+impl<'a> MessageRead<'a> for AuditRecordDo<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.id = r.read_uint64(bytes)?,
+                Ok(18) => msg.actor = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(26) => msg.actor_type = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(34) => msg.action = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(42) => msg.target_type = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(50) => msg.target_id = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(58) => msg.namespace = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(66) => msg.before = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(74) => msg.after = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(82) => msg.source_ip = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(88) => msg.create_time = r.read_uint64(bytes)?,
+                Ok(98) => msg.detail = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { r.read_unknown(bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for AuditRecordDo<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + if self.id == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.id) as u64) }
+        + if self.actor == "" { 0 } else { 1 + sizeof_len((&self.actor).len()) }
+        + if self.actor_type == "" { 0 } else { 1 + sizeof_len((&self.actor_type).len()) }
+        + if self.action == "" { 0 } else { 1 + sizeof_len((&self.action).len()) }
+        + if self.target_type == "" { 0 } else { 1 + sizeof_len((&self.target_type).len()) }
+        + if self.target_id == "" { 0 } else { 1 + sizeof_len((&self.target_id).len()) }
+        + if self.namespace == "" { 0 } else { 1 + sizeof_len((&self.namespace).len()) }
+        + if self.before == "" { 0 } else { 1 + sizeof_len((&self.before).len()) }
+        + if self.after == "" { 0 } else { 1 + sizeof_len((&self.after).len()) }
+        + if self.source_ip == "" { 0 } else { 1 + sizeof_len((&self.source_ip).len()) }
+        + if self.create_time == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.create_time) as u64) }
+        + if self.detail == "" { 0 } else { 1 + sizeof_len((&self.detail).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.id != 0u64 { w.write_with_tag(8, |w| w.write_uint64(*&self.id))?; }
+        if self.actor != "" { w.write_with_tag(18, |w| w.write_string(&**&self.actor))?; }
+        if self.actor_type != "" { w.write_with_tag(26, |w| w.write_string(&**&self.actor_type))?; }
+        if self.action != "" { w.write_with_tag(34, |w| w.write_string(&**&self.action))?; }
+        if self.target_type != "" { w.write_with_tag(42, |w| w.write_string(&**&self.target_type))?; }
+        if self.target_id != "" { w.write_with_tag(50, |w| w.write_string(&**&self.target_id))?; }
+        if self.namespace != "" { w.write_with_tag(58, |w| w.write_string(&**&self.namespace))?; }
+        if self.before != "" { w.write_with_tag(66, |w| w.write_string(&**&self.before))?; }
+        if self.after != "" { w.write_with_tag(74, |w| w.write_string(&**&self.after))?; }
+        if self.source_ip != "" { w.write_with_tag(82, |w| w.write_string(&**&self.source_ip))?; }
+        if self.create_time != 0u64 { w.write_with_tag(88, |w| w.write_uint64(*&self.create_time))?; }
+        if self.detail != "" { w.write_with_tag(98, |w| w.write_string(&**&self.detail))?; }
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
+pub struct JobVersionDo<'a> {
+    pub job_id: u64,
+    pub version_id: u64,
+    pub operator: Cow<'a, str>,
+    pub update_time: u64,
+    pub job: Option<data_object::JobDo<'a>>,
+}
+
+impl<'a> MessageRead<'a> for JobVersionDo<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.job_id = r.read_uint64(bytes)?,
+                Ok(16) => msg.version_id = r.read_uint64(bytes)?,
+                Ok(26) => msg.operator = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(32) => msg.update_time = r.read_uint64(bytes)?,
+                Ok(42) => msg.job = Some(r.read_message::<data_object::JobDo>(bytes)?),
+                Ok(t) => { r.read_unknown(bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for JobVersionDo<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + if self.job_id == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.job_id) as u64) }
+        + if self.version_id == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.version_id) as u64) }
+        + if self.operator == "" { 0 } else { 1 + sizeof_len((&self.operator).len()) }
+        + if self.update_time == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.update_time) as u64) }
+        + self.job.as_ref().map_or(0, |m| 1 + sizeof_len((m).get_size()))
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.job_id != 0u64 { w.write_with_tag(8, |w| w.write_uint64(*&self.job_id))?; }
+        if self.version_id != 0u64 { w.write_with_tag(16, |w| w.write_uint64(*&self.version_id))?; }
+        if self.operator != "" { w.write_with_tag(26, |w| w.write_string(&**&self.operator))?; }
+        if self.update_time != 0u64 { w.write_with_tag(32, |w| w.write_uint64(*&self.update_time))?; }
+        if let Some(ref s) = self.job { w.write_with_tag(42, |w| w.write_message(s))?; }
+        Ok(())
+    }
+}
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(serde::Serialize, Debug, Default, PartialEq, Clone)]
+pub struct JobGlueDo<'a> {
+    pub job_id: u64,
+    pub version_id: u64,
+    pub run_mode: Cow<'a, str>,
+    pub glue_source: Cow<'a, str>,
+    pub glue_update_time: u64,
+    pub operator: Cow<'a, str>,
+}
+
+impl<'a> MessageRead<'a> for JobGlueDo<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.job_id = r.read_uint64(bytes)?,
+                Ok(16) => msg.version_id = r.read_uint64(bytes)?,
+                Ok(26) => msg.run_mode = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(34) => msg.glue_source = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(40) => msg.glue_update_time = r.read_uint64(bytes)?,
+                Ok(50) => msg.operator = r.read_string(bytes).map(Cow::Borrowed)?,
+                Ok(t) => { r.read_unknown(bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for JobGlueDo<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + if self.job_id == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.job_id) as u64) }
+        + if self.version_id == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.version_id) as u64) }
+        + if self.run_mode == "" { 0 } else { 1 + sizeof_len((&self.run_mode).len()) }
+        + if self.glue_source == "" { 0 } else { 1 + sizeof_len((&self.glue_source).len()) }
+        + if self.glue_update_time == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.glue_update_time) as u64) }
+        + if self.operator == "" { 0 } else { 1 + sizeof_len((&self.operator).len()) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.job_id != 0u64 { w.write_with_tag(8, |w| w.write_uint64(*&self.job_id))?; }
+        if self.version_id != 0u64 { w.write_with_tag(16, |w| w.write_uint64(*&self.version_id))?; }
+        if self.run_mode != "" { w.write_with_tag(26, |w| w.write_string(&**&self.run_mode))?; }
+        if self.glue_source != "" { w.write_with_tag(34, |w| w.write_string(&**&self.glue_source))?; }
+        if self.glue_update_time != 0u64 { w.write_with_tag(40, |w| w.write_uint64(*&self.glue_update_time))?; }
+        if self.operator != "" { w.write_with_tag(50, |w| w.write_string(&**&self.operator))?; }
+        Ok(())
+    }
+}
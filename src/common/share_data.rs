@@ -1,6 +1,8 @@
 use crate::app::core::AppManager;
+use crate::audit::core::AuditManager;
 use crate::cache::core::CacheManager;
 use crate::common::app_config::AppConfig;
+use crate::executor::core::ExecutorStreamManager;
 use crate::job::core::JobManager;
 use crate::metrics::core::MetricsManager;
 use crate::namespace::core::NamespaceManager;
@@ -39,4 +41,6 @@ pub struct ShareData {
     pub batch_call_manager: Addr<BatchCallManager>,
     pub cache_manager: Addr<CacheManager>,
     pub user_manager: Addr<UserManager>,
+    pub audit_manager: Addr<AuditManager>,
+    pub executor_stream_manager: Addr<ExecutorStreamManager>,
 }
@@ -41,4 +41,50 @@ impl StringUtils {
         }
         v
     }
+
+    ///
+    /// 替换模板中的 `${name}` 变量,未知变量保持原样
+    pub fn render_template<F>(template: &str, resolver: F) -> String
+    where
+        F: Fn(&str) -> Option<String>,
+    {
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template;
+        while let Some(start) = rest.find("${") {
+            result.push_str(&rest[..start]);
+            let var_part = &rest[start + 2..];
+            if let Some(end) = var_part.find('}') {
+                let name = &var_part[..end];
+                if let Some(value) = resolver(name) {
+                    result.push_str(&value);
+                } else {
+                    result.push_str(&rest[start..start + end + 3]);
+                }
+                rest = &var_part[end + 1..];
+            } else {
+                result.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_template() {
+        let resolver = |name: &str| match name {
+            "jobId" => Some("12".to_owned()),
+            _ => None,
+        };
+        assert_eq!(
+            StringUtils::render_template("/job/${jobId}?a=${unknown}&b=${", resolver),
+            "/job/12?a=${unknown}&b=${"
+        );
+        assert_eq!(StringUtils::render_template("plain", resolver), "plain");
+    }
 }
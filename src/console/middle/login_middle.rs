@@ -25,6 +25,8 @@ lazy_static::lazy_static! {
     pub static ref IGNORE_CHECK_LOGIN: Vec<&'static str> = vec![
         "/ratchjob/p/login", "/ratchjob/404",
         "/ratchjob/api/console/v1/login/login", "/ratchjob/api/console/v1/login/captcha",
+        "/ratchjob/api/console/v1/login/oidc/config", "/ratchjob/api/console/v1/login/oidc/authorize",
+        "/ratchjob/api/console/v1/login/oidc/callback",
     ];
     pub static ref STATIC_FILE_PATH: Regex= Regex::new(r"(?i).*\.(js|css|png|jpg|jpeg|bmp|svg)").unwrap();
     pub static ref API_PATH: Regex = Regex::new(r"(?i)/api/.*").unwrap();
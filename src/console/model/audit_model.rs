@@ -0,0 +1,110 @@
+use crate::audit::model::audit::{diff_json, AuditFieldChange, AuditQueryParam, AuditRecord};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditQueryRequest {
+    pub actor: Option<Arc<String>>,
+    pub action: Option<Arc<String>>,
+    pub target_type: Option<Arc<String>>,
+    pub target_id: Option<Arc<String>>,
+    pub namespace: Option<Arc<String>>,
+    pub start_time: Option<u64>,
+    pub end_time: Option<u64>,
+    pub page_no: Option<usize>,
+    pub page_size: Option<usize>,
+    /// 导出格式: csv,json
+    pub format: Option<String>,
+}
+
+impl AuditQueryRequest {
+    pub fn to_param(&self) -> AuditQueryParam {
+        let limit = self.page_size.unwrap_or(0xffff_ffff);
+        let page_no = self.page_no.unwrap_or(1).max(1);
+        AuditQueryParam {
+            actor: self.actor.clone(),
+            action: self.action.clone(),
+            target_type: self.target_type.clone(),
+            target_id: self.target_id.clone(),
+            namespace: self.namespace.clone(),
+            start_time: self.start_time,
+            end_time: self.end_time,
+            offset: (page_no - 1) * limit,
+            limit,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditRecordVO {
+    pub id: u64,
+    pub actor: Arc<String>,
+    pub actor_type: Arc<String>,
+    pub action: Arc<String>,
+    pub target_type: Arc<String>,
+    pub target_id: Arc<String>,
+    pub namespace: Arc<String>,
+    pub source_ip: Arc<String>,
+    pub create_time: u64,
+    pub detail: Arc<String>,
+    pub before: Arc<String>,
+    pub after: Arc<String>,
+    pub changes: Vec<AuditFieldChange>,
+}
+
+impl From<&AuditRecord> for AuditRecordVO {
+    fn from(value: &AuditRecord) -> Self {
+        Self {
+            id: value.id,
+            actor: value.actor.clone(),
+            actor_type: value.actor_type.clone(),
+            action: value.action.clone(),
+            target_type: value.target_type.clone(),
+            target_id: value.target_id.clone(),
+            namespace: value.namespace.clone(),
+            source_ip: value.source_ip.clone(),
+            create_time: value.create_time,
+            detail: value.detail.clone(),
+            changes: diff_json(&value.before, &value.after),
+            before: value.before.clone(),
+            after: value.after.clone(),
+        }
+    }
+}
+
+impl AuditRecordVO {
+    pub const CSV_HEADER: &'static str =
+        "id,createTime,actor,actorType,action,targetType,targetId,namespace,sourceIp,detail,changes";
+
+    pub fn to_csv_line(&self) -> String {
+        let changes = serde_json::to_string(&self.changes).unwrap_or_default();
+        let columns = [
+            self.id.to_string(),
+            self.create_time.to_string(),
+            self.actor.to_string(),
+            self.actor_type.to_string(),
+            self.action.to_string(),
+            self.target_type.to_string(),
+            self.target_id.to_string(),
+            self.namespace.to_string(),
+            self.source_ip.to_string(),
+            self.detail.to_string(),
+            changes,
+        ];
+        columns
+            .iter()
+            .map(|v| Self::escape_csv(v))
+            .collect::<Vec<String>>()
+            .join(",")
+    }
+
+    fn escape_csv(value: &str) -> String {
+        if value.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", value.replace('"', "\"\""))
+        } else {
+            value.to_owned()
+        }
+    }
+}
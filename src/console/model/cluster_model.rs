@@ -11,6 +11,8 @@ pub struct ClusterNodeInfo {
     pub current_node: bool,
     pub raft_leader: bool,
     pub distro_valid: bool,
+    /// 是否参与调度分区
+    pub schedule_partition: bool,
 }
 
 impl From<ClusterNode> for ClusterNodeInfo {
@@ -21,6 +23,7 @@ impl From<ClusterNode> for ClusterNodeInfo {
             raft_leader: false,
             current_node: false,
             distro_valid: value.is_local || value.status == NodeStatus::Valid,
+            schedule_partition: false,
         }
     }
 }
@@ -1,3 +1,4 @@
+use crate::audit::model::audit::AuditFieldChange;
 use crate::common::datetime_utils::now_millis;
 use crate::common::model::privilege::PrivilegeGroup;
 use crate::common::model::UserSession;
@@ -7,7 +8,11 @@ use crate::job::job_index::JobQueryParam;
 use crate::job::model::enum_type::{
     ExecutorBlockStrategy, JobRunMode, PastDueStrategy, RouterStrategy, ScheduleType,
 };
-use crate::job::model::job::{JobParam, JobTaskLogQueryParam};
+use crate::job::model::http_config::JobHttpConfig;
+use crate::job::model::job::{JobHistoryInfo, JobInfoDto, JobParam, JobTaskLogQueryParam};
+use crate::job::model::label::{JobLabels, LabelSelector};
+use crate::job::model::retry_policy::JobRetryPolicy;
+use crate::task::model::enum_type::TaskStatusType;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
@@ -33,6 +38,12 @@ pub struct JobInfoParam {
     pub timeout_second: Option<u32>,
     pub try_times: Option<u32>,
     pub retry_interval: Option<u32>,
+    pub glue_source: Option<Arc<String>>,
+    pub http_config: Option<Arc<JobHttpConfig>>,
+    pub retry_policy: Option<Arc<JobRetryPolicy>>,
+    pub labels: Option<JobLabels>,
+    pub allow_time_window: Option<Arc<String>>,
+    pub forbid_time_window: Option<Arc<String>>,
 }
 
 impl JobInfoParam {
@@ -66,10 +77,66 @@ impl JobInfoParam {
             try_times: self.try_times,
             update_time: Some(now_millis()),
             retry_interval: self.retry_interval,
+            update_user: None,
+            glue_source: self.glue_source,
+            http_config: self.http_config,
+            retry_policy: self.retry_policy,
+            labels: self.labels,
+            allow_time_window: self.allow_time_window,
+            forbid_time_window: self.forbid_time_window,
         }
     }
 }
 
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JobVersionParam {
+    pub job_id: Option<u64>,
+    pub version_id: Option<u64>,
+    /// 比较的起始版本,为空时取目标版本的上一个版本
+    pub from_version: Option<u64>,
+    /// 比较的目标版本,为空时取当前版本
+    pub to_version: Option<u64>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobVersionVO {
+    pub version_id: u64,
+    pub operator: Arc<String>,
+    pub update_time: u64,
+    pub current: bool,
+    pub job: JobInfoDto,
+}
+
+impl JobVersionVO {
+    pub fn new_from(history: &JobHistoryInfo, current_version_id: u64) -> Self {
+        JobVersionVO {
+            version_id: history.version_id,
+            operator: history.operator.clone(),
+            update_time: history.update_time,
+            current: history.version_id == current_version_id,
+            job: JobInfoDto::new_from(&history.job),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobVersionDiffVO {
+    pub from_version: u64,
+    pub to_version: u64,
+    pub changes: Vec<AuditFieldChange>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TaskExecutorLogParam {
+    pub job_id: Option<u64>,
+    pub task_id: Option<u64>,
+    pub instance_addr: Option<Arc<String>>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct TriggerJobParam {
@@ -77,6 +144,12 @@ pub struct TriggerJobParam {
     pub instance_addr: Option<Arc<String>>,
 }
 
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelTaskParam {
+    pub task_id: Option<u64>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct JobQueryListRequest {
@@ -85,12 +158,24 @@ pub struct JobQueryListRequest {
     pub like_description: Option<Arc<String>>,
     pub like_handle_name: Option<Arc<String>>,
     pub like_key: Option<Arc<String>>,
+    /// 标签选择器,如 `team=payments,tier!=low`
+    pub label_selector: Option<String>,
     pub page_no: Option<usize>,
     pub page_size: Option<usize>,
 }
 
 impl JobQueryListRequest {
-    pub fn to_param_with_session(self, session: &Arc<UserSession>) -> JobQueryParam {
+    fn get_label_selector(&self) -> anyhow::Result<Option<LabelSelector>> {
+        match self.label_selector.as_ref() {
+            Some(v) if !v.trim().is_empty() => Ok(Some(LabelSelector::parse(v)?)),
+            _ => Ok(None),
+        }
+    }
+
+    pub fn to_param_with_session(
+        self,
+        session: &Arc<UserSession>,
+    ) -> anyhow::Result<JobQueryParam> {
         let limit = self.page_size.unwrap_or(0xffff_ffff);
         let page_no = if self.page_no.unwrap_or(1) < 1 {
             1
@@ -98,20 +183,22 @@ impl JobQueryListRequest {
             self.page_no.unwrap_or(1)
         };
         let offset = (page_no - 1) * limit;
-        JobQueryParam {
+        let label_selector = self.get_label_selector()?;
+        Ok(JobQueryParam {
             namespace: self.namespace,
             app_name: self.app_name,
             like_description: self.like_description,
             like_handle_name: self.like_handle_name,
             like_key: self.like_key,
+            label_selector,
             app_privilege: session.app_privilege.clone(),
             namespace_privilege: session.namespace_privilege.clone(),
             offset,
             limit,
-        }
+        })
     }
 
-    pub fn to_param(self) -> JobQueryParam {
+    pub fn to_param(self) -> anyhow::Result<JobQueryParam> {
         let limit = self.page_size.unwrap_or(0xffff_ffff);
         let page_no = if self.page_no.unwrap_or(1) < 1 {
             1
@@ -119,17 +206,19 @@ impl JobQueryListRequest {
             self.page_no.unwrap_or(1)
         };
         let offset = (page_no - 1) * limit;
-        JobQueryParam {
+        let label_selector = self.get_label_selector()?;
+        Ok(JobQueryParam {
             namespace: self.namespace,
             app_name: self.app_name,
             like_description: self.like_description,
             like_handle_name: self.like_handle_name,
             like_key: self.like_key,
+            label_selector,
             app_privilege: PrivilegeGroup::all(),
             namespace_privilege: PrivilegeGroup::all(),
             offset,
             limit,
-        }
+        })
     }
 }
 
@@ -141,6 +230,8 @@ pub struct JobTaskLogQueryListRequest {
     pub app_name: Option<String>,
     pub page_no: Option<usize>,
     pub page_size: Option<usize>,
+    /// 按状态过滤,如 TIMEOUT
+    pub status: Option<String>,
 }
 
 impl JobTaskLogQueryListRequest {
@@ -168,6 +259,38 @@ impl JobTaskLogQueryListRequest {
             limit,
             namespace,
             app_name,
+            status: self
+                .status
+                .filter(|v| !v.is_empty())
+                .map(|v| TaskStatusType::from_str(&v)),
         }
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerParamPreviewParam {
+    pub job_id: Option<u64>,
+    /// 为空时使用任务当前的触发参数
+    pub trigger_param: Option<String>,
+    /// 调度时间(毫秒),为空时使用当前时间
+    pub schedule_time: Option<u64>,
+    pub namespace: Option<Arc<String>>,
+    pub app_name: Option<Arc<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JobNextTriggerTimeParam {
+    pub job_id: Option<u64>,
+    /// 默认10次,最多100次
+    pub count: Option<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TriggerParamPreviewVO {
+    pub schedule_time: u64,
+    pub trigger_param: String,
+    pub result: String,
+}
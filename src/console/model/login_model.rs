@@ -14,3 +14,18 @@ pub struct LoginParam {
 pub struct LoginToken {
     pub token: String,
 }
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OidcLoginConfig {
+    pub enable: bool,
+    pub authorize_url: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+pub struct OidcCallbackParam {
+    pub code: Option<String>,
+    pub state: Option<String>,
+    pub error: Option<String>,
+    pub error_description: Option<String>,
+}
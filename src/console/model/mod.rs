@@ -1,4 +1,5 @@
 pub mod app;
+pub mod audit_model;
 pub mod cluster_model;
 pub mod job;
 pub mod login_model;
@@ -41,11 +41,13 @@ pub struct UserVO {
     pub extend_info: HashMap<String, String>,
     pub namespace_privilege: PrivilegeGroup<Arc<String>>,
     pub app_privilege: PrivilegeGroup<Arc<String>>,
+    pub source: String,
 }
 
 impl From<UserInfo> for UserVO {
     fn from(value: UserInfo) -> Self {
         Self {
+            source: value.get_source().to_owned(),
             username: value.username,
             nickname: value.nickname,
             gmt_create: value.gmt_create,
@@ -100,6 +102,7 @@ impl From<UpdateUserInfoParam> for UserDto {
             app_privilege: value.app_privilege_param,
             namespace_privilege: value.namespace_privilege_param,
             extend_info: None,
+            source: None,
         }
     }
 }
@@ -1,4 +1,8 @@
 use crate::app::model::{AppManagerRaftReq, AppManagerReq, AppManagerResult, RegisterType};
+use crate::audit::model::audit::{
+    AUDIT_ACTION_CREATE, AUDIT_ACTION_REMOVE, AUDIT_ACTION_UPDATE, AUDIT_TARGET_APP,
+};
+use crate::audit::recorder::AuditRecorder;
 use crate::common::model::{ApiResult, PageResult, UserSession};
 use crate::common::share_data::ShareData;
 use crate::console::model::app::{AppInfoParam, AppQueryListRequest};
@@ -103,6 +107,16 @@ pub(crate) async fn set_app(
             Some(format!("user no app permission:{}", &param.app_name)),
         ));
     }
+    let app_key = param.build_app_key();
+    let before = if let Ok(Ok(AppManagerResult::AppInfo(info))) = share_data
+        .app_manager
+        .send(AppManagerReq::GetApp(app_key.clone()))
+        .await
+    {
+        info
+    } else {
+        None
+    };
     if let Ok(_) = share_data
         .raft_request_route
         .request(ClientRequest::AppReq {
@@ -110,6 +124,23 @@ pub(crate) async fn set_app(
         })
         .await
     {
+        let action = if before.is_some() {
+            AUDIT_ACTION_UPDATE
+        } else {
+            AUDIT_ACTION_CREATE
+        };
+        let mut record =
+            AuditRecorder::console(&req, action, AUDIT_TARGET_APP, app_key.app_name.to_string());
+        record.namespace = app_key.namespace.clone();
+        record.set_before(before.as_ref());
+        if let Ok(Ok(AppManagerResult::AppInfo(info))) = share_data
+            .app_manager
+            .send(AppManagerReq::GetApp(app_key))
+            .await
+        {
+            record.set_after(info.as_ref());
+        }
+        AuditRecorder::submit(&share_data, record).await;
         HttpResponse::Ok().json(ApiResult::success(Some(())))
     } else {
         HttpResponse::Ok().json(ApiResult::<()>::error(
@@ -139,11 +170,19 @@ pub(crate) async fn remove_app(
             Some(format!("user no app permission:{}", &param.app_name)),
         ));
     }
+    let mut record = AuditRecorder::console(
+        &req,
+        AUDIT_ACTION_REMOVE,
+        AUDIT_TARGET_APP,
+        param.app_name.to_string(),
+    );
+    record.namespace = param.namespace.clone();
     if let Ok(Ok(AppManagerResult::AppInfo(Some(info)))) = share_data
         .app_manager
         .send(AppManagerReq::GetApp(param.build_app_key()))
         .await
     {
+        record.set_before(Some(&info));
         let register_type = RegisterType::from_str(&info.register_type);
         let is_empty = if let Some(addrs) = info.instance_addrs {
             addrs.is_empty()
@@ -167,6 +206,7 @@ pub(crate) async fn remove_app(
         })
         .await
     {
+        AuditRecorder::submit(&share_data, record).await;
         HttpResponse::Ok().json(ApiResult::success(Some(())))
     } else {
         let error_msg = format!("remove_app error,param:{:?}", &param);
@@ -0,0 +1,69 @@
+use crate::audit::model::actor_model::{AuditManagerReq, AuditManagerResult};
+use crate::common::model::{ApiResult, PageResult};
+use crate::common::share_data::ShareData;
+use crate::console::model::audit_model::{AuditQueryRequest, AuditRecordVO};
+use crate::console::v1::ERROR_CODE_SYSTEM_ERROR;
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+async fn query_audit(
+    share_data: &ShareData,
+    request: &AuditQueryRequest,
+) -> anyhow::Result<(usize, Vec<AuditRecordVO>)> {
+    let param = request.to_param();
+    let AuditManagerResult::PageInfo(total_count, list) = share_data
+        .audit_manager
+        .send(AuditManagerReq::Query(param))
+        .await??;
+    let list = list.iter().map(|v| v.as_ref().into()).collect();
+    Ok((total_count, list))
+}
+
+pub(crate) async fn query_audit_list(
+    share_data: Data<Arc<ShareData>>,
+    web::Query(request): web::Query<AuditQueryRequest>,
+) -> impl Responder {
+    match query_audit(&share_data, &request).await {
+        Ok((total_count, list)) => {
+            HttpResponse::Ok().json(ApiResult::success(Some(PageResult { total_count, list })))
+        }
+        Err(e) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(format!("query_audit_list error,{}", e)),
+        )),
+    }
+}
+
+/// 导出审计日志,默认csv格式
+pub(crate) async fn export_audit(
+    share_data: Data<Arc<ShareData>>,
+    web::Query(mut request): web::Query<AuditQueryRequest>,
+) -> impl Responder {
+    request.page_no = None;
+    request.page_size = None;
+    let list = match query_audit(&share_data, &request).await {
+        Ok((_, list)) => list,
+        Err(e) => {
+            return HttpResponse::Ok().json(ApiResult::<()>::error(
+                ERROR_CODE_SYSTEM_ERROR.to_string(),
+                Some(format!("export_audit error,{}", e)),
+            ))
+        }
+    };
+    if request.format.as_deref() == Some("json") {
+        return HttpResponse::Ok()
+            .insert_header(("Content-Disposition", "attachment; filename=\"audit.json\""))
+            .json(list);
+    }
+    let mut body = String::from(AuditRecordVO::CSV_HEADER);
+    body.push('\n');
+    for item in list.iter() {
+        body.push_str(&item.to_csv_line());
+        body.push('\n');
+    }
+    HttpResponse::Ok()
+        .insert_header(("Content-Type", "text/csv; charset=utf-8"))
+        .insert_header(("Content-Disposition", "attachment; filename=\"audit.csv\""))
+        .body(body)
+}
@@ -3,7 +3,12 @@ use std::sync::Arc;
 use crate::common::model::ApiResult;
 use crate::common::share_data::ShareData;
 use crate::console::model::cluster_model::ClusterNodeInfo;
+use crate::console::v1::ERROR_CODE_SYSTEM_ERROR;
 use crate::raft::cluster::node_manager::{ClusterNode, NodeManageRequest, NodeManageResponse};
+use crate::raft::store::backup::{backup_online, BackupInfo};
+use crate::raft::store::raftapply::StateApplyManager;
+use crate::raft::store::raftlog::RaftLogManager;
+use crate::schedule::model::actor_model::{ScheduleManagerReq, ScheduleManagerResult};
 use actix_web::{web, HttpResponse, Responder};
 
 async fn get_all_valid_nodes(app: &Arc<ShareData>) -> anyhow::Result<Vec<ClusterNode>> {
@@ -21,6 +26,15 @@ async fn get_all_valid_nodes(app: &Arc<ShareData>) -> anyhow::Result<Vec<Cluster
 pub async fn query_cluster_info(app: web::Data<Arc<ShareData>>) -> impl Responder {
     let nodes = get_all_valid_nodes(&app).await.unwrap();
     let leader_node = app.raft.current_leader().await;
+    let partition_nodes = if let Ok(Ok(ScheduleManagerResult::PartitionInfo(info))) = app
+        .schedule_manager
+        .send(ScheduleManagerReq::QueryPartition)
+        .await
+    {
+        info.node_ids
+    } else {
+        vec![]
+    };
     let mut list = vec![];
     for node in nodes {
         let mut node_info: ClusterNodeInfo = node.into();
@@ -32,7 +46,46 @@ pub async fn query_cluster_info(app: web::Data<Arc<ShareData>>) -> impl Responde
         if app.app_config.raft_node_id == node_info.node_id {
             node_info.current_node = true;
         }
+        if partition_nodes.is_empty() {
+            // 未分区时只由主节点调度
+            node_info.schedule_partition = node_info.raft_leader;
+        } else {
+            node_info.schedule_partition = partition_nodes.contains(&node_info.node_id);
+        }
         list.push(node_info);
     }
     HttpResponse::Ok().json(ApiResult::success(Some(list)))
 }
+
+async fn do_create_backup(app: &Arc<ShareData>) -> anyhow::Result<BackupInfo> {
+    let apply_manager: actix::Addr<StateApplyManager> = app
+        .factory_data
+        .get_actor()
+        .ok_or(anyhow::anyhow!("StateApplyManager is empty"))?;
+    let log_manager: actix::Addr<RaftLogManager> = app
+        .factory_data
+        .get_actor()
+        .ok_or(anyhow::anyhow!("RaftLogManager is empty"))?;
+    let path = std::path::Path::new(&app.app_config.backup_dir)
+        .join(format!(
+            "ratchjob_{}_{}.bak",
+            app.app_config.raft_node_id,
+            chrono::Local::now().format("%Y%m%d%H%M%S%3f")
+        ))
+        .to_string_lossy()
+        .into_owned();
+    backup_online(&apply_manager, &log_manager, &path).await
+}
+
+pub async fn create_backup(app: web::Data<Arc<ShareData>>) -> impl Responder {
+    match do_create_backup(&app).await {
+        Ok(info) => HttpResponse::Ok().json(ApiResult::success(Some(info))),
+        Err(e) => {
+            log::error!("create backup error,{}", e);
+            HttpResponse::Ok().json(ApiResult::<()>::error(
+                ERROR_CODE_SYSTEM_ERROR.to_string(),
+                Some(e.to_string()),
+            ))
+        }
+    }
+}
@@ -1,21 +1,35 @@
+use crate::audit::model::audit::{
+    diff_json, AUDIT_ACTION_CANCEL, AUDIT_ACTION_CREATE, AUDIT_ACTION_REMOVE,
+    AUDIT_ACTION_ROLLBACK, AUDIT_ACTION_TRIGGER, AUDIT_ACTION_UPDATE, AUDIT_TARGET_JOB,
+};
+use crate::audit::recorder::AuditRecorder;
 use crate::common::constant::{EMPTY_ARC_STR, SEQ_JOB_ID};
 use crate::common::datetime_utils::{now_millis, now_second_u32};
 use crate::common::model::{ApiResult, PageResult, UserSession};
 use crate::common::share_data::ShareData;
 use crate::console::model::job::{
-    JobInfoParam, JobQueryListRequest, JobTaskLogQueryListRequest, TriggerJobParam,
+    CancelTaskParam, JobInfoParam, JobNextTriggerTimeParam, JobQueryListRequest,
+    JobTaskLogQueryListRequest, JobVersionDiffVO, JobVersionParam, JobVersionVO,
+    TaskExecutorLogParam, TriggerJobParam, TriggerParamPreviewParam, TriggerParamPreviewVO,
 };
 use crate::console::v1::{
-    ERROR_CODE_JOB_KEY_DUPLICATE, ERROR_CODE_NO_APP_PERMISSION, ERROR_CODE_SYSTEM_ERROR,
+    ERROR_CODE_JOB_KEY_DUPLICATE, ERROR_CODE_JOB_VERSION_NOT_FOUND, ERROR_CODE_NO_APP_PERMISSION,
+    ERROR_CODE_SYSTEM_ERROR,
+};
+use crate::executor::model::{
+    ExecutorAddr, ExecutorRouteResponse, ExecutorStreamReq, ExecutorStreamResult,
 };
+use crate::job::batch::{apply_job_batch, JobBatchRequest};
 use crate::job::model::actor_model::{
     JobManagerRaftReq, JobManagerRaftResult, JobManagerReq, JobManagerResult,
 };
-use crate::job::model::job::{JobKey, JobParam};
+use crate::job::model::job::{JobHistoryInfo, JobInfo, JobInfoDto, JobKey, JobParam};
 use crate::raft::store::{ClientRequest, ClientResponse};
+use crate::schedule::maintenance;
 use crate::schedule::model::actor_model::{ScheduleManagerReq, ScheduleManagerResult};
 use crate::sequence::{SequenceRequest, SequenceResult};
 use crate::task::model::actor_model::{TaskManagerReq, TriggerItem};
+use crate::task::model::param_template::TriggerParamContext;
 use actix_http::HttpMessage;
 use actix_web::web::Data;
 use actix_web::{web, HttpResponse, Responder};
@@ -34,7 +48,15 @@ pub(crate) async fn query_job_list(
             Some("user session is invalid".to_string()),
         ));
     };
-    let param = request.to_param_with_session(&session);
+    let param = match request.to_param_with_session(&session) {
+        Ok(v) => v,
+        Err(e) => {
+            return HttpResponse::Ok().json(ApiResult::<()>::error(
+                ERROR_CODE_SYSTEM_ERROR.to_string(),
+                Some(e.to_string()),
+            ))
+        }
+    };
     if let Ok(Ok(JobManagerResult::JobPageInfo(total_count, list))) = share_data
         .job_manager
         .send(JobManagerReq::QueryJob(param))
@@ -89,6 +111,7 @@ pub(crate) async fn query_job_info(
 }
 
 async fn do_create_job(
+    req: &actix_web::HttpRequest,
     share_data: Data<Arc<ShareData>>,
     mut param: JobParam,
 ) -> anyhow::Result<HttpResponse> {
@@ -128,6 +151,9 @@ async fn do_create_job(
     {
         param.id = Some(id);
         param.update_time = Some(now_millis());
+        let mut record =
+            AuditRecorder::console(req, AUDIT_ACTION_CREATE, AUDIT_TARGET_JOB, id.to_string());
+        param.update_user = Some(record.actor.clone());
         if let ClientResponse::JobResp {
             resp: JobManagerRaftResult::JobInfo(job),
         } = share_data
@@ -137,6 +163,9 @@ async fn do_create_job(
             })
             .await?
         {
+            record.namespace = job.namespace.clone();
+            record.set_after(Some(&job));
+            AuditRecorder::submit(&share_data, record).await;
             Ok(HttpResponse::Ok().json(ApiResult::success(Some(job))))
         } else {
             Err(anyhow::anyhow!("create job result type error!"))
@@ -168,7 +197,7 @@ pub(crate) async fn create_job(
             ));
         }
     }
-    match do_create_job(share_data, param).await {
+    match do_create_job(&req, share_data, param).await {
         Ok(v) => v,
         Err(e) => {
             let error_msg = format!("create_job error,{}", e);
@@ -188,7 +217,7 @@ pub(crate) async fn update_job(
     share_data: Data<Arc<ShareData>>,
     web::Json(param): web::Json<JobInfoParam>,
 ) -> impl Responder {
-    let param = param.to_param();
+    let mut param = param.to_param();
     let id = param.id.clone().unwrap_or_default();
     if id == 0 {
         return HttpResponse::Ok().json(ApiResult::<()>::error(
@@ -272,6 +301,9 @@ pub(crate) async fn update_job(
             Some(format!("update_job error,{}", e)),
         ));
     }
+    let mut record =
+        AuditRecorder::console(&req, AUDIT_ACTION_UPDATE, AUDIT_TARGET_JOB, id.to_string());
+    param.update_user = Some(record.actor.clone());
     if let Ok(_) = share_data
         .raft_request_route
         .request(ClientRequest::JobReq {
@@ -279,6 +311,14 @@ pub(crate) async fn update_job(
         })
         .await
     {
+        if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) =
+            share_data.job_manager.send(JobManagerReq::GetJob(id)).await
+        {
+            record.namespace = info.namespace.clone();
+            record.set_after(Some(&info));
+        }
+        record.set_before(original_job_info.as_ref());
+        AuditRecorder::submit(&share_data, record).await;
         HttpResponse::Ok().json(ApiResult::success(Some(())))
     } else {
         HttpResponse::Ok().json(ApiResult::<()>::error(
@@ -308,6 +348,8 @@ pub(crate) async fn remove_job(
             Some("user session is invalid".to_string()),
         ));
     };
+    let mut record =
+        AuditRecorder::console(&req, AUDIT_ACTION_REMOVE, AUDIT_TARGET_JOB, id.to_string());
     if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) =
         share_data.job_manager.send(JobManagerReq::GetJob(id)).await
     {
@@ -317,6 +359,8 @@ pub(crate) async fn remove_job(
                 Some(format!("user no app permission:{}", &info.app_name)),
             ));
         }
+        record.namespace = info.namespace.clone();
+        record.set_before(Some(&info));
     }
     if let Ok(_) = share_data
         .raft_request_route
@@ -325,6 +369,7 @@ pub(crate) async fn remove_job(
         })
         .await
     {
+        AuditRecorder::submit(&share_data, record).await;
         HttpResponse::Ok().json(ApiResult::success(Some(())))
     } else {
         HttpResponse::Ok().json(ApiResult::<()>::error(
@@ -371,10 +416,15 @@ pub(crate) async fn trigger_job(
             Some("query_job_info error".to_string()),
         ));
     };
+    let mut record =
+        AuditRecorder::console(&req, AUDIT_ACTION_TRIGGER, AUDIT_TARGET_JOB, id.to_string());
+    record.namespace = job_info.namespace.clone();
+    let instance_addr = param.instance_addr.unwrap_or(EMPTY_ARC_STR.clone());
+    record.detail = instance_addr.clone();
     let task_item = TriggerItem::new_with_user(
         now_second_u32(),
         job_info,
-        param.instance_addr.unwrap_or(EMPTY_ARC_STR.clone()),
+        instance_addr,
         session.username.clone(),
     );
     log::info!("trigger_job task_item:{:?}", &task_item);
@@ -383,6 +433,7 @@ pub(crate) async fn trigger_job(
         .send(TaskManagerReq::TriggerTaskList(vec![task_item]))
         .await
     {
+        AuditRecorder::submit(&share_data, record).await;
         HttpResponse::Ok().json(ApiResult::success(Some(())))
     } else {
         HttpResponse::Ok().json(ApiResult::<()>::error(
@@ -392,6 +443,98 @@ pub(crate) async fn trigger_job(
     }
 }
 
+pub(crate) async fn batch_jobs(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Json(request): web::Json<JobBatchRequest>,
+) -> impl Responder {
+    let session = if let Some(session) = req.extensions().get::<Arc<UserSession>>() {
+        session.clone()
+    } else {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("user session is invalid".to_string()),
+        ));
+    };
+    //只操作有权限的命名空间与应用下的任务
+    let r = match request.to_query_param(
+        session.namespace_privilege.clone(),
+        session.app_privilege.clone(),
+    ) {
+        Ok(query_param) => {
+            apply_job_batch(
+                &req,
+                &share_data,
+                &request,
+                query_param,
+                session.username.clone(),
+                AuditRecorder::console,
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    match r {
+        Ok(v) => HttpResponse::Ok().json(ApiResult::success(Some(v))),
+        Err(e) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(format!("batch_jobs error,{}", e)),
+        )),
+    }
+}
+
+pub(crate) async fn cancel_task(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Json(param): web::Json<CancelTaskParam>,
+) -> impl Responder {
+    let task_id = param.task_id.unwrap_or_default();
+    let session = if let Some(session) = req.extensions().get::<Arc<UserSession>>() {
+        session.clone()
+    } else {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("user session is invalid".to_string()),
+        ));
+    };
+    let task = if let Ok(Ok(ScheduleManagerResult::TaskInfo(Some(task)))) = share_data
+        .schedule_manager
+        .send(ScheduleManagerReq::GetRunningTask(task_id))
+        .await
+    {
+        task
+    } else {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(format!("task {} is not pending or running", task_id)),
+        ));
+    };
+    if !session.app_privilege.check_permission(&task.app_name) {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_NO_APP_PERMISSION.to_string(),
+            Some(format!("user no app permission:{}", &task.app_name)),
+        ));
+    }
+    let mut record = AuditRecorder::console(
+        &req,
+        AUDIT_ACTION_CANCEL,
+        AUDIT_TARGET_JOB,
+        task.job_id.to_string(),
+    );
+    record.namespace = task.namespace.clone();
+    record.detail = Arc::new(format!("task_id:{}", task_id));
+    match maintenance::cancel_task(&share_data, task_id, session.username.clone()).await {
+        Ok(task) => {
+            AuditRecorder::submit(&share_data, record).await;
+            HttpResponse::Ok().json(ApiResult::success(Some(task)))
+        }
+        Err(err) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(err.to_string()),
+        )),
+    }
+}
+
 pub(crate) async fn query_job_task_logs(
     req: actix_web::HttpRequest,
     share_data: Data<Arc<ShareData>>,
@@ -450,3 +593,322 @@ pub(crate) async fn query_latest_task(
         ))
     }
 }
+
+/// 比较历史版本时忽略的字段
+const JOB_VERSION_DIFF_IGNORE_FIELDS: [&str; 2] = ["versionId", "lastModifiedMillis"];
+
+async fn get_job_with_privilege(
+    req: &actix_web::HttpRequest,
+    share_data: &ShareData,
+    job_id: u64,
+) -> Result<Arc<JobInfo>, HttpResponse> {
+    let app_privilege = if let Some(session) = req.extensions().get::<Arc<UserSession>>() {
+        session.app_privilege.clone()
+    } else {
+        return Err(HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("user session is invalid".to_string()),
+        )));
+    };
+    let job_info = if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) = share_data
+        .job_manager
+        .send(JobManagerReq::GetJob(job_id))
+        .await
+    {
+        info
+    } else {
+        return Err(HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(format!("job not found,id:{}", job_id)),
+        )));
+    };
+    if !app_privilege.check_permission(&job_info.app_name) {
+        return Err(HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_NO_APP_PERMISSION.to_string(),
+            Some(format!("user no app permission:{}", &job_info.app_name)),
+        )));
+    }
+    Ok(job_info)
+}
+
+async fn query_job_versions_with_privilege(
+    req: &actix_web::HttpRequest,
+    share_data: &ShareData,
+    job_id: u64,
+) -> Result<(Arc<JobInfo>, Vec<Arc<JobHistoryInfo>>), HttpResponse> {
+    let job_info = get_job_with_privilege(req, share_data, job_id).await?;
+    if let Ok(Ok(JobManagerResult::JobVersionList(list))) = share_data
+        .job_manager
+        .send(JobManagerReq::QueryJobVersions(job_id))
+        .await
+    {
+        Ok((job_info, list))
+    } else {
+        Err(HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("query job versions error".to_string()),
+        )))
+    }
+}
+
+pub(crate) async fn query_job_versions(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Query(param): web::Query<JobVersionParam>,
+) -> impl Responder {
+    let job_id = param.job_id.unwrap_or_default();
+    match query_job_versions_with_privilege(&req, &share_data, job_id).await {
+        Ok((job_info, list)) => {
+            let list: Vec<JobVersionVO> = list
+                .iter()
+                .map(|v| JobVersionVO::new_from(v, job_info.version_id))
+                .collect();
+            HttpResponse::Ok().json(ApiResult::success(Some(list)))
+        }
+        Err(resp) => resp,
+    }
+}
+
+pub(crate) async fn diff_job_version(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Query(param): web::Query<JobVersionParam>,
+) -> impl Responder {
+    let job_id = param.job_id.unwrap_or_default();
+    let (job_info, list) = match query_job_versions_with_privilege(&req, &share_data, job_id).await
+    {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let to_version = param.to_version.unwrap_or(job_info.version_id);
+    let to = list.iter().find(|v| v.version_id == to_version);
+    let from = match param.from_version {
+        Some(from_version) => list.iter().find(|v| v.version_id == from_version),
+        //列表按版本倒序,取目标版本的上一个版本
+        None => list.iter().find(|v| v.version_id < to_version),
+    };
+    let (from, to) = match (from, to) {
+        (Some(from), Some(to)) => (from, to),
+        _ => {
+            return HttpResponse::Ok().json(ApiResult::<()>::error(
+                ERROR_CODE_JOB_VERSION_NOT_FOUND.to_string(),
+                Some("job version not found".to_string()),
+            ))
+        }
+    };
+    let from_json = serde_json::to_string(&JobInfoDto::new_from(&from.job)).unwrap_or_default();
+    let to_json = serde_json::to_string(&JobInfoDto::new_from(&to.job)).unwrap_or_default();
+    let mut changes = diff_json(&from_json, &to_json);
+    changes.retain(|v| !JOB_VERSION_DIFF_IGNORE_FIELDS.contains(&v.field.as_str()));
+    let data = JobVersionDiffVO {
+        from_version: from.version_id,
+        to_version: to.version_id,
+        changes,
+    };
+    HttpResponse::Ok().json(ApiResult::success(Some(data)))
+}
+
+pub(crate) async fn rollback_job_version(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Json(param): web::Json<JobVersionParam>,
+) -> impl Responder {
+    let job_id = param.job_id.unwrap_or_default();
+    let (job_info, list) = match query_job_versions_with_privilege(&req, &share_data, job_id).await
+    {
+        Ok(v) => v,
+        Err(resp) => return resp,
+    };
+    let version_id = param.version_id.unwrap_or_default();
+    let history = if let Some(v) = list.iter().find(|v| v.version_id == version_id) {
+        v.clone()
+    } else {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_JOB_VERSION_NOT_FOUND.to_string(),
+            Some(format!("job version not found:{}", version_id)),
+        ));
+    };
+    //回滚不改变任务所属的应用
+    if history.job.namespace != job_info.namespace || history.job.app_name != job_info.app_name {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("rollback_job_version error,the job app was changed".to_string()),
+        ));
+    }
+    if !history.job.key.is_empty() {
+        if let Ok(Ok(JobManagerResult::JobId(Some(existing_id)))) = share_data
+            .job_manager
+            .send(JobManagerReq::GetJobIdByKey(history.job.build_job_key()))
+            .await
+        {
+            if existing_id != job_id {
+                return HttpResponse::Ok().json(ApiResult::<()>::error(
+                    ERROR_CODE_JOB_KEY_DUPLICATE.to_string(),
+                    Some(format!("job key already exists: key={}", &history.job.key)),
+                ));
+            }
+        }
+    }
+    let mut record = AuditRecorder::console(
+        &req,
+        AUDIT_ACTION_ROLLBACK,
+        AUDIT_TARGET_JOB,
+        job_id.to_string(),
+    );
+    record.namespace = job_info.namespace.clone();
+    record.detail = Arc::new(format!("version:{}", version_id));
+    record.set_before(Some(&job_info));
+    let mut job = history.job.as_ref().clone();
+    job.last_modified_millis = now_millis();
+    if share_data
+        .raft_request_route
+        .request(ClientRequest::JobReq {
+            req: JobManagerRaftReq::ReplaceJob {
+                job: Arc::new(job),
+                update_user: Some(record.actor.clone()),
+            },
+        })
+        .await
+        .is_ok()
+    {
+        if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) = share_data
+            .job_manager
+            .send(JobManagerReq::GetJob(job_id))
+            .await
+        {
+            record.set_after(Some(&info));
+        }
+        AuditRecorder::submit(&share_data, record).await;
+        HttpResponse::Ok().json(ApiResult::success(Some(())))
+    } else {
+        HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("rollback_job_version error".to_string()),
+        ))
+    }
+}
+
+/// GLUE脚本历史版本,按版本倒序
+pub(crate) async fn query_job_glue_versions(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Query(param): web::Query<JobVersionParam>,
+) -> impl Responder {
+    let job_id = param.job_id.unwrap_or_default();
+    if let Err(resp) = get_job_with_privilege(&req, &share_data, job_id).await {
+        return resp;
+    }
+    if let Ok(Ok(JobManagerResult::JobGlueVersionList(list))) = share_data
+        .job_manager
+        .send(JobManagerReq::QueryJobGlueVersions(job_id))
+        .await
+    {
+        HttpResponse::Ok().json(ApiResult::success(Some(list)))
+    } else {
+        HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("query job glue versions error".to_string()),
+        ))
+    }
+}
+
+/// 按指定调度时间预览触发参数模板的渲染结果
+pub(crate) async fn preview_trigger_param(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Query(param): web::Query<TriggerParamPreviewParam>,
+) -> impl Responder {
+    let schedule_time = param.schedule_time.unwrap_or_else(now_millis);
+    let mut context = TriggerParamContext {
+        schedule_time: (schedule_time / 1000) as u32,
+        job_id: 0,
+        task_id: 0,
+        namespace: param.namespace.unwrap_or_default(),
+        app_name: param.app_name.unwrap_or_default(),
+    };
+    let mut trigger_param = param.trigger_param.unwrap_or_default();
+    if let Some(job_id) = param.job_id.filter(|v| *v > 0) {
+        let job_info = match get_job_with_privilege(&req, &share_data, job_id).await {
+            Ok(v) => v,
+            Err(resp) => return resp,
+        };
+        context.job_id = job_info.id;
+        context.namespace = job_info.namespace.clone();
+        context.app_name = job_info.app_name.clone();
+        if trigger_param.is_empty() {
+            trigger_param = job_info.trigger_param.to_string();
+        }
+    }
+    let result = context.render(&trigger_param);
+    HttpResponse::Ok().json(ApiResult::success(Some(TriggerParamPreviewVO {
+        schedule_time,
+        trigger_param,
+        result,
+    })))
+}
+
+/// 查询任务后续的触发时间(秒),已应用时间窗口
+pub(crate) async fn query_next_trigger_times(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Query(param): web::Query<JobNextTriggerTimeParam>,
+) -> impl Responder {
+    let job_info =
+        match get_job_with_privilege(&req, &share_data, param.job_id.unwrap_or_default()).await {
+            Ok(v) => v,
+            Err(resp) => return resp,
+        };
+    let count = param.count.unwrap_or(10).clamp(1, 100);
+    match share_data
+        .schedule_manager
+        .send(ScheduleManagerReq::QueryNextTriggerTimes(job_info, count))
+        .await
+    {
+        Ok(Ok(ScheduleManagerResult::TriggerTimes(list))) => {
+            HttpResponse::Ok().json(ApiResult::success(Some(list)))
+        }
+        _ => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("query_next_trigger_times error".to_string()),
+        )),
+    }
+}
+
+/// 查询gRPC执行器上报的任务日志
+pub(crate) async fn query_task_executor_log(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Query(param): web::Query<TaskExecutorLogParam>,
+) -> impl Responder {
+    let job_id = param.job_id.unwrap_or_default();
+    if let Err(resp) = get_job_with_privilege(&req, &share_data, job_id).await {
+        return resp;
+    }
+    let instance_addr = param.instance_addr.unwrap_or_default();
+    if !ExecutorAddr::is_executor_addr(&instance_addr) {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(format!("not grpc executor addr:{}", &instance_addr)),
+        ));
+    }
+    match share_data
+        .executor_stream_manager
+        .send(ExecutorStreamReq::QueryTaskLog(
+            instance_addr,
+            param.task_id.unwrap_or_default(),
+        ))
+        .await
+    {
+        Ok(Ok(ExecutorStreamResult::RouteResponse(ExecutorRouteResponse::TaskLog(list)))) => {
+            HttpResponse::Ok().json(ApiResult::success(Some(list)))
+        }
+        Ok(Err(err)) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(err.to_string()),
+        )),
+        _ => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("query task executor log error".to_string()),
+        )),
+    }
+}
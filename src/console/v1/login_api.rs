@@ -2,7 +2,7 @@ use std::sync::Arc;
 
 use actix_web::http::header;
 use actix_web::{
-    cookie::Cookie,
+    cookie::{time::Duration as CookieDuration, Cookie, SameSite},
     web::{self, Data},
     HttpRequest, HttpResponse, Responder,
 };
@@ -13,11 +13,17 @@ use crate::cache::actor_model::{
     CacheManagerLocalReq, CacheManagerRaftReq, CacheManagerRaftResult, SetInfo,
 };
 use crate::common::constant::CONSOLE_TOKEN_COOKIE_KEY;
+use crate::common::datetime_utils::now_millis_i64;
 use crate::common::datetime_utils::now_second_i32;
 use crate::common::share_data::ShareData;
-use crate::console::model::login_model::{LoginParam, LoginToken};
+use crate::console::model::login_model::{
+    LoginParam, LoginToken, OidcCallbackParam, OidcLoginConfig,
+};
 use crate::raft::store::ClientRequest;
-use crate::user::actor_model::{UserManagerRaftResult, UserManagerReq};
+use crate::user::actor_model::{UserManagerRaftReq, UserManagerRaftResult, UserManagerReq};
+use crate::user::ldap::{self, LdapAuthResult, LdapClient, LdapConfig};
+use crate::user::model::{UserDto, UserInfo, USER_SOURCE_LDAP};
+use crate::user::oidc::{self, OidcClient, OidcConfig};
 use crate::{
     cache::model::{CacheKey, CacheType, CacheValue},
     common::{
@@ -26,6 +32,11 @@ use crate::{
     },
 };
 
+const OIDC_STATE_TTL: i32 = 300;
+const OIDC_AUTHORIZE_PATH: &str = "/ratchjob/api/console/v1/login/oidc/authorize";
+const OIDC_CALLBACK_PATH: &str = "/ratchjob/api/console/v1/login/oidc/callback";
+const OIDC_STATE_COOKIE_KEY: &str = "ratch_oidc_state";
+
 pub async fn login(
     request: HttpRequest,
     app: Data<Arc<ShareData>>,
@@ -84,6 +95,22 @@ pub async fn login(
             )));
         }
     };
+    if let Some(ldap_config) = LdapConfig::new(&app.app_config) {
+        match ldap_login(
+            &app,
+            Arc::new(ldap_config),
+            param.username.clone(),
+            &password,
+        )
+        .await
+        {
+            Ok(Some(token)) => return Ok(login_success_response(token)),
+            Ok(None) => {}
+            Err(code) => {
+                return Ok(HttpResponse::Ok().json(ApiResult::<()>::error(code.to_owned(), None)))
+            }
+        }
+    }
     let msg = UserManagerReq::CheckUser {
         name: param.username,
         password,
@@ -91,56 +118,8 @@ pub async fn login(
     if let Ok(Ok(res)) = app.user_manager.send(msg).await {
         if let UserManagerRaftResult::CheckUser(valid, user) = res {
             if valid {
-                //增加长度避免遍历
-                let token = Arc::new(
-                    uuid::Uuid::new_v4().to_string().replace('-', "")
-                        + &uuid::Uuid::new_v4().to_string().replace('-', ""),
-                );
-                let now = now_second_i32();
-                let session = Arc::new(UserSession {
-                    username: user.username,
-                    nickname: user.nickname,
-                    roles: user.roles,
-                    extend_infos: user.extend_info,
-                    namespace_privilege: user.namespace_privilege,
-                    app_privilege: user.app_privilege,
-                    refresh_time: now as u32,
-                });
-                let set_info = SetInfo {
-                    key: CacheKey::new(CacheType::UserSession, token.clone()),
-                    value: CacheValue::UserSession(session),
-                    ttl: app.app_config.console_login_timeout,
-                    now,
-                    nx: false,
-                    xx: false,
-                };
-                let cache_req = CacheManagerRaftReq::Set(set_info);
-                app.raft_request_route
-                    .request(ClientRequest::CacheReq { req: cache_req })
-                    .await
-                    .ok();
-                //登录成功后清除登陆限流计数
-                //let clear_limit_req =
-                //    CacheManagerReq::Remove(CacheKey::new(CacheType::String, limit_key));
-                //app.cache_manager.do_send(clear_limit_req);
-                let login_token = LoginToken {
-                    token: token.to_string(),
-                };
-                return Ok(HttpResponse::Ok()
-                    .cookie(
-                        Cookie::build(CONSOLE_TOKEN_COOKIE_KEY, token.as_str())
-                            .path("/")
-                            .http_only(true)
-                            .finish(),
-                    )
-                    .cookie(
-                        Cookie::build("ratch_captcha_token", "")
-                            .path("/")
-                            .http_only(true)
-                            .finish(),
-                    )
-                    .insert_header(header::ContentType(mime::APPLICATION_JSON))
-                    .json(ApiResult::success(Some(login_token))));
+                let token = create_user_session(&app, user).await;
+                return Ok(login_success_response(token));
             }
         }
         return Ok(
@@ -150,6 +129,27 @@ pub async fn login(
     Ok(HttpResponse::Ok().json(ApiResult::<()>::error("SYSTEM_ERROR".to_owned(), None)))
 }
 
+fn login_success_response(token: Arc<String>) -> HttpResponse {
+    let login_token = LoginToken {
+        token: token.to_string(),
+    };
+    HttpResponse::Ok()
+        .cookie(
+            Cookie::build(CONSOLE_TOKEN_COOKIE_KEY, token.as_str())
+                .path("/")
+                .http_only(true)
+                .finish(),
+        )
+        .cookie(
+            Cookie::build("ratch_captcha_token", "")
+                .path("/")
+                .http_only(true)
+                .finish(),
+        )
+        .insert_header(header::ContentType(mime::APPLICATION_JSON))
+        .json(ApiResult::success(Some(login_token)))
+}
+
 fn decode_password(password: &str, captcha_token: &str) -> anyhow::Result<String> {
     let password_data = crypto_utils::decode_base64(password)?;
     if captcha_token.is_empty() {
@@ -165,6 +165,294 @@ fn decode_password(password: &str, captcha_token: &str) -> anyhow::Result<String
     }
 }
 
+/// 登录成功后创建控制台会话，返回会话token
+async fn create_user_session(app: &ShareData, user: UserInfo) -> Arc<String> {
+    //增加长度避免遍历
+    let token = Arc::new(
+        uuid::Uuid::new_v4().to_string().replace('-', "")
+            + &uuid::Uuid::new_v4().to_string().replace('-', ""),
+    );
+    let now = now_second_i32();
+    let session = Arc::new(UserSession {
+        username: user.username,
+        nickname: user.nickname,
+        roles: user.roles,
+        extend_infos: user.extend_info,
+        namespace_privilege: user.namespace_privilege,
+        app_privilege: user.app_privilege,
+        refresh_time: now as u32,
+    });
+    let set_info = SetInfo {
+        key: CacheKey::new(CacheType::UserSession, token.clone()),
+        value: CacheValue::UserSession(session),
+        ttl: app.app_config.console_login_timeout,
+        now,
+        nx: false,
+        xx: false,
+    };
+    let cache_req = CacheManagerRaftReq::Set(set_info);
+    app.raft_request_route
+        .request(ClientRequest::CacheReq { req: cache_req })
+        .await
+        .ok();
+    token
+}
+
+pub async fn oidc_config(app: Data<Arc<ShareData>>) -> actix_web::Result<impl Responder> {
+    let enable = OidcConfig::new(&app.app_config).is_some();
+    let data = OidcLoginConfig {
+        enable,
+        authorize_url: if enable {
+            Some(OIDC_AUTHORIZE_PATH.to_owned())
+        } else {
+            None
+        },
+    };
+    Ok(HttpResponse::Ok().json(ApiResult::success(Some(data))))
+}
+
+/// 跳转到OIDC服务的授权页面
+pub async fn oidc_authorize(app: Data<Arc<ShareData>>) -> actix_web::Result<impl Responder> {
+    let config = match OidcConfig::new(&app.app_config) {
+        Some(v) => v,
+        None => return Ok(redirect_login_error("OIDC_NOT_ENABLE")),
+    };
+    let client = OidcClient::new(Arc::new(config));
+    let metadata = match client.discover().await {
+        Ok(v) => v,
+        Err(e) => {
+            log::error!("oidc discover error:{}", e);
+            return Ok(redirect_login_error("OIDC_PROVIDER_ERROR"));
+        }
+    };
+    let state = uuid::Uuid::new_v4().to_string().replace('-', "");
+    let nonce = uuid::Uuid::new_v4().to_string().replace('-', "");
+    let url = match client.build_authorize_url(&metadata, &state, &nonce) {
+        Ok(v) => v,
+        Err(e) => {
+            log::error!("oidc build authorize url error:{}", e);
+            return Ok(redirect_login_error("OIDC_PROVIDER_ERROR"));
+        }
+    };
+    let set_info = SetInfo {
+        key: oidc_state_key(&state),
+        value: CacheValue::String(Arc::new(nonce)),
+        ttl: OIDC_STATE_TTL,
+        now: now_second_i32(),
+        nx: false,
+        xx: false,
+    };
+    app.raft_request_route
+        .request(ClientRequest::CacheReq {
+            req: CacheManagerRaftReq::Set(set_info),
+        })
+        .await
+        .ok();
+    // state同时写入发起授权的浏览器，回调时校验，防止登录CSRF
+    Ok(HttpResponse::Found()
+        .cookie(
+            Cookie::build(OIDC_STATE_COOKIE_KEY, state)
+                .path(OIDC_CALLBACK_PATH)
+                .http_only(true)
+                .same_site(SameSite::Lax)
+                .max_age(CookieDuration::seconds(OIDC_STATE_TTL as i64))
+                .finish(),
+        )
+        .insert_header((header::LOCATION, url))
+        .finish())
+}
+
+/// OIDC授权回调，登录成功后写入会话cookie并跳转到控制台首页
+pub async fn oidc_callback(
+    request: HttpRequest,
+    app: Data<Arc<ShareData>>,
+    web::Query(param): web::Query<OidcCallbackParam>,
+) -> actix_web::Result<impl Responder> {
+    let cookie_state = request
+        .cookie(OIDC_STATE_COOKIE_KEY)
+        .map(|v| v.value().to_owned())
+        .unwrap_or_default();
+    let mut response = match oidc_login(&app, &cookie_state, param).await {
+        Ok(token) => HttpResponse::Found()
+            .cookie(
+                Cookie::build(CONSOLE_TOKEN_COOKIE_KEY, token.as_str())
+                    .path("/")
+                    .http_only(true)
+                    .finish(),
+            )
+            .insert_header((header::LOCATION, "/ratchjob/"))
+            .finish(),
+        Err(code) => redirect_login_error(code),
+    };
+    let mut state_cookie = Cookie::build(OIDC_STATE_COOKIE_KEY, "")
+        .path(OIDC_CALLBACK_PATH)
+        .finish();
+    state_cookie.make_removal();
+    response.add_cookie(&state_cookie).ok();
+    Ok(response)
+}
+
+async fn oidc_login(
+    app: &ShareData,
+    cookie_state: &str,
+    param: OidcCallbackParam,
+) -> Result<Arc<String>, &'static str> {
+    if let Some(error) = param.error {
+        log::warn!(
+            "oidc authorize error:{},{}",
+            &error,
+            param.error_description.unwrap_or_default()
+        );
+        return Err("OIDC_AUTHORIZE_ERROR");
+    }
+    let config = Arc::new(OidcConfig::new(&app.app_config).ok_or("OIDC_NOT_ENABLE")?);
+    let state = param.state.unwrap_or_default();
+    let code = param.code.unwrap_or_default();
+    if state.is_empty() || code.is_empty() || state != cookie_state {
+        return Err("OIDC_STATE_INVALID");
+    }
+    let state_key = oidc_state_key(&state);
+    let nonce = if let Ok(Ok(CacheManagerRaftResult::Value(CacheValue::String(v)))) = app
+        .cache_manager
+        .send(CacheManagerLocalReq::Get(state_key.clone()))
+        .await
+    {
+        v
+    } else {
+        return Err("OIDC_STATE_INVALID");
+    };
+    app.raft_request_route
+        .request(ClientRequest::CacheReq {
+            req: CacheManagerRaftReq::Remove(state_key),
+        })
+        .await
+        .ok();
+    let client = OidcClient::new(config.clone());
+    let claims = match client.discover().await {
+        Ok(metadata) => client.exchange_code(&metadata, &code, &nonce).await,
+        Err(e) => Err(e),
+    }
+    .map_err(|e| {
+        log::error!("oidc exchange code error:{}", e);
+        "OIDC_TOKEN_ERROR"
+    })?;
+    let username = oidc::get_username(&config, &claims).map_err(|_| "OIDC_USER_ERROR")?;
+    let exist_user = query_user(app, Arc::new(username)).await?;
+    if let Some(user) = &exist_user {
+        if !user.enable {
+            return Err("USER_DISABLED");
+        }
+    }
+    let user_dto = oidc::build_user_dto(&config, &claims, exist_user.as_ref(), now_millis_i64())
+        .map_err(|e| {
+            log::warn!("oidc build user error:{}", e);
+            "OIDC_USER_ERROR"
+        })?;
+    let user = save_external_user(app, exist_user, user_dto).await?;
+    Ok(create_user_session(app, user).await)
+}
+
+/// LDAP认证，返回None时继续使用本地用户认证；已有的LDAP用户认证失败时不回退到本地认证
+async fn ldap_login(
+    app: &ShareData,
+    config: Arc<LdapConfig>,
+    username: Arc<String>,
+    password: &str,
+) -> Result<Option<Arc<String>>, &'static str> {
+    let exist_user = query_user(app, username.clone()).await?;
+    // 本地及其它来源的用户不走LDAP认证
+    if let Some(user) = &exist_user {
+        if user.get_source() != USER_SOURCE_LDAP {
+            return Ok(None);
+        }
+    }
+    let is_ldap_user = exist_user.is_some();
+    let client = LdapClient::new(config.clone());
+    let entry = match client.authenticate(&username, password).await {
+        Ok(LdapAuthResult::Success(entry)) => entry,
+        Ok(LdapAuthResult::UserNotFound) if is_ldap_user => return Err("USER_CHECK_ERROR"),
+        Ok(LdapAuthResult::UserNotFound) => return Ok(None),
+        Ok(LdapAuthResult::InvalidPassword) => return Err("USER_CHECK_ERROR"),
+        Err(e) => {
+            log::error!("ldap authenticate error:{}", e);
+            if is_ldap_user {
+                return Err("SYSTEM_ERROR");
+            }
+            return Ok(None);
+        }
+    };
+    // 以目录中的用户名为准，登录输入的大小写或别名不同时按目录用户名查询本地用户
+    let exist_user = if entry.username == username.as_str() {
+        exist_user
+    } else {
+        query_user(app, Arc::new(entry.username.clone())).await?
+    };
+    if let Some(user) = &exist_user {
+        if !user.enable {
+            return Err("USER_DISABLED");
+        }
+    }
+    let user_dto = ldap::build_user_dto(&config, &entry, exist_user.as_ref(), now_millis_i64())
+        .map_err(|e| {
+            log::warn!("ldap build user error:{}", e);
+            "USER_CHECK_ERROR"
+        })?;
+    let user = save_external_user(app, exist_user, user_dto).await?;
+    Ok(Some(create_user_session(app, user).await))
+}
+
+async fn query_user(
+    app: &ShareData,
+    username: Arc<String>,
+) -> Result<Option<UserInfo>, &'static str> {
+    match app
+        .user_manager
+        .send(UserManagerReq::Query { name: username })
+        .await
+    {
+        Ok(Ok(UserManagerRaftResult::QueryUser(v))) => Ok(v),
+        _ => Err("SYSTEM_ERROR"),
+    }
+}
+
+/// 新增或更新外部来源(LDAP、OIDC)的用户，返回更新后的用户信息
+async fn save_external_user(
+    app: &ShareData,
+    exist_user: Option<UserInfo>,
+    user_dto: UserDto,
+) -> Result<UserInfo, &'static str> {
+    let (req, user) = if let Some(mut user) = exist_user {
+        user.update(user_dto.clone());
+        (UserManagerRaftReq::UpdateUser(user_dto), user)
+    } else {
+        (
+            UserManagerRaftReq::AddUser(user_dto.clone()),
+            user_dto.into(),
+        )
+    };
+    app.raft_request_route
+        .request(ClientRequest::UserReq { req })
+        .await
+        .map_err(|e| {
+            log::error!("save external user error:{}", e);
+            "SYSTEM_ERROR"
+        })?;
+    Ok(user)
+}
+
+fn oidc_state_key(state: &str) -> CacheKey {
+    CacheKey::new(CacheType::String, Arc::new(format!("OidcState_{}", state)))
+}
+
+fn redirect_login_error(code: &str) -> HttpResponse {
+    HttpResponse::Found()
+        .insert_header((
+            header::LOCATION,
+            format!("/ratchjob/p/login?oidcError={}", code),
+        ))
+        .finish()
+}
+
 const WIDTH: u32 = 220;
 const HEIGHT: u32 = 120;
 
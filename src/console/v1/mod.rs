@@ -1,9 +1,11 @@
 pub mod app_api;
+pub mod audit_api;
 pub mod cluster_api;
 pub mod job_api;
 pub mod login_api;
 pub mod metrics_api;
 pub mod namespace_api;
+pub mod schedule_api;
 pub mod user_api;
 
 use actix_web::web;
@@ -13,6 +15,7 @@ pub const ERROR_CODE_SYSTEM_ERROR: &str = "SYSTEM_ERROR";
 pub const ERROR_CODE_NO_PERMISSION: &str = "NO_PERMISSION";
 pub const ERROR_CODE_NO_APP_PERMISSION: &str = "NO_APP_PERMISSION";
 pub const ERROR_CODE_JOB_KEY_DUPLICATE: &str = "JOB_KEY_DUPLICATE";
+pub const ERROR_CODE_JOB_VERSION_NOT_FOUND: &str = "JOB_VERSION_NOT_FOUND";
 
 pub fn console_api_v1(config: &mut ServiceConfig) {
     config.service(
@@ -40,6 +43,17 @@ pub fn console_api_v1(config: &mut ServiceConfig) {
             .service(web::resource("/login/login").route(web::post().to(login_api::login)))
             .service(web::resource("/login/captcha").route(web::get().to(login_api::gen_captcha)))
             .service(web::resource("/login/logout").route(web::post().to(login_api::logout)))
+            .service(
+                web::resource("/login/oidc/config").route(web::get().to(login_api::oidc_config)),
+            )
+            .service(
+                web::resource("/login/oidc/authorize")
+                    .route(web::get().to(login_api::oidc_authorize)),
+            )
+            .service(
+                web::resource("/login/oidc/callback")
+                    .route(web::get().to(login_api::oidc_callback)),
+            )
             .service(web::resource("/user/info").route(web::get().to(user_api::get_user_info)))
             .service(web::resource("/user/list").route(web::get().to(user_api::get_user_page_list)))
             .service(web::resource("/user/add").route(web::post().to(user_api::add_user)))
@@ -63,9 +77,38 @@ pub fn console_api_v1(config: &mut ServiceConfig) {
             .service(web::resource("/job/update").route(web::post().to(job_api::update_job)))
             .service(web::resource("/job/remove").route(web::post().to(job_api::remove_job)))
             .service(web::resource("/job/trigger").route(web::post().to(job_api::trigger_job)))
+            .service(
+                web::resource("/job/version/list")
+                    .route(web::get().to(job_api::query_job_versions)),
+            )
+            .service(
+                web::resource("/job/version/diff").route(web::get().to(job_api::diff_job_version)),
+            )
+            .service(
+                web::resource("/job/glue/list")
+                    .route(web::get().to(job_api::query_job_glue_versions)),
+            )
+            .service(
+                web::resource("/job/trigger-time/next")
+                    .route(web::get().to(job_api::query_next_trigger_times)),
+            )
+            .service(
+                web::resource("/job/trigger-param/preview")
+                    .route(web::get().to(job_api::preview_trigger_param)),
+            )
+            .service(web::resource("/job/batch").route(web::post().to(job_api::batch_jobs)))
+            .service(
+                web::resource("/job/version/rollback")
+                    .route(web::post().to(job_api::rollback_job_version)),
+            )
             .service(
                 web::resource("/job/task/list").route(web::get().to(job_api::query_job_task_logs)),
             )
+            .service(web::resource("/job/task/cancel").route(web::post().to(job_api::cancel_task)))
+            .service(
+                web::resource("/job/task/executor-log")
+                    .route(web::get().to(job_api::query_task_executor_log)),
+            )
             .service(
                 web::resource("/job/task/latest-history")
                     .route(web::get().to(job_api::query_latest_task)),
@@ -78,6 +121,27 @@ pub fn console_api_v1(config: &mut ServiceConfig) {
             .service(
                 web::resource("/cluster/cluster_node_list")
                     .route(web::get().to(cluster_api::query_cluster_info)),
-            ),
+            )
+            .service(
+                web::resource("/cluster/backup").route(web::post().to(cluster_api::create_backup)),
+            )
+            .service(
+                web::resource("/schedule/pause/list")
+                    .route(web::get().to(schedule_api::query_pause_list)),
+            )
+            .service(
+                web::resource("/schedule/pause")
+                    .route(web::post().to(schedule_api::pause_schedule)),
+            )
+            .service(
+                web::resource("/schedule/resume")
+                    .route(web::post().to(schedule_api::resume_schedule)),
+            )
+            .service(
+                web::resource("/schedule/preview")
+                    .route(web::get().to(schedule_api::preview_schedule)),
+            )
+            .service(web::resource("/audit/list").route(web::get().to(audit_api::query_audit_list)))
+            .service(web::resource("/audit/export").route(web::get().to(audit_api::export_audit))),
     );
 }
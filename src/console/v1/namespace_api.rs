@@ -1,3 +1,8 @@
+use crate::audit::model::audit::{
+    AuditRecord, AUDIT_ACTION_CREATE, AUDIT_ACTION_REMOVE, AUDIT_ACTION_UPDATE,
+    AUDIT_TARGET_NAMESPACE,
+};
+use crate::audit::recorder::AuditRecorder;
 use crate::common::model::ApiResult;
 use crate::common::share_data::ShareData;
 use crate::common::string_utils::StringUtils;
@@ -7,9 +12,10 @@ use crate::namespace::model::actor_model::{
     NamespaceManagerRaftReq, NamespaceManagerRaftResult, NamespaceManagerReq,
     NamespaceManagerResult,
 };
+use crate::namespace::model::namespace::NamespaceInfo as NamespaceValue;
 use crate::raft::store::ClientRequest;
 use actix_web::web::Data;
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use std::sync::Arc;
 
 pub async fn query_namespace_list(app: Data<Arc<ShareData>>) -> impl Responder {
@@ -80,7 +86,26 @@ pub async fn query_namespace_info(
     }
 }
 
+async fn get_namespace(app: &ShareData, id: &Arc<String>) -> Option<Arc<NamespaceValue>> {
+    if let Ok(Ok(NamespaceManagerResult::NamespaceInfo(info))) = app
+        .namespace_manager
+        .send(NamespaceManagerReq::GetNamespace(id.clone()))
+        .await
+    {
+        info
+    } else {
+        None
+    }
+}
+
+fn build_audit_record(req: &HttpRequest, action: &str, id: &Arc<String>) -> AuditRecord {
+    let mut record = AuditRecorder::console(req, action, AUDIT_TARGET_NAMESPACE, id.to_string());
+    record.namespace = id.clone();
+    record
+}
+
 pub async fn update_namespace(
+    req: HttpRequest,
     app: Data<Arc<ShareData>>,
     web::Json(mut param): web::Json<NamespaceHandleRequest>,
 ) -> impl Responder {
@@ -94,6 +119,8 @@ pub async fn update_namespace(
         param.namespace_id = Some(Arc::new(uuid::Uuid::new_v4().to_string()));
     }
     let id = param.namespace_id.clone();
+    let namespace_id = id.clone().unwrap_or_default();
+    let before = get_namespace(&app, &namespace_id).await;
 
     let msg = NamespaceManagerRaftReq::UpdateNamespace(param.to_param());
     match app
@@ -104,6 +131,15 @@ pub async fn update_namespace(
         Ok(resp) => match resp {
             crate::raft::store::ClientResponse::NamespaceResp { resp } => match resp {
                 NamespaceManagerRaftResult::None => {
+                    let action = if before.is_some() {
+                        AUDIT_ACTION_UPDATE
+                    } else {
+                        AUDIT_ACTION_CREATE
+                    };
+                    let mut record = build_audit_record(&req, action, &namespace_id);
+                    record.set_before(before.as_ref());
+                    record.set_after(get_namespace(&app, &namespace_id).await.as_ref());
+                    AuditRecorder::submit(&app, record).await;
                     HttpResponse::Ok().json(ApiResult::success(Some(id)))
                 }
             },
@@ -120,6 +156,7 @@ pub async fn update_namespace(
 }
 
 pub async fn remove_namespace(
+    req: HttpRequest,
     app: Data<Arc<ShareData>>,
     web::Json(param): web::Json<NamespaceInfo>,
 ) -> impl Responder {
@@ -153,6 +190,8 @@ pub async fn remove_namespace(
         }
     }
 
+    let mut record = build_audit_record(&req, AUDIT_ACTION_REMOVE, &id);
+    record.set_before(get_namespace(&app, &id).await.as_ref());
     let msg = NamespaceManagerRaftReq::Remove(id);
     match app
         .raft_request_route
@@ -161,6 +200,7 @@ pub async fn remove_namespace(
     {
         Ok(resp) => match resp {
             crate::raft::store::ClientResponse::NamespaceResp { resp: _ } => {
+                AuditRecorder::submit(&app, record).await;
                 HttpResponse::Ok().json(ApiResult::success(Some(true)))
             }
             _ => HttpResponse::Ok().json(ApiResult::<()>::error(
@@ -0,0 +1,132 @@
+use crate::audit::recorder::AuditRecorder;
+use crate::common::model::{ApiResult, UserSession};
+use crate::common::share_data::ShareData;
+use crate::console::v1::{ERROR_CODE_NO_PERMISSION, ERROR_CODE_SYSTEM_ERROR};
+use crate::schedule::maintenance;
+use crate::schedule::model::pause::{SchedulePauseKey, SchedulePauseParam};
+use crate::schedule::model::preview::SchedulePreviewParam;
+use actix_http::HttpMessage;
+use actix_web::web::Data;
+use actix_web::{web, HttpResponse, Responder};
+use std::sync::Arc;
+
+/// 全局暂停需要全部命名空间与应用权限,命名空间暂停需要该命名空间下全部应用权限
+fn check_pause_permission(session: &UserSession, key: &SchedulePauseKey) -> bool {
+    if key.is_global() {
+        return session.namespace_privilege.is_all() && session.app_privilege.is_all();
+    }
+    if !session.namespace_privilege.check_permission(&key.namespace) {
+        return false;
+    }
+    if key.app_name.is_empty() {
+        session.app_privilege.is_all()
+    } else {
+        session.app_privilege.check_permission(&key.app_name)
+    }
+}
+
+fn get_session(req: &actix_web::HttpRequest) -> Option<Arc<UserSession>> {
+    req.extensions().get::<Arc<UserSession>>().cloned()
+}
+
+pub(crate) async fn query_pause_list(share_data: Data<Arc<ShareData>>) -> impl Responder {
+    match maintenance::query_pause_list(&share_data).await {
+        Ok(list) => HttpResponse::Ok().json(ApiResult::success(Some(list))),
+        Err(e) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(e.to_string()),
+        )),
+    }
+}
+
+pub(crate) async fn pause_schedule(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Json(param): web::Json<SchedulePauseParam>,
+) -> impl Responder {
+    let session = if let Some(session) = get_session(&req) {
+        session
+    } else {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("user session is invalid".to_string()),
+        ));
+    };
+    let key = match param.key() {
+        Ok(v) => v,
+        Err(e) => {
+            return HttpResponse::Ok().json(ApiResult::<()>::error(
+                ERROR_CODE_SYSTEM_ERROR.to_string(),
+                Some(e.to_string()),
+            ))
+        }
+    };
+    if !check_pause_permission(&session, &key) {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_NO_PERMISSION.to_string(),
+            Some(format!("user no permission:{}", key.to_key_string())),
+        ));
+    }
+    match maintenance::pause_schedule(
+        &req,
+        &share_data,
+        &param,
+        session.username.clone(),
+        AuditRecorder::console,
+    )
+    .await
+    {
+        Ok(info) => HttpResponse::Ok().json(ApiResult::success(Some(info))),
+        Err(e) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(format!("pause_schedule error,{}", e)),
+        )),
+    }
+}
+
+pub(crate) async fn resume_schedule(
+    req: actix_web::HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Json(param): web::Json<SchedulePauseParam>,
+) -> impl Responder {
+    let session = if let Some(session) = get_session(&req) {
+        session
+    } else {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some("user session is invalid".to_string()),
+        ));
+    };
+    let key = match param.key() {
+        Ok(v) => v,
+        Err(e) => {
+            return HttpResponse::Ok().json(ApiResult::<()>::error(
+                ERROR_CODE_SYSTEM_ERROR.to_string(),
+                Some(e.to_string()),
+            ))
+        }
+    };
+    if !check_pause_permission(&session, &key) {
+        return HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_NO_PERMISSION.to_string(),
+            Some(format!("user no permission:{}", key.to_key_string())),
+        ));
+    }
+    match maintenance::resume_schedule(&req, &share_data, &param, AuditRecorder::console).await {
+        Ok(info) => HttpResponse::Ok().json(ApiResult::success(Some(info))),
+        Err(e) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(format!("resume_schedule error,{}", e)),
+        )),
+    }
+}
+
+/// 预览调度配置的后续触发时间与描述,校验失败时返回错误位置
+pub(crate) async fn preview_schedule(
+    share_data: Data<Arc<ShareData>>,
+    web::Query(param): web::Query<SchedulePreviewParam>,
+) -> impl Responder {
+    HttpResponse::Ok().json(ApiResult::success(Some(
+        param.preview(&share_data.timezone_offset),
+    )))
+}
@@ -1,3 +1,8 @@
+use crate::audit::model::audit::{
+    AuditRecord, AUDIT_ACTION_CREATE, AUDIT_ACTION_REMOVE, AUDIT_ACTION_RESET_PASSWORD,
+    AUDIT_ACTION_UPDATE, AUDIT_TARGET_USER,
+};
+use crate::audit::recorder::AuditRecorder;
 use crate::common::constant::EMPTY_STR;
 use crate::common::datetime_utils::now_millis_i64;
 use crate::common::get_app_version;
@@ -8,7 +13,7 @@ use crate::console::model::user_model::{
 };
 use crate::raft::store::ClientRequest;
 use crate::user::actor_model::{UserManagerRaftReq, UserManagerRaftResult, UserManagerReq};
-use crate::user::model::UserDto;
+use crate::user::model::{UserDto, UserInfo};
 use crate::user::permission::UserRole;
 use actix_http::HttpMessage;
 use actix_web::web::Data;
@@ -80,7 +85,10 @@ pub async fn reset_password(
     };
     if let Ok(Ok(v)) = app.user_manager.send(msg).await {
         match v {
-            UserManagerRaftResult::CheckUser(valid, _user) => {
+            UserManagerRaftResult::CheckUser(valid, user) => {
+                if !user.is_local_user() {
+                    return Ok(user_source_not_local_response());
+                }
                 if valid {
                     let user_dto = UserDto {
                         username: username.clone(),
@@ -94,6 +102,13 @@ pub async fn reset_password(
                         .request(ClientRequest::UserReq { req: msg })
                         .await
                     {
+                        let record = AuditRecorder::console(
+                            &req,
+                            AUDIT_ACTION_RESET_PASSWORD,
+                            AUDIT_TARGET_USER,
+                            username.to_string(),
+                        );
+                        AuditRecorder::submit(&app, record).await;
                         return Ok(HttpResponse::Ok().json(ApiResult::success(Some(true))));
                     }
                 }
@@ -127,7 +142,35 @@ pub async fn get_user_page_list(
     }
 }
 
+async fn query_user(app: &ShareData, username: &Arc<String>) -> Option<UserInfo> {
+    let req = UserManagerReq::Query {
+        name: username.clone(),
+    };
+    if let Ok(Ok(UserManagerRaftResult::QueryUser(v))) = app.user_manager.send(req).await {
+        v
+    } else {
+        None
+    }
+}
+
+async fn submit_user_audit(
+    req: &HttpRequest,
+    app: &ShareData,
+    action: &str,
+    username: &Arc<String>,
+    before: Option<UserInfo>,
+) {
+    let mut record: AuditRecord =
+        AuditRecorder::console(req, action, AUDIT_TARGET_USER, username.to_string());
+    let before: Option<UserVO> = before.map(|v| v.into());
+    let after: Option<UserVO> = query_user(app, username).await.map(|v| v.into());
+    record.set_before(before.as_ref());
+    record.set_after(after.as_ref());
+    AuditRecorder::submit(app, record).await;
+}
+
 pub async fn add_user(
+    req: HttpRequest,
     app: Data<Arc<ShareData>>,
     web::Json(user_param): web::Json<UpdateUserInfoParam>,
 ) -> actix_web::Result<impl Responder> {
@@ -138,15 +181,22 @@ pub async fn add_user(
             Some("user roles is empty".to_owned()),
         )));
     }
+    let username = user.username.clone();
+    let before = query_user(&app, &username).await;
     let msg = UserManagerRaftReq::AddUser(user);
-    app.raft_request_route
+    if app
+        .raft_request_route
         .request(ClientRequest::UserReq { req: msg })
         .await
-        .ok();
+        .is_ok()
+    {
+        submit_user_audit(&req, &app, AUDIT_ACTION_CREATE, &username, before).await;
+    }
     Ok(HttpResponse::Ok().json(ApiResult::success(Some(true))))
 }
 
 pub async fn update_user(
+    req: HttpRequest,
     app: Data<Arc<ShareData>>,
     web::Json(user_param): web::Json<UpdateUserInfoParam>,
 ) -> actix_web::Result<impl Responder> {
@@ -158,28 +208,55 @@ pub async fn update_user(
             Some("user roles is empty".to_owned()),
         )));
     }
+    let before = query_user(&app, &user.username).await;
+    if user.password.is_some() {
+        if let Some(v) = before.as_ref() {
+            if !v.is_local_user() {
+                return Ok(user_source_not_local_response());
+            }
+        }
+    }
+    let username = user.username.clone();
     let msg = UserManagerRaftReq::UpdateUser(user);
-    app.raft_request_route
+    if app
+        .raft_request_route
         .request(ClientRequest::UserReq { req: msg })
         .await
-        .ok();
+        .is_ok()
+    {
+        submit_user_audit(&req, &app, AUDIT_ACTION_UPDATE, &username, before).await;
+    }
     Ok(HttpResponse::Ok().json(ApiResult::success(Some(true))))
 }
 
 pub async fn remove_user(
+    req: HttpRequest,
     app: Data<Arc<ShareData>>,
     web::Json(user): web::Json<UpdateUserInfoParam>,
 ) -> actix_web::Result<impl Responder> {
-    let msg = UserManagerRaftReq::Remove(user.username);
+    let username = user.username;
+    let before = query_user(&app, &username).await;
+    let msg = UserManagerRaftReq::Remove(username.clone());
     match app
         .raft_request_route
         .request(ClientRequest::UserReq { req: msg })
         .await
     {
-        Ok(_) => Ok(HttpResponse::Ok().json(ApiResult::success(Some(true)))),
+        Ok(_) => {
+            submit_user_audit(&req, &app, AUDIT_ACTION_REMOVE, &username, before).await;
+            Ok(HttpResponse::Ok().json(ApiResult::success(Some(true))))
+        }
         Err(e) => Ok(HttpResponse::Ok().json(ApiResult::<()>::error(
             "SYSTEM_ERROR".to_owned(),
             Some(e.to_string()),
         ))),
     }
 }
+
+/// LDAP、OIDC来源的用户不支持设置本地密码
+fn user_source_not_local_response() -> HttpResponse {
+    HttpResponse::Ok().json(ApiResult::<()>::error(
+        "USER_SOURCE_NOT_LOCAL".to_owned(),
+        Some("the user is not a local user, can't set password".to_owned()),
+    ))
+}
@@ -0,0 +1,569 @@
+use crate::app::model::{AppInstanceParam, AppKey, AppManagerRaftReq};
+use crate::common::app_config::AppConfig;
+use crate::common::datetime_utils::{now_millis_i64, now_second_u32};
+use crate::common::registry_util;
+use crate::executor::model::{
+    ExecutorAddr, ExecutorKillParam, ExecutorRegisterParam, ExecutorRegisterResult,
+    ExecutorRouteRequest, ExecutorRouteResponse, ExecutorStreamReq, ExecutorStreamResult,
+    ExecutorStreamSender, ExecutorTaskCallbackParam, ExecutorTaskLogParam, EXECUTOR_BEAT,
+    EXECUTOR_KILL_TASK, EXECUTOR_REGISTER, EXECUTOR_REGISTER_RESPONSE, EXECUTOR_RUN_TASK,
+    EXECUTOR_TASK_CALLBACK, EXECUTOR_TASK_LOG,
+};
+use crate::grpc::ratch_server_proto::Payload;
+use crate::grpc::PayloadUtils;
+use crate::raft::cluster::model::RouterRequest;
+use crate::raft::cluster::route::RaftRequestRoute;
+use crate::raft::store::ClientRequest;
+use crate::schedule::batch_call::{BatchCallManager, BatchCallManagerReq};
+use crate::task::model::request_model::JobRunParam;
+use crate::task::model::task::TaskCallBackParam;
+use actix::prelude::*;
+use bean_factory::{bean, BeanFactory, FactoryData, Inject};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+
+/// 最多保留日志的任务数
+const TASK_LOG_MAX_TASK_COUNT: usize = 1000;
+/// 单个任务最多保留的日志条数
+const TASK_LOG_MAX_LINE_COUNT: usize = 500;
+/// 单个连接最多记录的已下发未回调任务数
+const CONN_MAX_DISPATCH_TASK_COUNT: usize = 2000;
+
+struct ExecutorConn {
+    sender: ExecutorStreamSender,
+    app_key: Option<AppKey>,
+    instance_addr: Arc<String>,
+    /// 下发到该连接且未回调的任务,只接收这些任务的回调和日志
+    dispatch_task_ids: VecDeque<u64>,
+}
+
+impl ExecutorConn {
+    fn add_dispatch_task(&mut self, task_id: u64) {
+        if self.dispatch_task_ids.contains(&task_id) {
+            return;
+        }
+        if self.dispatch_task_ids.len() >= CONN_MAX_DISPATCH_TASK_COUNT {
+            self.dispatch_task_ids.pop_front();
+        }
+        self.dispatch_task_ids.push_back(task_id);
+    }
+
+    fn is_dispatch_task(&self, task_id: u64) -> bool {
+        self.dispatch_task_ids.contains(&task_id)
+    }
+
+    fn remove_dispatch_task(&mut self, task_id: u64) {
+        self.dispatch_task_ids.retain(|v| *v != task_id);
+    }
+}
+
+/// gRPC双向流执行器连接管理;
+/// 执行器注册后与xxl执行器一样作为应用实例参与路由,运行、终止任务通过连接所在节点下发
+#[bean(inject)]
+pub struct ExecutorStreamManager {
+    node_id: u64,
+    access_token: String,
+    conn_map: HashMap<Arc<String>, ExecutorConn>,
+    task_logs: HashMap<u64, VecDeque<Arc<String>>>,
+    task_log_ids: VecDeque<u64>,
+    raft_request_route: Option<Arc<RaftRequestRoute>>,
+    batch_call_manager: Option<Addr<BatchCallManager>>,
+}
+
+impl ExecutorStreamManager {
+    pub fn new(config: Arc<AppConfig>) -> Self {
+        Self {
+            node_id: config.raft_node_id,
+            access_token: config.xxl_default_access_token.clone(),
+            conn_map: HashMap::new(),
+            task_logs: HashMap::new(),
+            task_log_ids: VecDeque::new(),
+            raft_request_route: None,
+            batch_call_manager: None,
+        }
+    }
+
+    fn connect(&mut self, conn_id: Arc<String>, sender: ExecutorStreamSender) {
+        let instance_addr = ExecutorAddr::new(self.node_id, conn_id.clone()).to_addr();
+        self.conn_map.insert(
+            conn_id,
+            ExecutorConn {
+                sender,
+                app_key: None,
+                instance_addr,
+                dispatch_task_ids: VecDeque::new(),
+            },
+        );
+    }
+
+    fn disconnect(&mut self, conn_id: &Arc<String>, ctx: &mut Context<Self>) {
+        if let Some(conn) = self.conn_map.remove(conn_id) {
+            if let Some(app_key) = conn.app_key {
+                log::info!(
+                    "executor disconnect,app:{:?},addr:{}",
+                    &app_key,
+                    &conn.instance_addr
+                );
+                let req = AppManagerRaftReq::UnregisterInstance(AppInstanceParam {
+                    app_key,
+                    instance_addr: conn.instance_addr,
+                    last_modified_time: now_second_u32(),
+                });
+                self.spawn_app_raft_request(req, None, ctx);
+            }
+        }
+    }
+
+    fn handle_payload(
+        &mut self,
+        conn_id: Arc<String>,
+        payload: Payload,
+        ctx: &mut Context<Self>,
+    ) -> anyhow::Result<()> {
+        let body = payload.body.map(|v| v.value).unwrap_or_default();
+        match payload.r#type.as_str() {
+            EXECUTOR_REGISTER => {
+                let param: ExecutorRegisterParam = serde_json::from_slice(&body)?;
+                self.register(conn_id, param, ctx)?;
+            }
+            EXECUTOR_BEAT => {
+                if let Some(conn) = self.conn_map.get(&conn_id) {
+                    if let Some(app_key) = conn.app_key.clone() {
+                        let req = Self::build_register_req(app_key, conn.instance_addr.clone());
+                        self.spawn_app_raft_request(req, None, ctx);
+                    }
+                }
+            }
+            EXECUTOR_TASK_CALLBACK => {
+                let param: ExecutorTaskCallbackParam = serde_json::from_slice(&body)?;
+                let conn = self.get_registered_conn(&conn_id)?;
+                if !conn.is_dispatch_task(param.task_id) {
+                    return Err(anyhow::anyhow!(
+                        "task {} is not dispatched to {}",
+                        param.task_id,
+                        &conn.instance_addr
+                    ));
+                }
+                conn.remove_dispatch_task(param.task_id);
+                let instance_addr = conn.instance_addr.clone();
+                if let Some(batch_call_manager) = self.batch_call_manager.as_ref() {
+                    let callback = TaskCallBackParam {
+                        task_id: param.task_id,
+                        task_date_time: now_millis_i64(),
+                        success: param.success,
+                        handle_msg: param.handle_msg,
+                        result: param.result,
+                        instance_addr: Some(instance_addr),
+                    };
+                    batch_call_manager.do_send(BatchCallManagerReq::Callback(vec![callback]));
+                }
+            }
+            EXECUTOR_TASK_LOG => {
+                let param: ExecutorTaskLogParam = serde_json::from_slice(&body)?;
+                self.get_registered_conn(&conn_id)?;
+                self.append_task_log(param);
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "unknown executor payload type:{}",
+                    &payload.r#type
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// 只处理已注册连接的回调和日志
+    fn get_registered_conn(&mut self, conn_id: &Arc<String>) -> anyhow::Result<&mut ExecutorConn> {
+        self.conn_map
+            .get_mut(conn_id)
+            .filter(|v| v.app_key.is_some())
+            .ok_or_else(|| anyhow::anyhow!("executor is not registered:{}", conn_id))
+    }
+
+    fn register(
+        &mut self,
+        conn_id: Arc<String>,
+        param: ExecutorRegisterParam,
+        ctx: &mut Context<Self>,
+    ) -> anyhow::Result<()> {
+        if !self.access_token.is_empty()
+            && param.access_token.as_deref() != Some(self.access_token.as_str())
+        {
+            // token错误时返回错误状态结束流,并移除连接
+            if let Some(conn) = self.conn_map.remove(&conn_id) {
+                conn.sender
+                    .send(Err(tonic::Status::unauthenticated("access-token is error")))
+                    .ok();
+            }
+            return Ok(());
+        }
+        let app_key = if let Some(namespace) = param.namespace.filter(|v| !v.is_empty()) {
+            AppKey::new(param.app_name, namespace)
+        } else {
+            let parsed = registry_util::parse_registry_key(&param.app_name);
+            AppKey::new(parsed.app_name, parsed.namespace)
+        };
+        if app_key.app_name.is_empty() {
+            return Err(anyhow::anyhow!("executor app_name is empty"));
+        }
+        let conn = if let Some(conn) = self.conn_map.get_mut(&conn_id) {
+            conn
+        } else {
+            return Ok(());
+        };
+        log::info!(
+            "executor register,app:{:?},addr:{}",
+            &app_key,
+            &conn.instance_addr
+        );
+        conn.app_key = Some(app_key.clone());
+        let req = Self::build_register_req(app_key, conn.instance_addr.clone());
+        let result = ExecutorRegisterResult {
+            instance_addr: conn.instance_addr.clone(),
+        };
+        let sender = conn.sender.clone();
+        self.spawn_app_raft_request(req, Some((sender, result)), ctx);
+        Ok(())
+    }
+
+    fn build_register_req(app_key: AppKey, instance_addr: Arc<String>) -> AppManagerRaftReq {
+        AppManagerRaftReq::RegisterInstance(AppInstanceParam {
+            app_key,
+            instance_addr,
+            last_modified_time: now_second_u32(),
+        })
+    }
+
+    /// 通过raft同步应用实例变更,注册时把结果回写给执行器
+    fn spawn_app_raft_request(
+        &self,
+        req: AppManagerRaftReq,
+        register_reply: Option<(ExecutorStreamSender, ExecutorRegisterResult)>,
+        ctx: &mut Context<Self>,
+    ) {
+        let raft_request_route = if let Some(v) = self.raft_request_route.clone() {
+            v
+        } else {
+            return;
+        };
+        async move {
+            let r = raft_request_route
+                .request(ClientRequest::AppReq { req })
+                .await;
+            if let Some((sender, result)) = register_reply {
+                match r {
+                    Ok(_) => {
+                        let value = serde_json::to_vec(&result).unwrap_or_default();
+                        let payload =
+                            PayloadUtils::build_payload(EXECUTOR_REGISTER_RESPONSE, value);
+                        sender.send(Ok(payload)).ok();
+                    }
+                    Err(err) => {
+                        Self::send_error(&sender, format!("register error,{}", err));
+                    }
+                }
+            } else if let Err(err) = r {
+                log::error!("executor app instance update error:{}", err);
+            }
+        }
+        .into_actor(self)
+        .spawn(ctx);
+    }
+
+    fn send_error(sender: &ExecutorStreamSender, message: String) {
+        sender
+            .send(Ok(PayloadUtils::build_error_payload(500u16, message)))
+            .ok();
+    }
+
+    fn append_task_log(&mut self, param: ExecutorTaskLogParam) {
+        if let Some(lines) = self.task_logs.get_mut(&param.task_id) {
+            if lines.len() >= TASK_LOG_MAX_LINE_COUNT {
+                lines.pop_front();
+            }
+            lines.push_back(param.content);
+            return;
+        }
+        while self.task_log_ids.len() >= TASK_LOG_MAX_TASK_COUNT {
+            if let Some(task_id) = self.task_log_ids.pop_front() {
+                self.task_logs.remove(&task_id);
+            }
+        }
+        self.task_log_ids.push_back(param.task_id);
+        self.task_logs
+            .insert(param.task_id, VecDeque::from([param.content]));
+    }
+
+    fn send_to_executor(
+        &mut self,
+        conn_id: &Arc<String>,
+        r#type: &str,
+        value: Vec<u8>,
+        task_id: Option<u64>,
+    ) -> anyhow::Result<()> {
+        let conn = self
+            .conn_map
+            .get_mut(conn_id)
+            .filter(|v| v.app_key.is_some())
+            .ok_or_else(|| anyhow::anyhow!("executor connection not found:{}", conn_id))?;
+        if let Some(task_id) = task_id {
+            conn.add_dispatch_task(task_id);
+        }
+        conn.sender
+            .send(Ok(PayloadUtils::build_payload(r#type, value)))
+            .map_err(|_| anyhow::anyhow!("executor connection is closed:{}", conn_id))?;
+        Ok(())
+    }
+
+    fn handle_route_request(
+        &mut self,
+        req: ExecutorRouteRequest,
+    ) -> anyhow::Result<ExecutorRouteResponse> {
+        match req {
+            ExecutorRouteRequest::RunTask(conn_id, param) => {
+                self.send_to_executor(
+                    &conn_id,
+                    EXECUTOR_RUN_TASK,
+                    serde_json::to_vec(&param)?,
+                    Some(param.log_id),
+                )?;
+            }
+            ExecutorRouteRequest::KillTask(conn_id, param) => {
+                self.send_to_executor(
+                    &conn_id,
+                    EXECUTOR_KILL_TASK,
+                    serde_json::to_vec(&param)?,
+                    None,
+                )?;
+            }
+            ExecutorRouteRequest::QueryTaskLog(task_id) => {
+                let lines = self
+                    .task_logs
+                    .get(&task_id)
+                    .map(|v| v.iter().cloned().collect())
+                    .unwrap_or_default();
+                return Ok(ExecutorRouteResponse::TaskLog(lines));
+            }
+        }
+        Ok(ExecutorRouteResponse::None)
+    }
+
+    /// 连接在本节点时直接处理,否则转发到连接所在节点
+    fn dispatch(
+        &mut self,
+        instance_addr: &str,
+        build_req: impl FnOnce(Arc<String>) -> ExecutorRouteRequest,
+    ) -> ResponseActFuture<Self, anyhow::Result<ExecutorStreamResult>> {
+        let executor_addr = if let Some(v) = ExecutorAddr::parse(instance_addr) {
+            v
+        } else {
+            let err = anyhow::anyhow!("executor addr is invalid:{}", instance_addr);
+            return Box::pin(fut::ready(Err(err)));
+        };
+        let req = build_req(executor_addr.conn_id);
+        if executor_addr.node_id == self.node_id {
+            let r = self
+                .handle_route_request(req)
+                .map(ExecutorStreamResult::RouteResponse);
+            return Box::pin(fut::ready(r));
+        }
+        let raft_request_route = self.raft_request_route.clone();
+        let node_id = executor_addr.node_id;
+        let fut = async move {
+            let raft_request_route =
+                raft_request_route.ok_or_else(|| anyhow::anyhow!("raft_request_route is none"))?;
+            let resp = raft_request_route
+                .request_to_target(RouterRequest::ExecutorRouteRequest(req), node_id)
+                .await?;
+            let resp: ExecutorRouteResponse = resp.try_into()?;
+            Ok(ExecutorStreamResult::RouteResponse(resp))
+        }
+        .into_actor(self);
+        Box::pin(fut)
+    }
+
+    fn run_task(
+        &mut self,
+        instance_addr: &str,
+        param: JobRunParam,
+    ) -> ResponseActFuture<Self, anyhow::Result<ExecutorStreamResult>> {
+        self.dispatch(instance_addr, |conn_id| {
+            ExecutorRouteRequest::RunTask(conn_id, param)
+        })
+    }
+
+    fn kill_task(
+        &mut self,
+        instance_addr: &str,
+        param: ExecutorKillParam,
+    ) -> ResponseActFuture<Self, anyhow::Result<ExecutorStreamResult>> {
+        self.dispatch(instance_addr, |conn_id| {
+            ExecutorRouteRequest::KillTask(conn_id, param)
+        })
+    }
+}
+
+impl Actor for ExecutorStreamManager {
+    type Context = Context<Self>;
+
+    fn started(&mut self, _ctx: &mut Self::Context) {
+        log::info!("ExecutorStreamManager started");
+    }
+}
+
+impl Inject for ExecutorStreamManager {
+    type Context = Context<Self>;
+
+    fn inject(
+        &mut self,
+        factory_data: FactoryData,
+        _factory: BeanFactory,
+        _ctx: &mut Self::Context,
+    ) {
+        self.raft_request_route = factory_data.get_bean();
+        self.batch_call_manager = factory_data.get_actor();
+    }
+}
+
+impl Handler<ExecutorStreamReq> for ExecutorStreamManager {
+    type Result = ResponseActFuture<Self, anyhow::Result<ExecutorStreamResult>>;
+
+    fn handle(&mut self, msg: ExecutorStreamReq, ctx: &mut Context<Self>) -> Self::Result {
+        let r = match msg {
+            ExecutorStreamReq::Connect(conn_id, sender) => {
+                self.connect(conn_id, sender);
+                Ok(ExecutorStreamResult::None)
+            }
+            ExecutorStreamReq::Disconnect(conn_id) => {
+                self.disconnect(&conn_id, ctx);
+                Ok(ExecutorStreamResult::None)
+            }
+            ExecutorStreamReq::Payload(conn_id, payload) => {
+                if let Err(err) = self.handle_payload(conn_id.clone(), payload, ctx) {
+                    log::warn!("executor payload handle error,conn:{},{}", &conn_id, &err);
+                    if let Some(conn) = self.conn_map.get(&conn_id) {
+                        Self::send_error(&conn.sender, err.to_string());
+                    }
+                }
+                Ok(ExecutorStreamResult::None)
+            }
+            ExecutorStreamReq::RunTask(instance_addr, param) => {
+                return self.run_task(&instance_addr, param);
+            }
+            ExecutorStreamReq::KillTask(instance_addr, param) => {
+                return self.kill_task(&instance_addr, param);
+            }
+            ExecutorStreamReq::QueryTaskLog(instance_addr, task_id) => {
+                return self.dispatch(&instance_addr, |_| {
+                    ExecutorRouteRequest::QueryTaskLog(task_id)
+                });
+            }
+            ExecutorStreamReq::RouteRequest(req) => self
+                .handle_route_request(req)
+                .map(ExecutorStreamResult::RouteResponse),
+        };
+        Box::pin(fut::ready(r))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::executor::model::ExecutorRouteRequest;
+
+    fn build_payload<T: serde::Serialize>(r#type: &str, value: &T) -> Payload {
+        PayloadUtils::build_payload(r#type, serde_json::to_vec(value).unwrap())
+    }
+
+    fn callback_payload(task_id: u64) -> Payload {
+        build_payload(
+            EXECUTOR_TASK_CALLBACK,
+            &ExecutorTaskCallbackParam {
+                task_id,
+                success: true,
+                ..Default::default()
+            },
+        )
+    }
+
+    fn register_payload(access_token: &str) -> Payload {
+        build_payload(
+            EXECUTOR_REGISTER,
+            &ExecutorRegisterParam {
+                app_name: Arc::new("app01".to_owned()),
+                namespace: Some(Arc::new("dev".to_owned())),
+                access_token: Some(access_token.to_owned()),
+            },
+        )
+    }
+
+    #[actix_rt::test]
+    async fn executor_callback_check() {
+        let config = AppConfig {
+            xxl_default_access_token: "t1".to_owned(),
+            ..Default::default()
+        };
+        let manager = ExecutorStreamManager::new(Arc::new(config)).start();
+        let conn_id = Arc::new("127.0.0.1:50001".to_owned());
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let send = |req: ExecutorStreamReq| manager.send(req);
+        send(ExecutorStreamReq::Connect(conn_id.clone(), tx))
+            .await
+            .unwrap()
+            .unwrap();
+        // 未注册的连接不接收回调
+        send(ExecutorStreamReq::Payload(
+            conn_id.clone(),
+            callback_payload(7),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(rx.recv().await.unwrap().unwrap().r#type, "ErrorResponse");
+        send(ExecutorStreamReq::Payload(
+            conn_id.clone(),
+            register_payload("t1"),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        // 只接收下发到该连接的任务回调
+        send(ExecutorStreamReq::Payload(
+            conn_id.clone(),
+            callback_payload(7),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(rx.recv().await.unwrap().unwrap().r#type, "ErrorResponse");
+        let param = JobRunParam {
+            log_id: 7,
+            ..Default::default()
+        };
+        send(ExecutorStreamReq::RouteRequest(
+            ExecutorRouteRequest::RunTask(conn_id.clone(), param),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        assert_eq!(rx.recv().await.unwrap().unwrap().r#type, EXECUTOR_RUN_TASK);
+        send(ExecutorStreamReq::Payload(
+            conn_id.clone(),
+            callback_payload(7),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        // token错误时以错误状态结束连接
+        send(ExecutorStreamReq::Payload(
+            conn_id.clone(),
+            register_payload("t2"),
+        ))
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(rx.recv().await.unwrap().is_err());
+        assert!(rx.recv().await.is_none());
+    }
+}
@@ -0,0 +1,145 @@
+use crate::grpc::ratch_server_proto::Payload;
+use crate::task::model::request_model::JobRunParam;
+use actix::Message;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// gRPC执行器的实例地址前缀,完整格式为 `grpc://{节点id}/{连接id}`
+pub const EXECUTOR_ADDR_PREFIX: &str = "grpc://";
+
+// 执行器 -> 调度中心
+pub const EXECUTOR_REGISTER: &str = "ExecutorRegister";
+pub const EXECUTOR_BEAT: &str = "ExecutorBeat";
+pub const EXECUTOR_TASK_CALLBACK: &str = "ExecutorTaskCallback";
+pub const EXECUTOR_TASK_LOG: &str = "ExecutorTaskLog";
+// 调度中心 -> 执行器
+pub const EXECUTOR_REGISTER_RESPONSE: &str = "ExecutorRegisterResponse";
+pub const EXECUTOR_RUN_TASK: &str = "ExecutorRunTask";
+pub const EXECUTOR_KILL_TASK: &str = "ExecutorKillTask";
+
+pub type ExecutorStreamSender = UnboundedSender<Result<Payload, tonic::Status>>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutorAddr {
+    pub node_id: u64,
+    pub conn_id: Arc<String>,
+}
+
+impl ExecutorAddr {
+    pub fn new(node_id: u64, conn_id: Arc<String>) -> Self {
+        Self { node_id, conn_id }
+    }
+
+    pub fn is_executor_addr(addr: &str) -> bool {
+        addr.starts_with(EXECUTOR_ADDR_PREFIX)
+    }
+
+    pub fn parse(addr: &str) -> Option<Self> {
+        let value = addr.strip_prefix(EXECUTOR_ADDR_PREFIX)?;
+        let (node_id, conn_id) = value.split_once('/')?;
+        if conn_id.is_empty() {
+            return None;
+        }
+        Some(Self::new(
+            node_id.parse().ok()?,
+            Arc::new(conn_id.to_owned()),
+        ))
+    }
+
+    pub fn to_addr(&self) -> Arc<String> {
+        Arc::new(format!(
+            "{}{}/{}",
+            EXECUTOR_ADDR_PREFIX, self.node_id, self.conn_id
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutorRegisterParam {
+    pub app_name: Arc<String>,
+    pub namespace: Option<Arc<String>>,
+    pub access_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutorRegisterResult {
+    pub instance_addr: Arc<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutorTaskCallbackParam {
+    pub task_id: u64,
+    pub success: bool,
+    pub handle_msg: Option<Arc<String>>,
+    /// 结构化任务结果(json),下次运行时通过lastResult传回
+    #[serde(default)]
+    pub result: Option<Arc<String>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutorTaskLogParam {
+    pub task_id: u64,
+    pub content: Arc<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExecutorKillParam {
+    pub job_id: u64,
+    pub task_id: u64,
+}
+
+/// 发往持有执行器连接的节点处理的请求
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExecutorRouteRequest {
+    RunTask(Arc<String>, JobRunParam),
+    KillTask(Arc<String>, ExecutorKillParam),
+    QueryTaskLog(u64),
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ExecutorRouteResponse {
+    TaskLog(Vec<Arc<String>>),
+    None,
+}
+
+#[derive(Message)]
+#[rtype(result = "anyhow::Result<ExecutorStreamResult>")]
+pub enum ExecutorStreamReq {
+    Connect(Arc<String>, ExecutorStreamSender),
+    Disconnect(Arc<String>),
+    Payload(Arc<String>, Payload),
+    RunTask(Arc<String>, JobRunParam),
+    KillTask(Arc<String>, ExecutorKillParam),
+    QueryTaskLog(Arc<String>, u64),
+    RouteRequest(ExecutorRouteRequest),
+}
+
+pub enum ExecutorStreamResult {
+    RouteResponse(ExecutorRouteResponse),
+    None,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn executor_addr_parse() {
+        let addr = ExecutorAddr::new(2, Arc::new("10.0.0.1:52310".to_owned()));
+        let value = addr.to_addr();
+        assert_eq!(value.as_str(), "grpc://2/10.0.0.1:52310");
+        assert!(ExecutorAddr::is_executor_addr(&value));
+        assert_eq!(ExecutorAddr::parse(&value), Some(addr));
+        assert!(!ExecutorAddr::is_executor_addr("http://10.0.0.1:9999"));
+        assert_eq!(ExecutorAddr::parse("grpc://x/10.0.0.1:52310"), None);
+        assert_eq!(ExecutorAddr::parse("grpc://2/"), None);
+    }
+}
@@ -1,9 +1,11 @@
 use crate::common::share_data::ShareData;
+use crate::executor::model::ExecutorStreamReq;
 use crate::grpc::handler::InvokerHandler;
-use crate::grpc::ratch_server_proto::{request_server, Payload, Response};
+use crate::grpc::ratch_server_proto::{request_server, stream_server, Payload, Response};
 use crate::grpc::{PayloadHandler, RequestMeta};
 use std::sync::Arc;
-use tonic::{Request, Status};
+use tonic::codegen::tokio_stream::wrappers::UnboundedReceiverStream;
+use tonic::{Request, Status, Streaming};
 
 pub struct RequestServerImpl {
     #[allow(dead_code)]
@@ -46,3 +48,53 @@ impl request_server::Request for RequestServerImpl {
         }
     }
 }
+
+/// 执行器双向流连接,连接信息交由ExecutorStreamManager维护
+pub struct StreamServerImpl {
+    share_data: Arc<ShareData>,
+}
+
+impl StreamServerImpl {
+    pub fn new(share_data: Arc<ShareData>) -> Self {
+        StreamServerImpl { share_data }
+    }
+}
+
+#[tonic::async_trait]
+impl stream_server::Stream for StreamServerImpl {
+    type streamStream = UnboundedReceiverStream<Result<Payload, Status>>;
+
+    async fn stream(
+        &self,
+        request: Request<Streaming<Payload>>,
+    ) -> Result<tonic::Response<Self::streamStream>, Status> {
+        let remote_addr = request
+            .remote_addr()
+            .ok_or_else(|| Status::invalid_argument("remote addr is empty"))?;
+        let conn_id = Arc::new(remote_addr.to_string());
+        let (sender, receiver) = tokio::sync::mpsc::unbounded_channel();
+        let manager = self.share_data.executor_stream_manager.clone();
+        manager
+            .send(ExecutorStreamReq::Connect(conn_id.clone(), sender))
+            .await
+            .map_err(|err| Status::internal(err.to_string()))?
+            .map_err(|err| Status::internal(err.to_string()))?;
+        let mut inbound = request.into_inner();
+        tokio::spawn(async move {
+            loop {
+                match inbound.message().await {
+                    Ok(Some(payload)) => {
+                        manager.do_send(ExecutorStreamReq::Payload(conn_id.clone(), payload));
+                    }
+                    Ok(None) => break,
+                    Err(err) => {
+                        log::warn!("executor stream error,conn:{},{}", &conn_id, err);
+                        break;
+                    }
+                }
+            }
+            manager.do_send(ExecutorStreamReq::Disconnect(conn_id));
+        });
+        Ok(tonic::Response::new(UnboundedReceiverStream::new(receiver)))
+    }
+}
@@ -0,0 +1,280 @@
+use crate::audit::model::audit::{
+    AuditRecord, AUDIT_ACTION_REMOVE, AUDIT_ACTION_TRIGGER, AUDIT_ACTION_UPDATE, AUDIT_TARGET_JOB,
+};
+use crate::audit::recorder::AuditRecorder;
+use crate::common::constant::EMPTY_ARC_STR;
+use crate::common::datetime_utils::{now_millis, now_second_u32};
+use crate::common::model::privilege::PrivilegeGroup;
+use crate::common::share_data::ShareData;
+use crate::job::job_index::JobQueryParam;
+use crate::job::model::actor_model::{JobManagerRaftReq, JobManagerReq, JobManagerResult};
+use crate::job::model::enum_type::RouterStrategy;
+use crate::job::model::job::{JobInfo, JobParam};
+use crate::job::model::label::LabelSelector;
+use crate::raft::store::ClientRequest;
+use crate::task::model::actor_model::{TaskManagerReq, TriggerItem};
+use actix_web::HttpRequest;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+/// 控制台与开放接口的审计记录构造方法
+pub type AuditRecordBuilder = fn(&HttpRequest, &str, &str, String) -> AuditRecord;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum JobBatchAction {
+    Enable,
+    Disable,
+    Trigger,
+    Remove,
+    UpdateRouterStrategy,
+}
+
+///
+/// 按标签选择器批量操作任务
+/// 选择器不能为空,避免误操作全部任务
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobBatchRequest {
+    pub namespace: Option<Arc<String>>,
+    pub app_name: Option<Arc<String>>,
+    pub label_selector: Option<String>,
+    pub action: Option<JobBatchAction>,
+    /// UPDATE_ROUTER_STRATEGY 时必填
+    pub router_strategy: Option<String>,
+    /// 只返回匹配的任务,不执行
+    pub dry_run: Option<bool>,
+}
+
+impl JobBatchRequest {
+    pub fn to_query_param(
+        &self,
+        namespace_privilege: PrivilegeGroup<Arc<String>>,
+        app_privilege: PrivilegeGroup<Arc<String>>,
+    ) -> anyhow::Result<JobQueryParam> {
+        let label_selector = LabelSelector::parse(self.label_selector.as_deref().unwrap_or(""))?;
+        if label_selector.is_empty() {
+            return Err(anyhow::anyhow!("label_selector is empty!"));
+        }
+        Ok(JobQueryParam {
+            namespace: self.namespace.clone(),
+            app_name: self.app_name.clone(),
+            label_selector: Some(label_selector),
+            namespace_privilege,
+            app_privilege,
+            offset: 0,
+            limit: usize::MAX,
+            ..Default::default()
+        })
+    }
+
+    pub fn get_action(&self) -> anyhow::Result<JobBatchAction> {
+        let action = self
+            .action
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("action is empty!"))?;
+        if action == JobBatchAction::UpdateRouterStrategy {
+            self.get_router_strategy()?;
+        }
+        Ok(action)
+    }
+
+    fn get_router_strategy(&self) -> anyhow::Result<RouterStrategy> {
+        self.router_strategy
+            .as_deref()
+            .and_then(RouterStrategy::from_str)
+            .ok_or_else(|| anyhow::anyhow!("router_strategy is invalid!"))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobBatchItemResult {
+    pub job_id: u64,
+    pub namespace: Arc<String>,
+    pub app_name: Arc<String>,
+    pub key: Arc<String>,
+    pub description: Arc<String>,
+    pub success: bool,
+    pub message: Option<String>,
+}
+
+impl JobBatchItemResult {
+    fn new(job: &JobInfo) -> Self {
+        JobBatchItemResult {
+            job_id: job.id,
+            namespace: job.namespace.clone(),
+            app_name: job.app_name.clone(),
+            key: job.key.clone(),
+            description: job.description.clone(),
+            success: true,
+            message: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobBatchResult {
+    pub action: JobBatchAction,
+    pub dry_run: bool,
+    pub total: usize,
+    pub success_count: usize,
+    pub fail_count: usize,
+    pub items: Vec<JobBatchItemResult>,
+}
+
+///
+/// 按选择器批量操作任务,每个任务单独写入raft,单个失败不影响其它任务
+pub async fn apply_job_batch(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    request: &JobBatchRequest,
+    query_param: JobQueryParam,
+    operator: Arc<String>,
+    audit_builder: AuditRecordBuilder,
+) -> anyhow::Result<JobBatchResult> {
+    let action = request.get_action()?;
+    let jobs = if let JobManagerResult::JobInfoList(list) = share_data
+        .job_manager
+        .send(JobManagerReq::QueryJobInfoList(query_param))
+        .await??
+    {
+        list
+    } else {
+        return Err(anyhow::anyhow!("query jobs result type error!"));
+    };
+    let dry_run = request.dry_run.unwrap_or(false);
+    let mut items = Vec::with_capacity(jobs.len());
+    for job in jobs {
+        let mut item = JobBatchItemResult::new(&job);
+        if !dry_run {
+            match apply_job_item(
+                req,
+                share_data,
+                request,
+                &action,
+                job,
+                &operator,
+                audit_builder,
+            )
+            .await
+            {
+                Ok(message) => item.message = message,
+                Err(err) => {
+                    item.success = false;
+                    item.message = Some(err.to_string());
+                }
+            }
+        }
+        items.push(item);
+    }
+    let success_count = items.iter().filter(|v| v.success).count();
+    Ok(JobBatchResult {
+        action,
+        dry_run,
+        total: items.len(),
+        success_count,
+        fail_count: items.len() - success_count,
+        items,
+    })
+}
+
+/// 执行单个任务的操作,配置未变化时不写入,返回说明信息
+async fn apply_job_item(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    request: &JobBatchRequest,
+    action: &JobBatchAction,
+    job: Arc<JobInfo>,
+    operator: &Arc<String>,
+    audit_builder: AuditRecordBuilder,
+) -> anyhow::Result<Option<String>> {
+    let mut param = JobParam {
+        id: Some(job.id),
+        update_time: Some(now_millis()),
+        update_user: Some(operator.clone()),
+        ..Default::default()
+    };
+    match action {
+        JobBatchAction::Enable | JobBatchAction::Disable => {
+            let enable = action == &JobBatchAction::Enable;
+            if job.enable == enable {
+                return Ok(Some("unchanged".to_owned()));
+            }
+            param.enable = Some(enable);
+        }
+        JobBatchAction::UpdateRouterStrategy => {
+            let router_strategy = request.get_router_strategy()?;
+            if job.router_strategy == router_strategy {
+                return Ok(Some("unchanged".to_owned()));
+            }
+            param.router_strategy = Some(router_strategy);
+        }
+        JobBatchAction::Trigger => {
+            let mut record = audit_builder(
+                req,
+                AUDIT_ACTION_TRIGGER,
+                AUDIT_TARGET_JOB,
+                job.id.to_string(),
+            );
+            record.namespace = job.namespace.clone();
+            record.detail = Arc::new("batch".to_owned());
+            let item = TriggerItem::new_with_user(
+                now_second_u32(),
+                job,
+                EMPTY_ARC_STR.clone(),
+                operator.clone(),
+            );
+            share_data
+                .task_manager
+                .send(TaskManagerReq::TriggerTaskList(vec![item]))
+                .await??;
+            AuditRecorder::submit(share_data, record).await;
+            return Ok(None);
+        }
+        JobBatchAction::Remove => {
+            let mut record = audit_builder(
+                req,
+                AUDIT_ACTION_REMOVE,
+                AUDIT_TARGET_JOB,
+                job.id.to_string(),
+            );
+            record.namespace = job.namespace.clone();
+            record.detail = Arc::new("batch".to_owned());
+            record.set_before(Some(&job));
+            share_data
+                .raft_request_route
+                .request(ClientRequest::JobReq {
+                    req: JobManagerRaftReq::Remove(job.id),
+                })
+                .await?;
+            AuditRecorder::submit(share_data, record).await;
+            return Ok(None);
+        }
+    }
+    let mut record = audit_builder(
+        req,
+        AUDIT_ACTION_UPDATE,
+        AUDIT_TARGET_JOB,
+        job.id.to_string(),
+    );
+    record.namespace = job.namespace.clone();
+    record.detail = Arc::new("batch".to_owned());
+    record.set_before(Some(&job));
+    share_data
+        .raft_request_route
+        .request(ClientRequest::JobReq {
+            req: JobManagerRaftReq::UpdateJob(param),
+        })
+        .await?;
+    if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) = share_data
+        .job_manager
+        .send(JobManagerReq::GetJob(job.id))
+        .await
+    {
+        record.set_after(Some(&info));
+    }
+    AuditRecorder::submit(share_data, record).await;
+    Ok(None)
+}
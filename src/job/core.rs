@@ -1,14 +1,17 @@
 use crate::common::app_config::AppConfig;
 use crate::common::byte_utils::id_to_bin;
-use crate::common::constant::{JOB_TABLE_NAME, JOB_TASK_TABLE_NAME};
+use crate::common::constant::{
+    JOB_GLUE_TABLE_NAME, JOB_TABLE_NAME, JOB_TASK_TABLE_NAME, JOB_VERSION_TABLE_NAME,
+};
 use crate::common::datetime_utils::now_millis;
-use crate::common::pb::data_object::{JobDo, JobTaskDo};
-use crate::job::job_index::JobQueryParam;
+use crate::common::pb::data_object::{JobDo, JobGlueDo, JobTaskDo, JobVersionDo};
+use crate::job::job_index::{JobIndex, JobQueryParam};
 use crate::job::model::actor_model::{
     JobManagerRaftReq, JobManagerRaftResult, JobManagerReq, JobManagerResult,
 };
 use crate::job::model::job::{
-    JobInfo, JobInfoDto, JobKey, JobParam, JobTaskLogQueryParam, JobWrap,
+    JobGlueHistoryInfo, JobHistoryInfo, JobInfo, JobInfoDto, JobKey, JobParam,
+    JobTaskLogQueryParam, JobWrap,
 };
 use crate::raft::store::model::SnapshotRecordDto;
 use crate::raft::store::raftapply::{RaftApplyDataRequest, RaftApplyDataResponse};
@@ -28,7 +31,10 @@ pub struct JobManager {
     pub(crate) job_map: BTreeMap<u64, JobWrap>,
     schedule_manager: Option<Addr<ScheduleManager>>,
     job_key_map: HashMap<JobKey, u64>,
+    job_index: JobIndex,
     job_task_log_limit: usize,
+    job_version_limit: usize,
+    job_glue_version_limit: usize,
 }
 
 impl JobManager {
@@ -36,8 +42,11 @@ impl JobManager {
         JobManager {
             job_map: BTreeMap::new(),
             job_key_map: HashMap::new(),
+            job_index: JobIndex::new(),
             schedule_manager: None,
             job_task_log_limit: config.job_task_log_limit,
+            job_version_limit: config.job_version_limit,
+            job_glue_version_limit: config.job_glue_version_limit,
         }
     }
 
@@ -51,17 +60,33 @@ impl JobManager {
                 "CreateJob，The job already exists and is repeatedly created"
             ));
         }
+        let operator = job_param.update_user.clone();
         let mut job_info: JobInfo = job_param.into();
         job_info.check_valid()?;
         let now = now_millis();
         job_info.last_modified_millis = now;
         job_info.create_time = now;
+        if !job_info.glue_source.is_empty() {
+            job_info.glue_update_time = now;
+        }
         let value = Arc::new(job_info);
-        self.job_map.insert(value.id, JobWrap::new(value.clone()));
+        let mut job_wrap = JobWrap::new(value.clone());
+        if !value.glue_source.is_empty() {
+            job_wrap.add_glue_history(
+                Arc::new(JobGlueHistoryInfo::new(&value, operator.clone())),
+                self.job_glue_version_limit,
+            );
+        }
+        job_wrap.add_history(
+            Arc::new(JobHistoryInfo::new(value.clone(), operator)),
+            self.job_version_limit,
+        );
+        self.job_map.insert(value.id, job_wrap);
         if !value.key.is_empty() {
             let job_key = value.build_job_key();
             self.job_key_map.insert(job_key, value.id);
         }
+        self.job_index.insert_labels(value.id, &value.labels);
         if let Some(schedule_manager) = self.schedule_manager.as_ref() {
             schedule_manager.do_send(ScheduleManagerReq::UpdateJob(value.clone()));
         }
@@ -73,34 +98,75 @@ impl JobManager {
         if id == 0 {
             return Err(anyhow::anyhow!("UpdateJob JobParam.id==0 is invalid!"));
         }
-        if let Some(job_wrap) = self.job_map.get_mut(&id) {
-            let job_info = &job_wrap.job;
-            let old_job_key = if job_info.key.is_empty() {
-                None
-            } else {
-                Some(job_info.build_job_key())
-            };
-            let mut new_job = job_info.as_ref().clone();
-            new_job.update_param(job_param);
-            job_info.check_valid()?;
-            let value = Arc::new(new_job);
+        let job_info = if let Some(job_wrap) = self.job_map.get(&id) {
+            job_wrap.job.clone()
+        } else {
+            return Err(anyhow::anyhow!("UpdateJob,Nonexistent Job"));
+        };
+        let operator = job_param.update_user.clone();
+        let mut new_job = job_info.as_ref().clone();
+        new_job.update_param(job_param);
+        new_job.check_valid()?;
+        self.save_updated_job(new_job, operator);
+        Ok(())
+    }
 
-            if let Some(old_key) = old_job_key {
-                self.job_key_map.remove(&old_key);
-            }
-            if !value.key.is_empty() {
-                let new_job_key = value.build_job_key();
-                self.job_key_map.insert(new_job_key, value.id);
-            }
+    /// 用完整的任务配置替换现有任务，版本号、创建时间沿用现有任务
+    fn replace_job(
+        &mut self,
+        job: Arc<JobInfo>,
+        operator: Option<Arc<String>>,
+    ) -> anyhow::Result<()> {
+        let old_job = if let Some(job_wrap) = self.job_map.get(&job.id) {
+            job_wrap.job.clone()
+        } else {
+            return Err(anyhow::anyhow!("ReplaceJob,Nonexistent Job"));
+        };
+        let mut new_job = job.as_ref().clone();
+        new_job.check_valid()?;
+        new_job.version_id = old_job.version_id + 1;
+        new_job.create_time = old_job.create_time;
+        new_job.glue_update_time = if new_job.glue_source != old_job.glue_source {
+            new_job.last_modified_millis
+        } else {
+            old_job.glue_update_time
+        };
+        self.save_updated_job(new_job, operator);
+        Ok(())
+    }
 
-            job_wrap.job = value.clone();
-            if let Some(schedule_manager) = self.schedule_manager.as_ref() {
-                schedule_manager.do_send(ScheduleManagerReq::UpdateJob(value.clone()));
-            }
+    fn save_updated_job(&mut self, new_job: JobInfo, operator: Option<Arc<String>>) {
+        let job_wrap = if let Some(job_wrap) = self.job_map.get_mut(&new_job.id) {
+            job_wrap
         } else {
-            return Err(anyhow::anyhow!("UpdateJob,Nonexistent Job"));
+            return;
+        };
+        let value = Arc::new(new_job);
+        if !job_wrap.job.key.is_empty() {
+            self.job_key_map.remove(&job_wrap.job.build_job_key());
+        }
+        if !value.key.is_empty() {
+            let new_job_key = value.build_job_key();
+            self.job_key_map.insert(new_job_key, value.id);
+        }
+        self.job_index.remove_labels(value.id, &job_wrap.job.labels);
+        self.job_index.insert_labels(value.id, &value.labels);
+
+        //脚本内容变更时记录GLUE历史版本
+        if value.glue_source != job_wrap.job.glue_source {
+            job_wrap.add_glue_history(
+                Arc::new(JobGlueHistoryInfo::new(&value, operator.clone())),
+                self.job_glue_version_limit,
+            );
+        }
+        job_wrap.job = value.clone();
+        job_wrap.add_history(
+            Arc::new(JobHistoryInfo::new(value.clone(), operator)),
+            self.job_version_limit,
+        );
+        if let Some(schedule_manager) = self.schedule_manager.as_ref() {
+            schedule_manager.do_send(ScheduleManagerReq::UpdateJob(value));
         }
-        Ok(())
     }
 
     fn remove_job(&mut self, id: u64) {
@@ -109,6 +175,7 @@ impl JobManager {
                 let job_key = job_wrap.job.build_job_key();
                 self.job_key_map.remove(&job_key);
             }
+            self.job_index.remove_labels(id, &job_wrap.job.labels);
         }
         self.job_map.remove(&id);
         if let Some(schedule_manager) = self.schedule_manager.as_ref() {
@@ -122,6 +189,8 @@ impl JobManager {
                 let old_job_key = job_wrap.job.build_job_key();
                 self.job_key_map.remove(&old_job_key);
             }
+            self.job_index.remove_labels(job.id, &job_wrap.job.labels);
+            self.job_index.insert_labels(job.id, &job.labels);
             job_wrap.job = job.clone();
             if !job.key.is_empty() {
                 let new_job_key = job.build_job_key();
@@ -133,6 +202,7 @@ impl JobManager {
                 let job_key = job.build_job_key();
                 self.job_key_map.insert(job_key, job.id);
             }
+            self.job_index.insert_labels(job.id, &job.labels);
             if let Some(schedule_manager) = self.schedule_manager.as_ref() {
                 schedule_manager.do_send(ScheduleManagerReq::UpdateJob(job));
             }
@@ -186,24 +256,45 @@ impl JobManager {
         let end_index = query_param.offset + query_param.limit;
         let mut index = 0;
 
-        for (_, job_wrap) in self.job_map.iter().rev() {
-            let job_info = &job_wrap.job;
-            if query_param.match_namespace(&job_info.namespace)
-                && query_param.match_app_name(&job_info.app_name)
-                && query_param.match_description(&job_info.description)
-                && query_param.match_handle_name(&job_info.handle_name)
-                && query_param.match_key(&job_info.key)
-            {
-                if index >= query_param.offset && index < end_index {
-                    rlist.push(JobInfoDto::new_from(job_info));
-                }
-                index += 1;
+        for job_info in self.filter_jobs(query_param) {
+            if index >= query_param.offset && index < end_index {
+                rlist.push(JobInfoDto::new_from(job_info));
             }
+            index += 1;
         }
 
         (index, rlist)
     }
 
+    /// 按查询条件过滤任务(id倒序),有标签选择器时优先使用标签索引缩小范围
+    fn filter_jobs<'a>(
+        &'a self,
+        query_param: &'a JobQueryParam,
+    ) -> Box<dyn Iterator<Item = &'a Arc<JobInfo>> + 'a> {
+        let candidates = query_param
+            .label_selector
+            .as_ref()
+            .and_then(|selector| self.job_index.select_label_job_ids(selector));
+        let iter: Box<dyn Iterator<Item = &'a JobWrap> + 'a> = match candidates {
+            Some(ids) => Box::new(
+                ids.into_iter()
+                    .rev()
+                    .filter_map(|id| self.job_map.get(&id))
+                    .collect::<Vec<_>>()
+                    .into_iter(),
+            ),
+            None => Box::new(self.job_map.values().rev()),
+        };
+        Box::new(iter.map(|job_wrap| &job_wrap.job).filter(|job_info| {
+            query_param.match_namespace(&job_info.namespace)
+                && query_param.match_app_name(&job_info.app_name)
+                && query_param.match_description(&job_info.description)
+                && query_param.match_handle_name(&job_info.handle_name)
+                && query_param.match_key(&job_info.key)
+                && query_param.match_labels(&job_info.labels)
+        }))
+    }
+
     fn query_job_task_logs(
         &self,
         query_param: &JobTaskLogQueryParam,
@@ -215,6 +306,9 @@ impl JobManager {
 
         if let Some(job_wrap) = self.job_map.get(&query_param.job_id) {
             for (_task_id, task_log) in job_wrap.task_log_map.iter().rev() {
+                if !query_param.match_status(&task_log.status) {
+                    continue;
+                }
                 if index >= query_param.offset && index < end_index {
                     rlist.push(task_log.clone());
                 }
@@ -224,6 +318,22 @@ impl JobManager {
         (index, rlist)
     }
 
+    fn query_job_versions(&self, job_id: u64) -> Vec<Arc<JobHistoryInfo>> {
+        if let Some(job_wrap) = self.job_map.get(&job_id) {
+            job_wrap.histories.iter().rev().cloned().collect()
+        } else {
+            vec![]
+        }
+    }
+
+    fn query_job_glue_versions(&self, job_id: u64) -> Vec<Arc<JobGlueHistoryInfo>> {
+        if let Some(job_wrap) = self.job_map.get(&job_id) {
+            job_wrap.glue_histories.iter().rev().cloned().collect()
+        } else {
+            vec![]
+        }
+    }
+
     fn build_snapshot(&self, writer: Addr<SnapshotWriterActor>) -> anyhow::Result<()> {
         //任务
         for (key, job_wrap) in &self.job_map {
@@ -243,7 +353,15 @@ impl JobManager {
         }
         //任务运行实例
         for (_, job_wrap) in &self.job_map {
-            for (task_id, task_log) in job_wrap.task_log_map.iter() {
+            let last_result_task = job_wrap
+                .last_result_task
+                .as_ref()
+                .filter(|v| !job_wrap.task_log_map.contains_key(&v.task_id));
+            let task_logs = job_wrap
+                .task_log_map
+                .iter()
+                .chain(last_result_task.map(|v| (&v.task_id, v)));
+            for (task_id, task_log) in task_logs {
                 let mut buf = Vec::new();
                 {
                     let mut writer = Writer::new(&mut buf);
@@ -259,6 +377,46 @@ impl JobManager {
                 writer.do_send(SnapshotWriterRequest::Record(record));
             }
         }
+        //任务历史版本
+        for (job_id, job_wrap) in &self.job_map {
+            for history in job_wrap.histories.iter() {
+                let mut buf = Vec::new();
+                {
+                    let mut writer = Writer::new(&mut buf);
+                    let value_do = history.as_ref().to_do();
+                    writer.write_message(&value_do)?;
+                }
+                let mut key = id_to_bin(*job_id);
+                key.extend_from_slice(&id_to_bin(history.version_id));
+                let record = SnapshotRecordDto {
+                    tree: JOB_VERSION_TABLE_NAME.clone(),
+                    key,
+                    value: buf,
+                    op_type: 0,
+                };
+                writer.do_send(SnapshotWriterRequest::Record(record));
+            }
+        }
+        //GLUE脚本历史版本
+        for (job_id, job_wrap) in &self.job_map {
+            for history in job_wrap.glue_histories.iter() {
+                let mut buf = Vec::new();
+                {
+                    let mut writer = Writer::new(&mut buf);
+                    let value_do = history.as_ref().to_do();
+                    writer.write_message(&value_do)?;
+                }
+                let mut key = id_to_bin(*job_id);
+                key.extend_from_slice(&id_to_bin(history.version_id));
+                let record = SnapshotRecordDto {
+                    tree: JOB_GLUE_TABLE_NAME.clone(),
+                    key,
+                    value: buf,
+                    op_type: 0,
+                };
+                writer.do_send(SnapshotWriterRequest::Record(record));
+            }
+        }
         Ok(())
     }
 
@@ -275,11 +433,36 @@ impl JobManager {
             if let Some(job_wrap) = self.job_map.get_mut(&value.job_id) {
                 job_wrap.update_task_log(value, self.job_task_log_limit);
             }
+        } else if record.tree.as_str() == JOB_VERSION_TABLE_NAME.as_str() {
+            let mut reader = BytesReader::from_bytes(&record.value);
+            let value_do: JobVersionDo = reader.read_message(&record.value)?;
+            let value: Arc<JobHistoryInfo> = Arc::new(value_do.into());
+            if let Some(job_wrap) = self.job_map.get_mut(&value.job.id) {
+                job_wrap.add_history(value, self.job_version_limit);
+            }
+        } else if record.tree.as_str() == JOB_GLUE_TABLE_NAME.as_str() {
+            let mut reader = BytesReader::from_bytes(&record.value);
+            let value_do: JobGlueDo = reader.read_message(&record.value)?;
+            let value: Arc<JobGlueHistoryInfo> = Arc::new(value_do.into());
+            if let Some(job_wrap) = self.job_map.get_mut(&value.job_id) {
+                job_wrap.add_glue_history(value, self.job_glue_version_limit);
+            }
         }
         Ok(())
     }
 
     fn load_completed(&mut self, _ctx: &mut Context<Self>) -> anyhow::Result<()> {
+        //旧版本镜像没有历史版本,以当前配置作为第一个版本
+        for job_wrap in self.job_map.values_mut() {
+            if job_wrap.get_history(job_wrap.job.version_id).is_none() {
+                let history = Arc::new(JobHistoryInfo::new(job_wrap.job.clone(), None));
+                job_wrap.add_history(history, self.job_version_limit);
+            }
+            if job_wrap.glue_histories.is_empty() && !job_wrap.job.glue_source.is_empty() {
+                let history = Arc::new(JobGlueHistoryInfo::new(&job_wrap.job, None));
+                job_wrap.add_glue_history(history, self.job_glue_version_limit);
+            }
+        }
         Ok(())
     }
 
@@ -339,10 +522,44 @@ impl Handler<JobManagerReq> for JobManager {
                 let (size, list) = self.query_jobs(&query_param);
                 return Ok(JobManagerResult::JobPageInfo(size, list));
             }
+            JobManagerReq::QueryJobInfoList(query_param) => {
+                let list = self.filter_jobs(&query_param).cloned().collect();
+                return Ok(JobManagerResult::JobInfoList(list));
+            }
             JobManagerReq::QueryJobTaskLog(query_param) => {
                 let (size, list) = self.query_job_task_logs(&query_param);
                 return Ok(JobManagerResult::JobTaskLogPageInfo(size, list));
             }
+            JobManagerReq::QueryJobVersions(job_id) => {
+                let list = self.query_job_versions(job_id);
+                return Ok(JobManagerResult::JobVersionList(list));
+            }
+            JobManagerReq::QueryJobGlueVersions(job_id) => {
+                let list = self.query_job_glue_versions(job_id);
+                return Ok(JobManagerResult::JobGlueVersionList(list));
+            }
+            JobManagerReq::QueryJobLastResults(job_ids) => {
+                let mut map = HashMap::new();
+                for job_id in job_ids {
+                    if let Some(result) = self
+                        .job_map
+                        .get(&job_id)
+                        .and_then(|job_wrap| job_wrap.get_last_result())
+                    {
+                        map.insert(job_id, result);
+                    }
+                }
+                return Ok(JobManagerResult::JobLastResults(map));
+            }
+            JobManagerReq::QueryAppJobs(app_keys) => {
+                let list = self
+                    .job_map
+                    .values()
+                    .filter(|job_wrap| app_keys.contains(&job_wrap.job.build_app_key()))
+                    .map(|job_wrap| job_wrap.job.clone())
+                    .collect();
+                return Ok(JobManagerResult::JobInfoList(list));
+            }
             JobManagerReq::CountJobsByNamespace(namespace) => {
                 let count = self
                     .job_map
@@ -367,6 +584,9 @@ impl Handler<JobManagerRaftReq> for JobManager {
             JobManagerRaftReq::UpdateJob(job_param) => {
                 self.update_job(job_param)?;
             }
+            JobManagerRaftReq::ReplaceJob { job, update_user } => {
+                self.replace_job(job, update_user)?;
+            }
             JobManagerRaftReq::Remove(id) => {
                 self.remove_job(id);
             }
@@ -1,6 +1,7 @@
 use crate::common::model::privilege::PrivilegeGroup;
+use crate::job::model::label::{JobLabels, LabelRequirement, LabelSelector};
 use serde::{Deserialize, Serialize};
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
 use std::sync::Arc;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -11,6 +12,7 @@ pub struct JobQueryParam {
     pub like_description: Option<Arc<String>>,
     pub like_handle_name: Option<Arc<String>>,
     pub like_key: Option<Arc<String>>,
+    pub label_selector: Option<LabelSelector>,
     pub namespace_privilege: PrivilegeGroup<Arc<String>>,
     pub app_privilege: PrivilegeGroup<Arc<String>>,
     pub offset: usize,
@@ -63,6 +65,12 @@ impl JobQueryParam {
             true
         }
     }
+
+    pub fn match_labels(&self, labels: &JobLabels) -> bool {
+        self.label_selector
+            .as_ref()
+            .is_none_or(|selector| selector.matches(labels))
+    }
 }
 
 #[derive(Debug, Clone, Default)]
@@ -76,6 +84,8 @@ pub struct JobIndexInfo {
 pub struct JobIndex {
     pub(crate) group_data:
         BTreeMap<Arc<String>, BTreeMap<Arc<String>, BTreeMap<u64, JobIndexInfo>>>,
+    /// 标签索引: key -> value -> job_id
+    pub(crate) label_data: BTreeMap<String, BTreeMap<String, BTreeSet<u64>>>,
 }
 
 impl JobIndex {
@@ -125,6 +135,60 @@ impl JobIndex {
         }
     }
 
+    pub(crate) fn insert_labels(&mut self, job_id: u64, labels: &JobLabels) {
+        for (key, value) in labels {
+            self.label_data
+                .entry(key.clone())
+                .or_default()
+                .entry(value.clone())
+                .or_default()
+                .insert(job_id);
+        }
+    }
+
+    pub(crate) fn remove_labels(&mut self, job_id: u64, labels: &JobLabels) {
+        for (key, value) in labels {
+            if let Some(value_map) = self.label_data.get_mut(key) {
+                if let Some(ids) = value_map.get_mut(value) {
+                    ids.remove(&job_id);
+                    if ids.is_empty() {
+                        value_map.remove(value);
+                    }
+                }
+                if value_map.is_empty() {
+                    self.label_data.remove(key);
+                }
+            }
+        }
+    }
+
+    /// 按选择器中的 `key=value`、`key` 条件从索引中取候选任务;
+    /// 没有可用索引的条件时返回None,需要全量过滤
+    pub fn select_label_job_ids(&self, selector: &LabelSelector) -> Option<BTreeSet<u64>> {
+        let mut result: Option<BTreeSet<u64>> = None;
+        for requirement in &selector.requirements {
+            let ids: BTreeSet<u64> = match requirement {
+                LabelRequirement::Equal(key, value) => self
+                    .label_data
+                    .get(key)
+                    .and_then(|v| v.get(value))
+                    .cloned()
+                    .unwrap_or_default(),
+                LabelRequirement::Exists(key) => self
+                    .label_data
+                    .get(key)
+                    .map(|v| v.values().flatten().copied().collect())
+                    .unwrap_or_default(),
+                _ => continue,
+            };
+            result = Some(match result {
+                Some(v) => v.intersection(&ids).copied().collect(),
+                None => ids,
+            });
+        }
+        result
+    }
+
     pub fn query(&self, param: &JobQueryParam) -> (usize, Vec<JobIndexInfo>) {
         let mut rlist = Vec::new();
         let end_index = param.offset + param.limit;
@@ -1,4 +1,7 @@
+pub mod batch;
 pub mod core;
 pub mod job_index;
 pub mod metrics;
 pub mod model;
+pub mod sync;
+pub mod xxl_import;
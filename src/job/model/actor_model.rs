@@ -1,8 +1,12 @@
+use crate::app::model::AppKey;
 use crate::job::job_index::JobQueryParam;
-use crate::job::model::job::{JobInfo, JobInfoDto, JobKey, JobParam, JobTaskLogQueryParam};
+use crate::job::model::job::{
+    JobGlueHistoryInfo, JobHistoryInfo, JobInfo, JobInfoDto, JobKey, JobParam, JobTaskLogQueryParam,
+};
 use crate::task::model::task::JobTaskInfo;
 use actix::Message;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 #[derive(Debug, Message)]
@@ -12,8 +16,14 @@ pub enum JobManagerReq {
     GetJob(u64),
     GetJobIdByKey(JobKey),
     QueryJob(JobQueryParam),
+    /// 按查询条件取全部匹配的任务,不分页
+    QueryJobInfoList(JobQueryParam),
     QueryJobTaskLog(JobTaskLogQueryParam),
     CountJobsByNamespace(String),
+    QueryJobVersions(u64),
+    QueryJobGlueVersions(u64),
+    QueryJobLastResults(Vec<u64>),
+    QueryAppJobs(Vec<AppKey>),
 }
 
 #[derive(Debug, Clone)]
@@ -23,6 +33,10 @@ pub enum JobManagerResult {
     JobPageInfo(usize, Vec<JobInfoDto>),
     JobTaskLogPageInfo(usize, Vec<Arc<JobTaskInfo>>),
     Count(usize),
+    JobVersionList(Vec<Arc<JobHistoryInfo>>),
+    JobGlueVersionList(Vec<Arc<JobGlueHistoryInfo>>),
+    JobLastResults(HashMap<u64, Arc<String>>),
+    JobInfoList(Vec<Arc<JobInfo>>),
     None,
 }
 
@@ -31,6 +45,11 @@ pub enum JobManagerResult {
 pub enum JobManagerRaftReq {
     AddJob(JobParam),
     UpdateJob(JobParam),
+    /// 整体替换任务配置(回滚历史版本),为空的可选字段也会覆盖
+    ReplaceJob {
+        job: Arc<JobInfo>,
+        update_user: Option<Arc<String>>,
+    },
     UpdateTask(Arc<JobTaskInfo>),
     UpdateTaskList(Vec<Arc<JobTaskInfo>>),
     Remove(u64),
@@ -45,6 +45,8 @@ pub enum JobRunMode {
     GluePhp,
     GlueNodejs,
     GluePowerShell,
+    /// 由调度中心直接发起HTTP请求,不需要执行器
+    Http,
 }
 
 impl Default for JobRunMode {
@@ -63,10 +65,15 @@ impl JobRunMode {
             "GLUE_PHP" => Some(JobRunMode::GluePhp),
             "GLUE_NODEJS" => Some(JobRunMode::GlueNodejs),
             "GLUE_POWERSHELL" => Some(JobRunMode::GluePowerShell),
+            "HTTP" => Some(JobRunMode::Http),
             _ => None,
         }
     }
 
+    pub fn is_glue(&self) -> bool {
+        !matches!(self, JobRunMode::Bean | JobRunMode::Http)
+    }
+
     pub fn to_str(&self) -> &str {
         match self {
             JobRunMode::Bean => "BEAN",
@@ -76,6 +83,7 @@ impl JobRunMode {
             JobRunMode::GluePhp => "GLUE_PHP",
             JobRunMode::GlueNodejs => "GLUE_NODEJS",
             JobRunMode::GluePowerShell => "GLUE_POWERSHELL",
+            JobRunMode::Http => "HTTP",
         }
     }
 }
@@ -0,0 +1,136 @@
+use crate::common::pb::data_object::{JobHttpConfigDo, JobHttpHeaderDo};
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+
+const HTTP_METHODS: [&str; 6] = ["GET", "POST", "PUT", "DELETE", "PATCH", "HEAD"];
+
+/// HTTP任务配置,url、header值、body支持 `${name}` 变量
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobHttpConfig {
+    #[serde(default)]
+    pub method: String,
+    pub url: String,
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default)]
+    pub body: String,
+    /// 期望的响应状态码,如 `200,204` 或 `200-299`;为空时2xx为成功
+    #[serde(default)]
+    pub expected_status: String,
+    /// 请求超时,为0时使用任务的超时时间
+    #[serde(default)]
+    pub timeout_second: u32,
+}
+
+impl JobHttpConfig {
+    pub fn get_method(&self) -> String {
+        if self.method.is_empty() {
+            "POST".to_owned()
+        } else {
+            self.method.to_uppercase()
+        }
+    }
+
+    pub fn check_valid(&self) -> anyhow::Result<()> {
+        if !self.url.starts_with("http://") && !self.url.starts_with("https://") {
+            return Err(anyhow::anyhow!("http_config.url is invalid!"));
+        }
+        let method = self.get_method();
+        if !HTTP_METHODS.contains(&method.as_str()) {
+            return Err(anyhow::anyhow!("http_config.method {} is invalid!", method));
+        }
+        Self::parse_expected_status(&self.expected_status)?;
+        Ok(())
+    }
+
+    pub fn is_success_status(&self, status: u16) -> bool {
+        match Self::parse_expected_status(&self.expected_status) {
+            Ok(ranges) if !ranges.is_empty() => ranges
+                .iter()
+                .any(|(start, end)| *start <= status && status <= *end),
+            _ => (200..300).contains(&status),
+        }
+    }
+
+    fn parse_expected_status(value: &str) -> anyhow::Result<Vec<(u16, u16)>> {
+        let mut ranges = vec![];
+        for item in value.split(',') {
+            let item = item.trim();
+            if item.is_empty() {
+                continue;
+            }
+            let range = if let Some((start, end)) = item.split_once('-') {
+                (start.trim().parse()?, end.trim().parse()?)
+            } else {
+                let v = item.parse()?;
+                (v, v)
+            };
+            if range.0 > range.1 {
+                return Err(anyhow::anyhow!("expected_status {} is invalid!", item));
+            }
+            ranges.push(range);
+        }
+        Ok(ranges)
+    }
+
+    pub fn to_do(&self) -> JobHttpConfigDo<'_> {
+        JobHttpConfigDo {
+            method: Cow::Borrowed(&self.method),
+            url: Cow::Borrowed(&self.url),
+            headers: self
+                .headers
+                .iter()
+                .map(|(name, value)| JobHttpHeaderDo {
+                    name: Cow::Borrowed(name),
+                    value: Cow::Borrowed(value),
+                })
+                .collect(),
+            body: Cow::Borrowed(&self.body),
+            expected_status: Cow::Borrowed(&self.expected_status),
+            timeout_second: self.timeout_second,
+        }
+    }
+}
+
+impl<'a> From<JobHttpConfigDo<'a>> for JobHttpConfig {
+    fn from(value: JobHttpConfigDo<'a>) -> Self {
+        JobHttpConfig {
+            method: value.method.to_string(),
+            url: value.url.to_string(),
+            headers: value
+                .headers
+                .into_iter()
+                .map(|h| (h.name.to_string(), h.value.to_string()))
+                .collect(),
+            body: value.body.to_string(),
+            expected_status: value.expected_status.to_string(),
+            timeout_second: value.timeout_second,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn http_config_expected_status() {
+        let mut config = JobHttpConfig {
+            url: "http://127.0.0.1/api".to_owned(),
+            ..Default::default()
+        };
+        assert!(config.check_valid().is_ok());
+        assert!(config.is_success_status(204));
+        assert!(!config.is_success_status(302));
+        config.expected_status = "200, 300-399".to_owned();
+        assert!(config.is_success_status(302));
+        assert!(!config.is_success_status(201));
+        config.expected_status = "299-200".to_owned();
+        assert!(config.check_valid().is_err());
+        config.expected_status = String::new();
+        config.method = "CONNECT".to_owned();
+        assert!(config.check_valid().is_err());
+    }
+}
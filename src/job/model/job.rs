@@ -1,11 +1,15 @@
 use crate::app::model::AppKey;
 use crate::common::constant::EMPTY_ARC_STR;
 use crate::common::cron_utils::CronUtil;
-use crate::common::pb::data_object::JobDo;
+use crate::common::pb::data_object::{JobDo, JobGlueDo, JobVersionDo};
 use crate::common::string_utils::StringUtils;
 use crate::job::model::enum_type::{
     ExecutorBlockStrategy, JobRunMode, PastDueStrategy, RouterStrategy, ScheduleType,
 };
+use crate::job::model::http_config::JobHttpConfig;
+use crate::job::model::label::{check_labels_valid, JobLabels};
+use crate::job::model::retry_policy::JobRetryPolicy;
+use crate::job::model::time_window::JobTimeWindow;
 use crate::task::model::enum_type::TaskStatusType;
 use crate::task::model::task::JobTaskInfo;
 use serde::{Deserialize, Serialize};
@@ -13,6 +17,13 @@ use std::borrow::Cow;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 
+/// 校验cron表达式,错误信息包含出错字段与位置
+fn check_cron_value(cron_value: &str) -> anyhow::Result<()> {
+    CronUtil::check_cron_value(cron_value)
+        .map(|_| ())
+        .map_err(|e| anyhow::anyhow!("cron_value '{}' is invalid,{}", cron_value, e))
+}
+
 #[derive(Clone, Default, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct JobInfo {
@@ -38,6 +49,22 @@ pub struct JobInfo {
     pub last_modified_millis: u64,
     pub create_time: u64,
     pub retry_interval: u32,
+    /// GLUE模式的脚本源码,与trigger_param分开保存
+    pub glue_source: Arc<String>,
+    pub glue_update_time: u64,
+    /// HTTP模式的请求配置
+    pub http_config: Option<Arc<JobHttpConfig>>,
+    /// 重试策略,为空时按固定间隔重试
+    pub retry_policy: Option<Arc<JobRetryPolicy>>,
+    /// 自定义标签,如 team=payments
+    #[serde(default)]
+    pub labels: JobLabels,
+    /// 允许触发的时间窗口,为空时不限制
+    #[serde(default)]
+    pub allow_time_window: Arc<String>,
+    /// 禁止触发的时间窗口,优先于允许窗口
+    #[serde(default)]
+    pub forbid_time_window: Arc<String>,
 }
 
 impl JobInfo {
@@ -72,6 +99,28 @@ impl JobInfo {
         if let Some(trigger_param) = job_param.trigger_param {
             self.trigger_param = trigger_param;
         }
+        if let Some(http_config) = job_param.http_config {
+            self.http_config = Some(http_config);
+        }
+        if let Some(retry_policy) = job_param.retry_policy {
+            self.retry_policy = Some(retry_policy);
+        }
+        if let Some(labels) = job_param.labels {
+            self.labels = labels;
+        }
+        if let Some(allow_time_window) = job_param.allow_time_window {
+            self.allow_time_window = allow_time_window;
+        }
+        if let Some(forbid_time_window) = job_param.forbid_time_window {
+            self.forbid_time_window = forbid_time_window;
+        }
+        let mut glue_changed = false;
+        if let Some(glue_source) = job_param.glue_source {
+            if glue_source != self.glue_source {
+                self.glue_source = glue_source;
+                glue_changed = true;
+            }
+        }
         if let Some(router_strategy) = job_param.router_strategy {
             self.router_strategy = router_strategy;
         }
@@ -96,9 +145,45 @@ impl JobInfo {
                 self.create_time = update_time;
             }
         }
+        if glue_changed {
+            self.glue_update_time = self.last_modified_millis;
+        }
         self.version_id += 1;
     }
 
+    /// 转换为完整的更新参数,未设置的可选字段(如http_config)在更新时会保留原值
+    pub fn to_param(&self) -> JobParam {
+        JobParam {
+            id: Some(self.id),
+            enable: Some(self.enable),
+            app_name: Some(self.app_name.clone()),
+            namespace: Some(self.namespace.clone()),
+            key: Some(self.key.clone()),
+            description: Some(self.description.clone()),
+            schedule_type: Some(self.schedule_type.clone()),
+            cron_value: Some(self.cron_value.clone()),
+            delay_second: Some(self.delay_second),
+            interval_second: Some(self.interval_second),
+            run_mode: Some(self.run_mode.clone()),
+            handle_name: Some(self.handle_name.clone()),
+            trigger_param: Some(self.trigger_param.clone()),
+            router_strategy: Some(self.router_strategy.clone()),
+            past_due_strategy: Some(self.past_due_strategy.clone()),
+            blocking_strategy: Some(self.blocking_strategy.clone()),
+            timeout_second: Some(self.timeout_second),
+            try_times: Some(self.try_times),
+            update_time: None,
+            retry_interval: Some(self.retry_interval),
+            update_user: None,
+            glue_source: Some(self.glue_source.clone()),
+            http_config: self.http_config.clone(),
+            retry_policy: self.retry_policy.clone(),
+            labels: Some(self.labels.clone()),
+            allow_time_window: Some(self.allow_time_window.clone()),
+            forbid_time_window: Some(self.forbid_time_window.clone()),
+        }
+    }
+
     pub fn check_valid(&self) -> anyhow::Result<()> {
         if self.id == 0 {
             Err(anyhow::anyhow!("id is empty!"))
@@ -106,13 +191,22 @@ impl JobInfo {
             Err(anyhow::anyhow!("namespace or app_name is empty!"))
         } else if self.run_mode == JobRunMode::Bean && self.handle_name.is_empty() {
             Err(anyhow::anyhow!("bean handle_name is invalid!"))
-        } else if self.schedule_type == ScheduleType::Cron
-            && !CronUtil::check_cron_valid(&self.cron_value)
-        {
-            Err(anyhow::anyhow!("cron_value is invalid!"))
+        } else if self.run_mode == JobRunMode::Http && self.http_config.is_none() {
+            Err(anyhow::anyhow!("http_config is empty!"))
         } else if self.schedule_type == ScheduleType::Interval && self.interval_second == 0 {
             Err(anyhow::anyhow!("interval_second eq 0,it is invalid!"))
         } else {
+            if self.schedule_type == ScheduleType::Cron {
+                check_cron_value(&self.cron_value)?;
+            }
+            check_labels_valid(&self.labels)?;
+            let time_window =
+                JobTimeWindow::new(&self.allow_time_window, &self.forbid_time_window)?;
+            if self.schedule_type == ScheduleType::Cron {
+                if let Ok(cron_schedule) = CronUtil::check_cron_value(&self.cron_value) {
+                    time_window.check_cron(&self.cron_value, &cron_schedule)?;
+                }
+            }
             Ok(())
         }
     }
@@ -135,7 +229,9 @@ impl JobInfo {
     pub fn is_valid(&self) -> bool {
         if self.id == 0 || self.namespace.is_empty() || self.app_name.is_empty() {
             false
-        } else if self.run_mode == JobRunMode::Bean && self.handle_name.is_empty() {
+        } else if (self.run_mode == JobRunMode::Bean && self.handle_name.is_empty())
+            || (self.run_mode == JobRunMode::Http && self.http_config.is_none())
+        {
             false
         } else if self.schedule_type == ScheduleType::Cron
             && !CronUtil::check_cron_valid(&self.cron_value)
@@ -170,6 +266,17 @@ impl JobInfo {
             last_modified_millis: self.last_modified_millis,
             create_time: self.create_time,
             retry_interval: self.retry_interval,
+            glue_source: Cow::Borrowed(&self.glue_source),
+            glue_update_time: self.glue_update_time,
+            http_config: self.http_config.as_ref().map(|v| v.to_do()),
+            retry_policy: self.retry_policy.as_ref().map(|v| v.to_do()),
+            labels: self
+                .labels
+                .iter()
+                .map(|(k, v)| (Cow::Borrowed(k.as_str()), Cow::Borrowed(v.as_str())))
+                .collect(),
+            allow_time_window: Cow::Borrowed(&self.allow_time_window),
+            forbid_time_window: Cow::Borrowed(&self.forbid_time_window),
         }
     }
 }
@@ -200,26 +307,121 @@ impl<'a> From<JobDo<'a>> for JobInfo {
             last_modified_millis: job_do.last_modified_millis,
             create_time: job_do.create_time,
             retry_interval: job_do.retry_interval,
+            glue_source: Arc::new(job_do.glue_source.to_string()),
+            glue_update_time: job_do.glue_update_time,
+            http_config: job_do.http_config.map(|v| Arc::new(v.into())),
+            retry_policy: job_do.retry_policy.map(|v| Arc::new(v.into())),
+            labels: job_do
+                .labels
+                .into_iter()
+                .map(|(k, v)| (k.into_owned(), v.into_owned()))
+                .collect(),
+            allow_time_window: Arc::new(job_do.allow_time_window.to_string()),
+            forbid_time_window: Arc::new(job_do.forbid_time_window.to_string()),
         }
     }
 }
 
+/// 任务配置的历史版本
 #[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 pub struct JobHistoryInfo {
     pub version_id: u64,
-    pub schedule_type: ScheduleType,
-    pub cron_value: Arc<String>,
-    pub delay_second: u32,
-    pub interval_second: u32,
-    pub trigger_mode: Arc<String>,
-    pub handle_name: Arc<String>,
-    pub trigger_param: Arc<String>,
+    pub operator: Arc<String>,
+    pub update_time: u64,
+    pub job: Arc<JobInfo>,
+}
+
+impl JobHistoryInfo {
+    pub fn new(job: Arc<JobInfo>, operator: Option<Arc<String>>) -> Self {
+        JobHistoryInfo {
+            version_id: job.version_id,
+            operator: operator.unwrap_or(EMPTY_ARC_STR.clone()),
+            update_time: job.last_modified_millis,
+            job,
+        }
+    }
+
+    pub fn to_do(&self) -> JobVersionDo<'_> {
+        JobVersionDo {
+            job_id: self.job.id,
+            version_id: self.version_id,
+            operator: Cow::Borrowed(&self.operator),
+            update_time: self.update_time,
+            job: Some(self.job.to_do()),
+        }
+    }
+}
+
+impl<'a> From<JobVersionDo<'a>> for JobHistoryInfo {
+    fn from(value: JobVersionDo<'a>) -> Self {
+        let job: JobInfo = value.job.map(|v| v.into()).unwrap_or_default();
+        JobHistoryInfo {
+            version_id: value.version_id,
+            operator: Arc::new(value.operator.to_string()),
+            update_time: value.update_time,
+            job: Arc::new(job),
+        }
+    }
+}
+
+/// GLUE脚本的历史版本,只在脚本内容变更时记录
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobGlueHistoryInfo {
+    pub job_id: u64,
+    /// 脚本变更时对应的任务版本
+    pub version_id: u64,
+    pub run_mode: JobRunMode,
+    pub glue_source: Arc<String>,
+    pub glue_update_time: u64,
+    pub operator: Arc<String>,
+}
+
+impl JobGlueHistoryInfo {
+    pub fn new(job: &JobInfo, operator: Option<Arc<String>>) -> Self {
+        JobGlueHistoryInfo {
+            job_id: job.id,
+            version_id: job.version_id,
+            run_mode: job.run_mode.clone(),
+            glue_source: job.glue_source.clone(),
+            glue_update_time: job.glue_update_time,
+            operator: operator.unwrap_or(EMPTY_ARC_STR.clone()),
+        }
+    }
+
+    pub fn to_do(&self) -> JobGlueDo<'_> {
+        JobGlueDo {
+            job_id: self.job_id,
+            version_id: self.version_id,
+            run_mode: Cow::Borrowed(self.run_mode.to_str()),
+            glue_source: Cow::Borrowed(&self.glue_source),
+            glue_update_time: self.glue_update_time,
+            operator: Cow::Borrowed(&self.operator),
+        }
+    }
+}
+
+impl<'a> From<JobGlueDo<'a>> for JobGlueHistoryInfo {
+    fn from(value: JobGlueDo<'a>) -> Self {
+        JobGlueHistoryInfo {
+            job_id: value.job_id,
+            version_id: value.version_id,
+            run_mode: JobRunMode::from_str(&value.run_mode).unwrap_or_default(),
+            glue_source: Arc::new(value.glue_source.to_string()),
+            glue_update_time: value.glue_update_time,
+            operator: Arc::new(value.operator.to_string()),
+        }
+    }
 }
 
 pub struct JobWrap {
     pub job: Arc<JobInfo>,
     pub histories: Vec<Arc<JobHistoryInfo>>,
+    pub glue_histories: Vec<Arc<JobGlueHistoryInfo>>,
     pub task_log_map: BTreeMap<u64, Arc<JobTaskInfo>>,
+    /// 最近一次带结果的成功任务,不受任务日志数量限制
+    pub last_result_task: Option<Arc<JobTaskInfo>>,
 }
 
 impl JobWrap {
@@ -227,20 +429,70 @@ impl JobWrap {
         Self {
             job,
             histories: vec![],
+            glue_histories: vec![],
             task_log_map: BTreeMap::new(),
+            last_result_task: None,
         }
     }
 
+    pub fn get_last_result(&self) -> Option<Arc<String>> {
+        self.last_result_task.as_ref().map(|v| v.result.clone())
+    }
+
+    fn update_last_result(&mut self, task_log: &Arc<JobTaskInfo>) {
+        if task_log.status != TaskStatusType::Success || task_log.result.is_empty() {
+            return;
+        }
+        if let Some(last) = self.last_result_task.as_ref() {
+            if last.task_id > task_log.task_id {
+                return;
+            }
+        }
+        self.last_result_task = Some(task_log.clone());
+    }
+
+    pub fn add_glue_history(&mut self, history: Arc<JobGlueHistoryInfo>, limit_count: usize) {
+        self.glue_histories
+            .retain(|v| v.version_id != history.version_id);
+        self.glue_histories.push(history);
+        self.glue_histories.sort_by_key(|v| v.version_id);
+        while self.glue_histories.len() > limit_count {
+            self.glue_histories.remove(0);
+        }
+    }
+
+    pub fn add_history(&mut self, history: Arc<JobHistoryInfo>, limit_count: usize) {
+        self.histories
+            .retain(|v| v.version_id != history.version_id);
+        self.histories.push(history);
+        self.histories.sort_by_key(|v| v.version_id);
+        while self.histories.len() > limit_count {
+            self.histories.remove(0);
+        }
+    }
+
+    pub fn get_history(&self, version_id: u64) -> Option<Arc<JobHistoryInfo>> {
+        self.histories
+            .iter()
+            .find(|v| v.version_id == version_id)
+            .cloned()
+    }
+
     pub fn update_task_log(
         &mut self,
         new_task_log: Arc<JobTaskInfo>,
         limit_count: usize,
     ) -> Option<bool> {
+        self.update_last_result(&new_task_log);
         if let Some(task_log) = self.task_log_map.get_mut(&new_task_log.task_id) {
             if task_log.status.is_finish() && new_task_log.status.is_running() {
                 //先收到sdk的响应再收到内部运行中状态
                 return Some(task_log.status == TaskStatusType::Success);
             }
+            if task_log.status.is_closed() && task_log.status != new_task_log.status {
+                //超时、取消、终止后执行器的迟到回调不再修改任务状态
+                return Some(false);
+            }
             *task_log = new_task_log;
         } else {
             self.task_log_map.insert(new_task_log.task_id, new_task_log);
@@ -318,6 +570,16 @@ pub struct JobParam {
     pub try_times: Option<u32>,
     pub update_time: Option<u64>,
     pub retry_interval: Option<u32>,
+    /// 修改人,用于记录任务的历史版本
+    pub update_user: Option<Arc<String>>,
+    pub glue_source: Option<Arc<String>>,
+    pub http_config: Option<Arc<JobHttpConfig>>,
+    pub retry_policy: Option<Arc<JobRetryPolicy>>,
+    /// 为空时不修改,设置时整体替换
+    pub labels: Option<JobLabels>,
+    /// 为空时不修改,设置为空字符串时清除
+    pub allow_time_window: Option<Arc<String>>,
+    pub forbid_time_window: Option<Arc<String>>,
 }
 
 impl JobParam {
@@ -332,6 +594,26 @@ impl JobParam {
             {
                 return Err(anyhow::anyhow!("bean handle_name is invalid!"));
             }
+            if run_mode == &JobRunMode::Http && self.http_config.is_none() {
+                return Err(anyhow::anyhow!("http_config is empty!"));
+            }
+        }
+        if let Some(http_config) = self.http_config.as_ref() {
+            http_config.check_valid()?;
+        }
+        if let Some(retry_policy) = self.retry_policy.as_ref() {
+            retry_policy.check_valid()?;
+        }
+        if let Some(labels) = self.labels.as_ref() {
+            check_labels_valid(labels)?;
+        }
+        if self.allow_time_window.is_some() || self.forbid_time_window.is_some() {
+            JobTimeWindow::new(
+                self.allow_time_window.as_deref().map_or("", |v| v.as_str()),
+                self.forbid_time_window
+                    .as_deref()
+                    .map_or("", |v| v.as_str()),
+            )?;
         }
         if let Some(schedule_type) = self.schedule_type.as_ref() {
             if schedule_type == &ScheduleType::Interval
@@ -341,9 +623,7 @@ impl JobParam {
             }
             if schedule_type == &ScheduleType::Cron {
                 if let Some(v) = self.cron_value.as_ref() {
-                    if !CronUtil::check_cron_valid(v) {
-                        return Err(anyhow::anyhow!("cron_value is invalid!"));
-                    }
+                    check_cron_value(v)?;
                 } else {
                     return Err(anyhow::anyhow!("cron_value is invalid!"));
                 }
@@ -383,7 +663,16 @@ impl From<JobParam> for JobInfo {
             version_id: 0,
             last_modified_millis: job_param.update_time.unwrap_or(0),
             create_time: 0,
-            retry_interval: job_param.interval_second.unwrap_or(0),
+            retry_interval: job_param.retry_interval.unwrap_or(0),
+            glue_source: job_param.glue_source.unwrap_or(EMPTY_ARC_STR.clone()),
+            glue_update_time: 0,
+            http_config: job_param.http_config,
+            retry_policy: job_param.retry_policy,
+            labels: job_param.labels.unwrap_or_default(),
+            allow_time_window: job_param.allow_time_window.unwrap_or(EMPTY_ARC_STR.clone()),
+            forbid_time_window: job_param
+                .forbid_time_window
+                .unwrap_or(EMPTY_ARC_STR.clone()),
         }
     }
 }
@@ -396,6 +685,13 @@ pub struct JobTaskLogQueryParam {
     pub limit: usize,
     pub namespace: Option<String>,
     pub app_name: Option<String>,
+    pub status: Option<TaskStatusType>,
+}
+
+impl JobTaskLogQueryParam {
+    pub fn match_status(&self, status: &TaskStatusType) -> bool {
+        self.status.as_ref().is_none_or(|v| v == status)
+    }
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
@@ -423,6 +719,13 @@ pub struct JobInfoDto {
     pub last_modified_millis: u64,
     pub register_time: u64,
     pub retry_interval: u32,
+    pub glue_source: Arc<String>,
+    pub glue_update_time: u64,
+    pub http_config: Option<Arc<JobHttpConfig>>,
+    pub retry_policy: Option<Arc<JobRetryPolicy>>,
+    pub labels: JobLabels,
+    pub allow_time_window: Arc<String>,
+    pub forbid_time_window: Arc<String>,
 }
 
 impl JobInfoDto {
@@ -450,6 +753,121 @@ impl JobInfoDto {
             last_modified_millis: job_info.last_modified_millis,
             register_time: job_info.create_time,
             retry_interval: job_info.retry_interval,
+            glue_source: job_info.glue_source.clone(),
+            glue_update_time: job_info.glue_update_time,
+            http_config: job_info.http_config.clone(),
+            retry_policy: job_info.retry_policy.clone(),
+            labels: job_info.labels.clone(),
+            allow_time_window: job_info.allow_time_window.clone(),
+            forbid_time_window: job_info.forbid_time_window.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_history(version_id: u64) -> Arc<JobHistoryInfo> {
+        let job = JobInfo {
+            id: 1,
+            version_id,
+            ..Default::default()
+        };
+        Arc::new(JobHistoryInfo::new(Arc::new(job), None))
+    }
+
+    #[test]
+    fn job_history_limit() {
+        let mut wrap = JobWrap::new(Arc::new(JobInfo::default()));
+        for version_id in [2, 1, 3, 4] {
+            wrap.add_history(build_history(version_id), 3);
         }
+        wrap.add_history(build_history(4), 3);
+        let versions: Vec<u64> = wrap.histories.iter().map(|v| v.version_id).collect();
+        assert_eq!(versions, vec![2, 3, 4]);
+        assert!(wrap.get_history(1).is_none());
+        assert!(wrap.get_history(3).is_some());
+    }
+
+    #[test]
+    fn glue_update_time_only_changed_by_source() {
+        let mut job = JobInfo {
+            id: 1,
+            run_mode: JobRunMode::GlueShell,
+            ..Default::default()
+        };
+        job.update_param(JobParam {
+            glue_source: Some(Arc::new("echo 1".to_owned())),
+            update_time: Some(100),
+            ..Default::default()
+        });
+        assert_eq!(job.glue_update_time, 100);
+        job.update_param(JobParam {
+            glue_source: Some(Arc::new("echo 1".to_owned())),
+            trigger_param: Some(Arc::new("a=1".to_owned())),
+            update_time: Some(200),
+            ..Default::default()
+        });
+        assert_eq!(job.glue_update_time, 100);
+        assert_eq!(job.trigger_param.as_str(), "a=1");
+        assert_eq!(job.glue_source.as_str(), "echo 1");
+    }
+
+    #[test]
+    fn timeout_task_ignore_late_update() {
+        let mut wrap = JobWrap::new(Arc::new(JobInfo::default()));
+        let mut task = JobTaskInfo {
+            task_id: 1,
+            status: TaskStatusType::Timeout,
+            ..Default::default()
+        };
+        assert!(wrap.update_task_log(Arc::new(task.clone()), 10).is_none());
+        task.status = TaskStatusType::Success;
+        assert_eq!(wrap.update_task_log(Arc::new(task), 10), Some(false));
+        assert_eq!(
+            wrap.task_log_map.get(&1).map(|v| v.status.clone()),
+            Some(TaskStatusType::Timeout)
+        );
+    }
+
+    #[test]
+    fn closed_task_ignore_late_update() {
+        let mut wrap = JobWrap::new(Arc::new(JobInfo::default()));
+        for (task_id, status) in [(1, TaskStatusType::Cancelled), (2, TaskStatusType::Killed)] {
+            let mut task = JobTaskInfo {
+                task_id,
+                status: status.clone(),
+                ..Default::default()
+            };
+            assert!(wrap.update_task_log(Arc::new(task.clone()), 10).is_none());
+            task.status = TaskStatusType::Fail;
+            assert_eq!(wrap.update_task_log(Arc::new(task), 10), Some(false));
+            assert_eq!(
+                wrap.task_log_map.get(&task_id).map(|v| v.status.clone()),
+                Some(status)
+            );
+        }
+        assert!(TaskStatusType::Killed.is_fail());
+        assert!(!TaskStatusType::Skipped.is_fail() && TaskStatusType::Skipped.can_retry());
+        assert_eq!(
+            TaskStatusType::from_str(TaskStatusType::Cancelled.to_str()),
+            TaskStatusType::Cancelled
+        );
+    }
+
+    #[test]
+    fn check_valid_cron_error_position() {
+        let job = JobInfo {
+            id: 1,
+            namespace: Arc::new("dev".to_owned()),
+            app_name: Arc::new("app1".to_owned()),
+            run_mode: JobRunMode::GlueShell,
+            schedule_type: ScheduleType::Cron,
+            cron_value: Arc::new("0 0 25 * * *".to_owned()),
+            ..Default::default()
+        };
+        let message = job.check_valid().unwrap_err().to_string();
+        assert!(message.contains("hour field '25' at column 5"));
     }
 }
@@ -0,0 +1,135 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+pub type JobLabels = BTreeMap<String, String>;
+
+const LABEL_KEY_MAX_LEN: usize = 64;
+const LABEL_VALUE_MAX_LEN: usize = 128;
+const LABEL_MAX_COUNT: usize = 32;
+
+fn is_valid_label_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.' || c == '/'
+}
+
+/// 标签key只支持字母、数字与 `_-./`;value可为空,字符规则相同
+pub fn check_labels_valid(labels: &JobLabels) -> anyhow::Result<()> {
+    if labels.len() > LABEL_MAX_COUNT {
+        return Err(anyhow::anyhow!(
+            "labels count {} is more than {}!",
+            labels.len(),
+            LABEL_MAX_COUNT
+        ));
+    }
+    for (key, value) in labels {
+        if key.is_empty() || key.len() > LABEL_KEY_MAX_LEN || !key.chars().all(is_valid_label_char)
+        {
+            return Err(anyhow::anyhow!("label key '{}' is invalid!", key));
+        }
+        if value.len() > LABEL_VALUE_MAX_LEN || !value.chars().all(is_valid_label_char) {
+            return Err(anyhow::anyhow!(
+                "label value '{}={}' is invalid!",
+                key,
+                value
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LabelRequirement {
+    /// `key=value`
+    Equal(String, String),
+    /// `key!=value`,不存在该标签也满足
+    NotEqual(String, String),
+    /// `key`
+    Exists(String),
+    /// `!key`
+    NotExists(String),
+}
+
+impl LabelRequirement {
+    pub fn matches(&self, labels: &JobLabels) -> bool {
+        match self {
+            LabelRequirement::Equal(key, value) => labels.get(key) == Some(value),
+            LabelRequirement::NotEqual(key, value) => labels.get(key) != Some(value),
+            LabelRequirement::Exists(key) => labels.contains_key(key),
+            LabelRequirement::NotExists(key) => !labels.contains_key(key),
+        }
+    }
+}
+
+///
+/// 标签选择器,多个条件用逗号分隔,需同时满足
+/// 如: `team=payments,tier!=low,critical,!deprecated`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LabelSelector {
+    pub requirements: Vec<LabelRequirement>,
+}
+
+impl LabelSelector {
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        let mut requirements = Vec::new();
+        for item in value.split(',') {
+            let item = item.trim();
+            if item.is_empty() {
+                continue;
+            }
+            let requirement = if let Some((key, value)) = item.split_once("!=") {
+                LabelRequirement::NotEqual(key.trim().to_owned(), value.trim().to_owned())
+            } else if let Some((key, value)) = item.split_once('=') {
+                LabelRequirement::Equal(key.trim().to_owned(), value.trim().to_owned())
+            } else if let Some(key) = item.strip_prefix('!') {
+                LabelRequirement::NotExists(key.trim().to_owned())
+            } else {
+                LabelRequirement::Exists(item.to_owned())
+            };
+            let key = match &requirement {
+                LabelRequirement::Equal(key, _)
+                | LabelRequirement::NotEqual(key, _)
+                | LabelRequirement::Exists(key)
+                | LabelRequirement::NotExists(key) => key,
+            };
+            if key.is_empty() || !key.chars().all(is_valid_label_char) {
+                return Err(anyhow::anyhow!("label selector '{}' is invalid!", item));
+            }
+            requirements.push(requirement);
+        }
+        Ok(LabelSelector { requirements })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.requirements.is_empty()
+    }
+
+    pub fn matches(&self, labels: &JobLabels) -> bool {
+        self.requirements.iter().all(|v| v.matches(labels))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn label_selector_match() {
+        let labels: JobLabels = [("team", "payments"), ("tier", "critical")]
+            .into_iter()
+            .map(|(k, v)| (k.to_owned(), v.to_owned()))
+            .collect();
+        assert!(check_labels_valid(&labels).is_ok());
+        let selector = LabelSelector::parse("team=payments, tier!=low,!deprecated").unwrap();
+        assert_eq!(selector.requirements.len(), 3);
+        assert!(selector.matches(&labels));
+        assert!(LabelSelector::parse("tier").unwrap().matches(&labels));
+        assert!(!LabelSelector::parse("team=orders")
+            .unwrap()
+            .matches(&labels));
+        assert!(!LabelSelector::parse("team,owner").unwrap().matches(&labels));
+        assert!(LabelSelector::parse("").unwrap().is_empty());
+        assert!(LabelSelector::parse("=a").is_err());
+        let mut invalid = labels.clone();
+        invalid.insert("bad key".to_owned(), "v".to_owned());
+        assert!(check_labels_valid(&invalid).is_err());
+    }
+}
@@ -1,3 +1,8 @@
 pub mod actor_model;
 pub mod enum_type;
+pub mod http_config;
 pub mod job;
+pub mod label;
+pub mod retry_policy;
+pub mod schedule_describe;
+pub mod time_window;
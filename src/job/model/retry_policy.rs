@@ -0,0 +1,192 @@
+use crate::common::pb::data_object::JobRetryPolicyDo;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// 指数退避最多翻倍的次数,避免溢出
+const MAX_EXPONENTIAL_SHIFT: u32 = 20;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RetryBackoffType {
+    #[default]
+    Fixed,
+    Linear,
+    Exponential,
+}
+
+impl RetryBackoffType {
+    pub fn from_name(value: &str) -> RetryBackoffType {
+        match value {
+            "LINEAR" => RetryBackoffType::Linear,
+            "EXPONENTIAL" => RetryBackoffType::Exponential,
+            _ => RetryBackoffType::Fixed,
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            RetryBackoffType::Fixed => "FIXED",
+            RetryBackoffType::Linear => "LINEAR",
+            RetryBackoffType::Exponential => "EXPONENTIAL",
+        }
+    }
+}
+
+/// 任务失败类型
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum RetryFailType {
+    /// 调度中心调用执行器失败,如没有可用实例、请求失败
+    CallError,
+    /// 执行器回调上报执行失败
+    ExecutorFail,
+    /// 执行超时
+    Timeout,
+}
+
+impl RetryFailType {
+    pub fn from_name(value: &str) -> Option<RetryFailType> {
+        match value {
+            "CALL_ERROR" => Some(RetryFailType::CallError),
+            "EXECUTOR_FAIL" => Some(RetryFailType::ExecutorFail),
+            "TIMEOUT" => Some(RetryFailType::Timeout),
+            _ => None,
+        }
+    }
+
+    pub fn to_str(&self) -> &str {
+        match self {
+            RetryFailType::CallError => "CALL_ERROR",
+            RetryFailType::ExecutorFail => "EXECUTOR_FAIL",
+            RetryFailType::Timeout => "TIMEOUT",
+        }
+    }
+}
+
+/// 任务重试策略,重试次数与基础间隔使用任务的 try_times、retry_interval
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobRetryPolicy {
+    #[serde(default)]
+    pub backoff: RetryBackoffType,
+    /// 最大重试间隔(秒),为0时不限制
+    #[serde(default)]
+    pub max_interval: u32,
+    /// 随机抖动比例(0-100),在计算出的间隔上增加不超过该比例的随机时间
+    #[serde(default)]
+    pub jitter_percent: u32,
+    /// 重试时排除已尝试过的实例
+    #[serde(default)]
+    pub exclude_tried_instance: bool,
+    /// 需要重试的失败类型,为空时所有失败都重试
+    #[serde(default)]
+    pub retry_on: Vec<RetryFailType>,
+}
+
+impl JobRetryPolicy {
+    pub fn check_valid(&self) -> anyhow::Result<()> {
+        if self.jitter_percent > 100 {
+            return Err(anyhow::anyhow!(
+                "retry_policy.jitter_percent {} is invalid!",
+                self.jitter_percent
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn can_retry_on(&self, fail_type: &RetryFailType) -> bool {
+        self.retry_on.is_empty() || self.retry_on.contains(fail_type)
+    }
+
+    /// 第 retry_times 次重试(从1开始)的间隔,不含抖动与最大间隔限制
+    pub fn get_base_interval(&self, interval: u32, retry_times: u32) -> u32 {
+        let retry_times = retry_times.max(1);
+        match self.backoff {
+            RetryBackoffType::Fixed => interval,
+            RetryBackoffType::Linear => interval.saturating_mul(retry_times),
+            RetryBackoffType::Exponential => {
+                let shift = (retry_times - 1).min(MAX_EXPONENTIAL_SHIFT);
+                interval.saturating_mul(1 << shift)
+            }
+        }
+    }
+
+    /// 重试间隔,增加抖动后再按最大间隔截断
+    pub fn get_retry_interval(&self, interval: u32, retry_times: u32) -> u32 {
+        let value = self.get_base_interval(interval, retry_times);
+        let jitter = value as u64 * self.jitter_percent.min(100) as u64 / 100;
+        let value = if jitter == 0 {
+            value
+        } else {
+            let jitter = rand::thread_rng().gen_range(0..=jitter) as u32;
+            value.saturating_add(jitter)
+        };
+        if self.max_interval > 0 {
+            value.min(self.max_interval)
+        } else {
+            value
+        }
+    }
+
+    pub fn to_do(&self) -> JobRetryPolicyDo<'_> {
+        JobRetryPolicyDo {
+            backoff: Cow::Borrowed(self.backoff.to_str()),
+            max_interval: self.max_interval,
+            jitter_percent: self.jitter_percent,
+            exclude_tried_instance: self.exclude_tried_instance,
+            retry_on: self
+                .retry_on
+                .iter()
+                .map(|v| Cow::Borrowed(v.to_str()))
+                .collect(),
+        }
+    }
+}
+
+impl<'a> From<JobRetryPolicyDo<'a>> for JobRetryPolicy {
+    fn from(value: JobRetryPolicyDo<'a>) -> Self {
+        JobRetryPolicy {
+            backoff: RetryBackoffType::from_name(&value.backoff),
+            max_interval: value.max_interval,
+            jitter_percent: value.jitter_percent,
+            exclude_tried_instance: value.exclude_tried_instance,
+            retry_on: value
+                .retry_on
+                .iter()
+                .filter_map(|v| RetryFailType::from_name(v))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retry_policy_backoff_interval() {
+        let mut policy = JobRetryPolicy::default();
+        assert_eq!(policy.get_retry_interval(10, 3), 10);
+        policy.backoff = RetryBackoffType::Linear;
+        assert_eq!(policy.get_retry_interval(10, 3), 30);
+        policy.backoff = RetryBackoffType::Exponential;
+        assert_eq!(policy.get_retry_interval(10, 1), 10);
+        assert_eq!(policy.get_retry_interval(10, 4), 80);
+        assert_eq!(
+            policy.get_retry_interval(10, 100),
+            10 << MAX_EXPONENTIAL_SHIFT
+        );
+        policy.max_interval = 60;
+        assert_eq!(policy.get_retry_interval(10, 4), 60);
+        policy.jitter_percent = 50;
+        assert_eq!(policy.get_retry_interval(10, 4), 60);
+        let v = policy.get_retry_interval(10, 2);
+        assert!((20..=30).contains(&v));
+        policy.jitter_percent = 101;
+        assert!(policy.check_valid().is_err());
+        assert!(policy.can_retry_on(&RetryFailType::Timeout));
+        policy.retry_on = vec![RetryFailType::CallError];
+        assert!(!policy.can_retry_on(&RetryFailType::Timeout));
+    }
+}
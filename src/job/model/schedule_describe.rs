@@ -0,0 +1,470 @@
+use crate::common::cron_utils::{CronUtil, CronValueError};
+use serde::{Deserialize, Serialize};
+
+const MONTH_NAMES_EN: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+/// cron中周的取值1为周日
+const WEEKDAY_NAMES_EN: [&str; 7] = [
+    "Sunday",
+    "Monday",
+    "Tuesday",
+    "Wednesday",
+    "Thursday",
+    "Friday",
+    "Saturday",
+];
+const WEEKDAY_NAMES_ZH: [&str; 7] = ["周日", "周一", "周二", "周三", "周四", "周五", "周六"];
+/// 各字段的英文单位(单数,复数)与中文单位,顺序同cron字段
+const UNITS_EN: [(&str, &str); 7] = [
+    ("second", "seconds"),
+    ("minute", "minutes"),
+    ("hour", "hours"),
+    ("day", "days"),
+    ("month", "months"),
+    ("day", "days"),
+    ("year", "years"),
+];
+const UNITS_ZH: [&str; 7] = ["秒", "分钟", "小时", "天", "个月", "天", "年"];
+const MONTH_INDEX: usize = 4;
+const WEEKDAY_INDEX: usize = 5;
+
+/// 调度配置的可读描述
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleDescription {
+    pub en: String,
+    pub zh: String,
+}
+
+impl ScheduleDescription {
+    pub fn new(en: String, zh: String) -> Self {
+        Self { en, zh }
+    }
+
+    pub fn cron(cron_value: &str) -> Result<Self, CronValueError> {
+        CronUtil::check_cron_value(cron_value)?;
+        let cron_value = match cron_value.trim() {
+            "@yearly" => "0 0 0 1 1 *",
+            "@monthly" => "0 0 0 1 * *",
+            "@weekly" => "0 0 0 * * 1",
+            "@daily" => "0 0 0 * * *",
+            "@hourly" => "0 0 * * * *",
+            v => v,
+        };
+        let fields: Vec<Vec<CronItem>> = cron_value
+            .split_whitespace()
+            .map(|v| v.split(',').map(CronItem::parse).collect())
+            .collect();
+        Ok(CronDescriber { fields }.describe())
+    }
+
+    pub fn interval(interval_second: u32) -> Self {
+        match describe_duration(interval_second) {
+            (0, _, _) => Self::new("Invalid interval".to_owned(), "无效的间隔".to_owned()),
+            (1, unit_en, unit_zh) => {
+                Self::new(format!("Every {}", unit_en), format!("每{}", unit_zh))
+            }
+            (value, unit_en, unit_zh) => Self::new(
+                format!("Every {} {}s", value, unit_en),
+                format!("每{}{}", value, unit_zh),
+            ),
+        }
+    }
+
+    pub fn delay(delay_second: u32) -> Self {
+        match describe_duration(delay_second) {
+            (0, _, _) => Self::new(
+                "Immediately after the previous run finishes".to_owned(),
+                "上次执行结束后立即执行".to_owned(),
+            ),
+            (value, unit_en, unit_zh) => Self::new(
+                format!(
+                    "{} {}{} after the previous run finishes",
+                    value,
+                    unit_en,
+                    if value > 1 { "s" } else { "" }
+                ),
+                format!("上次执行结束{}{}后执行", value, unit_zh),
+            ),
+        }
+    }
+
+    pub fn none() -> Self {
+        Self::new(
+            "Not scheduled, manual trigger only".to_owned(),
+            "不自动调度,仅手动触发".to_owned(),
+        )
+    }
+
+    pub fn with_time_window(mut self, allow: &str, forbid: &str) -> Self {
+        if !allow.trim().is_empty() {
+            self.en.push_str(&format!("; only within {}", allow.trim()));
+            self.zh.push_str(&format!(";仅在{}内", allow.trim()));
+        }
+        if !forbid.trim().is_empty() {
+            self.en.push_str(&format!("; except {}", forbid.trim()));
+            self.zh.push_str(&format!(";{}除外", forbid.trim()));
+        }
+        self
+    }
+}
+
+/// 按能整除的最大单位描述时长,返回(数值,英文单位,中文单位)
+fn describe_duration(second: u32) -> (u32, &'static str, &'static str) {
+    if second == 0 {
+        (0, "second", "秒")
+    } else if second.is_multiple_of(86400) {
+        (second / 86400, "day", "天")
+    } else if second.is_multiple_of(3600) {
+        (second / 3600, "hour", "小时")
+    } else if second.is_multiple_of(60) {
+        (second / 60, "minute", "分钟")
+    } else {
+        (second, "second", "秒")
+    }
+}
+
+#[derive(Clone, Debug)]
+enum CronItem {
+    All,
+    Point(String),
+    Range(String, String),
+    Period(Box<CronItem>, u32),
+}
+
+impl CronItem {
+    /// 表达式已通过校验
+    fn parse(value: &str) -> Self {
+        if let Some((base, step)) = value.split_once('/') {
+            return Self::Period(
+                Box::new(Self::parse(base)),
+                step.parse().unwrap_or_default(),
+            );
+        }
+        match value {
+            "*" | "?" => Self::All,
+            _ => match value.split_once('-') {
+                Some((start, end)) => Self::Range(start.to_owned(), end.to_owned()),
+                None => Self::Point(value.to_owned()),
+            },
+        }
+    }
+}
+
+struct CronDescriber {
+    fields: Vec<Vec<CronItem>>,
+}
+
+impl CronDescriber {
+    fn is_all(&self, index: usize) -> bool {
+        self.fields
+            .get(index)
+            .is_none_or(|v| matches!(v.as_slice(), [CronItem::All]))
+    }
+
+    fn single_point(&self, index: usize) -> Option<u32> {
+        match self.fields[index].as_slice() {
+            [CronItem::Point(v)] => v.parse().ok(),
+            _ => None,
+        }
+    }
+
+    /// 秒、分、时都为单个值时按时刻描述
+    fn time_of_day(&self) -> Option<String> {
+        match (
+            self.single_point(0),
+            self.single_point(1),
+            self.single_point(2),
+        ) {
+            (Some(second), Some(minute), Some(hour)) => {
+                Some(format!("{:02}:{:02}:{:02}", hour, minute, second))
+            }
+            _ => None,
+        }
+    }
+
+    /// 名称或数字转为取值序号,月份与周支持英文缩写
+    fn ordinal(index: usize, value: &str) -> Option<usize> {
+        if let Ok(v) = value.parse::<usize>() {
+            return Some(v);
+        }
+        let prefix = value.get(..3)?.to_ascii_lowercase();
+        let names: &[&str] = if index == MONTH_INDEX {
+            &MONTH_NAMES_EN
+        } else {
+            &WEEKDAY_NAMES_EN
+        };
+        names
+            .iter()
+            .position(|v| v[..3].eq_ignore_ascii_case(&prefix))
+            .map(|v| v + 1)
+    }
+
+    fn is_named(index: usize) -> bool {
+        index == MONTH_INDEX || index == WEEKDAY_INDEX
+    }
+
+    fn value_en(index: usize, value: &str) -> String {
+        let ordinal = Self::ordinal(index, value).unwrap_or_default();
+        let name = match index {
+            MONTH_INDEX => MONTH_NAMES_EN.get(ordinal.wrapping_sub(1)),
+            WEEKDAY_INDEX => WEEKDAY_NAMES_EN.get(ordinal.wrapping_sub(1)),
+            _ => None,
+        };
+        name.map_or_else(|| value.to_owned(), |v| v.to_string())
+    }
+
+    fn value_zh(index: usize, value: &str) -> String {
+        let ordinal = Self::ordinal(index, value).unwrap_or_default();
+        match index {
+            0 => format!("第{}秒", ordinal),
+            1 => format!("第{}分", ordinal),
+            2 => format!("{}点", ordinal),
+            3 => format!("{}号", ordinal),
+            MONTH_INDEX => format!("{}月", ordinal),
+            WEEKDAY_INDEX => WEEKDAY_NAMES_ZH
+                .get(ordinal.wrapping_sub(1))
+                .map_or_else(|| value.to_owned(), |v| v.to_string()),
+            _ => format!("{}年", ordinal),
+        }
+    }
+
+    fn item_en(index: usize, item: &CronItem) -> String {
+        let (unit, units) = UNITS_EN[index];
+        let named = Self::is_named(index);
+        match item {
+            CronItem::All => format!("every {}", unit),
+            CronItem::Point(v) if named => Self::value_en(index, v),
+            CronItem::Point(v) => format!("{} {}", unit, v),
+            CronItem::Range(start, end) => {
+                let range = format!(
+                    "{} through {}",
+                    Self::value_en(index, start),
+                    Self::value_en(index, end)
+                );
+                if named {
+                    range
+                } else {
+                    format!("{} {}", units, range)
+                }
+            }
+            CronItem::Period(base, step) => {
+                let every = if *step == 1 {
+                    format!("every {}", unit)
+                } else {
+                    format!("every {} {}", step, units)
+                };
+                match base.as_ref() {
+                    CronItem::Point(v) => format!(
+                        "{} starting at {}",
+                        every,
+                        Self::item_en(index, &CronItem::Point(v.clone()))
+                    ),
+                    CronItem::Range(start, end) => format!(
+                        "{} from {} through {}",
+                        every,
+                        Self::value_en(index, start),
+                        Self::value_en(index, end)
+                    ),
+                    _ => every,
+                }
+            }
+        }
+    }
+
+    fn item_zh(index: usize, item: &CronItem) -> String {
+        let unit = UNITS_ZH[index];
+        match item {
+            CronItem::All => format!("每{}", unit),
+            CronItem::Point(v) => Self::value_zh(index, v),
+            CronItem::Range(start, end) => format!(
+                "{}到{}",
+                Self::value_zh(index, start),
+                Self::value_zh(index, end)
+            ),
+            CronItem::Period(base, step) => {
+                let every = if *step == 1 {
+                    format!("每{}", unit)
+                } else {
+                    format!("每{}{}", step, unit)
+                };
+                match base.as_ref() {
+                    CronItem::Point(v) => format!("从{}开始{}", Self::value_zh(index, v), every),
+                    CronItem::Range(start, end) => format!(
+                        "{}到{}{}",
+                        Self::value_zh(index, start),
+                        Self::value_zh(index, end),
+                        every
+                    ),
+                    _ => every,
+                }
+            }
+        }
+    }
+
+    fn field_en(&self, index: usize) -> String {
+        let items = &self.fields[index];
+        let points: Vec<&String> = items
+            .iter()
+            .filter_map(|v| match v {
+                CronItem::Point(v) => Some(v),
+                _ => None,
+            })
+            .collect();
+        let text = if points.len() > 1 && points.len() == items.len() {
+            let values: Vec<String> = points.iter().map(|v| Self::value_en(index, v)).collect();
+            if Self::is_named(index) {
+                values.join(", ")
+            } else {
+                format!("{} {}", UNITS_EN[index].1, values.join(", "))
+            }
+        } else {
+            items
+                .iter()
+                .map(|v| Self::item_en(index, v))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+        if text.starts_with("every") {
+            return match index {
+                3 => format!("{} of the month", text),
+                WEEKDAY_INDEX => format!("{} of the week", text),
+                _ => text,
+            };
+        }
+        match index {
+            3 => format!("on {} of the month", text),
+            MONTH_INDEX | 6 => format!("in {}", text),
+            WEEKDAY_INDEX => format!("on {}", text),
+            _ => text,
+        }
+    }
+
+    fn field_zh(&self, index: usize) -> String {
+        self.fields[index]
+            .iter()
+            .map(|v| Self::item_zh(index, v))
+            .collect::<Vec<String>>()
+            .join("、")
+    }
+
+    /// 更小的字段只包含固定值或范围
+    fn is_fixed(&self, index: usize) -> bool {
+        self.fields[index]
+            .iter()
+            .all(|v| matches!(v, CronItem::Point(_) | CronItem::Range(_, _)))
+    }
+
+    /// 时分秒中值为`*`的字段只在更小的字段为固定值时描述,如`0 * * * * *`描述为每分钟第0秒
+    fn time_parts(&self, describe: impl Fn(usize) -> String) -> Vec<(usize, String)> {
+        (0..3)
+            .filter(|i| !self.is_all(*i) || *i == 0 || self.is_fixed(*i - 1))
+            .map(|i| (i, describe(i)))
+            .collect()
+    }
+
+    fn describe(&self) -> ScheduleDescription {
+        let date_indexes: Vec<usize> = (3..self.fields.len())
+            .filter(|i| !self.is_all(*i))
+            .collect();
+        let time_of_day = self.time_of_day();
+        // 英文从小到大: 时间、日期、月份、周、年份
+        let mut en_parts: Vec<String> = match time_of_day.as_ref() {
+            Some(v) => vec![format!("at {}", v)],
+            None => self
+                .time_parts(|i| {
+                    if self.is_all(i) {
+                        format!("every {}", UNITS_EN[i].0)
+                    } else {
+                        self.field_en(i)
+                    }
+                })
+                .into_iter()
+                .map(|(_, v)| v)
+                .collect(),
+        };
+        en_parts.extend(date_indexes.iter().map(|i| self.field_en(*i)));
+        let mut en = en_parts.join(", ");
+        if let Some(first) = en.get(..1) {
+            en = first.to_ascii_uppercase() + &en[1..];
+        }
+        // 中文从大到小: 年份、月份、日期、周、时间
+        let date_zh: Vec<String> = [6, MONTH_INDEX, 3, WEEKDAY_INDEX]
+            .iter()
+            .filter(|i| date_indexes.contains(i))
+            .map(|i| self.field_zh(*i))
+            .collect();
+        let zh = match time_of_day {
+            Some(v) if date_zh.is_empty() => format!("每天 {}", v),
+            Some(v) => format!("{} {}", date_zh.join(","), v),
+            None => {
+                let mut time_zh = self.time_parts(|i| self.field_zh(i));
+                time_zh.reverse();
+                date_zh
+                    .into_iter()
+                    .chain(time_zh.into_iter().map(|(_, v)| v))
+                    .collect::<Vec<String>>()
+                    .join(",")
+            }
+        };
+        ScheduleDescription::new(en, zh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn describe(cron_value: &str) -> (String, String) {
+        let v = ScheduleDescription::cron(cron_value).unwrap();
+        (v.en, v.zh)
+    }
+
+    #[test]
+    fn describe_schedule() {
+        assert_eq!(
+            describe("0 30 9 * * *"),
+            ("At 09:30:00".to_owned(), "每天 09:30:00".to_owned())
+        );
+        assert_eq!(
+            describe("0 0/30 9-17 * * MON-FRI"),
+            (
+                "Second 0, every 30 minutes starting at minute 0, hours 9 through 17, on Monday through Friday"
+                    .to_owned(),
+                "周一到周五,9点到17点,从第0分开始每30分钟,第0秒".to_owned()
+            )
+        );
+        assert_eq!(
+            describe("0 0 1 1,15 * ?"),
+            (
+                "At 01:00:00, on days 1, 15 of the month".to_owned(),
+                "1号、15号 01:00:00".to_owned()
+            )
+        );
+        assert_eq!(
+            describe("*/5 * * * * *"),
+            ("Every 5 seconds".to_owned(), "每5秒".to_owned())
+        );
+        assert_eq!(
+            describe("@daily"),
+            ("At 00:00:00".to_owned(), "每天 00:00:00".to_owned())
+        );
+        assert_eq!(
+            ScheduleDescription::interval(7200).en,
+            "Every 2 hours".to_owned()
+        );
+        assert_eq!(ScheduleDescription::delay(60).zh, "上次执行结束1分钟后执行");
+    }
+}
@@ -0,0 +1,322 @@
+use crate::common::cron_utils::CronUtil;
+use chrono::FixedOffset;
+use cron::Schedule;
+
+const DAY_SECONDS: i64 = 86400;
+/// cron任务顺延到时间窗口内时的最大查找次数
+const TIME_WINDOW_CRON_MAX_SKIP: usize = 1000;
+/// 时间窗口按周循环,向后查找可执行时间的天数
+const SEARCH_DAYS: i64 = 8;
+const WEEKDAY_NAMES: [&str; 7] = ["MON", "TUE", "WED", "THU", "FRI", "SAT", "SUN"];
+
+///
+/// 单个时间窗口,如 `MON-FRI 09:00-18:00`、`MON,WED,FRI 22:00-06:00`、`00:00-08:00`
+/// 星期为空表示每天;结束时间小于等于开始时间表示跨天,跨天部分归属开始当天
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TimeWindow {
+    /// bit0为周一,bit6为周日
+    weekdays: u8,
+    /// 当天开始秒数
+    start: u32,
+    /// 当天结束秒数(不包含)
+    end: u32,
+}
+
+impl TimeWindow {
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        let value = value.trim();
+        let (weekdays, range) = match value.split_once(char::is_whitespace) {
+            Some((weekdays, range)) => (Self::parse_weekdays(weekdays.trim())?, range.trim()),
+            None => (0x7f, value),
+        };
+        let (start, end) = range
+            .split_once('-')
+            .ok_or_else(|| anyhow::anyhow!("time range '{}' is invalid!", range))?;
+        let start = Self::parse_time(start.trim())?;
+        let end = Self::parse_time(end.trim())?;
+        if start == end || start == DAY_SECONDS as u32 {
+            return Err(anyhow::anyhow!("time range '{}' is invalid!", range));
+        }
+        Ok(Self {
+            weekdays,
+            start,
+            end,
+        })
+    }
+
+    /// 只支持英文缩写,数字写法在cron中周日为1,容易混淆
+    fn parse_weekday(value: &str) -> anyhow::Result<u8> {
+        let value = value.trim().to_ascii_uppercase();
+        WEEKDAY_NAMES
+            .iter()
+            .position(|v| *v == value)
+            .map(|v| v as u8)
+            .ok_or_else(|| anyhow::anyhow!("weekday '{}' is invalid,use MON~SUN!", value))
+    }
+
+    /// 支持 `MON-FRI`、`SAT,SUN`,范围可跨周如 `FRI-MON`
+    fn parse_weekdays(value: &str) -> anyhow::Result<u8> {
+        let mut weekdays = 0u8;
+        for item in value.split(',') {
+            if let Some((start, end)) = item.split_once('-') {
+                let start = Self::parse_weekday(start)?;
+                let end = Self::parse_weekday(end)?;
+                let mut day = start;
+                loop {
+                    weekdays |= 1 << day;
+                    if day == end {
+                        break;
+                    }
+                    day = (day + 1) % 7;
+                }
+            } else {
+                weekdays |= 1 << Self::parse_weekday(item)?;
+            }
+        }
+        Ok(weekdays)
+    }
+
+    /// `HH:MM`,支持 `24:00` 表示当天结束
+    fn parse_time(value: &str) -> anyhow::Result<u32> {
+        let (hour, minute) = value
+            .split_once(':')
+            .ok_or_else(|| anyhow::anyhow!("time '{}' is invalid!", value))?;
+        let (hour, minute) = match (hour.parse::<u32>(), minute.parse::<u32>()) {
+            (Ok(hour), Ok(minute)) if minute < 60 && (hour < 24 || (hour == 24 && minute == 0)) => {
+                (hour, minute)
+            }
+            _ => return Err(anyhow::anyhow!("time '{}' is invalid!", value)),
+        };
+        Ok(hour * 3600 + minute * 60)
+    }
+
+    fn has_weekday(&self, weekday: i64) -> bool {
+        self.weekdays & (1 << weekday) > 0
+    }
+
+    /// weekday: 0为周一
+    fn contains(&self, weekday: i64, day_second: u32) -> bool {
+        if self.start < self.end {
+            self.has_weekday(weekday) && self.start <= day_second && day_second < self.end
+        } else {
+            (self.has_weekday(weekday) && day_second >= self.start)
+                || (self.has_weekday((weekday + 6) % 7) && day_second < self.end)
+        }
+    }
+
+    /// 窗口在某天的开始与结束边界(本地秒数)
+    fn boundaries(&self, local_day_start: i64) -> [i64; 2] {
+        let end = if self.start < self.end {
+            local_day_start + self.end as i64
+        } else {
+            local_day_start + DAY_SECONDS + self.end as i64
+        };
+        [local_day_start + self.start as i64, end]
+    }
+}
+
+/// 多个时间窗口用`;`分隔,满足任一窗口即可
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TimeWindowList {
+    windows: Vec<TimeWindow>,
+}
+
+impl TimeWindowList {
+    pub fn parse(value: &str) -> anyhow::Result<Self> {
+        let mut windows = Vec::new();
+        for item in value.split(';') {
+            if item.trim().is_empty() {
+                continue;
+            }
+            windows.push(
+                TimeWindow::parse(item)
+                    .map_err(|e| anyhow::anyhow!("time window '{}' is invalid,{}", item, e))?,
+            );
+        }
+        Ok(Self { windows })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.windows.is_empty()
+    }
+
+    fn contains(&self, weekday: i64, day_second: u32) -> bool {
+        self.windows.iter().any(|v| v.contains(weekday, day_second))
+    }
+}
+
+///
+/// 任务的允许与禁止执行时间窗口,按调度时区计算
+/// 设置允许窗口时只在窗口内触发,禁止窗口优先
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct JobTimeWindow {
+    allow: TimeWindowList,
+    forbid: TimeWindowList,
+}
+
+impl JobTimeWindow {
+    pub fn new(allow: &str, forbid: &str) -> anyhow::Result<Self> {
+        let v = Self {
+            allow: TimeWindowList::parse(allow)?,
+            forbid: TimeWindowList::parse(forbid)?,
+        };
+        // 0表示找不到可执行时间,从非0时间开始检查
+        if v.next_permitted_time(DAY_SECONDS as u32, &FixedOffset::east_opt(0).unwrap()) == 0 {
+            return Err(anyhow::anyhow!(
+                "time window has no allowed time,allow:'{}',forbid:'{}'",
+                allow,
+                forbid
+            ));
+        }
+        Ok(v)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.allow.is_empty() && self.forbid.is_empty()
+    }
+
+    fn to_local(timestamp: i64, offset: &FixedOffset) -> i64 {
+        timestamp + offset.local_minus_utc() as i64
+    }
+
+    fn is_local_permitted(&self, local: i64) -> bool {
+        let days = local.div_euclid(DAY_SECONDS);
+        // 1970-01-01 为周四
+        let weekday = (days + 3).rem_euclid(7);
+        let day_second = local.rem_euclid(DAY_SECONDS) as u32;
+        (self.allow.is_empty() || self.allow.contains(weekday, day_second))
+            && !self.forbid.contains(weekday, day_second)
+    }
+
+    pub fn is_permitted(&self, timestamp: u32, offset: &FixedOffset) -> bool {
+        self.is_local_permitted(Self::to_local(timestamp as i64, offset))
+    }
+
+    /// 大于等于trigger_time的cron触发时间中最早在窗口内的,超过最大查找次数时返回0
+    pub fn next_permitted_cron_time(
+        &self,
+        cron_schedule: &Schedule,
+        trigger_time: u32,
+        offset: &FixedOffset,
+    ) -> u32 {
+        let mut trigger_time = trigger_time;
+        for _ in 0..TIME_WINDOW_CRON_MAX_SKIP {
+            let permitted_time = self.next_permitted_time(trigger_time, offset);
+            if permitted_time == trigger_time || permitted_time == 0 {
+                return permitted_time;
+            }
+            trigger_time = match CronUtil::next_cron_time_by_timestamp(
+                cron_schedule,
+                offset,
+                permitted_time - 1,
+            ) {
+                Ok(v) => v,
+                Err(_) => return 0,
+            };
+        }
+        0
+    }
+
+    /// cron触发时间需能落在窗口内;cron与窗口按同一时区计算,这里统一按UTC检查;
+    /// 会在raft应用日志时调用,从固定时间开始查找,结果不随当前时间变化
+    pub fn check_cron(&self, cron_value: &str, cron_schedule: &Schedule) -> anyhow::Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+        let offset = FixedOffset::east_opt(0).unwrap();
+        // 没有后续触发时间的cron不在这里检查
+        let trigger_time =
+            match CronUtil::next_cron_time_by_timestamp(cron_schedule, &offset, DAY_SECONDS as u32)
+            {
+                Ok(v) => v,
+                Err(_) => return Ok(()),
+            };
+        if self.next_permitted_cron_time(cron_schedule, trigger_time, &offset) == 0 {
+            return Err(anyhow::anyhow!(
+                "cron_value '{}' has no trigger time in the time window",
+                cron_value
+            ));
+        }
+        Ok(())
+    }
+
+    /// 大于等于timestamp的最早可执行时间,找不到时返回0
+    pub fn next_permitted_time(&self, timestamp: u32, offset: &FixedOffset) -> u32 {
+        let local = Self::to_local(timestamp as i64, offset);
+        if self.is_local_permitted(local) {
+            return timestamp;
+        }
+        // 可执行状态只在窗口边界变化
+        let first_day = local.div_euclid(DAY_SECONDS) - 1;
+        let mut boundaries: Vec<i64> = (first_day..=first_day + SEARCH_DAYS)
+            .flat_map(|day| {
+                self.allow
+                    .windows
+                    .iter()
+                    .chain(self.forbid.windows.iter())
+                    .flat_map(move |w| w.boundaries(day * DAY_SECONDS))
+            })
+            .filter(|v| *v > local)
+            .collect();
+        boundaries.sort_unstable();
+        boundaries
+            .into_iter()
+            .find(|v| self.is_local_permitted(*v))
+            .map(|v| (v - offset.local_minus_utc() as i64) as u32)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn job_time_window_next_permitted() {
+        let offset = FixedOffset::east_opt(8 * 3600).unwrap();
+        // 2024-01-01 00:00:00 +08:00 为周一
+        let monday = 1704038400u32;
+        let hour = 3600u32;
+        let v = JobTimeWindow::new("MON-FRI 09:00-18:00", "").unwrap();
+        assert!(!v.is_permitted(monday + 8 * hour, &offset));
+        assert!(v.is_permitted(monday + 9 * hour, &offset));
+        assert_eq!(
+            v.next_permitted_time(monday + 18 * hour, &offset),
+            monday + (24 + 9) * hour
+        );
+        // 周六跳到下周一
+        assert_eq!(
+            v.next_permitted_time(monday + 5 * 24 * hour, &offset),
+            monday + (7 * 24 + 9) * hour
+        );
+        let v = JobTimeWindow::new("", "09:00-18:00").unwrap();
+        assert_eq!(
+            v.next_permitted_time(monday + 10 * hour, &offset),
+            monday + 18 * hour
+        );
+        // 跨天窗口
+        let v = JobTimeWindow::new("SUN 22:00-06:00", "").unwrap();
+        assert!(v.is_permitted(monday + 5 * hour, &offset));
+        assert!(!v.is_permitted(monday + 6 * hour, &offset));
+        assert!(JobTimeWindow::new("MON-FRI 09:00-18:00", "00:00-24:00").is_err());
+        // 数字星期与cron的周日为1冲突,不支持
+        assert!(JobTimeWindow::new("1-5 09:00-18:00", "").is_err());
+        assert!(JobTimeWindow::new("sat,sun 09:00-18:00", "").is_ok());
+        assert!(JobTimeWindow::new("MON 9-18", "").is_err());
+        assert!(JobTimeWindow::new("XYZ 09:00-18:00", "").is_err());
+        assert!(JobTimeWindow::new("", "").unwrap().is_empty());
+    }
+
+    #[test]
+    fn job_time_window_check_cron() {
+        let v = JobTimeWindow::new("MON 09:00-10:00", "").unwrap();
+        let check = |cron_value: &str| {
+            v.check_cron(cron_value, &CronUtil::check_cron_value(cron_value).unwrap())
+        };
+        assert!(check("0 0 3 * * *").is_err());
+        assert!(check("0 30 9 * * *").is_ok());
+        assert!(check("0 0/20 * * * *").is_ok());
+        // 从固定时间检查,与当前时间无关
+        assert!(check("0 0 3 * * * 2020").is_err());
+    }
+}
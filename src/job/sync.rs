@@ -0,0 +1,515 @@
+use crate::app::model::{AppInfoDto, AppKey, AppParam, RegisterType};
+use crate::audit::model::audit::{diff_json, AuditFieldChange};
+use crate::common::datetime_utils::now_second_u32;
+use crate::common::model::ApiResult;
+use crate::common::namespace_util::get_namespace_by_option;
+use crate::job::model::job::{JobInfo, JobInfoDto, JobKey, JobParam};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::sync::Arc;
+
+/// 比较任务配置时忽略的字段
+const JOB_SYNC_DIFF_IGNORE_FIELDS: [&str; 5] = [
+    "id",
+    "versionId",
+    "lastModifiedMillis",
+    "registerTime",
+    "glueUpdateTime",
+];
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobSyncAppItem {
+    pub namespace: Option<Arc<String>>,
+    pub app_name: Arc<String>,
+    pub label: Option<Arc<String>>,
+    pub register_type: Option<String>,
+    pub instance_addrs: Option<Vec<Arc<String>>>,
+}
+
+///
+/// 声明式同步清单(yaml或json)
+/// 任务按 namespace/appName/key 匹配已有任务,清单中的任务即为完整配置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobSyncManifest {
+    /// 应用与任务未设置命名空间时使用
+    pub namespace: Option<Arc<String>>,
+    #[serde(default)]
+    pub apps: Vec<JobSyncAppItem>,
+    #[serde(default)]
+    pub jobs: Vec<JobParam>,
+}
+
+impl JobSyncManifest {
+    /// json是yaml的子集,统一按yaml解析
+    pub fn parse(content: &str) -> anyhow::Result<Self> {
+        let manifest: JobSyncManifest = serde_yaml::from_str(content)
+            .map_err(|e| anyhow::anyhow!("parse manifest error:{}", e))?;
+        Ok(manifest)
+    }
+
+    /// 清单涉及的应用,用于限定清理范围
+    pub fn app_keys(&self) -> BTreeSet<AppKey> {
+        let mut keys = BTreeSet::new();
+        for app in &self.apps {
+            keys.insert(AppKey::new(
+                app.app_name.clone(),
+                self.get_namespace(&app.namespace),
+            ));
+        }
+        for job in &self.jobs {
+            keys.insert(AppKey::new(
+                job.app_name.clone().unwrap_or_default(),
+                self.get_namespace(&job.namespace),
+            ));
+        }
+        keys
+    }
+
+    fn get_namespace(&self, namespace: &Option<Arc<String>>) -> Arc<String> {
+        if namespace.as_ref().is_some_and(|v| !v.is_empty()) {
+            get_namespace_by_option(namespace)
+        } else {
+            get_namespace_by_option(&self.namespace)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum JobSyncAction {
+    Create,
+    Update,
+    Delete,
+    Unchanged,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobSyncAppPlanItem {
+    pub namespace: Arc<String>,
+    pub app_name: Arc<String>,
+    pub action: JobSyncAction,
+    pub changes: Vec<AuditFieldChange>,
+    #[serde(skip)]
+    pub param: Option<AppParam>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobSyncJobPlanItem {
+    pub namespace: Arc<String>,
+    pub app_name: Arc<String>,
+    pub key: Arc<String>,
+    pub id: Option<u64>,
+    pub action: JobSyncAction,
+    pub changes: Vec<AuditFieldChange>,
+    /// 同步后的任务配置,删除时为空
+    #[serde(skip)]
+    pub job: Option<Arc<JobInfo>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobSyncSummary {
+    pub create: usize,
+    pub update: usize,
+    pub delete: usize,
+    pub unchanged: usize,
+}
+
+impl JobSyncSummary {
+    fn add(&mut self, action: JobSyncAction) {
+        match action {
+            JobSyncAction::Create => self.create += 1,
+            JobSyncAction::Update => self.update += 1,
+            JobSyncAction::Delete => self.delete += 1,
+            JobSyncAction::Unchanged => self.unchanged += 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobSyncPlan {
+    pub dry_run: bool,
+    pub prune: bool,
+    pub apps: Vec<JobSyncAppPlanItem>,
+    pub jobs: Vec<JobSyncJobPlanItem>,
+    pub summary: JobSyncSummary,
+    /// 执行失败的变更
+    pub errors: Vec<String>,
+}
+
+impl JobSyncPlan {
+    ///
+    /// 根据清单与现有配置计算变更计划
+    /// existing_jobs 为清单涉及应用下的全部任务;prune为true时删除清单中不存在的任务
+    pub fn build(
+        manifest: &JobSyncManifest,
+        existing_apps: &HashMap<AppKey, AppInfoDto>,
+        existing_jobs: &[Arc<JobInfo>],
+        prune: bool,
+    ) -> anyhow::Result<Self> {
+        let mut plan = JobSyncPlan {
+            prune,
+            ..Default::default()
+        };
+        let mut errors = vec![];
+        let mut app_set = BTreeSet::new();
+        for app in &manifest.apps {
+            let app_key = AppKey::new(app.app_name.clone(), manifest.get_namespace(&app.namespace));
+            if app_key.app_name.is_empty() {
+                errors.push("app name is empty".to_owned());
+                continue;
+            }
+            if !app_set.insert(app_key.clone()) {
+                errors.push(format!(
+                    "duplicate app:{}/{}",
+                    &app_key.namespace, &app_key.app_name
+                ));
+                continue;
+            }
+            plan.apps
+                .push(Self::build_app_item(app, app_key, existing_apps));
+        }
+        let job_map: HashMap<JobKey, &Arc<JobInfo>> = existing_jobs
+            .iter()
+            .filter(|v| !v.key.is_empty())
+            .map(|v| (v.build_job_key(), v))
+            .collect();
+        let mut job_set = BTreeSet::new();
+        for job in &manifest.jobs {
+            let mut param = job.clone();
+            param.namespace = Some(manifest.get_namespace(&job.namespace));
+            let job_key = JobKey::new_by_arc(
+                param.namespace.clone().unwrap_or_default(),
+                param.app_name.clone().unwrap_or_default(),
+                param.key.clone().unwrap_or_default(),
+            );
+            let job_name = format!(
+                "{}/{}/{}",
+                &job_key.namespace, &job_key.app_name, &job_key.job_key
+            );
+            if let Err(err) = job_key.is_valid().and_then(|_| param.check_valid()) {
+                errors.push(format!("job {} is invalid,{}", &job_name, err));
+                continue;
+            }
+            if !job_set.insert(job_key.clone()) {
+                errors.push(format!("duplicate job:{}", &job_name));
+                continue;
+            }
+            param.id = None;
+            let existing = job_map.get(&job_key).copied();
+            let item = Self::build_job_item(param, job_key, existing);
+            plan.jobs.push(item);
+        }
+        if !errors.is_empty() {
+            return Err(anyhow::anyhow!("manifest is invalid:{}", errors.join(";")));
+        }
+        if prune {
+            let app_keys = manifest.app_keys();
+            for job in existing_jobs {
+                if !app_keys.contains(&job.build_app_key()) {
+                    continue;
+                }
+                if !job.key.is_empty() && job_set.contains(&job.build_job_key()) {
+                    continue;
+                }
+                plan.jobs.push(JobSyncJobPlanItem {
+                    namespace: job.namespace.clone(),
+                    app_name: job.app_name.clone(),
+                    key: job.key.clone(),
+                    id: Some(job.id),
+                    action: JobSyncAction::Delete,
+                    changes: vec![],
+                    job: None,
+                });
+            }
+        }
+        for item in &plan.apps {
+            plan.summary.add(item.action);
+        }
+        for item in &plan.jobs {
+            plan.summary.add(item.action);
+        }
+        Ok(plan)
+    }
+
+    fn build_app_item(
+        app: &JobSyncAppItem,
+        app_key: AppKey,
+        existing_apps: &HashMap<AppKey, AppInfoDto>,
+    ) -> JobSyncAppPlanItem {
+        let register_type = app
+            .register_type
+            .as_ref()
+            .map(|v| RegisterType::from_str(v))
+            .unwrap_or_default();
+        let mut instance_addrs = app.instance_addrs.clone();
+        if let Some(addrs) = instance_addrs.as_mut() {
+            addrs.sort();
+        }
+        let label = app.label.clone().unwrap_or_default();
+        let mut after = BTreeMap::new();
+        after.insert("label", serde_json::json!(label));
+        after.insert("registerType", serde_json::json!(register_type.to_str()));
+        if instance_addrs.is_some() {
+            after.insert("instanceAddrs", serde_json::json!(instance_addrs));
+        }
+        let (action, changes) = if let Some(old) = existing_apps.get(&app_key) {
+            let mut old_addrs = old.instance_addrs.clone().unwrap_or_default();
+            old_addrs.sort();
+            let mut before = BTreeMap::new();
+            before.insert("label", serde_json::json!(old.label));
+            before.insert("registerType", serde_json::json!(old.register_type));
+            if instance_addrs.is_some() {
+                before.insert("instanceAddrs", serde_json::json!(old_addrs));
+            }
+            let changes = diff_json(
+                &serde_json::to_string(&before).unwrap_or_default(),
+                &serde_json::to_string(&after).unwrap_or_default(),
+            );
+            if changes.is_empty() {
+                (JobSyncAction::Unchanged, changes)
+            } else {
+                (JobSyncAction::Update, changes)
+            }
+        } else {
+            (JobSyncAction::Create, vec![])
+        };
+        JobSyncAppPlanItem {
+            namespace: app_key.namespace.clone(),
+            app_name: app_key.app_name.clone(),
+            action,
+            changes,
+            param: Some(AppParam {
+                app_name: app_key.app_name,
+                namespace: app_key.namespace,
+                label: Some(label),
+                register_type: Some(register_type),
+                instance_addrs,
+                last_modified_time: now_second_u32(),
+            }),
+        }
+    }
+
+    fn build_job_item(
+        param: JobParam,
+        job_key: JobKey,
+        existing: Option<&Arc<JobInfo>>,
+    ) -> JobSyncJobPlanItem {
+        let keep_labels = param.labels.is_none();
+        let keep_allow_time_window = param.allow_time_window.is_none();
+        let keep_forbid_time_window = param.forbid_time_window.is_none();
+        let mut desired: JobInfo = param.into();
+        let (action, changes) = if let Some(old) = existing {
+            desired.id = old.id;
+            //未设置http配置、重试策略、标签、时间窗口时保留原配置,与更新接口一致
+            if desired.http_config.is_none() {
+                desired.http_config = old.http_config.clone();
+            }
+            if desired.retry_policy.is_none() {
+                desired.retry_policy = old.retry_policy.clone();
+            }
+            if keep_labels {
+                desired.labels = old.labels.clone();
+            }
+            if keep_allow_time_window {
+                desired.allow_time_window = old.allow_time_window.clone();
+            }
+            if keep_forbid_time_window {
+                desired.forbid_time_window = old.forbid_time_window.clone();
+            }
+            let mut changes = diff_json(
+                &serde_json::to_string(&JobInfoDto::new_from(old)).unwrap_or_default(),
+                &serde_json::to_string(&JobInfoDto::new_from(&desired)).unwrap_or_default(),
+            );
+            changes.retain(|v| !JOB_SYNC_DIFF_IGNORE_FIELDS.contains(&v.field.as_str()));
+            if changes.is_empty() {
+                (JobSyncAction::Unchanged, changes)
+            } else {
+                (JobSyncAction::Update, changes)
+            }
+        } else {
+            (JobSyncAction::Create, vec![])
+        };
+        JobSyncJobPlanItem {
+            namespace: job_key.namespace,
+            app_name: job_key.app_name,
+            key: job_key.job_key,
+            id: existing.map(|v| v.id),
+            action,
+            changes,
+            job: Some(Arc::new(desired)),
+        }
+    }
+}
+
+impl JobSyncPlan {
+    /// 输出计划,unchanged的条目不输出
+    pub fn write_to<W: std::io::Write>(&self, out: &mut W) -> std::io::Result<()> {
+        for item in self.apps.iter() {
+            if item.action == JobSyncAction::Unchanged {
+                continue;
+            }
+            writeln!(
+                out,
+                "{:?} app {}/{}",
+                item.action, &item.namespace, &item.app_name
+            )?;
+            write_changes(out, &item.changes)?;
+        }
+        for item in self.jobs.iter() {
+            if item.action == JobSyncAction::Unchanged {
+                continue;
+            }
+            writeln!(
+                out,
+                "{:?} job {}/{}/{} id:{}",
+                item.action,
+                &item.namespace,
+                &item.app_name,
+                &item.key,
+                item.id.map(|v| v.to_string()).unwrap_or_default()
+            )?;
+            write_changes(out, &item.changes)?;
+        }
+        writeln!(
+            out,
+            "{}create:{},update:{},delete:{},unchanged:{}",
+            if self.dry_run { "[dry-run] " } else { "" },
+            self.summary.create,
+            self.summary.update,
+            self.summary.delete,
+            self.summary.unchanged
+        )?;
+        for err in self.errors.iter() {
+            writeln!(out, "error: {}", err)?;
+        }
+        Ok(())
+    }
+}
+
+fn write_changes<W: std::io::Write>(
+    out: &mut W,
+    changes: &[AuditFieldChange],
+) -> std::io::Result<()> {
+    for change in changes {
+        writeln!(
+            out,
+            "    {}: {} -> {}",
+            &change.field, &change.before, &change.after
+        )?;
+    }
+    Ok(())
+}
+
+/// 命令行同步:把清单内容提交到服务端的 `/ratch/v1/job/sync`
+pub async fn sync_remote(
+    content: String,
+    server: &str,
+    dry_run: bool,
+    prune: bool,
+) -> anyhow::Result<JobSyncPlan> {
+    // 先在本地校验清单格式
+    JobSyncManifest::parse(&content)?;
+    let url = format!(
+        "{}/ratch/v1/job/sync?dryRun={}&prune={}",
+        server.trim_end_matches('/'),
+        dry_run,
+        prune
+    );
+    let resp = reqwest::Client::new()
+        .post(url)
+        .header("Content-Type", "application/yaml")
+        .body(content)
+        .send()
+        .await?;
+    let status = resp.status();
+    if !status.is_success() {
+        return Err(anyhow::anyhow!("sync request error,status:{}", status));
+    }
+    let result: ApiResult<JobSyncPlan> = resp.json().await?;
+    if !result.success {
+        return Err(anyhow::anyhow!(
+            "sync error,{}",
+            result.message.unwrap_or_default()
+        ));
+    }
+    result
+        .data
+        .ok_or_else(|| anyhow::anyhow!("sync result is empty"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::model::enum_type::ScheduleType;
+
+    #[test]
+    fn job_sync_plan() {
+        let manifest = JobSyncManifest::parse(
+            r#"
+namespace: dev
+jobs:
+  - appName: demo
+    key: daily
+    handleName: dailyHandler
+    scheduleType: CRON
+    cronValue: "0 0 1 * * *"
+  - appName: demo
+    key: hourly
+    handleName: hourlyHandler
+    scheduleType: CRON
+    cronValue: "0 0 * * * *"
+"#,
+        )
+        .unwrap();
+        let mut daily: JobInfo = manifest.jobs[0].clone().into();
+        daily.id = 1;
+        daily.namespace = Arc::new("dev".to_owned());
+        daily.version_id = 3;
+        let mut hourly = daily.clone();
+        hourly.id = 2;
+        hourly.key = Arc::new("hourly".to_owned());
+        hourly.handle_name = Arc::new("oldHandler".to_owned());
+        hourly.cron_value = Arc::new("0 0 * * * *".to_owned());
+        let mut other = daily.clone();
+        other.id = 3;
+        other.key = Arc::new("other".to_owned());
+        let mut other_app = other.clone();
+        other_app.id = 4;
+        other_app.app_name = Arc::new("other-app".to_owned());
+        let existing = vec![
+            Arc::new(daily),
+            Arc::new(hourly),
+            Arc::new(other),
+            Arc::new(other_app),
+        ];
+        let plan = JobSyncPlan::build(&manifest, &HashMap::new(), &existing, true).unwrap();
+        let actions: Vec<_> = plan.jobs.iter().map(|v| (v.id, v.action)).collect();
+        assert_eq!(
+            actions,
+            vec![
+                (Some(1), JobSyncAction::Unchanged),
+                (Some(2), JobSyncAction::Update),
+                (Some(3), JobSyncAction::Delete),
+            ]
+        );
+        assert_eq!(plan.jobs[1].changes.len(), 1);
+        assert_eq!(plan.jobs[1].changes[0].field, "handleName");
+        assert_eq!(
+            plan.jobs[0].job.as_ref().unwrap().schedule_type,
+            ScheduleType::Cron
+        );
+
+        let plan = JobSyncPlan::build(&manifest, &HashMap::new(), &[], false).unwrap();
+        assert_eq!(plan.summary.create, 2);
+
+        let invalid =
+            JobSyncManifest::parse(r#"{"jobs":[{"appName":"demo","handleName":"h"}]}"#).unwrap();
+        assert!(JobSyncPlan::build(&invalid, &HashMap::new(), &[], false).is_err());
+    }
+}
@@ -0,0 +1,657 @@
+use crate::app::model::RegisterType;
+use crate::common::constant::DEFAULT_XXL_NAMESPACE;
+use crate::job::model::enum_type::{
+    ExecutorBlockStrategy, JobRunMode, PastDueStrategy, RouterStrategy, ScheduleType,
+};
+use crate::job::model::job::JobParam;
+use crate::job::sync::{JobSyncAppItem, JobSyncManifest};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const XXL_TABLE_GROUP: &str = "xxl_job_group";
+const XXL_TABLE_INFO: &str = "xxl_job_info";
+const XXL_TABLE_LOG: &str = "xxl_job_log";
+const XXL_JOB_KEY_PREFIX: &str = "xxl-";
+
+/// INSERT语句没有列名时使用xxl-job建表脚本中的列顺序
+const XXL_GROUP_COLUMNS: [&str; 6] = [
+    "id",
+    "app_name",
+    "title",
+    "address_type",
+    "address_list",
+    "update_time",
+];
+const XXL_INFO_COLUMNS: [&str; 24] = [
+    "id",
+    "job_group",
+    "job_desc",
+    "add_time",
+    "update_time",
+    "author",
+    "alarm_email",
+    "schedule_type",
+    "schedule_conf",
+    "misfire_strategy",
+    "executor_route_strategy",
+    "executor_handler",
+    "executor_param",
+    "executor_block_strategy",
+    "executor_timeout",
+    "executor_fail_retry_count",
+    "glue_type",
+    "glue_source",
+    "glue_remark",
+    "glue_updatetime",
+    "child_jobid",
+    "trigger_status",
+    "trigger_last_time",
+    "trigger_next_time",
+];
+
+/// 一行记录,值为NULL的列不放入
+pub type XxlRow = HashMap<String, String>;
+
+///
+/// xxl-job admin 数据库导出的数据
+#[derive(Debug, Clone, Default)]
+pub struct XxlDumpData {
+    pub groups: Vec<XxlRow>,
+    pub jobs: Vec<XxlRow>,
+    pub log_count: usize,
+}
+
+impl XxlDumpData {
+    /// 解析mysqldump导出的sql文件,只处理 xxl_job_group、xxl_job_info、xxl_job_log 的INSERT语句
+    pub fn parse_sql(content: &str) -> anyhow::Result<Self> {
+        let mut data = Self::default();
+        let mut parser = SqlInsertParser::new(content);
+        while let Some(insert) = parser.next_insert()? {
+            match insert.table.as_str() {
+                XXL_TABLE_GROUP => data.groups.extend(insert.into_rows(&XXL_GROUP_COLUMNS)?),
+                XXL_TABLE_INFO => data.jobs.extend(insert.into_rows(&XXL_INFO_COLUMNS)?),
+                XXL_TABLE_LOG => data.log_count += insert.values.len(),
+                _ => {}
+            }
+        }
+        Ok(data)
+    }
+
+    /// 解析带表头的csv导出文件,空值、`NULL`、`\N` 当作NULL
+    pub fn parse_csv(
+        group_content: &str,
+        job_content: &str,
+        log_content: Option<&str>,
+    ) -> anyhow::Result<Self> {
+        let mut data = Self {
+            groups: parse_csv_rows(group_content)?,
+            jobs: parse_csv_rows(job_content)?,
+            log_count: 0,
+        };
+        if let Some(log_content) = log_content {
+            data.log_count = parse_csv_rows(log_content)?.len();
+        }
+        Ok(data)
+    }
+}
+
+fn parse_csv_rows(content: &str) -> anyhow::Result<Vec<XxlRow>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+    let headers: Vec<String> = reader
+        .headers()?
+        .iter()
+        .map(|v| v.trim().trim_matches('`').to_owned())
+        .collect();
+    let mut rows = vec![];
+    for record in reader.records() {
+        let record = record?;
+        let mut row = XxlRow::new();
+        for (name, value) in headers.iter().zip(record.iter()) {
+            if value.is_empty() || value == "NULL" || value == "\\N" {
+                continue;
+            }
+            row.insert(name.to_owned(), value.to_owned());
+        }
+        rows.push(row);
+    }
+    Ok(rows)
+}
+
+struct SqlInsert {
+    table: String,
+    columns: Vec<String>,
+    values: Vec<Vec<Option<String>>>,
+}
+
+impl SqlInsert {
+    fn into_rows(self, default_columns: &[&str]) -> anyhow::Result<Vec<XxlRow>> {
+        let columns: Vec<String> = if self.columns.is_empty() {
+            default_columns.iter().map(|v| v.to_string()).collect()
+        } else {
+            self.columns
+        };
+        let mut rows = Vec::with_capacity(self.values.len());
+        for values in self.values {
+            if values.len() != columns.len() {
+                return Err(anyhow::anyhow!(
+                    "table {} column count {} not match value count {}",
+                    &self.table,
+                    columns.len(),
+                    values.len()
+                ));
+            }
+            let row = columns
+                .iter()
+                .zip(values)
+                .filter_map(|(name, value)| value.map(|v| (name.to_owned(), v)))
+                .collect();
+            rows.push(row);
+        }
+        Ok(rows)
+    }
+}
+
+/// 简单的INSERT语句解析,支持mysqldump的多值插入与字符串转义
+struct SqlInsertParser<'a> {
+    content: &'a str,
+    pos: usize,
+}
+
+impl<'a> SqlInsertParser<'a> {
+    fn new(content: &'a str) -> Self {
+        Self { content, pos: 0 }
+    }
+
+    fn next_insert(&mut self) -> anyhow::Result<Option<SqlInsert>> {
+        if !self.seek_insert() {
+            return Ok(None);
+        }
+        let table = self.read_identifier()?;
+        // 去掉库名
+        let table = match table.rsplit_once('.') {
+            Some((_, v)) => v.trim_matches('`').to_owned(),
+            None => table,
+        };
+        let mut columns = vec![];
+        self.skip_whitespace();
+        if self.peek() == Some('(') {
+            self.pos += 1;
+            loop {
+                columns.push(self.read_identifier()?);
+                self.skip_whitespace();
+                match self.next_char() {
+                    Some(',') => continue,
+                    Some(')') => break,
+                    _ => return Err(self.error("column list is invalid")),
+                }
+            }
+        }
+        self.skip_whitespace();
+        if !self.consume_keyword("VALUES") {
+            return Err(self.error("VALUES is expected"));
+        }
+        let mut values = vec![];
+        loop {
+            self.skip_whitespace();
+            if self.next_char() != Some('(') {
+                return Err(self.error("'(' is expected"));
+            }
+            values.push(self.read_tuple()?);
+            self.skip_whitespace();
+            match self.next_char() {
+                Some(',') => continue,
+                Some(';') | None => break,
+                _ => return Err(self.error("',' or ';' is expected")),
+            }
+        }
+        Ok(Some(SqlInsert {
+            table,
+            columns,
+            values,
+        }))
+    }
+
+    /// 跳到下一个INSERT INTO之后;语句之间的内容(建表语句、注释等)忽略
+    fn seek_insert(&mut self) -> bool {
+        let bytes = self.content.as_bytes();
+        while self.pos < bytes.len() {
+            let rest = &bytes[self.pos..];
+            if rest.len() >= 6 && rest[..6].eq_ignore_ascii_case(b"INSERT") {
+                let at_word_start =
+                    self.pos == 0 || !(bytes[self.pos - 1] as char).is_ascii_alphanumeric();
+                if at_word_start {
+                    self.pos += 6;
+                    self.skip_whitespace();
+                    // INSERT IGNORE INTO
+                    self.consume_keyword("IGNORE");
+                    self.skip_whitespace();
+                    if self.consume_keyword("INTO") {
+                        return true;
+                    }
+                    continue;
+                }
+            }
+            self.pos += 1;
+        }
+        false
+    }
+
+    fn read_tuple(&mut self) -> anyhow::Result<Vec<Option<String>>> {
+        let mut values = vec![];
+        loop {
+            self.skip_whitespace();
+            values.push(self.read_value()?);
+            self.skip_whitespace();
+            match self.next_char() {
+                Some(',') => continue,
+                Some(')') => return Ok(values),
+                _ => return Err(self.error("',' or ')' is expected")),
+            }
+        }
+    }
+
+    fn read_value(&mut self) -> anyhow::Result<Option<String>> {
+        if self.peek() == Some('\'') {
+            self.pos += 1;
+            return self.read_string().map(Some);
+        }
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c == ',' || c == ')' || c.is_whitespace() {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+        let value = &self.content[start..self.pos];
+        if value.is_empty() {
+            return Err(self.error("value is empty"));
+        }
+        if value.eq_ignore_ascii_case("NULL") {
+            Ok(None)
+        } else {
+            Ok(Some(value.to_owned()))
+        }
+    }
+
+    fn read_string(&mut self) -> anyhow::Result<String> {
+        let mut value = String::new();
+        loop {
+            match self.next_char() {
+                Some('\\') => match self.next_char() {
+                    Some('n') => value.push('\n'),
+                    Some('r') => value.push('\r'),
+                    Some('t') => value.push('\t'),
+                    Some('0') => value.push('\0'),
+                    Some('Z') => value.push('\x1a'),
+                    Some(c) => value.push(c),
+                    None => break,
+                },
+                Some('\'') => {
+                    if self.peek() == Some('\'') {
+                        self.pos += 1;
+                        value.push('\'');
+                    } else {
+                        return Ok(value);
+                    }
+                }
+                Some(c) => value.push(c),
+                None => break,
+            }
+        }
+        Err(self.error("string is not closed"))
+    }
+
+    fn read_identifier(&mut self) -> anyhow::Result<String> {
+        self.skip_whitespace();
+        let start = self.pos;
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || c == '(' || c == ')' || c == ',' {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+        let value = &self.content[start..self.pos];
+        if value.is_empty() {
+            return Err(self.error("identifier is empty"));
+        }
+        Ok(value.trim_matches('`').to_owned())
+    }
+
+    fn consume_keyword(&mut self, keyword: &str) -> bool {
+        let rest = &self.content.as_bytes()[self.pos..];
+        if rest.len() >= keyword.len()
+            && rest[..keyword.len()].eq_ignore_ascii_case(keyword.as_bytes())
+        {
+            self.pos += keyword.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            if !c.is_whitespace() {
+                break;
+            }
+            self.pos += c.len_utf8();
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.content[self.pos..].chars().next()
+    }
+
+    fn next_char(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn error(&self, msg: &str) -> anyhow::Error {
+        let line = self.content[..self.pos].matches('\n').count() + 1;
+        anyhow::anyhow!("parse sql error at line {},{}", line, msg)
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XxlImportReport {
+    pub app_count: usize,
+    pub job_count: usize,
+    pub skip_job_count: usize,
+    pub log_count: usize,
+    /// 不能完全映射的配置
+    pub warnings: Vec<String>,
+}
+
+///
+/// 把xxl-job的执行器、任务转为同步清单
+/// 任务key为 `xxl-{xxl任务id}`,重复导入时按key更新,不会重复创建
+pub fn convert_to_manifest(
+    data: &XxlDumpData,
+    namespace: Option<Arc<String>>,
+) -> (JobSyncManifest, XxlImportReport) {
+    let mut report = XxlImportReport {
+        log_count: data.log_count,
+        ..Default::default()
+    };
+    let mut manifest = JobSyncManifest {
+        namespace,
+        ..Default::default()
+    };
+    let mut group_map = HashMap::new();
+    for group in &data.groups {
+        let id = get_value(group, "id");
+        let app_name = get_value(group, "app_name");
+        if app_name.is_empty() {
+            report
+                .warnings
+                .push(format!("group {} app_name is empty,skip", id));
+            continue;
+        }
+        let register_type = if get_value(group, "address_type") == "1" {
+            RegisterType::Manual
+        } else {
+            RegisterType::Auto
+        };
+        let instance_addrs = if register_type.is_auto() {
+            None
+        } else {
+            Some(
+                get_value(group, "address_list")
+                    .split(',')
+                    .map(|v| v.trim())
+                    .filter(|v| !v.is_empty())
+                    .map(|v| Arc::new(v.to_owned()))
+                    .collect(),
+            )
+        };
+        let app_name = Arc::new(app_name.to_owned());
+        group_map.insert(id.to_owned(), app_name.clone());
+        manifest.apps.push(JobSyncAppItem {
+            namespace: None,
+            app_name,
+            label: Some(Arc::new(get_value(group, "title").to_owned())),
+            register_type: Some(register_type.to_str().to_owned()),
+            instance_addrs,
+        });
+    }
+    report.app_count = manifest.apps.len();
+    for job in &data.jobs {
+        let mut warnings = vec![];
+        match convert_job(job, &group_map, &mut warnings) {
+            Ok(param) => {
+                manifest.jobs.push(param);
+                report.job_count += 1;
+            }
+            Err(err) => {
+                warnings.push(format!("skip,{}", err));
+                report.skip_job_count += 1;
+            }
+        }
+        let id = get_value(job, "id");
+        report
+            .warnings
+            .extend(warnings.into_iter().map(|v| format!("job {}: {}", id, v)));
+    }
+    if report.log_count > 0 {
+        report.warnings.push(format!(
+            "xxl_job_log {} rows are not imported",
+            report.log_count
+        ));
+    }
+    (manifest, report)
+}
+
+/// 导入任务的key
+pub fn xxl_job_key(xxl_job_id: &str) -> String {
+    format!("{}{}", XXL_JOB_KEY_PREFIX, xxl_job_id)
+}
+
+/// 从导入任务的key中取出xxl-job任务id
+pub fn parse_xxl_job_key(key: &str) -> Option<u64> {
+    key.strip_prefix(XXL_JOB_KEY_PREFIX)
+        .and_then(|v| v.parse().ok())
+}
+
+fn convert_job(
+    job: &XxlRow,
+    group_map: &HashMap<String, Arc<String>>,
+    warnings: &mut Vec<String>,
+) -> anyhow::Result<JobParam> {
+    let id = get_value(job, "id");
+    if id.is_empty() {
+        return Err(anyhow::anyhow!("id is empty"));
+    }
+    let app_name = group_map
+        .get(get_value(job, "job_group"))
+        .cloned()
+        .ok_or_else(|| anyhow::anyhow!("job_group {} not found", get_value(job, "job_group")))?;
+    let mut param = convert_job_param(job, warnings)?;
+    param.app_name = Some(app_name);
+    param.key = Some(Arc::new(xxl_job_key(id)));
+    // 校验时补上命名空间,实际命名空间由清单决定
+    let mut check_param = param.clone();
+    check_param.namespace = Some(DEFAULT_XXL_NAMESPACE.clone());
+    check_param.check_valid()?;
+    Ok(param)
+}
+
+///
+/// 把xxl-job任务配置(xxl_job_info的列)转为任务参数,不包含应用、key
+/// 不能完全映射的配置记录到warnings
+pub fn convert_job_param(job: &XxlRow, warnings: &mut Vec<String>) -> anyhow::Result<JobParam> {
+    let glue_type = get_value(job, "glue_type");
+    let run_mode = match JobRunMode::from_str(glue_type) {
+        Some(JobRunMode::Http) | None => {
+            return Err(anyhow::anyhow!("glue_type {} is not supported", glue_type))
+        }
+        Some(v) => v,
+    };
+    let mut param = JobParam {
+        description: Some(Arc::new(get_value(job, "job_desc").to_owned())),
+        enable: Some(get_value(job, "trigger_status") == "1"),
+        handle_name: Some(Arc::new(get_value(job, "executor_handler").to_owned())),
+        trigger_param: Some(Arc::new(get_value(job, "executor_param").to_owned())),
+        timeout_second: Some(parse_number(job, "executor_timeout")?),
+        try_times: Some(parse_number(job, "executor_fail_retry_count")?),
+        ..Default::default()
+    };
+    if run_mode.is_glue() {
+        param.glue_source = Some(Arc::new(get_value(job, "glue_source").to_owned()));
+    }
+    param.run_mode = Some(run_mode);
+    // 2.3.0之前的版本只有job_cron
+    let schedule_type = match job.get("schedule_type") {
+        Some(v) => v.as_str(),
+        None => "CRON",
+    };
+    let schedule_conf = job
+        .get("schedule_conf")
+        .or_else(|| job.get("job_cron"))
+        .map(|v| v.trim())
+        .unwrap_or_default();
+    match schedule_type {
+        "CRON" => {
+            param.schedule_type = Some(ScheduleType::Cron);
+            param.cron_value = Some(Arc::new(schedule_conf.to_owned()));
+        }
+        "FIX_RATE" | "FIX_DELAY" => {
+            let second: u32 = schedule_conf
+                .parse()
+                .map_err(|_| anyhow::anyhow!("schedule_conf {} is not a number", schedule_conf))?;
+            if schedule_type == "FIX_DELAY" {
+                //上次执行结束后延迟触发
+                param.schedule_type = Some(ScheduleType::Delay);
+                param.delay_second = Some(second);
+            } else {
+                param.schedule_type = Some(ScheduleType::Interval);
+                param.interval_second = Some(second);
+            }
+        }
+        "NONE" => param.schedule_type = Some(ScheduleType::None),
+        _ => {
+            return Err(anyhow::anyhow!(
+                "schedule_type {} is not supported",
+                schedule_type
+            ))
+        }
+    }
+    param.past_due_strategy = Some(match get_value(job, "misfire_strategy") {
+        "DO_NOTHING" => PastDueStrategy::Ignore,
+        "FIRE_ONCE_NOW" => PastDueStrategy::Execute,
+        _ => PastDueStrategy::Default,
+    });
+    let route = get_value(job, "executor_route_strategy");
+    param.router_strategy = Some(match route {
+        "ROUND" => RouterStrategy::RoundRobin,
+        _ => RouterStrategy::from_str(route).unwrap_or_else(|| {
+            warnings.push(format!(
+                "executor_route_strategy {} is not supported,use ROUND_ROBIN",
+                route
+            ));
+            RouterStrategy::RoundRobin
+        }),
+    });
+    let block = get_value(job, "executor_block_strategy");
+    param.blocking_strategy = Some(match ExecutorBlockStrategy::from_str(block) {
+        ExecutorBlockStrategy::Other => {
+            warnings.push(format!(
+                "executor_block_strategy {} is not supported,use SERIAL_EXECUTION",
+                block
+            ));
+            ExecutorBlockStrategy::SerialExecution
+        }
+        v => v,
+    });
+    let child_job_id = get_value(job, "child_jobid");
+    if !child_job_id.is_empty() {
+        warnings.push(format!("child_jobid {} is not supported", child_job_id));
+    }
+    let alarm_email = get_value(job, "alarm_email");
+    if !alarm_email.is_empty() {
+        warnings.push(format!("alarm_email {} is not supported", alarm_email));
+    }
+    Ok(param)
+}
+
+fn get_value<'a>(row: &'a XxlRow, name: &str) -> &'a str {
+    row.get(name).map(|v| v.as_str()).unwrap_or_default()
+}
+
+fn parse_number(row: &XxlRow, name: &str) -> anyhow::Result<u32> {
+    let value = get_value(row, name).trim();
+    if value.is_empty() {
+        return Ok(0);
+    }
+    value
+        .parse()
+        .map_err(|_| anyhow::anyhow!("{} {} is not a number", name, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn import_xxl_sql_dump() {
+        let sql = r#"
+-- Dump of xxl_job
+CREATE TABLE `xxl_job_group` (`id` int(11) NOT NULL AUTO_INCREMENT);
+INSERT INTO `xxl_job_group` VALUES (1,'xxl-job-executor-sample','示例执行器',0,NULL,'2024-01-01 00:00:00'),(2,'manual-app','手动',1,'http://10.0.0.1:9999/,http://10.0.0.2:9999/','2024-01-01 00:00:00');
+INSERT INTO `xxl_job_info` (`id`,`job_group`,`job_desc`,`schedule_type`,`schedule_conf`,`misfire_strategy`,`executor_route_strategy`,`executor_handler`,`executor_param`,`executor_block_strategy`,`executor_timeout`,`executor_fail_retry_count`,`glue_type`,`glue_source`,`child_jobid`,`trigger_status`) VALUES
+(1,1,'demo, it''s \'ok\'','CRON','0 0 0 * * ? *','DO_NOTHING','FIRST','demoJobHandler','a=1','SERIAL_EXECUTION',0,2,'BEAN','','',1),
+(2,2,'shell','FIX_RATE','30','FIRE_ONCE_NOW','FAILOVER','','','COVER_EARLY',10,0,'GLUE_SHELL','#!/bin/bash\necho \"INSERT INTO x\"','1',0),
+(3,9,'no group','NONE','','DO_NOTHING','FIRST','','','SERIAL_EXECUTION',0,0,'BEAN','','',0),
+(4,1,'delay','FIX_DELAY','60','DO_NOTHING','FIRST','delayJobHandler','','SERIAL_EXECUTION',0,0,'BEAN','','',1);
+INSERT INTO `xxl_job_log` VALUES (1,1,1),(2,1,1);
+"#;
+        let data = XxlDumpData::parse_sql(sql).unwrap();
+        assert_eq!(data.groups.len(), 2);
+        assert_eq!(data.jobs.len(), 4);
+        assert_eq!(data.log_count, 2);
+        let (manifest, report) = convert_to_manifest(&data, None);
+        assert_eq!(manifest.apps.len(), 2);
+        assert_eq!(manifest.apps[1].instance_addrs.as_ref().unwrap().len(), 2);
+        assert_eq!(report.job_count, 3);
+        assert_eq!(report.skip_job_count, 1);
+        let job = &manifest.jobs[0];
+        assert_eq!(job.key.as_ref().unwrap().as_str(), "xxl-1");
+        assert_eq!(
+            job.description.as_ref().unwrap().as_str(),
+            "demo, it's 'ok'"
+        );
+        assert_eq!(job.past_due_strategy, Some(PastDueStrategy::Ignore));
+        assert_eq!(job.enable, Some(true));
+        let job = &manifest.jobs[1];
+        assert_eq!(job.interval_second, Some(30));
+        assert_eq!(job.router_strategy, Some(RouterStrategy::RoundRobin));
+        assert_eq!(
+            job.glue_source.as_ref().unwrap().as_str(),
+            "#!/bin/bash\necho \"INSERT INTO x\""
+        );
+        let job = &manifest.jobs[2];
+        assert_eq!(job.schedule_type, Some(ScheduleType::Delay));
+        assert_eq!(job.delay_second, Some(60));
+        // FAILOVER、child_jobid、缺少执行器、日志
+        assert_eq!(report.warnings.len(), 4);
+
+        let csv_data = XxlDumpData::parse_csv(
+            "id,app_name,title,address_type,address_list\n1,demo,Demo,0,NULL\n",
+            "id,job_group,job_desc,job_cron,executor_route_strategy,executor_block_strategy,glue_type,executor_handler,trigger_status\n5,1,\"a,b\",0 0 1 * * ?,ROUND,DISCARD_LATER,BEAN,demo,1\n",
+            None,
+        )
+        .unwrap();
+        let (manifest, report) = convert_to_manifest(&csv_data, None);
+        assert!(report.warnings.is_empty());
+        assert_eq!(
+            manifest.jobs[0].cron_value.as_ref().unwrap().as_str(),
+            "0 0 1 * * ?"
+        );
+    }
+}
@@ -1,8 +1,11 @@
+pub mod agent;
 pub mod app;
+pub mod audit;
 pub mod cache;
 pub mod cli;
 pub mod common;
 pub mod console;
+pub mod executor;
 pub mod grpc;
 pub mod job;
 pub mod metrics;
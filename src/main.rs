@@ -3,20 +3,31 @@ use actix_web::{middleware, App, HttpServer};
 use clap::Parser;
 use env_logger::TimestampPrecision;
 use env_logger_timezone_fmt::{TimeZoneFormat, TimeZoneFormatEnv};
+use ratchjob::agent;
+use ratchjob::agent::model::AgentConfig;
 use ratchjob::cli;
-use ratchjob::cli::Commands;
+use ratchjob::cli::{Commands, InspectCommands};
 use ratchjob::common::app_config::AppConfig;
 use ratchjob::common::get_app_version;
 use ratchjob::common::share_data::ShareData;
 use ratchjob::console::middle::login_middle::CheckLogin;
 use ratchjob::grpc::handler::InvokerHandler;
 use ratchjob::grpc::ratch_server_proto::request_server::RequestServer;
-use ratchjob::grpc::server::RequestServerImpl;
+use ratchjob::grpc::ratch_server_proto::stream_server::StreamServer;
+use ratchjob::grpc::server::{RequestServerImpl, StreamServerImpl};
+use ratchjob::job::sync;
+use ratchjob::job::xxl_import;
+use ratchjob::job::xxl_import::XxlDumpData;
 use ratchjob::openapi::middle::CheckMiddle;
+use ratchjob::raft::store::backup;
+use ratchjob::raft::store::backup::RestoreParam;
+use ratchjob::raft::store::inspect;
+use ratchjob::raft::store::inspect::LogQueryParam;
 use ratchjob::starter::{build_share_data, config_factory};
 use ratchjob::web_config::app_config;
 use ratchjob::web_config::console_config;
 use std::error::Error;
+use std::io::Write;
 use std::sync::Arc;
 use tonic::transport::Server;
 
@@ -35,7 +46,7 @@ async fn main() -> Result<(), Box<dyn Error>> {
         .format(move |buf, record| TimeZoneFormat::new(buf, &timezone_fmt).write(record))
         .init();
     if let Some(cmd) = cli_opt.command {
-        return run_subcommand(cmd).await;
+        return run_subcommand(cmd, sys_config).await;
     }
     // 这里不使用log:info避免日志等级高于info时不打印
     println!("version:{}, RUST_LOG:{}", get_app_version(), &rust_log);
@@ -55,8 +66,10 @@ async fn main() -> Result<(), Box<dyn Error>> {
     tokio::spawn(async move {
         let addr = grpc_addr.parse().unwrap();
         let request_server = RequestServerImpl::new(grpc_app_data.clone(), invoker);
+        let stream_server = StreamServerImpl::new(grpc_app_data.clone());
         Server::builder()
             .add_service(RequestServer::new(request_server))
+            .add_service(StreamServer::new(stream_server))
             .serve(addr)
             .await
             .unwrap();
@@ -92,11 +105,168 @@ async fn main() -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-async fn run_subcommand(commands: Commands) -> Result<(), Box<dyn Error>> {
+async fn run_subcommand(
+    commands: Commands,
+    sys_config: Arc<AppConfig>,
+) -> Result<(), Box<dyn Error>> {
     match commands {
         Commands::About => {
             log::info!("version:{}", get_app_version());
         }
+        Commands::Backup { output } => {
+            let info = backup::backup_offline(&sys_config.local_db_dir, &output).await?;
+            log::info!("backup finished,{:?}", &info);
+        }
+        Commands::Restore {
+            input,
+            end_index,
+            force,
+        } => {
+            let param = RestoreParam {
+                backup_path: input,
+                data_dir: sys_config.local_db_dir.clone(),
+                node_id: sys_config.raft_node_id,
+                node_addr: Arc::new(sys_config.raft_node_addr.clone()),
+                end_index,
+                force,
+            };
+            let info = backup::restore_backup(param).await?;
+            log::info!("restore finished,{:?}", &info);
+        }
+        Commands::Inspect { command } => {
+            run_inspect(command).await?;
+        }
+        Commands::Agent {
+            app_name,
+            admin_addresses,
+            access_token,
+            port,
+            ip,
+            address,
+            data_dir,
+            work_dir,
+            envs,
+            log_retention_days,
+            shell,
+            python,
+        } => {
+            let address = address.unwrap_or_else(|| {
+                format!(
+                    "http://{}:{}/",
+                    ip.unwrap_or_else(agent::get_local_ip),
+                    port
+                )
+            });
+            let config = AgentConfig {
+                admin_addresses: admin_addresses
+                    .split(',')
+                    .map(|v| v.trim().to_owned())
+                    .filter(|v| !v.is_empty())
+                    .collect(),
+                app_name,
+                access_token,
+                port,
+                address,
+                data_dir: data_dir.into(),
+                work_dir: work_dir.map(|v| v.into()),
+                envs: AgentConfig::parse_envs(&envs)?,
+                log_retention_days,
+                shell,
+                python,
+            };
+            agent::run_agent(config).await?;
+        }
+        Commands::Sync {
+            file,
+            server,
+            dry_run,
+            prune,
+        } => {
+            let content = std::fs::read_to_string(&file)?;
+            let plan = sync::sync_remote(content, &server, dry_run, prune).await?;
+            plan.write_to(&mut std::io::stdout().lock())?;
+            if !plan.errors.is_empty() {
+                return Err(format!("sync failed,error count:{}", plan.errors.len()).into());
+            }
+        }
+        Commands::ImportXxl {
+            sql,
+            group_csv,
+            job_csv,
+            log_csv,
+            namespace,
+            server,
+            dry_run,
+            output,
+        } => {
+            let data = if let Some(sql) = sql {
+                XxlDumpData::parse_sql(&std::fs::read_to_string(sql)?)?
+            } else if let (Some(group_csv), Some(job_csv)) = (group_csv, job_csv) {
+                let log_content = log_csv.map(std::fs::read_to_string).transpose()?;
+                XxlDumpData::parse_csv(
+                    &std::fs::read_to_string(group_csv)?,
+                    &std::fs::read_to_string(job_csv)?,
+                    log_content.as_deref(),
+                )?
+            } else {
+                return Err("--sql or --group-csv and --job-csv is required".into());
+            };
+            let (manifest, report) =
+                xxl_import::convert_to_manifest(&data, namespace.map(Arc::new));
+            let mut out = std::io::stdout().lock();
+            for warning in report.warnings.iter() {
+                writeln!(out, "warning: {}", warning)?;
+            }
+            writeln!(
+                out,
+                "xxl-job data,app:{},job:{},skip job:{},log:{}",
+                report.app_count, report.job_count, report.skip_job_count, report.log_count
+            )?;
+            if let Some(output) = output {
+                std::fs::write(&output, serde_yaml::to_string(&manifest)?)?;
+                writeln!(out, "manifest is written to {}", &output)?;
+                return Ok(());
+            }
+            let content = serde_json::to_string(&manifest)?;
+            let plan = sync::sync_remote(content, &server, dry_run, false).await?;
+            plan.write_to(&mut out)?;
+            if !plan.errors.is_empty() {
+                return Err(format!("import failed,error count:{}", plan.errors.len()).into());
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_inspect(command: InspectCommands) -> anyhow::Result<()> {
+    let mut out = std::io::stdout().lock();
+    match command {
+        InspectCommands::Index { data_dir } => {
+            inspect::dump_index(&data_dir, &mut out).await?;
+        }
+        InspectCommands::Snapshot { path, limit } => {
+            inspect::dump_snapshot(&path, limit, &mut out).await?;
+        }
+        InspectCommands::Logs {
+            data_dir,
+            start,
+            limit,
+            tail,
+        } => {
+            let param = LogQueryParam { start, limit, tail };
+            inspect::dump_logs(&data_dir, &param, &mut out).await?;
+        }
+        InspectCommands::Verify { data_dir } => {
+            let report = inspect::verify_data_dir(&data_dir).await?;
+            serde_json::to_writer_pretty(&mut out, &report)?;
+            writeln!(out)?;
+            if !report.is_ok() {
+                return Err(anyhow::anyhow!(
+                    "verify failed,error count:{}",
+                    report.errors.len()
+                ));
+            }
+        }
     }
     Ok(())
 }
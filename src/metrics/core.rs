@@ -12,9 +12,12 @@ use crate::metrics::timeline::model::{MetricsSnapshot, TimelineGroupType};
 use actix::prelude::*;
 use bean_factory::{bean, BeanFactory, FactoryData, Inject};
 use bytes::BytesMut;
+use std::path::Path;
 use std::sync::Arc;
 use std::time::Duration;
-use sysinfo::{Pid, System};
+use sysinfo::{Disks, Pid, System};
+
+const DISK_METRICS_COLLECT_INTERVAL: u64 = 5 * 60 * 1000;
 
 #[bean(inject)]
 #[derive(Debug)]
@@ -26,6 +29,8 @@ pub struct MetricsManager {
     summary_key_config: Vec<(MetricsKey, MetricsKey)>,
     metrics_timeline_manager: MetricsTimelineManager,
     system: System,
+    disks: Disks,
+    last_disk_collect_time: u64,
     current_process_id: u32,
     start_time_millis: u64,
     total_memory: f32,
@@ -53,6 +58,8 @@ impl MetricsManager {
             summary_key_config: Default::default(),
             metrics_timeline_manager: MetricsTimelineManager::new(),
             system,
+            disks: Disks::new_with_refreshed_list(),
+            last_disk_collect_time: 0,
             current_process_id,
             start_time_millis,
             total_memory,
@@ -70,6 +77,10 @@ impl MetricsManager {
         counter_manager.absolute(MetricsKey::TaskRedoSize, 0);
         counter_manager.absolute(MetricsKey::TaskSuccessSize, 0);
         counter_manager.absolute(MetricsKey::TaskFailSize, 0);
+        counter_manager.absolute(MetricsKey::TaskTimeoutSize, 0);
+        counter_manager.absolute(MetricsKey::TaskSkipSize, 0);
+        counter_manager.absolute(MetricsKey::TaskCancelSize, 0);
+        counter_manager.absolute(MetricsKey::TaskKillSize, 0);
         counter_manager.absolute(MetricsKey::TaskCallApiSize, 0);
         counter_manager.absolute(MetricsKey::TaskFinishTotalCount, 0);
         counter_manager.absolute(MetricsKey::HttpRequestTotalCount, 0);
@@ -191,9 +202,58 @@ impl MetricsManager {
             self.gauge_manager
                 .set(MetricsKey::AppMemoryUsage, rss_usage);
         }
+        self.load_disk_metrics();
         self.last_collect_time = now_millis();
     }
 
+    fn get_dir_size(path: &Path) -> u64 {
+        let mut size = 0;
+        if let Ok(dir) = std::fs::read_dir(path) {
+            for entry in dir.flatten() {
+                match entry.metadata() {
+                    Ok(meta) if meta.is_dir() => size += Self::get_dir_size(&entry.path()),
+                    Ok(meta) => size += meta.len(),
+                    Err(_) => {}
+                }
+            }
+        }
+        size
+    }
+
+    /// 数据目录大小及所在磁盘的使用率，遍历目录开销较大，按较长间隔采集
+    fn load_disk_metrics(&mut self) {
+        let now = now_millis();
+        if now < self.last_disk_collect_time + DISK_METRICS_COLLECT_INTERVAL {
+            return;
+        }
+        self.last_disk_collect_time = now;
+        let data_dir = Path::new(&self.app_sys_config.local_db_dir);
+        let dir_size = Self::get_dir_size(data_dir) as f32 / (1024.0 * 1024.0);
+        self.gauge_manager.set(MetricsKey::DataDirSize, dir_size);
+        let data_dir = data_dir
+            .canonicalize()
+            .unwrap_or_else(|_| data_dir.to_path_buf());
+        self.disks.refresh();
+        let disk = self
+            .disks
+            .list()
+            .iter()
+            .filter(|e| data_dir.starts_with(e.mount_point()))
+            .max_by_key(|e| e.mount_point().as_os_str().len());
+        if let Some(disk) = disk {
+            let total = disk.total_space() as f32;
+            let available = disk.available_space() as f32;
+            self.gauge_manager
+                .set(MetricsKey::DataDiskAvailable, available / (1024.0 * 1024.0));
+            if total > 0.0 {
+                self.gauge_manager.set(
+                    MetricsKey::DataDiskUsage,
+                    (total - available) / total * 100.0,
+                );
+            }
+        }
+    }
+
     fn print_sys_metrics(&self) {
         let cpu_usage = self
             .gauge_manager
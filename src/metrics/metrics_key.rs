@@ -15,6 +15,9 @@ pub enum MetricsKey {
     AppVmsMemory,
     AppMemoryUsage,
     AppCpuUsage,
+    DataDirSize,
+    DataDiskAvailable,
+    DataDiskUsage,
     // job app instance
     JobAppSize,
     JobAppInstanceSize,
@@ -27,6 +30,10 @@ pub enum MetricsKey {
     TaskRedoSize,
     TaskSuccessSize,
     TaskFailSize,
+    TaskTimeoutSize,
+    TaskSkipSize,
+    TaskCancelSize,
+    TaskKillSize,
     TaskPendingSize,
     TaskRunningSize,
     TaskCallApiSize,
@@ -48,6 +55,9 @@ lazy_static! {
         MetricsKey::AppVmsMemory,
         MetricsKey::AppMemoryUsage,
         MetricsKey::AppCpuUsage,
+        MetricsKey::DataDirSize,
+        MetricsKey::DataDiskAvailable,
+        MetricsKey::DataDiskUsage,
         // job app instance
         MetricsKey::JobAppSize,
         MetricsKey::JobAppInstanceSize,
@@ -60,6 +70,10 @@ lazy_static! {
         MetricsKey::TaskRedoSize,
         MetricsKey::TaskSuccessSize,
         MetricsKey::TaskFailSize,
+        MetricsKey::TaskTimeoutSize,
+        MetricsKey::TaskSkipSize,
+        MetricsKey::TaskCancelSize,
+        MetricsKey::TaskKillSize,
         MetricsKey::TaskPendingSize,
         MetricsKey::TaskRunningSize,
         MetricsKey::TaskCallApiSize,
@@ -86,6 +100,9 @@ impl MetricsKey {
             MetricsKey::AppVmsMemory => "app_vms_memory",
             MetricsKey::AppMemoryUsage => "app_memory_usage",
             MetricsKey::AppCpuUsage => "app_cpu_usage",
+            MetricsKey::DataDirSize => "data_dir_size",
+            MetricsKey::DataDiskAvailable => "data_disk_available",
+            MetricsKey::DataDiskUsage => "data_disk_usage",
             MetricsKey::JobAppSize => "job_app_size",
             MetricsKey::JobAppInstanceSize => "job_app_instance_size",
             MetricsKey::JobSize => "job_size",
@@ -95,6 +112,10 @@ impl MetricsKey {
             MetricsKey::TaskRedoSize => "task_redo_size",
             MetricsKey::TaskSuccessSize => "task_success_size",
             MetricsKey::TaskFailSize => "task_fail_size",
+            MetricsKey::TaskTimeoutSize => "task_timeout_size",
+            MetricsKey::TaskSkipSize => "task_skip_size",
+            MetricsKey::TaskCancelSize => "task_cancel_size",
+            MetricsKey::TaskKillSize => "task_kill_size",
             MetricsKey::TaskRunningSize => "task_running_size",
             MetricsKey::TaskPendingSize => "task_pending_size",
             MetricsKey::TaskCallApiSize => "task_call_api_size",
@@ -131,6 +152,9 @@ impl MetricsKey {
             MetricsKey::AppVmsMemory => "App vms memory,unit is M",
             MetricsKey::AppMemoryUsage => "App memory usage",
             MetricsKey::AppCpuUsage => "App cpu usage",
+            MetricsKey::DataDirSize => "Data dir size,unit is M",
+            MetricsKey::DataDiskAvailable => "Data dir disk available space,unit is M",
+            MetricsKey::DataDiskUsage => "Data dir disk usage",
             MetricsKey::JobAppSize => "Job app size",
             MetricsKey::JobAppInstanceSize => "Job app instance size",
             MetricsKey::JobSize => "Job size",
@@ -140,6 +164,10 @@ impl MetricsKey {
             MetricsKey::TaskRedoSize => "Task redo size",
             MetricsKey::TaskSuccessSize => "Task success size",
             MetricsKey::TaskFailSize => "Task fail size",
+            MetricsKey::TaskTimeoutSize => "Task timeout size",
+            MetricsKey::TaskSkipSize => "Task skip size",
+            MetricsKey::TaskCancelSize => "Task cancel size",
+            MetricsKey::TaskKillSize => "Task kill size",
             MetricsKey::TaskRunningSize => "Task running size",
             MetricsKey::TaskPendingSize => "Task pending size",
             MetricsKey::TaskCallApiSize => "Task call api size",
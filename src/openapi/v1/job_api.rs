@@ -1,25 +1,37 @@
+use crate::app::model::{AppKey, AppManagerRaftReq, AppManagerReq, AppManagerResult};
+use crate::audit::model::audit::{
+    AUDIT_ACTION_CANCEL, AUDIT_ACTION_CREATE, AUDIT_ACTION_REMOVE, AUDIT_ACTION_UPDATE,
+    AUDIT_TARGET_APP, AUDIT_TARGET_JOB,
+};
+use crate::audit::recorder::AuditRecorder;
 use crate::common::constant::SEQ_JOB_ID;
 use crate::common::datetime_utils::now_millis;
+use crate::common::model::privilege::PrivilegeGroup;
 use crate::common::model::{ApiResult, PageResult};
 use crate::common::share_data::ShareData;
-use crate::console::model::job::JobQueryListRequest;
+use crate::console::model::job::{CancelTaskParam, JobQueryListRequest};
 use crate::console::v1::ERROR_CODE_SYSTEM_ERROR;
+use crate::job::batch::{apply_job_batch, JobBatchRequest};
 use crate::job::model::actor_model::{
     JobManagerRaftReq, JobManagerRaftResult, JobManagerReq, JobManagerResult,
 };
-use crate::job::model::job::{JobKey, JobParam};
+use crate::job::model::job::{JobInfo, JobKey, JobParam};
+use crate::job::sync::{JobSyncAction, JobSyncManifest, JobSyncPlan};
 use crate::openapi::v1::model::job_model::{
-    JobKeyQueryRequest, JobTaskHistoryRequest, JobTaskListRequest,
+    JobKeyQueryRequest, JobSyncRequest, JobTaskHistoryRequest, JobTaskListRequest,
 };
 use crate::openapi::xxljob::model::XxlApiResult;
 use crate::raft::store::{ClientRequest, ClientResponse};
+use crate::schedule::maintenance;
 use crate::schedule::model::actor_model::{ScheduleManagerReq, ScheduleManagerResult};
 use crate::sequence::{SequenceRequest, SequenceResult};
 use actix_web::web::Data;
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 async fn do_create_job(
+    req: &HttpRequest,
     share_data: Data<Arc<ShareData>>,
     mut param: JobParam,
 ) -> anyhow::Result<HttpResponse> {
@@ -63,6 +75,7 @@ async fn do_create_job(
         }
 
         param.update_time = Some(now_millis());
+        param.update_user = Some(AuditRecorder::openapi_actor(req));
         if let ClientResponse::JobResp {
             resp: JobManagerRaftResult::JobInfo(job),
         } = share_data
@@ -72,6 +85,11 @@ async fn do_create_job(
             })
             .await?
         {
+            let mut record =
+                AuditRecorder::openapi(req, AUDIT_ACTION_CREATE, AUDIT_TARGET_JOB, id.to_string());
+            record.namespace = job.namespace.clone();
+            record.set_after(Some(&job));
+            AuditRecorder::submit(&share_data, record).await;
             Ok(HttpResponse::Ok().json(XxlApiResult::success(Some(job))))
         } else {
             Err(anyhow::anyhow!("create job result type error!"))
@@ -81,10 +99,11 @@ async fn do_create_job(
     }
 }
 pub(crate) async fn create_job(
+    req: HttpRequest,
     share_data: Data<Arc<ShareData>>,
     web::Json(param): web::Json<JobParam>,
 ) -> impl Responder {
-    match do_create_job(share_data, param).await {
+    match do_create_job(&req, share_data, param).await {
         Ok(v) => v,
         Err(e) => {
             let error_msg = format!("create_job error,{}", e);
@@ -98,6 +117,7 @@ pub(crate) async fn create_job(
 }
 
 async fn do_update_job(
+    req: &HttpRequest,
     share_data: Data<Arc<ShareData>>,
     mut param: JobParam,
 ) -> anyhow::Result<HttpResponse> {
@@ -106,13 +126,12 @@ async fn do_update_job(
         return Err(anyhow::anyhow!("job id is null"));
     }
 
-    let original_job_info = if param.namespace.is_none() || param.app_name.is_none() {
-        match share_data.job_manager.send(JobManagerReq::GetJob(id)).await {
-            Ok(Ok(JobManagerResult::JobInfo(Some(info)))) => Some(info),
-            _ => return Err(anyhow::anyhow!("job not found, id={}", id)),
+    let original_job_info = match share_data.job_manager.send(JobManagerReq::GetJob(id)).await {
+        Ok(Ok(JobManagerResult::JobInfo(Some(info)))) => Some(info),
+        _ if param.namespace.is_none() || param.app_name.is_none() => {
+            return Err(anyhow::anyhow!("job not found, id={}", id))
         }
-    } else {
-        None
+        _ => None,
     };
 
     if let Some(ref key) = param.key {
@@ -148,19 +167,31 @@ async fn do_update_job(
     }
 
     param.update_time = Some(now_millis());
+    param.update_user = Some(AuditRecorder::openapi_actor(req));
     share_data
         .raft_request_route
         .request(ClientRequest::JobReq {
             req: JobManagerRaftReq::UpdateJob(param),
         })
         .await?;
+    let mut record =
+        AuditRecorder::openapi(req, AUDIT_ACTION_UPDATE, AUDIT_TARGET_JOB, id.to_string());
+    record.set_before(original_job_info.as_ref());
+    if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) =
+        share_data.job_manager.send(JobManagerReq::GetJob(id)).await
+    {
+        record.namespace = info.namespace.clone();
+        record.set_after(Some(&info));
+    }
+    AuditRecorder::submit(&share_data, record).await;
     Ok(HttpResponse::Ok().json(XxlApiResult::success(Some(()))))
 }
 pub(crate) async fn update_job(
+    req: HttpRequest,
     share_data: Data<Arc<ShareData>>,
     web::Json(param): web::Json<JobParam>,
 ) -> impl Responder {
-    match do_update_job(share_data, param).await {
+    match do_update_job(&req, share_data, param).await {
         Ok(v) => v,
         Err(e) => {
             let error_msg = format!("update_job error,{}", e);
@@ -174,6 +205,7 @@ pub(crate) async fn update_job(
 }
 
 async fn do_remove_job(
+    req: &HttpRequest,
     share_data: Data<Arc<ShareData>>,
     param: JobParam,
 ) -> anyhow::Result<HttpResponse> {
@@ -182,20 +214,30 @@ async fn do_remove_job(
     } else {
         return Err(anyhow::anyhow!("job id is null"));
     };
+    let mut record =
+        AuditRecorder::openapi(req, AUDIT_ACTION_REMOVE, AUDIT_TARGET_JOB, id.to_string());
+    if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) =
+        share_data.job_manager.send(JobManagerReq::GetJob(id)).await
+    {
+        record.namespace = info.namespace.clone();
+        record.set_before(Some(&info));
+    }
     share_data
         .raft_request_route
         .request(ClientRequest::JobReq {
             req: JobManagerRaftReq::Remove(id),
         })
         .await?;
+    AuditRecorder::submit(&share_data, record).await;
     Ok(HttpResponse::Ok().json(XxlApiResult::success(Some(()))))
 }
 
 pub(crate) async fn remove_job(
+    req: HttpRequest,
     share_data: Data<Arc<ShareData>>,
     web::Json(param): web::Json<JobParam>,
 ) -> impl Responder {
-    match do_remove_job(share_data, param).await {
+    match do_remove_job(&req, share_data, param).await {
         Ok(v) => v,
         Err(e) => {
             let error_msg = format!("remove_job error,{}", e);
@@ -231,7 +273,15 @@ pub(crate) async fn query_job_list(
     share_data: Data<Arc<ShareData>>,
     web::Query(request): web::Query<JobQueryListRequest>,
 ) -> impl Responder {
-    let param = request.to_param();
+    let param = match request.to_param() {
+        Ok(v) => v,
+        Err(e) => {
+            return HttpResponse::Ok().json(ApiResult::<()>::error(
+                ERROR_CODE_SYSTEM_ERROR.to_string(),
+                Some(e.to_string()),
+            ))
+        }
+    };
     if let Ok(Ok(JobManagerResult::JobPageInfo(total_count, list))) = share_data
         .job_manager
         .send(JobManagerReq::QueryJob(param))
@@ -251,7 +301,15 @@ pub(crate) async fn export_jobs(
     share_data: Data<Arc<ShareData>>,
     web::Query(request): web::Query<JobQueryListRequest>,
 ) -> impl Responder {
-    let mut param = request.to_param();
+    let mut param = match request.to_param() {
+        Ok(v) => v,
+        Err(e) => {
+            return HttpResponse::Ok().json(ApiResult::<()>::error(
+                ERROR_CODE_SYSTEM_ERROR.to_string(),
+                Some(e.to_string()),
+            ))
+        }
+    };
     param.offset = 0;
     param.limit = 0xffff_ffff;
     if let Ok(Ok(JobManagerResult::JobPageInfo(_total_count, list))) = share_data
@@ -272,6 +330,7 @@ pub(crate) async fn export_jobs(
 
 /// 导入任务列表（JSON 格式）
 pub(crate) async fn import_jobs(
+    req: HttpRequest,
     share_data: Data<Arc<ShareData>>,
     web::Json(params): web::Json<Vec<JobParam>>,
 ) -> impl Responder {
@@ -289,6 +348,7 @@ pub(crate) async fn import_jobs(
             Ok(Ok(SequenceResult::NextId(id))) => {
                 param.id = Some(id);
                 param.update_time = Some(now_millis());
+                param.update_user = Some(AuditRecorder::openapi_actor(&req));
                 match share_data
                     .raft_request_route
                     .request(ClientRequest::JobReq {
@@ -296,6 +356,21 @@ pub(crate) async fn import_jobs(
                     })
                     .await
                 {
+                    Ok(ClientResponse::JobResp {
+                        resp: JobManagerRaftResult::JobInfo(job),
+                    }) => {
+                        success_count += 1;
+                        let mut record = AuditRecorder::openapi(
+                            &req,
+                            AUDIT_ACTION_CREATE,
+                            AUDIT_TARGET_JOB,
+                            id.to_string(),
+                        );
+                        record.namespace = job.namespace.clone();
+                        record.detail = Arc::new("import".to_owned());
+                        record.set_after(Some(&job));
+                        AuditRecorder::submit(&share_data, record).await;
+                    }
                     Ok(_) => success_count += 1,
                     Err(e) => {
                         fail_count += 1;
@@ -324,6 +399,256 @@ pub(crate) async fn import_jobs(
     }
 }
 
+async fn build_sync_plan(
+    share_data: &ShareData,
+    manifest: &JobSyncManifest,
+    prune: bool,
+) -> anyhow::Result<JobSyncPlan> {
+    let app_keys: Vec<AppKey> = manifest.app_keys().into_iter().collect();
+    let mut existing_apps = HashMap::new();
+    for app_key in app_keys.iter() {
+        if let AppManagerResult::AppInfo(Some(info)) = share_data
+            .app_manager
+            .send(AppManagerReq::GetApp(app_key.clone()))
+            .await??
+        {
+            existing_apps.insert(app_key.clone(), info);
+        }
+    }
+    let existing_jobs = if let JobManagerResult::JobInfoList(list) = share_data
+        .job_manager
+        .send(JobManagerReq::QueryAppJobs(app_keys))
+        .await??
+    {
+        list
+    } else {
+        return Err(anyhow::anyhow!("query app jobs result type error!"));
+    };
+    JobSyncPlan::build(manifest, &existing_apps, &existing_jobs, prune)
+}
+
+/// 按计划依次执行变更,单个变更失败不影响其它变更
+async fn apply_sync_plan(req: &HttpRequest, share_data: &ShareData, plan: &mut JobSyncPlan) {
+    let actor = AuditRecorder::openapi_actor(req);
+    for item in plan.apps.iter() {
+        let param = match (item.action, item.param.as_ref()) {
+            (JobSyncAction::Create | JobSyncAction::Update, Some(param)) => param.clone(),
+            _ => continue,
+        };
+        let action = if item.action == JobSyncAction::Create {
+            AUDIT_ACTION_CREATE
+        } else {
+            AUDIT_ACTION_UPDATE
+        };
+        let mut record =
+            AuditRecorder::openapi(req, action, AUDIT_TARGET_APP, item.app_name.to_string());
+        record.namespace = item.namespace.clone();
+        record.detail = Arc::new("sync".to_owned());
+        record.set_after(Some(&param));
+        match share_data
+            .raft_request_route
+            .request(ClientRequest::AppReq {
+                req: AppManagerRaftReq::UpdateApp(param),
+            })
+            .await
+        {
+            Ok(_) => AuditRecorder::submit(share_data, record).await,
+            Err(err) => plan.errors.push(format!(
+                "sync app {}/{} error,{}",
+                &item.namespace, &item.app_name, err
+            )),
+        }
+    }
+    for item in plan.jobs.iter() {
+        if item.action == JobSyncAction::Unchanged {
+            continue;
+        }
+        let job_name = format!("{}/{}/{}", &item.namespace, &item.app_name, &item.key);
+        let r = match item.action {
+            JobSyncAction::Create => {
+                let job = item.job.clone().unwrap_or_default();
+                sync_create_job(req, share_data, &job, &actor).await
+            }
+            JobSyncAction::Update => {
+                let job = item.job.clone().unwrap_or_default();
+                sync_update_job(req, share_data, &job, &actor).await
+            }
+            JobSyncAction::Delete => {
+                sync_remove_job(req, share_data, item.id.unwrap_or_default()).await
+            }
+            JobSyncAction::Unchanged => Ok(()),
+        };
+        if let Err(err) = r {
+            plan.errors
+                .push(format!("sync job {} error,{}", &job_name, err));
+        }
+    }
+}
+
+async fn sync_create_job(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    job: &JobInfo,
+    actor: &Arc<String>,
+) -> anyhow::Result<()> {
+    let id = if let SequenceResult::NextId(id) = share_data
+        .sequence_manager
+        .send(SequenceRequest::GetNextId(SEQ_JOB_ID.clone()))
+        .await??
+    {
+        id
+    } else {
+        return Err(anyhow::anyhow!("get job id error!"));
+    };
+    let mut param = job.to_param();
+    param.id = Some(id);
+    param.update_time = Some(now_millis());
+    param.update_user = Some(actor.clone());
+    if let ClientResponse::JobResp {
+        resp: JobManagerRaftResult::JobInfo(job),
+    } = share_data
+        .raft_request_route
+        .request(ClientRequest::JobReq {
+            req: JobManagerRaftReq::AddJob(param),
+        })
+        .await?
+    {
+        let mut record =
+            AuditRecorder::openapi(req, AUDIT_ACTION_CREATE, AUDIT_TARGET_JOB, id.to_string());
+        record.namespace = job.namespace.clone();
+        record.detail = Arc::new("sync".to_owned());
+        record.set_after(Some(&job));
+        AuditRecorder::submit(share_data, record).await;
+    }
+    Ok(())
+}
+
+async fn sync_update_job(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    job: &JobInfo,
+    actor: &Arc<String>,
+) -> anyhow::Result<()> {
+    let mut record = AuditRecorder::openapi(
+        req,
+        AUDIT_ACTION_UPDATE,
+        AUDIT_TARGET_JOB,
+        job.id.to_string(),
+    );
+    record.namespace = job.namespace.clone();
+    record.detail = Arc::new("sync".to_owned());
+    if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) = share_data
+        .job_manager
+        .send(JobManagerReq::GetJob(job.id))
+        .await
+    {
+        record.set_before(Some(&info));
+    }
+    let mut param = job.to_param();
+    param.update_time = Some(now_millis());
+    param.update_user = Some(actor.clone());
+    share_data
+        .raft_request_route
+        .request(ClientRequest::JobReq {
+            req: JobManagerRaftReq::UpdateJob(param),
+        })
+        .await?;
+    if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) = share_data
+        .job_manager
+        .send(JobManagerReq::GetJob(job.id))
+        .await
+    {
+        record.set_after(Some(&info));
+    }
+    AuditRecorder::submit(share_data, record).await;
+    Ok(())
+}
+
+async fn sync_remove_job(req: &HttpRequest, share_data: &ShareData, id: u64) -> anyhow::Result<()> {
+    let mut record =
+        AuditRecorder::openapi(req, AUDIT_ACTION_REMOVE, AUDIT_TARGET_JOB, id.to_string());
+    record.detail = Arc::new("sync".to_owned());
+    if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) =
+        share_data.job_manager.send(JobManagerReq::GetJob(id)).await
+    {
+        record.namespace = info.namespace.clone();
+        record.set_before(Some(&info));
+    }
+    share_data
+        .raft_request_route
+        .request(ClientRequest::JobReq {
+            req: JobManagerRaftReq::Remove(id),
+        })
+        .await?;
+    AuditRecorder::submit(share_data, record).await;
+    Ok(())
+}
+
+/// 声明式同步任务:按清单计算变更计划(create/update/delete/unchanged),dryRun时只返回计划
+pub(crate) async fn sync_jobs(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Query(request): web::Query<JobSyncRequest>,
+    body: String,
+) -> impl Responder {
+    let manifest = match JobSyncManifest::parse(&body) {
+        Ok(v) => v,
+        Err(e) => {
+            return HttpResponse::Ok().json(ApiResult::<()>::error(
+                ERROR_CODE_SYSTEM_ERROR.to_string(),
+                Some(e.to_string()),
+            ))
+        }
+    };
+    let prune = request.prune.unwrap_or(false);
+    let mut plan = match build_sync_plan(&share_data, &manifest, prune).await {
+        Ok(v) => v,
+        Err(e) => {
+            let error_msg = format!("sync_jobs error,{}", e);
+            log::error!("{}", &error_msg);
+            return HttpResponse::Ok().json(ApiResult::<()>::error(
+                ERROR_CODE_SYSTEM_ERROR.to_string(),
+                Some(error_msg),
+            ));
+        }
+    };
+    plan.dry_run = request.dry_run.unwrap_or(false);
+    if !plan.dry_run {
+        apply_sync_plan(&req, &share_data, &mut plan).await;
+    }
+    HttpResponse::Ok().json(ApiResult::success(Some(plan)))
+}
+
+/// 按标签选择器批量启用、停用、触发、删除任务或修改路由策略
+pub(crate) async fn batch_jobs(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Json(request): web::Json<JobBatchRequest>,
+) -> impl Responder {
+    let actor = AuditRecorder::openapi_actor(&req);
+    let r = match request.to_query_param(PrivilegeGroup::all(), PrivilegeGroup::all()) {
+        Ok(query_param) => {
+            apply_job_batch(
+                &req,
+                &share_data,
+                &request,
+                query_param,
+                actor,
+                AuditRecorder::openapi,
+            )
+            .await
+        }
+        Err(e) => Err(e),
+    };
+    match r {
+        Ok(v) => HttpResponse::Ok().json(ApiResult::success(Some(v))),
+        Err(e) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(format!("batch_jobs error,{}", e)),
+        )),
+    }
+}
+
 pub(crate) async fn query_job_task_list(
     share_data: Data<Arc<ShareData>>,
     web::Query(request): web::Query<JobTaskListRequest>,
@@ -425,3 +750,30 @@ pub(crate) async fn get_job_info_by_key(
         )),
     }
 }
+
+pub(crate) async fn cancel_task(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Json(param): web::Json<CancelTaskParam>,
+) -> impl Responder {
+    let task_id = param.task_id.unwrap_or_default();
+    let actor = AuditRecorder::openapi_actor(&req);
+    match maintenance::cancel_task(&share_data, task_id, actor).await {
+        Ok(task) => {
+            let mut record = AuditRecorder::openapi(
+                &req,
+                AUDIT_ACTION_CANCEL,
+                AUDIT_TARGET_JOB,
+                task.job_id.to_string(),
+            );
+            record.namespace = task.namespace.clone();
+            record.detail = Arc::new(format!("task_id:{}", task_id));
+            AuditRecorder::submit(&share_data, record).await;
+            HttpResponse::Ok().json(ApiResult::success(Some(task)))
+        }
+        Err(err) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(err.to_string()),
+        )),
+    }
+}
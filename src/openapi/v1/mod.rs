@@ -2,6 +2,7 @@ pub mod app_api;
 pub mod job_api;
 pub mod model;
 pub mod raft_api;
+pub mod schedule_api;
 
 use crate::web_config::about_info;
 use actix_web::web;
@@ -51,6 +52,8 @@ pub fn v1_api_config(config: &mut ServiceConfig) {
             .service(web::resource("/job/list").route(web::get().to(job_api::query_job_list)))
             .service(web::resource("/job/export").route(web::get().to(job_api::export_jobs)))
             .service(web::resource("/job/import").route(web::post().to(job_api::import_jobs)))
+            .service(web::resource("/job/sync").route(web::post().to(job_api::sync_jobs)))
+            .service(web::resource("/job/batch").route(web::post().to(job_api::batch_jobs)))
             .service(
                 web::resource("/job/queryIdByKey").route(web::get().to(job_api::get_job_id_by_key)),
             )
@@ -61,10 +64,27 @@ pub fn v1_api_config(config: &mut ServiceConfig) {
             .service(
                 web::resource("/job/task/list").route(web::get().to(job_api::query_job_task_list)),
             )
+            .service(web::resource("/job/task/cancel").route(web::post().to(job_api::cancel_task)))
             .service(
                 web::resource("/job/task/latest-history")
                     .route(web::get().to(job_api::query_latest_task_history)),
             )
+            .service(
+                web::resource("/schedule/pause/list")
+                    .route(web::get().to(schedule_api::query_pause_list)),
+            )
+            .service(
+                web::resource("/schedule/pause")
+                    .route(web::post().to(schedule_api::pause_schedule)),
+            )
+            .service(
+                web::resource("/schedule/resume")
+                    .route(web::post().to(schedule_api::resume_schedule)),
+            )
+            .service(
+                web::resource("/schedule/preview")
+                    .route(web::get().to(schedule_api::preview_schedule)),
+            )
             .service(web::resource("/raft/metrics").route(web::get().to(raft_api::metrics)))
             .service(web::resource("/about").route(web::get().to(about_info))),
     );
@@ -1,6 +1,13 @@
 use crate::job::model::job::{JobKey, JobTaskLogQueryParam};
+use crate::task::model::enum_type::TaskStatusType;
 use serde::{Deserialize, Serialize};
 
+fn get_status_param(status: Option<String>) -> Option<TaskStatusType> {
+    status
+        .filter(|v| !v.is_empty())
+        .map(|v| TaskStatusType::from_str(&v.to_uppercase()))
+}
+
 #[derive(Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct JobTaskListRequest {
@@ -9,6 +16,7 @@ pub struct JobTaskListRequest {
     pub app_name: Option<String>,
     pub page_no: Option<usize>,
     pub page_size: Option<usize>,
+    pub status: Option<String>,
 }
 
 impl JobTaskListRequest {
@@ -26,6 +34,7 @@ impl JobTaskListRequest {
             limit,
             namespace: self.namespace,
             app_name: self.app_name,
+            status: get_status_param(self.status),
         }
     }
 }
@@ -38,6 +47,7 @@ pub struct JobTaskHistoryRequest {
     pub app_name: Option<String>,
     pub page_no: Option<usize>,
     pub page_size: Option<usize>,
+    pub status: Option<String>,
 }
 
 impl JobTaskHistoryRequest {
@@ -55,6 +65,7 @@ impl JobTaskHistoryRequest {
             limit,
             namespace: self.namespace,
             app_name: self.app_name,
+            status: get_status_param(self.status),
         }
     }
 }
@@ -80,3 +91,12 @@ impl JobKeyQueryRequest {
         Some(JobKey::new(&namespace, &app_name, &job_key))
     }
 }
+
+#[derive(Debug, Serialize, Deserialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct JobSyncRequest {
+    /// 只计算变更计划,不执行
+    pub dry_run: Option<bool>,
+    /// 删除清单涉及的应用下、清单中不存在的任务
+    pub prune: Option<bool>,
+}
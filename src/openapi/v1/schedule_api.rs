@@ -0,0 +1,61 @@
+use crate::audit::recorder::AuditRecorder;
+use crate::common::model::ApiResult;
+use crate::common::share_data::ShareData;
+use crate::console::v1::ERROR_CODE_SYSTEM_ERROR;
+use crate::schedule::maintenance;
+use crate::schedule::model::pause::SchedulePauseParam;
+use crate::schedule::model::preview::SchedulePreviewParam;
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use std::sync::Arc;
+
+pub(crate) async fn query_pause_list(share_data: Data<Arc<ShareData>>) -> impl Responder {
+    match maintenance::query_pause_list(&share_data).await {
+        Ok(list) => HttpResponse::Ok().json(ApiResult::success(Some(list))),
+        Err(e) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(e.to_string()),
+        )),
+    }
+}
+
+pub(crate) async fn pause_schedule(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Json(param): web::Json<SchedulePauseParam>,
+) -> impl Responder {
+    let actor = AuditRecorder::openapi_actor(&req);
+    match maintenance::pause_schedule(&req, &share_data, &param, actor, AuditRecorder::openapi)
+        .await
+    {
+        Ok(info) => HttpResponse::Ok().json(ApiResult::success(Some(info))),
+        Err(e) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(format!("pause_schedule error,{}", e)),
+        )),
+    }
+}
+
+pub(crate) async fn resume_schedule(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Json(param): web::Json<SchedulePauseParam>,
+) -> impl Responder {
+    match maintenance::resume_schedule(&req, &share_data, &param, AuditRecorder::openapi).await {
+        Ok(info) => HttpResponse::Ok().json(ApiResult::success(Some(info))),
+        Err(e) => HttpResponse::Ok().json(ApiResult::<()>::error(
+            ERROR_CODE_SYSTEM_ERROR.to_string(),
+            Some(format!("resume_schedule error,{}", e)),
+        )),
+    }
+}
+
+/// 预览调度配置的后续触发时间与描述,校验失败时返回错误位置
+pub(crate) async fn preview_schedule(
+    share_data: Data<Arc<ShareData>>,
+    web::Query(param): web::Query<SchedulePreviewParam>,
+) -> impl Responder {
+    HttpResponse::Ok().json(ApiResult::success(Some(
+        param.preview(&share_data.timezone_offset),
+    )))
+}
@@ -0,0 +1,493 @@
+use crate::audit::model::audit::{
+    AuditRecord, AUDIT_ACTION_CREATE, AUDIT_ACTION_REMOVE, AUDIT_ACTION_TRIGGER,
+    AUDIT_ACTION_UPDATE, AUDIT_TARGET_JOB,
+};
+use crate::audit::recorder::AuditRecorder;
+use crate::common::constant::{EMPTY_ARC_STR, SEQ_JOB_ID};
+use crate::common::datetime_utils::{now_millis, now_second_u32};
+use crate::common::share_data::ShareData;
+use crate::job::model::actor_model::{JobManagerRaftReq, JobManagerReq, JobManagerResult};
+use crate::job::model::job::{JobInfo, JobParam, JobTaskLogQueryParam};
+use crate::job::xxl_import::{convert_job_param, xxl_job_key};
+use crate::openapi::xxljob::model::admin_request::{
+    job_form_to_row, XxlGroupMapping, XxlJobIdParam, XxlJobInfoPageParam, XxlJobInfoVO,
+    XxlJobLogPageParam, XxlJobLogVO, XxlPageResult,
+};
+use crate::openapi::xxljob::model::XxlApiResult;
+use crate::raft::store::ClientRequest;
+use crate::schedule::model::actor_model::{ScheduleManagerReq, ScheduleManagerResult};
+use crate::sequence::{SequenceRequest, SequenceResult};
+use crate::task::model::actor_model::{TaskManagerReq, TriggerItem};
+use actix_web::web::Data;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const DEFAULT_PAGE_LENGTH: usize = 10;
+/// 查询全部执行记录时使用的条数
+const QUERY_ALL_LIMIT: usize = u32::MAX as usize;
+
+fn get_group_mapping(share_data: &ShareData) -> XxlGroupMapping {
+    XxlGroupMapping::parse(&share_data.app_config.xxl_admin_group_mapping)
+}
+
+fn to_response(action: &str, result: anyhow::Result<Option<String>>) -> HttpResponse {
+    match result {
+        Ok(v) => HttpResponse::Ok().json(XxlApiResult::success(v)),
+        Err(e) => {
+            let error_msg = format!("{} error,{}", action, e);
+            log::error!("{}", &error_msg);
+            HttpResponse::Ok().json(XxlApiResult::<String>::fail(Some(error_msg)))
+        }
+    }
+}
+
+///
+/// 按xxl-job任务id查找任务
+/// 优先匹配导入的任务(key为 `xxl-{id}`),找不到时按ratch-job任务id查找
+async fn find_job(
+    share_data: &ShareData,
+    mapping: &XxlGroupMapping,
+    id: u64,
+) -> anyhow::Result<Arc<JobInfo>> {
+    let app_keys = mapping.app_keys();
+    if !app_keys.is_empty() {
+        if let JobManagerResult::JobInfoList(list) = share_data
+            .job_manager
+            .send(JobManagerReq::QueryAppJobs(app_keys))
+            .await??
+        {
+            let key = xxl_job_key(&id.to_string());
+            if let Some(job) = list.into_iter().find(|v| v.key.as_str() == key) {
+                return Ok(job);
+            }
+        }
+    }
+    if let JobManagerResult::JobInfo(Some(job)) = share_data
+        .job_manager
+        .send(JobManagerReq::GetJob(id))
+        .await??
+    {
+        Ok(job)
+    } else {
+        Err(anyhow::anyhow!("job not found,id:{}", id))
+    }
+}
+
+async fn query_group_jobs(
+    share_data: &ShareData,
+    mapping: &XxlGroupMapping,
+    job_group: u64,
+) -> anyhow::Result<Vec<Arc<JobInfo>>> {
+    let app_key = if let Some(v) = mapping.get_app(job_group) {
+        v.clone()
+    } else {
+        return Ok(vec![]);
+    };
+    if let JobManagerResult::JobInfoList(list) = share_data
+        .job_manager
+        .send(JobManagerReq::QueryAppJobs(vec![app_key]))
+        .await??
+    {
+        Ok(list)
+    } else {
+        Ok(vec![])
+    }
+}
+
+/// 把表单转为任务参数,不能完全映射的配置只记录日志
+fn form_to_param(form: &HashMap<String, String>) -> anyhow::Result<JobParam> {
+    let row = job_form_to_row(form);
+    let mut warnings = vec![];
+    let param = convert_job_param(&row, &mut warnings)?;
+    if !warnings.is_empty() {
+        log::warn!("xxl-job admin api,{}", warnings.join(";"));
+    }
+    Ok(param)
+}
+
+async fn update_job_param(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    before: &JobInfo,
+    mut param: JobParam,
+    mut record: AuditRecord,
+) -> anyhow::Result<()> {
+    param.id = Some(before.id);
+    param.update_time = Some(now_millis());
+    param.update_user = Some(AuditRecorder::openapi_actor(req));
+    share_data
+        .raft_request_route
+        .request(ClientRequest::JobReq {
+            req: JobManagerRaftReq::UpdateJob(param),
+        })
+        .await?;
+    record.namespace = before.namespace.clone();
+    record.set_before(Some(before));
+    if let Ok(Ok(JobManagerResult::JobInfo(Some(info)))) = share_data
+        .job_manager
+        .send(JobManagerReq::GetJob(before.id))
+        .await
+    {
+        record.set_after(Some(&info));
+    }
+    AuditRecorder::submit(share_data, record).await;
+    Ok(())
+}
+
+async fn do_add_job(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    form: HashMap<String, String>,
+) -> anyhow::Result<Option<String>> {
+    let mapping = get_group_mapping(share_data);
+    let job_group: u64 = form
+        .get("jobGroup")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_default();
+    let app_key = mapping
+        .get_app(job_group)
+        .ok_or_else(|| anyhow::anyhow!("jobGroup {} is not mapped", job_group))?;
+    let mut param = form_to_param(&form)?;
+    param.namespace = Some(app_key.namespace.clone());
+    param.app_name = Some(app_key.app_name.clone());
+    param.check_valid()?;
+    let id = if let SequenceResult::NextId(id) = share_data
+        .sequence_manager
+        .send(SequenceRequest::GetNextId(SEQ_JOB_ID.clone()))
+        .await??
+    {
+        id
+    } else {
+        return Err(anyhow::anyhow!("get job id error!"));
+    };
+    param.id = Some(id);
+    param.update_time = Some(now_millis());
+    param.update_user = Some(AuditRecorder::openapi_actor(req));
+    let mut record =
+        AuditRecorder::openapi(req, AUDIT_ACTION_CREATE, AUDIT_TARGET_JOB, id.to_string());
+    record.namespace = app_key.namespace.clone();
+    record.set_after(Some(&param));
+    share_data
+        .raft_request_route
+        .request(ClientRequest::JobReq {
+            req: JobManagerRaftReq::AddJob(param),
+        })
+        .await?;
+    AuditRecorder::submit(share_data, record).await;
+    Ok(Some(id.to_string()))
+}
+
+pub(crate) async fn add_job(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Form(form): web::Form<HashMap<String, String>>,
+) -> impl Responder {
+    to_response("add_job", do_add_job(&req, &share_data, form).await)
+}
+
+async fn do_update_job(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    form: HashMap<String, String>,
+) -> anyhow::Result<Option<String>> {
+    let mapping = get_group_mapping(share_data);
+    let id: u64 = form
+        .get("id")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_default();
+    let before = find_job(share_data, &mapping, id).await?;
+    let mut param = form_to_param(&form)?;
+    let job_group: u64 = form
+        .get("jobGroup")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or_default();
+    let app_key = mapping.get_app(job_group);
+    param.namespace = Some(
+        app_key
+            .map(|v| v.namespace.clone())
+            .unwrap_or(before.namespace.clone()),
+    );
+    param.app_name = Some(
+        app_key
+            .map(|v| v.app_name.clone())
+            .unwrap_or(before.app_name.clone()),
+    );
+    // 与xxl-job一致,更新时不修改运行状态与GLUE源码
+    param.enable = None;
+    if !form.contains_key("glueSource") {
+        param.glue_source = None;
+    }
+    param.check_valid()?;
+    let record = AuditRecorder::openapi(
+        req,
+        AUDIT_ACTION_UPDATE,
+        AUDIT_TARGET_JOB,
+        before.id.to_string(),
+    );
+    update_job_param(req, share_data, &before, param, record).await?;
+    Ok(None)
+}
+
+pub(crate) async fn update_job(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Form(form): web::Form<HashMap<String, String>>,
+) -> impl Responder {
+    to_response("update_job", do_update_job(&req, &share_data, form).await)
+}
+
+async fn do_remove_job(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    param: XxlJobIdParam,
+) -> anyhow::Result<Option<String>> {
+    let mapping = get_group_mapping(share_data);
+    let job = find_job(share_data, &mapping, param.id).await?;
+    let mut record = AuditRecorder::openapi(
+        req,
+        AUDIT_ACTION_REMOVE,
+        AUDIT_TARGET_JOB,
+        job.id.to_string(),
+    );
+    record.namespace = job.namespace.clone();
+    record.set_before(Some(&job));
+    share_data
+        .raft_request_route
+        .request(ClientRequest::JobReq {
+            req: JobManagerRaftReq::Remove(job.id),
+        })
+        .await?;
+    AuditRecorder::submit(share_data, record).await;
+    Ok(None)
+}
+
+pub(crate) async fn remove_job(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Form(param): web::Form<XxlJobIdParam>,
+) -> impl Responder {
+    to_response("remove_job", do_remove_job(&req, &share_data, param).await)
+}
+
+async fn do_update_job_status(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    id: u64,
+    enable: bool,
+) -> anyhow::Result<Option<String>> {
+    let mapping = get_group_mapping(share_data);
+    let before = find_job(share_data, &mapping, id).await?;
+    let param = JobParam {
+        enable: Some(enable),
+        ..Default::default()
+    };
+    let mut record = AuditRecorder::openapi(
+        req,
+        AUDIT_ACTION_UPDATE,
+        AUDIT_TARGET_JOB,
+        before.id.to_string(),
+    );
+    record.detail = Arc::new(if enable { "start" } else { "stop" }.to_owned());
+    update_job_param(req, share_data, &before, param, record).await?;
+    Ok(None)
+}
+
+pub(crate) async fn start_job(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Form(param): web::Form<XxlJobIdParam>,
+) -> impl Responder {
+    to_response(
+        "start_job",
+        do_update_job_status(&req, &share_data, param.id, true).await,
+    )
+}
+
+pub(crate) async fn stop_job(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Form(param): web::Form<XxlJobIdParam>,
+) -> impl Responder {
+    to_response(
+        "stop_job",
+        do_update_job_status(&req, &share_data, param.id, false).await,
+    )
+}
+
+async fn do_trigger_job(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    param: XxlJobIdParam,
+) -> anyhow::Result<Option<String>> {
+    let mapping = get_group_mapping(share_data);
+    let mut job_info = find_job(share_data, &mapping, param.id).await?;
+    // 本次触发使用指定的参数
+    if let Some(executor_param) = param.executor_param {
+        let mut job = job_info.as_ref().clone();
+        job.trigger_param = Arc::new(executor_param);
+        job_info = Arc::new(job);
+    }
+    let instance_addr = param
+        .address_list
+        .as_ref()
+        .and_then(|v| v.split(',').map(|v| v.trim()).find(|v| !v.is_empty()))
+        .map(|v| Arc::new(v.to_owned()))
+        .unwrap_or(EMPTY_ARC_STR.clone());
+    let actor = AuditRecorder::openapi_actor(req);
+    let mut record = AuditRecorder::openapi(
+        req,
+        AUDIT_ACTION_TRIGGER,
+        AUDIT_TARGET_JOB,
+        job_info.id.to_string(),
+    );
+    record.namespace = job_info.namespace.clone();
+    record.detail = instance_addr.clone();
+    let task_item = TriggerItem::new_with_user(now_second_u32(), job_info, instance_addr, actor);
+    share_data
+        .task_manager
+        .send(TaskManagerReq::TriggerTaskList(vec![task_item]))
+        .await??;
+    AuditRecorder::submit(share_data, record).await;
+    Ok(None)
+}
+
+pub(crate) async fn trigger_job(
+    req: HttpRequest,
+    share_data: Data<Arc<ShareData>>,
+    web::Form(param): web::Form<XxlJobIdParam>,
+) -> impl Responder {
+    to_response(
+        "trigger_job",
+        do_trigger_job(&req, &share_data, param).await,
+    )
+}
+
+async fn do_query_job_page(
+    share_data: &ShareData,
+    param: XxlJobInfoPageParam,
+) -> anyhow::Result<XxlPageResult<XxlJobInfoVO>> {
+    let mapping = get_group_mapping(share_data);
+    let mut list: Vec<Arc<JobInfo>> = query_group_jobs(share_data, &mapping, param.job_group)
+        .await?
+        .into_iter()
+        .filter(|v| match param.trigger_status.unwrap_or(-1) {
+            0 => !v.enable,
+            1 => v.enable,
+            _ => true,
+        })
+        .filter(|v| {
+            param
+                .job_desc
+                .as_ref()
+                .is_none_or(|desc| v.description.contains(desc.as_str()))
+        })
+        .filter(|v| {
+            param
+                .executor_handler
+                .as_ref()
+                .is_none_or(|handler| v.handle_name.contains(handler.as_str()))
+        })
+        .collect();
+    list.sort_by_key(|v| std::cmp::Reverse(v.id));
+    let total = list.len();
+    let data = list
+        .iter()
+        .skip(param.start.unwrap_or_default())
+        .take(param.length.unwrap_or(DEFAULT_PAGE_LENGTH))
+        .map(|v| XxlJobInfoVO::new(v, &mapping))
+        .collect();
+    Ok(XxlPageResult::new(total, data))
+}
+
+pub(crate) async fn query_job_page(
+    share_data: Data<Arc<ShareData>>,
+    web::Form(param): web::Form<XxlJobInfoPageParam>,
+) -> impl Responder {
+    match do_query_job_page(&share_data, param).await {
+        Ok(v) => HttpResponse::Ok().json(v),
+        Err(e) => to_response("query_job_page", Err(e)),
+    }
+}
+
+async fn do_query_job_log_page(
+    share_data: &ShareData,
+    param: XxlJobLogPageParam,
+) -> anyhow::Result<XxlPageResult<XxlJobLogVO>> {
+    let mapping = get_group_mapping(share_data);
+    let job_id = param.job_id.unwrap_or_default();
+    let (job_map, task_list) = if job_id > 0 {
+        let job = find_job(share_data, &mapping, job_id).await?;
+        let query_param = JobTaskLogQueryParam {
+            job_id: job.id,
+            offset: 0,
+            limit: QUERY_ALL_LIMIT,
+            ..Default::default()
+        };
+        let task_list = if let JobManagerResult::JobTaskLogPageInfo(_, list) = share_data
+            .job_manager
+            .send(JobManagerReq::QueryJobTaskLog(query_param))
+            .await??
+        {
+            list
+        } else {
+            vec![]
+        };
+        (HashMap::from([(job.id, job)]), task_list)
+    } else {
+        let app_key = if let Some(v) = mapping.get_app(param.job_group) {
+            v.clone()
+        } else {
+            return Ok(XxlPageResult::new(0, vec![]));
+        };
+        let job_map: HashMap<u64, Arc<JobInfo>> =
+            query_group_jobs(share_data, &mapping, param.job_group)
+                .await?
+                .into_iter()
+                .map(|v| (v.id, v))
+                .collect();
+        let query_param = JobTaskLogQueryParam {
+            offset: 0,
+            limit: QUERY_ALL_LIMIT,
+            namespace: Some(app_key.namespace.to_string()),
+            app_name: Some(app_key.app_name.to_string()),
+            ..Default::default()
+        };
+        let task_list = if let ScheduleManagerResult::JobTaskLogPageInfo(_, list) = share_data
+            .schedule_manager
+            .send(ScheduleManagerReq::QueryJobTaskLog(query_param))
+            .await??
+        {
+            list
+        } else {
+            vec![]
+        };
+        (job_map, task_list)
+    };
+    let time_range = param.get_time_range();
+    let list: Vec<_> = task_list
+        .into_iter()
+        .filter(|v| param.match_status(&v.status))
+        .filter(|v| {
+            time_range.is_none_or(|(start, end)| v.trigger_time >= start && v.trigger_time <= end)
+        })
+        .collect();
+    let total = list.len();
+    let data = list
+        .iter()
+        .skip(param.start.unwrap_or_default())
+        .take(param.length.unwrap_or(DEFAULT_PAGE_LENGTH))
+        .map(|v| XxlJobLogVO::new(v, job_map.get(&v.job_id).map(|v| v.as_ref()), &mapping))
+        .collect();
+    Ok(XxlPageResult::new(total, data))
+}
+
+pub(crate) async fn query_job_log_page(
+    share_data: Data<Arc<ShareData>>,
+    web::Form(param): web::Form<XxlJobLogPageParam>,
+) -> impl Responder {
+    match do_query_job_log_page(&share_data, param).await {
+        Ok(v) => HttpResponse::Ok().json(v),
+        Err(e) => to_response("query_job_log_page", Err(e)),
+    }
+}
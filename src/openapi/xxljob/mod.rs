@@ -3,6 +3,7 @@ use actix_web::web;
 use actix_web::web::ServiceConfig;
 use std::sync::Arc;
 
+pub mod admin_api;
 pub mod model;
 pub mod server_api;
 
@@ -14,6 +15,9 @@ pub fn xxl_api_config(config: &mut ServiceConfig, app_config: &Arc<AppConfig>) {
                 web::resource("/api/registryRemove").route(web::post().to(server_api::unregister)),
             )
             .service(web::resource("/api/callback").route(web::post().to(server_api::callback)));
+        if app_config.xxl_admin_api_enable {
+            xxl_admin_api_config(config);
+        }
     } else {
         config.service(
             web::scope(app_config.xxl_job_prefix_path.as_str())
@@ -22,9 +26,29 @@ pub fn xxl_api_config(config: &mut ServiceConfig, app_config: &Arc<AppConfig>) {
                     web::resource("/api/registryRemove")
                         .route(web::post().to(server_api::unregister)),
                 )
-                .service(
-                    web::resource("/api/callback").route(web::post().to(server_api::callback)),
-                ),
+                .service(web::resource("/api/callback").route(web::post().to(server_api::callback)))
+                .configure(|config| {
+                    if app_config.xxl_admin_api_enable {
+                        xxl_admin_api_config(config);
+                    }
+                }),
         );
     }
 }
+
+/// xxl-job admin 管理接口兼容,供已有的运维工具迁移期间使用
+fn xxl_admin_api_config(config: &mut ServiceConfig) {
+    config
+        .service(
+            web::resource("/jobinfo/pageList").route(web::post().to(admin_api::query_job_page)),
+        )
+        .service(web::resource("/jobinfo/add").route(web::post().to(admin_api::add_job)))
+        .service(web::resource("/jobinfo/update").route(web::post().to(admin_api::update_job)))
+        .service(web::resource("/jobinfo/remove").route(web::post().to(admin_api::remove_job)))
+        .service(web::resource("/jobinfo/start").route(web::post().to(admin_api::start_job)))
+        .service(web::resource("/jobinfo/stop").route(web::post().to(admin_api::stop_job)))
+        .service(web::resource("/jobinfo/trigger").route(web::post().to(admin_api::trigger_job)))
+        .service(
+            web::resource("/joblog/pageList").route(web::post().to(admin_api::query_job_log_page)),
+        );
+}
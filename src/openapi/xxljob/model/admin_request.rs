@@ -0,0 +1,316 @@
+use crate::app::model::AppKey;
+use crate::common::datetime_utils::{get_datetime_millis, get_local_offset};
+use crate::common::registry_util;
+use crate::job::model::enum_type::{PastDueStrategy, RouterStrategy, ScheduleType};
+use crate::job::model::job::JobInfo;
+use crate::job::xxl_import::{parse_xxl_job_key, XxlRow};
+use crate::task::model::enum_type::TaskStatusType;
+use crate::task::model::task::JobTaskInfo;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+const XXL_TIME_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// xxl-job admin 表单字段到xxl_job_info列名的映射
+const XXL_JOB_FORM_FIELDS: [(&str, &str); 19] = [
+    ("id", "id"),
+    ("jobGroup", "job_group"),
+    ("jobDesc", "job_desc"),
+    ("author", "author"),
+    ("alarmEmail", "alarm_email"),
+    ("scheduleType", "schedule_type"),
+    ("scheduleConf", "schedule_conf"),
+    ("jobCron", "job_cron"),
+    ("misfireStrategy", "misfire_strategy"),
+    ("executorRouteStrategy", "executor_route_strategy"),
+    ("executorHandler", "executor_handler"),
+    ("executorParam", "executor_param"),
+    ("executorBlockStrategy", "executor_block_strategy"),
+    ("executorTimeout", "executor_timeout"),
+    ("executorFailRetryCount", "executor_fail_retry_count"),
+    ("glueType", "glue_type"),
+    ("glueSource", "glue_source"),
+    ("childJobId", "child_jobid"),
+    ("triggerStatus", "trigger_status"),
+];
+
+/// 把 /jobinfo/add、/jobinfo/update 的表单转为xxl_job_info的一行
+pub fn job_form_to_row(form: &HashMap<String, String>) -> XxlRow {
+    let mut row = XxlRow::new();
+    for (field, column) in XXL_JOB_FORM_FIELDS.iter() {
+        if let Some(v) = form.get(*field) {
+            row.insert(column.to_string(), v.to_owned());
+        }
+    }
+    row
+}
+
+///
+/// xxl-job执行器分组id与应用的映射
+/// 配置格式: `{分组id}:{应用名}`,多个用`,`分隔;应用名支持 `ns://{namespace}/{appName}`
+#[derive(Debug, Clone, Default)]
+pub struct XxlGroupMapping {
+    group_map: HashMap<u64, AppKey>,
+    app_map: HashMap<AppKey, u64>,
+}
+
+impl XxlGroupMapping {
+    pub fn parse(value: &str) -> Self {
+        let mut mapping = Self::default();
+        for item in value.split(',') {
+            let Some((group_id, app_name)) = item.trim().split_once(':') else {
+                continue;
+            };
+            let Ok(group_id) = group_id.trim().parse::<u64>() else {
+                continue;
+            };
+            let app_name = app_name.trim();
+            if app_name.is_empty() {
+                continue;
+            }
+            let parsed = registry_util::parse_registry_key(app_name);
+            let app_key = AppKey::new(parsed.app_name, parsed.namespace);
+            mapping.app_map.insert(app_key.clone(), group_id);
+            mapping.group_map.insert(group_id, app_key);
+        }
+        mapping
+    }
+
+    pub fn get_app(&self, group_id: u64) -> Option<&AppKey> {
+        self.group_map.get(&group_id)
+    }
+
+    /// 没有映射的应用返回0
+    pub fn get_group_id(&self, namespace: &Arc<String>, app_name: &Arc<String>) -> u64 {
+        self.app_map
+            .get(&AppKey::new(app_name.clone(), namespace.clone()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn app_keys(&self) -> Vec<AppKey> {
+        self.group_map.values().cloned().collect()
+    }
+}
+
+/// 导入的任务使用xxl-job原任务id,其它任务使用ratch-job任务id
+pub fn get_xxl_job_id(job_info: &JobInfo) -> u64 {
+    parse_xxl_job_key(&job_info.key).unwrap_or(job_info.id)
+}
+
+fn format_millis(millis: u64) -> Option<String> {
+    if millis == 0 {
+        return None;
+    }
+    get_datetime_millis(millis as i64, &get_local_offset())
+        .map(|v| v.format(XXL_TIME_FORMAT).to_string())
+}
+
+fn format_second(second: u32) -> Option<String> {
+    format_millis(second as u64 * 1000)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XxlPageResult<T> {
+    pub records_total: usize,
+    pub records_filtered: usize,
+    pub data: Vec<T>,
+}
+
+impl<T> XxlPageResult<T> {
+    pub fn new(total: usize, data: Vec<T>) -> Self {
+        Self {
+            records_total: total,
+            records_filtered: total,
+            data,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XxlJobIdParam {
+    pub id: u64,
+    pub executor_param: Option<String>,
+    pub address_list: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XxlJobInfoPageParam {
+    pub job_group: u64,
+    /// -1表示全部,0停止,1运行
+    pub trigger_status: Option<i32>,
+    pub job_desc: Option<String>,
+    pub executor_handler: Option<String>,
+    pub start: Option<usize>,
+    pub length: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XxlJobLogPageParam {
+    pub job_group: u64,
+    /// 0表示分组下的全部任务
+    pub job_id: Option<u64>,
+    /// -1全部,1成功,2失败,3进行中
+    pub log_status: Option<i32>,
+    /// `yyyy-MM-dd HH:mm:ss - yyyy-MM-dd HH:mm:ss`
+    pub filter_time: Option<String>,
+    pub start: Option<usize>,
+    pub length: Option<usize>,
+}
+
+impl XxlJobLogPageParam {
+    pub fn match_status(&self, status: &TaskStatusType) -> bool {
+        match self.log_status.unwrap_or(-1) {
+            1 => status == &TaskStatusType::Success,
+            2 => status.is_fail() || status == &TaskStatusType::Skipped,
+            3 => matches!(status, TaskStatusType::Init | TaskStatusType::Running),
+            _ => true,
+        }
+    }
+
+    /// 触发时间范围(秒),格式不正确时不过滤
+    pub fn get_time_range(&self) -> Option<(u32, u32)> {
+        let (start, end) = self.filter_time.as_ref()?.split_once(" - ")?;
+        let offset = get_local_offset();
+        let parse = |v: &str| {
+            chrono::NaiveDateTime::parse_from_str(v.trim(), XXL_TIME_FORMAT)
+                .ok()?
+                .and_local_timezone(offset)
+                .single()
+                .map(|v| v.timestamp() as u32)
+        };
+        Some((parse(start)?, parse(end)?))
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XxlJobInfoVO {
+    pub id: u64,
+    pub job_group: u64,
+    pub job_desc: Arc<String>,
+    pub add_time: Option<String>,
+    pub update_time: Option<String>,
+    pub author: String,
+    pub alarm_email: String,
+    pub schedule_type: String,
+    pub schedule_conf: String,
+    pub misfire_strategy: String,
+    pub executor_route_strategy: String,
+    pub executor_handler: Arc<String>,
+    pub executor_param: Arc<String>,
+    pub executor_block_strategy: String,
+    pub executor_timeout: u32,
+    pub executor_fail_retry_count: u32,
+    pub glue_type: String,
+    pub glue_source: Arc<String>,
+    pub glue_remark: String,
+    pub glue_updatetime: Option<String>,
+    pub child_job_id: String,
+    pub trigger_status: i32,
+    pub trigger_last_time: u64,
+    pub trigger_next_time: u64,
+}
+
+impl XxlJobInfoVO {
+    pub fn new(job_info: &JobInfo, mapping: &XxlGroupMapping) -> Self {
+        let (schedule_type, schedule_conf) = match job_info.schedule_type {
+            ScheduleType::Cron => ("CRON", job_info.cron_value.to_string()),
+            ScheduleType::Interval => ("FIX_RATE", job_info.interval_second.to_string()),
+            ScheduleType::Delay => ("FIX_DELAY", job_info.delay_second.to_string()),
+            ScheduleType::None => ("NONE", String::new()),
+        };
+        let misfire_strategy = match job_info.past_due_strategy {
+            PastDueStrategy::Execute => "FIRE_ONCE_NOW",
+            PastDueStrategy::Default | PastDueStrategy::Ignore => "DO_NOTHING",
+        };
+        let route_strategy = match job_info.router_strategy {
+            RouterStrategy::RoundRobin => "ROUND",
+            ref v => v.to_str(),
+        };
+        Self {
+            id: get_xxl_job_id(job_info),
+            job_group: mapping.get_group_id(&job_info.namespace, &job_info.app_name),
+            job_desc: job_info.description.clone(),
+            add_time: format_millis(job_info.create_time),
+            update_time: format_millis(job_info.last_modified_millis),
+            schedule_type: schedule_type.to_owned(),
+            schedule_conf,
+            misfire_strategy: misfire_strategy.to_owned(),
+            executor_route_strategy: route_strategy.to_owned(),
+            executor_handler: job_info.handle_name.clone(),
+            executor_param: job_info.trigger_param.clone(),
+            executor_block_strategy: job_info.blocking_strategy.to_str().to_owned(),
+            executor_timeout: job_info.timeout_second,
+            executor_fail_retry_count: job_info.try_times,
+            glue_type: job_info.run_mode.to_str().to_owned(),
+            glue_source: job_info.glue_source.clone(),
+            glue_updatetime: format_millis(job_info.glue_update_time),
+            trigger_status: if job_info.enable { 1 } else { 0 },
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct XxlJobLogVO {
+    pub id: u64,
+    pub job_group: u64,
+    pub job_id: u64,
+    pub executor_address: Arc<String>,
+    pub executor_handler: Arc<String>,
+    pub executor_param: Arc<String>,
+    pub executor_sharding_param: Option<String>,
+    pub executor_fail_retry_count: u32,
+    pub trigger_time: Option<String>,
+    pub trigger_code: i32,
+    pub trigger_msg: Arc<String>,
+    pub handle_time: Option<String>,
+    pub handle_code: i32,
+    pub handle_msg: Arc<String>,
+    pub alarm_status: i32,
+}
+
+impl XxlJobLogVO {
+    pub fn new(task: &JobTaskInfo, job_info: Option<&JobInfo>, mapping: &XxlGroupMapping) -> Self {
+        let (trigger_code, handle_code) = match task.status {
+            TaskStatusType::Success => (200, 200),
+            // 没有执行器地址表示调度失败
+            TaskStatusType::Fail if task.instance_addr.is_empty() => (500, 0),
+            TaskStatusType::Fail => (200, 500),
+            // 与xxl-job一致,超时使用502
+            TaskStatusType::Timeout => (200, 502),
+            // 没有可用执行器,未触发
+            TaskStatusType::Skipped => (500, 0),
+            TaskStatusType::Cancelled | TaskStatusType::Killed => (200, 500),
+            TaskStatusType::Init | TaskStatusType::Running => (200, 0),
+        };
+        let mut vo = Self {
+            id: task.task_id,
+            job_group: mapping.get_group_id(&task.namespace, &task.app_name),
+            job_id: task.job_id,
+            executor_address: task.instance_addr.clone(),
+            executor_fail_retry_count: task.try_times,
+            trigger_time: format_second(task.trigger_time),
+            trigger_code,
+            trigger_msg: task.trigger_message.clone(),
+            handle_time: format_second(task.finish_time),
+            handle_code,
+            handle_msg: task.callback_message.clone(),
+            ..Default::default()
+        };
+        if let Some(job_info) = job_info {
+            vo.job_group = mapping.get_group_id(&job_info.namespace, &job_info.app_name);
+            vo.job_id = get_xxl_job_id(job_info);
+            vo.executor_handler = job_info.handle_name.clone();
+            vo.executor_param = job_info.trigger_param.clone();
+        }
+        vo
+    }
+}
@@ -1,3 +1,4 @@
+pub mod admin_request;
 pub mod server_request;
 
 use serde::{Deserialize, Serialize};
@@ -18,6 +18,9 @@ pub struct CallbackParam {
     pub log_date_time: i64,
     pub handle_code: i32,
     pub handle_msg: Option<Arc<String>>,
+    /// ratch-job扩展:结构化任务结果(json)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub handle_result: Option<Arc<String>>,
 }
 
 impl From<CallbackParam> for crate::task::model::task::TaskCallBackParam {
@@ -27,6 +30,8 @@ impl From<CallbackParam> for crate::task::model::task::TaskCallBackParam {
             task_date_time: value.log_date_time,
             success: value.handle_code == SUCCESS_CODE,
             handle_msg: value.handle_msg,
+            result: value.handle_result,
+            instance_addr: None,
         }
     }
 }
@@ -87,7 +87,9 @@ pub(crate) async fn callback(
         .collect();
     if let Ok(_) = share_data
         .batch_call_manager
-        .send(BatchCallManagerReq::Callback(params))
+        .send(BatchCallManagerReq::Callback(
+            params.into_iter().map(|v| v.into()).collect(),
+        ))
         .await
     {
         #[cfg(feature = "debug")]
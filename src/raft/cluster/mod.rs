@@ -4,6 +4,7 @@ pub mod route;
 
 use crate::app::model::{AppManagerReq, AppManagerResult};
 use crate::common::share_data::ShareData;
+use crate::executor::model::{ExecutorStreamReq, ExecutorStreamResult};
 use crate::grpc::handler::RAFT_ROUTE_REQUEST;
 use crate::grpc::PayloadUtils;
 use crate::metrics::model::{MetricsRequest, MetricsResponse};
@@ -59,6 +60,18 @@ pub async fn handle_route(
                 Err(anyhow::anyhow!("MetricsResponse::TimelineResponse error"))
             }
         }
+        RouterRequest::ExecutorRouteRequest(req) => {
+            if let ExecutorStreamResult::RouteResponse(resp) = app
+                .executor_stream_manager
+                .send(ExecutorStreamReq::RouteRequest(req))
+                .await??
+            {
+                Ok(RouterResponse::ExecutorRouteResponse(resp))
+            } else {
+                Err(anyhow::anyhow!("ExecutorStreamReq::RouteRequest error"))
+            }
+        }
+        RouterRequest::Ping => Ok(RouterResponse::None),
     }
 }
 
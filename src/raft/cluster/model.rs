@@ -1,4 +1,5 @@
 use crate::app::model::{AppRouteRequest, AppRouteResponse};
+use crate::executor::model::{ExecutorRouteRequest, ExecutorRouteResponse};
 use crate::metrics::timeline::model::{TimelineQueryParam, TimelineQueryResponse};
 use crate::raft::store::{ClientRequest, ClientResponse};
 use actix::Message;
@@ -20,6 +21,9 @@ pub enum RouterRequest {
     AppRouteRequest(AppRouteRequest),
     RaftRequest(ClientRequest),
     MetricsTimelineQuery(TimelineQueryParam),
+    ExecutorRouteRequest(ExecutorRouteRequest),
+    /// 节点存活探测
+    Ping,
 }
 
 impl From<ClientRequest> for RouterRequest {
@@ -46,6 +50,7 @@ pub enum RouterResponse {
     AppRouteResponse(AppRouteResponse),
     RaftResponse(ClientResponse),
     MetricsTimeLineResponse(TimelineQueryResponse),
+    ExecutorRouteResponse(ExecutorRouteResponse),
 }
 
 impl From<ClientResponse> for RouterResponse {
@@ -81,6 +86,16 @@ impl TryFrom<RouterResponse> for AppRouteResponse {
     }
 }
 
+impl TryFrom<RouterResponse> for ExecutorRouteResponse {
+    type Error = anyhow::Error;
+    fn try_from(value: RouterResponse) -> Result<Self, Self::Error> {
+        match value {
+            RouterResponse::ExecutorRouteResponse(resp) => Ok(resp),
+            _ => Err(anyhow::anyhow!("Invalid ExecutorRouteResponse")),
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct VoteInfo {
     pub voted_for: u64,
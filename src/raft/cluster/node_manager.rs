@@ -4,6 +4,7 @@ use crate::raft::cluster::model::{RouterRequest, VoteChangeRequest, VoteInfo};
 use crate::raft::cluster::router_request;
 use crate::raft::network::factory::RaftClusterRequestSender;
 use crate::schedule::core::ScheduleManager;
+use crate::schedule::model::actor_model::ScheduleManagerReq;
 use crate::user::core::UserManager;
 use actix::prelude::*;
 use bean_factory::{bean, BeanFactory, FactoryData, Inject};
@@ -11,6 +12,11 @@ use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
+/// 节点探活间隔
+const NODE_CHECK_INTERVAL_MILLIS: u64 = 3000;
+/// 超过该时长未探活成功的节点标记为无效
+const NODE_INVALID_TIMEOUT_MILLIS: u64 = 10000;
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum NodeStatus {
     Valid,
@@ -43,7 +49,6 @@ pub struct ClusterInnerNode {
 }
 
 impl ClusterInnerNode {
-    #[allow(dead_code)]
     pub(crate) fn is_valid(&self) -> bool {
         self.is_local || self.status == NodeStatus::Valid
     }
@@ -120,6 +125,7 @@ impl ClusterNodeManager {
         let local_node = self.get_this_node();
         self.all_nodes.entry(self.local_id).or_insert(local_node);
         self.update_nodes_index();
+        self.notify_members_change();
         //第一次需要触发从其它实例加载snapshot
         if !self.first_init {
             self.first_init = true;
@@ -160,7 +166,7 @@ impl ClusterNodeManager {
     }
 
     fn notify_vote_change(&self) {
-        let local_is_master = self.local_id == self.last_vote.voted_for;
+        let local_is_master = self.local_is_master();
         if let Some(schedule_manager) = self.schedule_manager.as_ref() {
             schedule_manager.do_send(VoteChangeRequest::VoteChange {
                 vote_info: self.last_vote.clone(),
@@ -175,6 +181,90 @@ impl ClusterNodeManager {
         }
     }
 
+    fn local_is_master(&self) -> bool {
+        self.local_id == self.last_vote.voted_for
+    }
+
+    /// 有效成员变更通知调度模块重新分配分区
+    fn notify_members_change(&self) {
+        if let Some(schedule_manager) = self.schedule_manager.as_ref() {
+            let members: Vec<u64> = self
+                .all_nodes
+                .values()
+                .filter(|e| e.is_valid())
+                .map(|e| e.id)
+                .collect();
+            schedule_manager.do_send(ScheduleManagerReq::UpdateMembers(members));
+        }
+    }
+
+    async fn ping_nodes(
+        addrs: Vec<(u64, Arc<String>)>,
+        sender: Arc<RaftClusterRequestSender>,
+    ) -> Vec<(u64, bool)> {
+        let mut result = Vec::with_capacity(addrs.len());
+        for (id, addr) in addrs {
+            let ok = router_request(RouterRequest::Ping, addr, &sender)
+                .await
+                .is_ok();
+            result.push((id, ok));
+        }
+        result
+    }
+
+    fn update_nodes_status(&mut self, ping_result: Vec<(u64, bool)>) {
+        let now = now_millis();
+        let mut changed = false;
+        for (id, ok) in ping_result {
+            if let Some(node) = self.all_nodes.get_mut(&id) {
+                let status = if ok {
+                    node.last_active_time = now;
+                    NodeStatus::Valid
+                } else if now > node.last_active_time + NODE_INVALID_TIMEOUT_MILLIS {
+                    NodeStatus::Invalid
+                } else {
+                    continue;
+                };
+                if node.status != status {
+                    log::warn!(
+                        "ClusterNodeManager|node {} status change to {:?}",
+                        id,
+                        &status
+                    );
+                    node.status = status;
+                    changed = true;
+                }
+            }
+        }
+        if changed {
+            self.notify_members_change();
+        }
+    }
+
+    /// 主节点定时探测其它节点，用于调度分区分配
+    fn check_nodes(&mut self, ctx: &mut Context<Self>) {
+        if self.local_is_master() {
+            if let Some(cluster_sender) = self.cluster_sender.clone() {
+                let addrs: Vec<(u64, Arc<String>)> = self
+                    .all_nodes
+                    .values()
+                    .filter(|e| !e.is_local)
+                    .map(|e| (e.id, e.addr.clone()))
+                    .collect();
+                Self::ping_nodes(addrs, cluster_sender)
+                    .into_actor(self)
+                    .map(|result, act, _ctx| act.update_nodes_status(result))
+                    .spawn(ctx);
+            }
+        }
+        ctx.run_later(
+            Duration::from_millis(NODE_CHECK_INTERVAL_MILLIS),
+            |act, ctx| {
+                act.check_nodes(ctx);
+            },
+        );
+    }
+
     async fn do_send_to_other_nodes(
         req: RouterRequest,
         addrs: Vec<Arc<String>>,
@@ -208,8 +298,9 @@ impl ClusterNodeManager {
 
 impl Actor for ClusterNodeManager {
     type Context = Context<Self>;
-    fn started(&mut self, _ctx: &mut Self::Context) {
+    fn started(&mut self, ctx: &mut Self::Context) {
         log::info!("ClusterNodeManager started!");
+        self.check_nodes(ctx);
     }
 }
 
@@ -283,7 +374,16 @@ impl Handler<NodeManageRequest> for ClusterNodeManager {
                 );
                 let vote_info = VoteInfo::new(voted_for, current_term);
                 self.last_vote = vote_info;
+                if self.local_is_master() {
+                    // 新主节点重新开始计算节点存活时间
+                    let now = now_millis();
+                    for node in self.all_nodes.values_mut() {
+                        node.last_active_time = now;
+                        node.status = NodeStatus::Valid;
+                    }
+                }
                 self.notify_vote_change();
+                self.notify_members_change();
                 Ok(NodeManageResponse::None)
             }
             NodeManageRequest::SendToOtherNodes(req) => {
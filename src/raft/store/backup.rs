@@ -0,0 +1,747 @@
+use std::{borrow::Cow, collections::HashMap, path::Path, sync::Arc};
+
+use actix::prelude::*;
+use async_raft_ext::raft::EntryPayload;
+use quick_protobuf::{BytesReader, MessageRead, MessageWrite, Writer};
+use serde::{Deserialize, Serialize};
+use tokio::{
+    fs::OpenOptions,
+    io::{AsyncReadExt, AsyncWriteExt},
+};
+
+use super::{
+    log::{BackupItem, LogRange, LogRecord, LogSnapshotItem, SnapshotHeader, SnapshotRange},
+    model::{LogRecordDto, RaftIndexDto, SnapshotHeaderDto, SnapshotRecordDto},
+    raftapply::{StateApplyAsyncRequest, StateApplyManager, StateApplyRequest, StateApplyResponse},
+    raftindex::{RaftIndexInnerManager, RaftIndexManager},
+    raftlog::{
+        LogInnerManager, LogWriteMark, RaftLogManager, RaftLogManagerAsyncRequest, RaftLogResponse,
+        ReadOnlyLogFile,
+    },
+    raftsnapshot::{RaftSnapshotManager, SnapshotReader, SnapshotWriter},
+    ClientRequest, LogValueFormat, StoreUtils,
+};
+use crate::common::byte_utils::{bin_to_id_result, id_to_bin};
+use crate::common::protobuf_utils::MessageBufReader;
+
+const ITEM_TYPE_SNAPSHOT_HEADER: u32 = 1;
+const ITEM_TYPE_SNAPSHOT_RECORD: u32 = 2;
+const ITEM_TYPE_LOG_RECORD: u32 = 3;
+const ITEM_TYPE_END: u32 = 4;
+
+/// 每批读取的日志数量
+const LOG_BATCH_SIZE: u64 = 1000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupInfo {
+    pub path: String,
+    pub snapshot_index: u64,
+    pub snapshot_term: u64,
+    pub snapshot_record_count: u64,
+    pub log_record_count: u64,
+    pub last_index: u64,
+    pub last_term: u64,
+}
+
+#[derive(Debug)]
+pub enum BackupRecord {
+    SnapshotHeader(SnapshotHeaderDto),
+    SnapshotRecord(SnapshotRecordDto),
+    LogRecord(LogRecordDto),
+    End {
+        snapshot_record_count: u64,
+        log_record_count: u64,
+    },
+}
+
+fn encode_message<M: MessageWrite>(message: &M) -> anyhow::Result<Vec<u8>> {
+    let mut buf = Vec::new();
+    let mut writer = Writer::new(&mut buf);
+    writer.write_message(message)?;
+    Ok(buf)
+}
+
+fn decode_message<'a, M: MessageRead<'a>>(buf: &'a [u8]) -> anyhow::Result<M> {
+    let mut reader = BytesReader::from_bytes(buf);
+    Ok(reader.read_message(buf)?)
+}
+
+///
+/// 备份文件由一组BackupItem组成：镜像头、镜像记录、镜像之后的日志、结束标记；
+/// 每个条目都带有crc32校验值
+pub struct BackupWriter {
+    file: tokio::fs::File,
+    info: BackupInfo,
+}
+
+impl BackupWriter {
+    pub async fn init(path: &str, header: &SnapshotHeaderDto) -> anyhow::Result<Self> {
+        if let Some(parent) = Path::new(path).parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let file = OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .await?;
+        let mut writer = Self {
+            file,
+            info: BackupInfo {
+                path: path.to_owned(),
+                snapshot_index: header.last_index,
+                snapshot_term: header.last_term,
+                last_index: header.last_index,
+                last_term: header.last_term,
+                ..Default::default()
+            },
+        };
+        let data = encode_message(&header.to_record_do())?;
+        writer.write_item(ITEM_TYPE_SNAPSHOT_HEADER, &data).await?;
+        Ok(writer)
+    }
+
+    async fn write_item(&mut self, item_type: u32, data: &[u8]) -> anyhow::Result<()> {
+        let item = BackupItem {
+            item_type,
+            data: Cow::Borrowed(data),
+            checksum: crc32fast::hash(data),
+        };
+        self.file.write_all(&encode_message(&item)?).await?;
+        Ok(())
+    }
+
+    pub async fn write_snapshot_record(
+        &mut self,
+        record: &SnapshotRecordDto,
+    ) -> anyhow::Result<()> {
+        let data = encode_message(&record.to_record_do())?;
+        self.write_item(ITEM_TYPE_SNAPSHOT_RECORD, &data).await?;
+        self.info.snapshot_record_count += 1;
+        Ok(())
+    }
+
+    pub async fn write_log_record(&mut self, record: &LogRecordDto) -> anyhow::Result<()> {
+        if record.index != self.info.last_index + 1 {
+            return Err(anyhow::anyhow!(
+                "backup log index is discontinuous,expect:{},actual:{}",
+                self.info.last_index + 1,
+                record.index
+            ));
+        }
+        let data = encode_message(&record.to_record_do())?;
+        self.write_item(ITEM_TYPE_LOG_RECORD, &data).await?;
+        self.info.log_record_count += 1;
+        self.info.last_index = record.index;
+        self.info.last_term = record.term;
+        Ok(())
+    }
+
+    pub async fn finish(mut self) -> anyhow::Result<BackupInfo> {
+        let mut data = id_to_bin(self.info.snapshot_record_count);
+        data.extend(id_to_bin(self.info.log_record_count));
+        self.write_item(ITEM_TYPE_END, &data).await?;
+        self.file.flush().await?;
+        self.file.sync_all().await?;
+        Ok(self.info)
+    }
+}
+
+pub struct BackupReader {
+    file: tokio::fs::File,
+    message_reader: MessageBufReader,
+    is_end: bool,
+}
+
+impl BackupReader {
+    pub async fn init(path: &str) -> anyhow::Result<Self> {
+        let file = OpenOptions::new().read(true).open(path).await?;
+        Ok(Self {
+            file,
+            message_reader: MessageBufReader::new(),
+            is_end: false,
+        })
+    }
+
+    /// 读取下一个条目，校验失败时返回错误
+    pub async fn read_record(&mut self) -> anyhow::Result<Option<BackupRecord>> {
+        if self.is_end {
+            return Ok(None);
+        }
+        loop {
+            if let Some(v) = self.message_reader.next_message_vec() {
+                let item: BackupItem = decode_message(v)?;
+                if crc32fast::hash(&item.data) != item.checksum {
+                    return Err(anyhow::anyhow!(
+                        "backup item checksum error,item_type:{}",
+                        item.item_type
+                    ));
+                }
+                return Self::decode_record(item).map(Some);
+            }
+            let mut buf = vec![0u8; 1024];
+            let read_len = self.file.read(&mut buf).await?;
+            if read_len == 0 {
+                self.is_end = true;
+                return Ok(None);
+            }
+            self.message_reader.append_next_buf(&buf[..read_len]);
+        }
+    }
+
+    fn decode_record(item: BackupItem) -> anyhow::Result<BackupRecord> {
+        let record = match item.item_type {
+            ITEM_TYPE_SNAPSHOT_HEADER => {
+                let header: SnapshotHeader = decode_message(&item.data)?;
+                BackupRecord::SnapshotHeader(header.into())
+            }
+            ITEM_TYPE_SNAPSHOT_RECORD => {
+                let record: LogSnapshotItem = decode_message(&item.data)?;
+                BackupRecord::SnapshotRecord(record.into())
+            }
+            ITEM_TYPE_LOG_RECORD => {
+                let record: LogRecord = decode_message(&item.data)?;
+                BackupRecord::LogRecord(record.into())
+            }
+            ITEM_TYPE_END => {
+                if item.data.len() < 16 {
+                    return Err(anyhow::anyhow!("backup end item is error"));
+                }
+                BackupRecord::End {
+                    snapshot_record_count: bin_to_id_result(&item.data[0..8])?,
+                    log_record_count: bin_to_id_result(&item.data[8..16])?,
+                }
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "unknown backup item type:{}",
+                    item.item_type
+                ))
+            }
+        };
+        Ok(record)
+    }
+}
+
+/// 完整读取一遍备份文件，校验每条记录及日志连续性
+pub async fn verify_backup(path: &str) -> anyhow::Result<BackupInfo> {
+    let mut reader = BackupReader::init(path).await?;
+    let mut info = if let Some(BackupRecord::SnapshotHeader(header)) = reader.read_record().await? {
+        BackupInfo {
+            path: path.to_owned(),
+            snapshot_index: header.last_index,
+            snapshot_term: header.last_term,
+            last_index: header.last_index,
+            last_term: header.last_term,
+            ..Default::default()
+        }
+    } else {
+        return Err(anyhow::anyhow!("backup snapshot header is not found"));
+    };
+    let mut is_end = false;
+    while let Some(record) = reader.read_record().await? {
+        if is_end {
+            return Err(anyhow::anyhow!("backup has data after end item"));
+        }
+        match record {
+            BackupRecord::SnapshotHeader(_) => {
+                return Err(anyhow::anyhow!("backup snapshot header is duplicate"));
+            }
+            BackupRecord::SnapshotRecord(_) => {
+                if info.log_record_count > 0 {
+                    return Err(anyhow::anyhow!("backup snapshot record is after log"));
+                }
+                info.snapshot_record_count += 1;
+            }
+            BackupRecord::LogRecord(record) => {
+                if record.index != info.last_index + 1 {
+                    return Err(anyhow::anyhow!(
+                        "backup log index is discontinuous,expect:{},actual:{}",
+                        info.last_index + 1,
+                        record.index
+                    ));
+                }
+                info.log_record_count += 1;
+                info.last_index = record.index;
+                info.last_term = record.term;
+            }
+            BackupRecord::End {
+                snapshot_record_count,
+                log_record_count,
+            } => {
+                if snapshot_record_count != info.snapshot_record_count
+                    || log_record_count != info.log_record_count
+                {
+                    return Err(anyhow::anyhow!(
+                        "backup record count is error,snapshot:{}/{},log:{}/{}",
+                        info.snapshot_record_count,
+                        snapshot_record_count,
+                        info.log_record_count,
+                        log_record_count
+                    ));
+                }
+                is_end = true;
+            }
+        }
+    }
+    if !is_end {
+        return Err(anyhow::anyhow!("backup file is incomplete"));
+    }
+    Ok(info)
+}
+
+async fn write_snapshot_to_backup(
+    snapshot_path: &str,
+    writer: &mut BackupWriter,
+) -> anyhow::Result<()> {
+    let mut reader = SnapshotReader::init(snapshot_path).await?;
+    while let Some(record) = reader.read_record().await? {
+        writer.write_snapshot_record(&record).await?;
+    }
+    Ok(())
+}
+
+/// 运行中的节点在线备份：先通过RaftSnapshotManager构建一份独立镜像，再追加镜像之后已应用的日志
+pub async fn backup_online(
+    apply_manager: &Addr<StateApplyManager>,
+    log_manager: &Addr<RaftLogManager>,
+    path: &str,
+) -> anyhow::Result<BackupInfo> {
+    let (header, snapshot_path) = match apply_manager
+        .send(StateApplyAsyncRequest::BuildBackupSnapshot)
+        .await??
+    {
+        StateApplyResponse::Snapshot(header, path, _) => (header, path),
+        _ => return Err(anyhow::anyhow!("StateApplyResponse is error")),
+    };
+    let r = do_backup_online(apply_manager, log_manager, path, &header, &snapshot_path).await;
+    tokio::fs::remove_file(snapshot_path.as_str()).await.ok();
+    r
+}
+
+async fn do_backup_online(
+    apply_manager: &Addr<StateApplyManager>,
+    log_manager: &Addr<RaftLogManager>,
+    path: &str,
+    header: &SnapshotHeaderDto,
+    snapshot_path: &str,
+) -> anyhow::Result<BackupInfo> {
+    let mut writer = BackupWriter::init(path, header).await?;
+    write_snapshot_to_backup(snapshot_path, &mut writer).await?;
+    let last_applied_log = match apply_manager
+        .send(StateApplyRequest::GetLastAppliedLog)
+        .await??
+    {
+        StateApplyResponse::LastAppliedLog(v) => v,
+        _ => return Err(anyhow::anyhow!("StateApplyResponse is error")),
+    };
+    let mut start = header.last_index + 1;
+    while start <= last_applied_log {
+        let end = std::cmp::min(start + LOG_BATCH_SIZE, last_applied_log + 1);
+        let records = match log_manager
+            .send(RaftLogManagerAsyncRequest::Query { start, end })
+            .await??
+        {
+            RaftLogResponse::QueryResult(list) => list,
+            _ => return Err(anyhow::anyhow!("RaftLogResponse is error")),
+        };
+        if records.is_empty() {
+            return Err(anyhow::anyhow!("backup query log is empty,start:{}", start));
+        }
+        for record in &records {
+            writer.write_log_record(record).await?;
+        }
+        start += records.len() as u64;
+    }
+    writer.finish().await
+}
+
+/// 离线备份数据目录，需要先停止服务
+pub async fn backup_offline(data_dir: &str, path: &str) -> anyhow::Result<BackupInfo> {
+    let _lock = RaftIndexManager::try_lock(data_dir)?;
+    let index_path = Path::new(data_dir)
+        .join("index")
+        .to_string_lossy()
+        .into_owned();
+    let index_manager = RaftIndexInnerManager::init(&index_path).await?;
+    let raft_index = index_manager.raft_index.clone();
+    let last_applied_log = index_manager.last_applied_log;
+    let (header, snapshot_path) = if let Some(snapshot) = raft_index.snapshots.last() {
+        let snapshot_path = RaftSnapshotManager::get_snapshot_path(data_dir, snapshot.id);
+        let reader = SnapshotReader::init(&snapshot_path).await?;
+        (reader.get_header().to_owned(), Some(snapshot_path))
+    } else {
+        let header = SnapshotHeaderDto {
+            last_index: 0,
+            last_term: 0,
+            member: raft_index.member.clone(),
+            member_after_consensus: raft_index.member_after_consensus.clone(),
+            node_addrs: raft_index.node_addrs.clone(),
+        };
+        (header, None)
+    };
+    let mut writer = BackupWriter::init(path, &header).await?;
+    if let Some(snapshot_path) = &snapshot_path {
+        write_snapshot_to_backup(snapshot_path, &mut writer).await?;
+    }
+    let end = last_applied_log + 1;
+    for log_range in &raft_index.logs {
+        let start = header.last_index + 1;
+        if log_range.is_close && log_range.start_index + log_range.record_count <= start {
+            continue;
+        }
+        if log_range.start_index >= end {
+            break;
+        }
+        let log_file = ReadOnlyLogFile::open(data_dir, log_range).await?;
+        let mut log_manager = LogInnerManager::init(
+            log_file.path.clone(),
+            log_range.start_index,
+            log_range.pre_term,
+            log_range.split_off_index,
+        )
+        .await?;
+        let mut batch_start = std::cmp::max(writer.info.last_index + 1, log_range.start_index);
+        while batch_start < end {
+            let batch_end = std::cmp::min(batch_start + LOG_BATCH_SIZE, end);
+            let records = log_manager.read_records(batch_start, batch_end).await?;
+            if records.is_empty() {
+                break;
+            }
+            for record in &records {
+                writer.write_log_record(record).await?;
+            }
+            batch_start += records.len() as u64;
+        }
+    }
+    if writer.info.last_index < last_applied_log {
+        return Err(anyhow::anyhow!(
+            "backup log is incomplete,last index:{},last applied log:{}",
+            writer.info.last_index,
+            last_applied_log
+        ));
+    }
+    writer.finish().await
+}
+
+#[derive(Debug, Clone)]
+pub struct RestoreParam {
+    pub backup_path: String,
+    pub data_dir: String,
+    pub node_id: u64,
+    pub node_addr: Arc<String>,
+    /// 恢复到指定日志序号，为空时恢复全部日志
+    pub end_index: Option<u64>,
+    /// 数据目录不为空时清空后恢复
+    pub force: bool,
+}
+
+/// 集群成员相关的日志替换为空日志，恢复后作为新的单节点集群启动
+fn convert_restore_log(record: LogRecordDto) -> anyhow::Result<LogRecordDto> {
+    //保持原日志格式
+    let format = LogValueFormat::from_value(&record.value);
+    let mut entry = StoreUtils::log_record_to_entry(record)?;
+    let keep = match &entry.payload {
+        EntryPayload::Normal(req) => !matches!(
+            &req.data,
+            ClientRequest::NodeAddr { .. } | ClientRequest::Members(_)
+        ),
+        _ => false,
+    };
+    if !keep {
+        entry.payload = EntryPayload::Blank;
+    }
+    StoreUtils::entry_to_record(&entry, format)
+}
+
+fn new_log_range(id: u64, start_index: u64, pre_term: u64) -> LogRange {
+    LogRange {
+        id,
+        pre_term,
+        start_index,
+        split_off_index: start_index,
+        record_count: 0,
+        is_close: false,
+        mark_remove: false,
+        compress_type: 0,
+    }
+}
+
+/// 恢复目录除锁文件外必须为空，force时清空已有文件
+async fn prepare_restore_dir(data_dir: &str, force: bool) -> anyhow::Result<()> {
+    let mut entries = tokio::fs::read_dir(data_dir).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        if entry.file_name() == "db_lock" {
+            continue;
+        }
+        if !force {
+            return Err(anyhow::anyhow!(
+                "restore data dir is not empty,path:{},found:{}",
+                data_dir,
+                entry.file_name().to_string_lossy()
+            ));
+        }
+        if entry.file_type().await?.is_dir() {
+            tokio::fs::remove_dir_all(entry.path()).await?;
+        } else {
+            tokio::fs::remove_file(entry.path()).await?;
+        }
+    }
+    Ok(())
+}
+
+/// 从备份文件恢复出一个新的单节点集群数据目录
+pub async fn restore_backup(param: RestoreParam) -> anyhow::Result<BackupInfo> {
+    let info = verify_backup(&param.backup_path).await?;
+    let end_index = param.end_index.unwrap_or(info.last_index);
+    if end_index < info.snapshot_index || end_index > info.last_index {
+        return Err(anyhow::anyhow!(
+            "restore end index is out of range,{}~{}",
+            info.snapshot_index,
+            info.last_index
+        ));
+    }
+    let data_dir = param.data_dir.as_str();
+    tokio::fs::create_dir_all(data_dir).await?;
+    let _lock = RaftIndexManager::try_lock(data_dir)?;
+    prepare_restore_dir(data_dir, param.force).await?;
+
+    let mut reader = BackupReader::init(&param.backup_path).await?;
+    let mut header =
+        if let Some(BackupRecord::SnapshotHeader(header)) = reader.read_record().await? {
+            header
+        } else {
+            return Err(anyhow::anyhow!("backup snapshot header is not found"));
+        };
+    let mut node_addrs = HashMap::new();
+    node_addrs.insert(param.node_id, param.node_addr.clone());
+    header.member = vec![param.node_id];
+    header.member_after_consensus = vec![];
+    header.node_addrs = node_addrs.clone();
+
+    let mut snapshot_writer = if header.last_index > 0 {
+        let path = RaftSnapshotManager::get_snapshot_path(data_dir, 1);
+        Some(SnapshotWriter::init(&path, header.clone()).await?)
+    } else {
+        None
+    };
+    let start_index = header.last_index + 1;
+    let mut log_ranges = vec![new_log_range(1, start_index, header.last_term)];
+    let mut log_writer = LogInnerManager::init(
+        RaftLogManager::get_log_path(data_dir, &log_ranges[0]),
+        start_index,
+        header.last_term,
+        start_index,
+    )
+    .await?;
+    let mut result = BackupInfo {
+        path: param.backup_path.clone(),
+        snapshot_index: header.last_index,
+        snapshot_term: header.last_term,
+        last_index: header.last_index,
+        last_term: header.last_term,
+        ..Default::default()
+    };
+    while let Some(record) = reader.read_record().await? {
+        match record {
+            BackupRecord::SnapshotRecord(record) => {
+                if let Some(writer) = snapshot_writer.as_mut() {
+                    writer.write_record(&record).await?;
+                }
+                result.snapshot_record_count += 1;
+            }
+            BackupRecord::LogRecord(record) => {
+                if record.index > end_index {
+                    break;
+                }
+                result.last_index = record.index;
+                result.last_term = record.term;
+                let record = convert_restore_log(record)?;
+                let mut mark = log_writer.write(&record).await?;
+                if let LogWriteMark::Failure = mark {
+                    // 当前日志文件已写满，切换到新文件后重写
+                    log_writer = switch_restore_log(data_dir, &mut log_ranges, &record).await?;
+                    mark = log_writer.write(&record).await?;
+                }
+                match mark {
+                    LogWriteMark::Success => {}
+                    LogWriteMark::SuccessToEnd => {
+                        let next = LogRecordDto {
+                            index: record.index + 1,
+                            term: record.term,
+                            value: vec![],
+                        };
+                        log_writer = switch_restore_log(data_dir, &mut log_ranges, &next).await?;
+                    }
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "restore write log error,index:{}",
+                            record.index
+                        ))
+                    }
+                }
+                result.log_record_count += 1;
+            }
+            _ => {}
+        }
+    }
+    if let Some(writer) = snapshot_writer.as_mut() {
+        writer.flush().await?;
+    }
+    log_writer.flush_log().await?;
+
+    let snapshots = if header.last_index > 0 {
+        vec![SnapshotRange {
+            id: 1,
+            end_index: header.last_index,
+        }]
+    } else {
+        vec![]
+    };
+    let raft_index = RaftIndexDto {
+        current_log: log_ranges.last().map(|e| e.id).unwrap_or_default(),
+        logs: log_ranges,
+        snapshots,
+        last_snapshot: 0,
+        last_snapshot_index: 0,
+        last_snapshot_term: 0,
+        current_term: result.last_term,
+        voted_for: 0,
+        member: vec![param.node_id],
+        member_after_consensus: vec![],
+        node_addrs,
+    };
+    let index_path = Path::new(data_dir).join("index");
+    let mut index_manager = RaftIndexInnerManager::init(&index_path.to_string_lossy()).await?;
+    index_manager.write_index(raft_index).await?;
+    index_manager
+        .write_last_applied_log(result.last_index)
+        .await?;
+    index_manager.flush().await?;
+    Ok(result)
+}
+
+async fn switch_restore_log(
+    data_dir: &str,
+    log_ranges: &mut Vec<LogRange>,
+    next_record: &LogRecordDto,
+) -> anyhow::Result<LogInnerManager> {
+    let next_id = if let Some(last) = log_ranges.last_mut() {
+        last.is_close = true;
+        last.record_count = next_record.index - last.start_index;
+        last.id + 1
+    } else {
+        1
+    };
+    let pre_term = next_record.term;
+    let log_range = new_log_range(next_id, next_record.index, pre_term);
+    let log_path = RaftLogManager::get_log_path(data_dir, &log_range);
+    log_ranges.push(log_range);
+    LogInnerManager::init(log_path, next_record.index, pre_term, next_record.index).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> String {
+        let dir = std::env::temp_dir().join(format!("ratchjob_{}_{}", name, std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.to_string_lossy().into_owned()
+    }
+
+    fn build_header() -> SnapshotHeaderDto {
+        SnapshotHeaderDto {
+            last_index: 3,
+            last_term: 1,
+            member: vec![1, 2],
+            member_after_consensus: vec![],
+            node_addrs: HashMap::new(),
+        }
+    }
+
+    async fn write_backup(path: &str) -> BackupInfo {
+        let mut writer = BackupWriter::init(path, &build_header()).await.unwrap();
+        writer
+            .write_snapshot_record(&SnapshotRecordDto {
+                tree: Arc::new("T_TEST".to_owned()),
+                key: vec![1],
+                value: vec![2, 3],
+                op_type: 0,
+            })
+            .await
+            .unwrap();
+        for index in 4..6 {
+            writer
+                .write_log_record(&LogRecordDto {
+                    index,
+                    term: 2,
+                    value: serde_json::to_vec(&EntryPayload::<ClientRequest>::Blank).unwrap(),
+                })
+                .await
+                .unwrap();
+        }
+        writer.finish().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn backup_verify() {
+        let dir = temp_dir("backup_verify");
+        let path = Path::new(&dir).join("a.bak").to_string_lossy().into_owned();
+        let info = write_backup(&path).await;
+        let verify_info = verify_backup(&path).await.unwrap();
+        assert_eq!(verify_info.snapshot_record_count, 1);
+        assert_eq!(verify_info.log_record_count, 2);
+        assert_eq!(verify_info.last_index, info.last_index);
+
+        // 修改结束标记中的数据后校验失败
+        let mut data = std::fs::read(&path).unwrap();
+        let len = data.len();
+        data[len - 10] ^= 0xff;
+        std::fs::write(&path, &data).unwrap();
+        assert!(verify_backup(&path).await.is_err());
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[tokio::test]
+    async fn restore_to_empty_dir() {
+        let dir = temp_dir("restore");
+        let path = Path::new(&dir).join("a.bak").to_string_lossy().into_owned();
+        write_backup(&path).await;
+        let data_dir = Path::new(&dir).join("db").to_string_lossy().into_owned();
+        let param = RestoreParam {
+            backup_path: path,
+            data_dir: data_dir.clone(),
+            node_id: 1,
+            node_addr: Arc::new("127.0.0.1:8825".to_owned()),
+            end_index: Some(4),
+            force: false,
+        };
+        let info = restore_backup(param.clone()).await.unwrap();
+        assert_eq!(info.last_index, 4);
+        assert_eq!(info.log_record_count, 1);
+        // 已有数据的目录不能恢复
+        assert!(restore_backup(param.clone()).await.is_err());
+        // 没有索引文件但有其它文件的目录也不能恢复，force时清空后恢复
+        let other_dir = Path::new(&dir).join("db2").to_string_lossy().into_owned();
+        std::fs::create_dir_all(&other_dir).unwrap();
+        std::fs::write(Path::new(&other_dir).join("log_1"), b"x").unwrap();
+        let mut other_param = RestoreParam {
+            data_dir: other_dir.clone(),
+            ..param
+        };
+        assert!(restore_backup(other_param.clone()).await.is_err());
+        other_param.force = true;
+        assert!(restore_backup(other_param).await.is_ok());
+
+        let index_path = Path::new(&data_dir).join("index");
+        let index_manager = RaftIndexInnerManager::init(&index_path.to_string_lossy())
+            .await
+            .unwrap();
+        assert_eq!(index_manager.last_applied_log, 4);
+        assert_eq!(index_manager.raft_index.member, vec![1]);
+        assert_eq!(index_manager.raft_index.snapshots[0].end_index, 3);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
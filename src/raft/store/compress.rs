@@ -0,0 +1,143 @@
+use std::fs::File;
+use std::io::{BufReader, BufWriter, Write};
+use std::path::Path;
+
+const ZSTD_LEVEL: i32 = 3;
+
+/// 已封存日志文件的压缩方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogCompressType {
+    #[default]
+    None,
+    Zstd,
+    Lz4,
+}
+
+impl LogCompressType {
+    pub fn from_name(name: &str) -> Self {
+        match name.to_lowercase().as_str() {
+            "zstd" => LogCompressType::Zstd,
+            "lz4" => LogCompressType::Lz4,
+            _ => LogCompressType::None,
+        }
+    }
+
+    pub fn from_data(v: u32) -> anyhow::Result<Self> {
+        match v {
+            0 => Ok(LogCompressType::None),
+            1 => Ok(LogCompressType::Zstd),
+            2 => Ok(LogCompressType::Lz4),
+            _ => Err(anyhow::anyhow!("unknown log compress type {}", v)),
+        }
+    }
+
+    pub fn get_data(&self) -> u32 {
+        match self {
+            LogCompressType::None => 0,
+            LogCompressType::Zstd => 1,
+            LogCompressType::Lz4 => 2,
+        }
+    }
+
+    pub fn get_file_suffix(&self) -> &'static str {
+        match self {
+            LogCompressType::None => "",
+            LogCompressType::Zstd => ".zst",
+            LogCompressType::Lz4 => ".lz4",
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        *self == LogCompressType::None
+    }
+}
+
+/// 先写临时文件再改名，避免中断后留下不完整的文件
+fn write_by_tmp(
+    dst: &Path,
+    f: impl FnOnce(&mut BufWriter<File>) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let mut tmp_path = dst.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let mut writer = BufWriter::new(File::create(&tmp_path)?);
+    f(&mut writer)?;
+    writer.flush()?;
+    writer.get_ref().sync_all()?;
+    drop(writer);
+    std::fs::rename(&tmp_path, dst)?;
+    Ok(())
+}
+
+/// 压缩文件，返回压缩后的文件大小
+pub fn compress_file(
+    src: &Path,
+    dst: &Path,
+    compress_type: LogCompressType,
+) -> anyhow::Result<u64> {
+    let mut reader = BufReader::new(File::open(src)?);
+    write_by_tmp(dst, |writer| {
+        match compress_type {
+            LogCompressType::Zstd => {
+                zstd::stream::copy_encode(&mut reader, writer, ZSTD_LEVEL)?;
+            }
+            LogCompressType::Lz4 => {
+                let mut encoder = lz4_flex::frame::FrameEncoder::new(writer);
+                std::io::copy(&mut reader, &mut encoder)?;
+                encoder.finish()?;
+            }
+            LogCompressType::None => {
+                std::io::copy(&mut reader, writer)?;
+            }
+        }
+        Ok(())
+    })?;
+    Ok(std::fs::metadata(dst)?.len())
+}
+
+pub fn decompress_file(
+    src: &Path,
+    dst: &Path,
+    compress_type: LogCompressType,
+) -> anyhow::Result<()> {
+    let mut reader = BufReader::new(File::open(src)?);
+    write_by_tmp(dst, |writer| {
+        match compress_type {
+            LogCompressType::Zstd => {
+                zstd::stream::copy_decode(&mut reader, writer)?;
+            }
+            LogCompressType::Lz4 => {
+                let mut decoder = lz4_flex::frame::FrameDecoder::new(reader);
+                std::io::copy(&mut decoder, writer)?;
+            }
+            LogCompressType::None => {
+                std::io::copy(&mut reader, writer)?;
+            }
+        }
+        Ok(())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compress_and_decompress() {
+        let dir = std::env::temp_dir().join(format!("ratchjob_compress_{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let src = dir.join("log_1");
+        let mut data = b"raft log data".repeat(1000);
+        data.resize(1024 * 1024, 0);
+        std::fs::write(&src, &data).unwrap();
+        for compress_type in [LogCompressType::Zstd, LogCompressType::Lz4] {
+            let compress_path = dir.join(format!("log_1{}", compress_type.get_file_suffix()));
+            let len = compress_file(&src, &compress_path, compress_type).unwrap();
+            assert!(len < data.len() as u64 / 10);
+            let dst = dir.join("log_1_restore");
+            decompress_file(&compress_path, &dst, compress_type).unwrap();
+            assert_eq!(std::fs::read(&dst).unwrap(), data);
+        }
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -10,8 +10,8 @@ use crate::raft::store::raftlog::{
 use crate::raft::store::raftsnapshot::{
     RaftSnapshotManager, RaftSnapshotRequest, RaftSnapshotResponse,
 };
-use crate::raft::store::StoreUtils;
 use crate::raft::store::{ClientRequest, ClientResponse, ShutdownError};
+use crate::raft::store::{LogValueFormat, StoreUtils};
 use actix::prelude::*;
 use async_raft_ext::raft::{Entry, MembershipConfig};
 use async_raft_ext::storage::{CurrentSnapshotData, HardState, InitialState};
@@ -36,6 +36,7 @@ pub struct Store {
     log_manager: Addr<RaftLogManager>,
     //data_store: Addr<RaftDataStore>,
     apply_manager: Addr<StateApplyManager>,
+    log_value_format: LogValueFormat,
 }
 
 impl Store {
@@ -46,6 +47,7 @@ impl Store {
         log_manager: Addr<RaftLogManager>,
         //data_store: Addr<RaftDataStore>,
         apply_manager: Addr<StateApplyManager>,
+        log_value_format: LogValueFormat,
     ) -> Self {
         Self {
             node_id,
@@ -54,6 +56,7 @@ impl Store {
             log_manager,
             //data_store,
             apply_manager,
+            log_value_format,
         }
     }
 
@@ -215,7 +218,7 @@ impl RaftStorage<ClientRequest, ClientResponse> for Store {
     }
 
     async fn append_entry_to_log(&self, entry: &Entry<ClientRequest>) -> anyhow::Result<()> {
-        let record = StoreUtils::entry_to_record(entry)?;
+        let record = StoreUtils::entry_to_record(entry, self.log_value_format)?;
         self.log_manager
             .send(RaftLogManagerRequest::Write(record))
             .await??;
@@ -225,7 +228,7 @@ impl RaftStorage<ClientRequest, ClientResponse> for Store {
     async fn replicate_to_log(&self, entries: &[Entry<ClientRequest>]) -> anyhow::Result<()> {
         let mut records = Vec::with_capacity(entries.len());
         for item in entries {
-            let record = StoreUtils::entry_to_record(item)?;
+            let record = StoreUtils::entry_to_record(item, self.log_value_format)?;
             records.push(record);
         }
         self.log_manager
@@ -303,7 +306,7 @@ impl RaftStorage<ClientRequest, ClientResponse> for Store {
                     snapshot_id.to_string(),
                     membership_config,
                 );
-                let record = StoreUtils::entry_to_record(&entry)?;
+                let record = StoreUtils::entry_to_record(&entry, self.log_value_format)?;
                 self.log_manager
                     .send(RaftLogManagerRequest::BuildSnapshotPointerLog(record))
                     .await??;
@@ -363,7 +366,7 @@ impl RaftStorage<ClientRequest, ClientResponse> for Store {
         //add new_snapshot_pointer
         let membership_config = self.get_membership_config().await?;
         let entry = Entry::new_snapshot_pointer(index, term, id, membership_config);
-        let record = StoreUtils::entry_to_record(&entry)?;
+        let record = StoreUtils::entry_to_record(&entry, self.log_value_format)?;
         self.log_manager
             .send(RaftLogManagerRequest::InstallSnapshotPointerLog(record))
             .await??;
@@ -0,0 +1,638 @@
+//! 离线查看raft数据文件，只读打开，建议在复制出来的数据目录上执行
+use std::collections::VecDeque;
+use std::io::{Cursor, SeekFrom, Write};
+use std::path::Path;
+
+use base64::{engine::general_purpose, Engine};
+use binrw::BinReaderExt;
+use quick_protobuf::{BytesReader, MessageRead};
+use serde::Serialize;
+use serde_json::{json, Value};
+use tokio::{
+    fs::OpenOptions,
+    io::{AsyncReadExt, AsyncSeekExt},
+};
+
+use super::{
+    log::{LogRange, LogRecord, LogSnapshotItem, RaftIndex, SnapshotHeader},
+    model::{LogIndexHeaderDo, LogRecordDto, RaftIndexDto, SnapshotHeaderDto, SnapshotRecordDto},
+    raftlog::{RaftLogManager, ReadOnlyLogFile},
+    raftsnapshot::RaftSnapshotManager,
+    StoreUtils,
+};
+use crate::cache::model::CacheKey;
+use crate::common::byte_utils::{bin_to_id, bin_to_id_result};
+use crate::common::constant::{
+    APP_INFO_TABLE_NAME, AUDIT_TABLE_NAME, CACHE_TABLE_NAME, JOB_GLUE_TABLE_NAME, JOB_TABLE_NAME,
+    JOB_TASK_HISTORY_TABLE_NAME, JOB_TASK_RUNNING_TABLE_NAME, JOB_TASK_TABLE_NAME,
+    JOB_VERSION_TABLE_NAME, NAMESPACE_TABLE_NAME, SCHEDULE_PARTITION_TABLE_NAME,
+    SCHEDULE_PAUSE_TABLE_NAME, SEQUENCE_TABLE_NAME, USER_TABLE_NAME,
+};
+use crate::common::pb::data_object::{
+    AppInfoDo, AuditRecordDo, CacheItemDo, JobDo, JobGlueDo, JobTaskDo, JobVersionDo, NamespaceDo,
+    SchedulePartitionDo, SchedulePauseDo, UserInfoDo,
+};
+use crate::common::protobuf_utils::MessageBufReader;
+
+const READ_BUF_SIZE: usize = 64 * 1024;
+const LOG_MAGIC: u32 = 0x42313644;
+
+/// 顺序读取文件中长度前缀的protobuf消息
+struct FileMessageScanner {
+    file: tokio::fs::File,
+    reader: MessageBufReader,
+    /// 已读入缓存的数据末尾位置
+    read_position: u64,
+    /// 已解析消息的末尾位置
+    position: u64,
+    is_eof: bool,
+}
+
+impl FileMessageScanner {
+    /// 文件需已定位到start
+    fn new(file: tokio::fs::File, start: u64) -> Self {
+        Self {
+            file,
+            reader: MessageBufReader::new(),
+            read_position: start,
+            position: start,
+            is_eof: false,
+        }
+    }
+
+    async fn open(path: &str, start: u64) -> anyhow::Result<Self> {
+        let mut file = OpenOptions::new().read(true).open(path).await?;
+        file.seek(SeekFrom::Start(start)).await?;
+        Ok(Self::new(file, start))
+    }
+
+    /// 读到文件末尾或0填充区域时返回None；末尾消息不完整时返回错误
+    async fn next_message(&mut self) -> anyhow::Result<Option<Vec<u8>>> {
+        loop {
+            if self.read_position > self.position && self.reader.is_empty() {
+                // 日志文件尾部为0填充
+                return Ok(None);
+            }
+            if let Some(v) = self.reader.next_message_vec() {
+                self.position += v.len() as u64;
+                return Ok(Some(v.to_vec()));
+            }
+            if self.is_eof {
+                if self.read_position > self.position {
+                    return Err(anyhow::anyhow!(
+                        "incomplete message at position:{},file len:{}",
+                        self.position,
+                        self.read_position
+                    ));
+                }
+                return Ok(None);
+            }
+            let mut buf = vec![0u8; READ_BUF_SIZE];
+            let read_len = self.file.read(&mut buf).await?;
+            if read_len == 0 {
+                self.is_eof = true;
+            } else {
+                self.reader.append_next_buf(&buf[..read_len]);
+                self.read_position += read_len as u64;
+            }
+        }
+    }
+}
+
+fn decode_message<'a, M: MessageRead<'a>>(buf: &'a [u8]) -> anyhow::Result<M> {
+    let mut reader = BytesReader::from_bytes(buf);
+    Ok(reader.read_message(buf)?)
+}
+
+fn bytes_to_json(data: &[u8]) -> Value {
+    match std::str::from_utf8(data) {
+        Ok(v) if !v.chars().any(|c| c.is_control()) => Value::String(v.to_owned()),
+        _ => Value::String(format!("base64:{}", general_purpose::STANDARD.encode(data))),
+    }
+}
+
+fn to_json<T: Serialize>(v: &T) -> anyhow::Result<Value> {
+    Ok(serde_json::to_value(v)?)
+}
+
+fn key_to_json(tree: &str, key: &[u8]) -> Value {
+    if tree == CACHE_TABLE_NAME.as_str() {
+        if let Ok(v) = CacheKey::from_db_key(key.to_vec()) {
+            return json!({"cacheType": v.cache_type, "key": v.key});
+        }
+    } else if (tree == JOB_VERSION_TABLE_NAME.as_str() || tree == JOB_GLUE_TABLE_NAME.as_str())
+        && key.len() == 16
+    {
+        return json!(format!("{}:{}", bin_to_id(&key[..8]), bin_to_id(&key[8..])));
+    } else if tree != SEQUENCE_TABLE_NAME.as_str() && key.len() == 8 {
+        return json!(bin_to_id(key));
+    }
+    bytes_to_json(key)
+}
+
+fn value_to_json(tree: &str, value: &[u8]) -> anyhow::Result<Value> {
+    let v = match tree {
+        t if t == SEQUENCE_TABLE_NAME.as_str() => json!(bin_to_id_result(value)?),
+        t if t == APP_INFO_TABLE_NAME.as_str() => to_json(&decode_message::<AppInfoDo>(value)?)?,
+        t if t == JOB_TABLE_NAME.as_str() => to_json(&decode_message::<JobDo>(value)?)?,
+        t if t == JOB_TASK_TABLE_NAME.as_str()
+            || t == JOB_TASK_RUNNING_TABLE_NAME.as_str()
+            || t == JOB_TASK_HISTORY_TABLE_NAME.as_str() =>
+        {
+            to_json(&decode_message::<JobTaskDo>(value)?)?
+        }
+        t if t == CACHE_TABLE_NAME.as_str() => {
+            let item: CacheItemDo = decode_message(value)?;
+            json!({
+                "cache_type": item.cache_type,
+                "key": item.key,
+                "data": bytes_to_json(&item.data),
+                "timeout": item.timeout,
+            })
+        }
+        t if t == USER_TABLE_NAME.as_str() => {
+            //不输出密码摘要
+            let mut v = to_json(&decode_message::<UserInfoDo>(value)?)?;
+            if let Some(obj) = v.as_object_mut() {
+                obj.remove("password_hash");
+            }
+            v
+        }
+        t if t == NAMESPACE_TABLE_NAME.as_str() => to_json(&decode_message::<NamespaceDo>(value)?)?,
+        t if t == JOB_VERSION_TABLE_NAME.as_str() => {
+            to_json(&decode_message::<JobVersionDo>(value)?)?
+        }
+        t if t == JOB_GLUE_TABLE_NAME.as_str() => to_json(&decode_message::<JobGlueDo>(value)?)?,
+        t if t == AUDIT_TABLE_NAME.as_str() => to_json(&decode_message::<AuditRecordDo>(value)?)?,
+        t if t == SCHEDULE_PARTITION_TABLE_NAME.as_str() => {
+            to_json(&decode_message::<SchedulePartitionDo>(value)?)?
+        }
+        t if t == SCHEDULE_PAUSE_TABLE_NAME.as_str() => {
+            to_json(&decode_message::<SchedulePauseDo>(value)?)?
+        }
+        _ => bytes_to_json(value),
+    };
+    Ok(v)
+}
+
+pub fn snapshot_header_to_json(header: &SnapshotHeaderDto) -> Value {
+    json!({
+        "lastIndex": header.last_index,
+        "lastTerm": header.last_term,
+        "member": header.member,
+        "memberAfterConsensus": header.member_after_consensus,
+        "nodeAddrs": header.node_addrs,
+    })
+}
+
+/// 快照记录按表解码为对应的数据对象
+pub fn snapshot_record_to_json(record: &SnapshotRecordDto) -> anyhow::Result<Value> {
+    Ok(json!({
+        "tree": record.tree,
+        "key": key_to_json(&record.tree, &record.key),
+        "opType": record.op_type,
+        "value": value_to_json(&record.tree, &record.value)?,
+    }))
+}
+
+pub fn log_record_to_json(record: LogRecordDto) -> Value {
+    let index = record.index;
+    let term = record.term;
+    let raw = record.value.clone();
+    match StoreUtils::log_record_to_entry(record) {
+        Ok(entry) => json!({
+            "index": index,
+            "term": term,
+            "payload": entry.payload,
+        }),
+        Err(e) => json!({
+            "index": index,
+            "term": term,
+            "error": e.to_string(),
+            "raw": bytes_to_json(&raw),
+        }),
+    }
+}
+
+fn write_json_line<W: Write>(out: &mut W, v: &Value) -> anyhow::Result<()> {
+    serde_json::to_writer(&mut *out, v)?;
+    out.write_all(b"\n")?;
+    Ok(())
+}
+
+/// 只读方式读取索引文件
+pub async fn read_index(data_dir: &str) -> anyhow::Result<(u64, RaftIndexDto)> {
+    let index_path = Path::new(data_dir)
+        .join("index")
+        .to_string_lossy()
+        .into_owned();
+    let mut file = OpenOptions::new().read(true).open(&index_path).await?;
+    let mut header_buf = vec![0u8; 8];
+    file.read_exact(&mut header_buf).await?;
+    let mut scanner = FileMessageScanner::new(file, 8);
+    let last_applied_log = bin_to_id_result(&header_buf)?;
+    let buf = scanner
+        .next_message()
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("raft index is empty,path:{}", &index_path))?;
+    let index: RaftIndex = decode_message(&buf)?;
+    Ok((last_applied_log, index.into()))
+}
+
+pub async fn dump_index<W: Write>(data_dir: &str, out: &mut W) -> anyhow::Result<()> {
+    let (last_applied_log, index) = read_index(data_dir).await?;
+    let logs: Vec<Value> = index
+        .logs
+        .iter()
+        .map(|e| {
+            json!({
+                "id": e.id,
+                "preTerm": e.pre_term,
+                "startIndex": e.start_index,
+                "recordCount": e.record_count,
+                "splitOffIndex": e.split_off_index,
+                "isClose": e.is_close,
+                "markRemove": e.mark_remove,
+                "compressType": e.compress_type,
+            })
+        })
+        .collect();
+    let snapshots: Vec<Value> = index
+        .snapshots
+        .iter()
+        .map(|e| json!({"id": e.id, "endIndex": e.end_index}))
+        .collect();
+    let v = json!({
+        "lastAppliedLog": last_applied_log,
+        "logs": logs,
+        "currentLog": index.current_log,
+        "snapshots": snapshots,
+        "lastSnapshot": index.last_snapshot,
+        "lastSnapshotIndex": index.last_snapshot_index,
+        "lastSnapshotTerm": index.last_snapshot_term,
+        "currentTerm": index.current_term,
+        "votedFor": index.voted_for,
+        "member": index.member,
+        "memberAfterConsensus": index.member_after_consensus,
+        "nodeAddrs": index.node_addrs,
+    });
+    write_json_line(out, &v)
+}
+
+async fn open_snapshot(path: &str) -> anyhow::Result<(SnapshotHeaderDto, FileMessageScanner)> {
+    let mut scanner = FileMessageScanner::open(path, 0).await?;
+    let buf = scanner
+        .next_message()
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("read snapshot head error,path:{}", path))?;
+    let header: SnapshotHeader = decode_message(&buf)?;
+    Ok((header.into(), scanner))
+}
+
+/// 输出快照头及每条快照记录，每行一个json
+pub async fn dump_snapshot<W: Write>(
+    path: &str,
+    limit: Option<u64>,
+    out: &mut W,
+) -> anyhow::Result<u64> {
+    let (header, mut scanner) = open_snapshot(path).await?;
+    write_json_line(out, &json!({ "header": snapshot_header_to_json(&header) }))?;
+    let mut count = 0;
+    while limit.map(|v| count < v).unwrap_or(true) {
+        let buf = if let Some(buf) = scanner.next_message().await? {
+            buf
+        } else {
+            break;
+        };
+        let item: LogSnapshotItem = decode_message(&buf)?;
+        let record: SnapshotRecordDto = item.into();
+        write_json_line(out, &snapshot_record_to_json(&record)?)?;
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// 只读方式读取一个日志文件的全部记录
+async fn read_log_file(
+    path: &str,
+    mut f: impl FnMut(LogRecordDto) -> anyhow::Result<bool>,
+) -> anyhow::Result<LogIndexHeaderDo> {
+    let mut file = OpenOptions::new().read(true).open(path).await?;
+    let mut header_buf = vec![0u8; 4096];
+    file.read_exact(&mut header_buf).await?;
+    let header: LogIndexHeaderDo = Cursor::new(&header_buf).read_be()?;
+    if header.magic != LOG_MAGIC {
+        return Err(anyhow::anyhow!(
+            "log file magic error,path:{},magic:{:x}",
+            path,
+            header.magic
+        ));
+    }
+    file.seek(SeekFrom::Start(header.data_area_index as u64))
+        .await?;
+    let mut scanner = FileMessageScanner::new(file, header.data_area_index as u64);
+    while let Some(buf) = scanner.next_message().await? {
+        let record: LogRecord = decode_message(&buf)?;
+        if !f(record.into())? {
+            break;
+        }
+    }
+    Ok(header)
+}
+
+/// 压缩的日志文件解压到临时目录后读取，不修改数据目录
+async fn read_log_range(
+    data_dir: &str,
+    log_range: &LogRange,
+    f: impl FnMut(LogRecordDto) -> anyhow::Result<bool>,
+) -> anyhow::Result<LogIndexHeaderDo> {
+    let log_file = ReadOnlyLogFile::open(data_dir, log_range).await?;
+    read_log_file(&log_file.path, f).await
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LogQueryParam {
+    /// 从该日志序号开始输出
+    pub start: Option<u64>,
+    pub limit: u64,
+    /// 输出最后limit条日志
+    pub tail: bool,
+}
+
+/// 按序号输出raft日志，每行一个json
+pub async fn dump_logs<W: Write>(
+    data_dir: &str,
+    param: &LogQueryParam,
+    out: &mut W,
+) -> anyhow::Result<u64> {
+    let (_, index) = read_index(data_dir).await?;
+    let start = param.start.unwrap_or(0);
+    let mut tail_records = VecDeque::new();
+    let mut count = 0;
+    for log_range in index.logs.iter().filter(|e| !e.mark_remove) {
+        if log_range.is_close && log_range.start_index + log_range.record_count <= start {
+            continue;
+        }
+        if !param.tail && count >= param.limit {
+            break;
+        }
+        let split_off_index = std::cmp::max(log_range.split_off_index, start);
+        read_log_range(data_dir, log_range, |record| {
+            if record.index < split_off_index {
+                return Ok(true);
+            }
+            if param.tail {
+                if tail_records.len() as u64 >= param.limit {
+                    tail_records.pop_front();
+                }
+                tail_records.push_back(record);
+                return Ok(true);
+            }
+            write_json_line(out, &log_record_to_json(record))?;
+            count += 1;
+            Ok(count < param.limit)
+        })
+        .await?;
+    }
+    if param.tail {
+        count = tail_records.len() as u64;
+        for record in tail_records {
+            write_json_line(out, &log_record_to_json(record))?;
+        }
+    }
+    Ok(count)
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyReport {
+    pub snapshot_count: u64,
+    pub snapshot_record_count: u64,
+    pub log_file_count: u64,
+    pub log_record_count: u64,
+    pub last_log_index: u64,
+    pub last_applied_log: u64,
+    pub errors: Vec<String>,
+}
+
+impl VerifyReport {
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+async fn verify_snapshot(
+    path: &str,
+    end_index: u64,
+    report: &mut VerifyReport,
+) -> anyhow::Result<()> {
+    let (header, mut scanner) = open_snapshot(path).await?;
+    if header.last_index != end_index {
+        report.errors.push(format!(
+            "snapshot last index not match,path:{},header:{},index:{}",
+            path, header.last_index, end_index
+        ));
+    }
+    while let Some(buf) = scanner.next_message().await? {
+        let item: LogSnapshotItem = decode_message(&buf)?;
+        let record: SnapshotRecordDto = item.into();
+        if let Err(e) = value_to_json(&record.tree, &record.value) {
+            report.errors.push(format!(
+                "snapshot record decode error,path:{},tree:{},{}",
+                path, &record.tree, e
+            ));
+        }
+        report.snapshot_record_count += 1;
+    }
+    Ok(())
+}
+
+/// 校验数据目录：索引、快照与日志文件能否完整解析，日志序号是否连续
+pub async fn verify_data_dir(data_dir: &str) -> anyhow::Result<VerifyReport> {
+    let (last_applied_log, index) = read_index(data_dir).await?;
+    let mut report = VerifyReport {
+        last_applied_log,
+        ..Default::default()
+    };
+    for snapshot in &index.snapshots {
+        let path = RaftSnapshotManager::get_snapshot_path(data_dir, snapshot.id);
+        report.snapshot_count += 1;
+        if let Err(e) = verify_snapshot(&path, snapshot.end_index, &mut report).await {
+            report
+                .errors
+                .push(format!("snapshot read error,path:{},{}", &path, e));
+        }
+    }
+    let mut next_start_index = None;
+    for log_range in index.logs.iter().filter(|e| !e.mark_remove) {
+        let log_path = RaftLogManager::get_log_path(data_dir, log_range);
+        report.log_file_count += 1;
+        if let Some(next_start_index) = next_start_index {
+            if log_range.start_index != next_start_index {
+                report.errors.push(format!(
+                    "log range not continuous,path:{},start index:{},expect:{}",
+                    &log_path, log_range.start_index, next_start_index
+                ));
+            }
+        }
+        let mut expect_index = log_range.start_index;
+        let mut errors = vec![];
+        let r = read_log_range(data_dir, log_range, |record| {
+            if record.index != expect_index {
+                errors.push(format!(
+                    "log index not continuous,path:{},index:{},expect:{}",
+                    &log_path, record.index, expect_index
+                ));
+                return Ok(false);
+            }
+            if let Err(e) = StoreUtils::log_record_to_entry(record) {
+                errors.push(format!(
+                    "log payload decode error,path:{},index:{},{}",
+                    &log_path, expect_index, e
+                ));
+            }
+            expect_index += 1;
+            Ok(true)
+        })
+        .await;
+        match r {
+            Ok(header) => {
+                if header.first_index != log_range.start_index {
+                    errors.push(format!(
+                        "log header first index not match,path:{},header:{},index:{}",
+                        &log_path, header.first_index, log_range.start_index
+                    ));
+                }
+            }
+            Err(e) => errors.push(format!("log read error,path:{},{}", &log_path, e)),
+        }
+        let record_count = expect_index - log_range.start_index;
+        if log_range.is_close && record_count != log_range.record_count {
+            errors.push(format!(
+                "log record count not match,path:{},read:{},index:{}",
+                &log_path, record_count, log_range.record_count
+            ));
+        }
+        report.errors.append(&mut errors);
+        report.log_record_count += record_count;
+        if expect_index > log_range.start_index {
+            report.last_log_index = expect_index - 1;
+        }
+        next_start_index = Some(expect_index);
+    }
+    let last_index = std::cmp::max(report.last_log_index, index.last_snapshot_index);
+    if last_applied_log > last_index {
+        report.errors.push(format!(
+            "last applied log is beyond last log,last applied:{},last index:{}",
+            last_applied_log, last_index
+        ));
+    }
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::byte_utils::id_to_bin;
+    use crate::raft::store::backup::{restore_backup, BackupWriter, RestoreParam};
+    use crate::raft::store::ClientRequest;
+    use async_raft_ext::raft::EntryPayload;
+    use quick_protobuf::Writer;
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    fn read_lines(out: Vec<u8>) -> Vec<Value> {
+        String::from_utf8(out)
+            .unwrap()
+            .lines()
+            .map(|v| serde_json::from_str(v).unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn inspect_restored_dir() {
+        let dir = std::env::temp_dir().join(format!("ratchjob_inspect_{}", std::process::id()));
+        std::fs::remove_dir_all(&dir).ok();
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("a.bak").to_string_lossy().into_owned();
+        let data_dir = dir.join("db").to_string_lossy().into_owned();
+        let header = SnapshotHeaderDto {
+            last_index: 2,
+            last_term: 1,
+            member: vec![1],
+            member_after_consensus: vec![],
+            node_addrs: HashMap::new(),
+        };
+        let mut writer = BackupWriter::init(&path, &header).await.unwrap();
+        writer
+            .write_snapshot_record(&SnapshotRecordDto {
+                tree: SEQUENCE_TABLE_NAME.clone(),
+                key: b"job".to_vec(),
+                value: id_to_bin(10),
+                op_type: 0,
+            })
+            .await
+            .unwrap();
+        for index in 3..8 {
+            writer
+                .write_log_record(&LogRecordDto {
+                    index,
+                    term: 2,
+                    value: serde_json::to_vec(&EntryPayload::<ClientRequest>::Blank).unwrap(),
+                })
+                .await
+                .unwrap();
+        }
+        writer.finish().await.unwrap();
+        restore_backup(RestoreParam {
+            backup_path: path,
+            data_dir: data_dir.clone(),
+            node_id: 1,
+            node_addr: Arc::new("127.0.0.1:8825".to_owned()),
+            end_index: None,
+            force: false,
+        })
+        .await
+        .unwrap();
+
+        let report = verify_data_dir(&data_dir).await.unwrap();
+        assert!(report.is_ok(), "{:?}", &report);
+        assert_eq!(report.snapshot_record_count, 1);
+        assert_eq!(report.log_record_count, 5);
+        assert_eq!(report.last_log_index, 7);
+
+        let mut out = Vec::new();
+        let snapshot_path = RaftSnapshotManager::get_snapshot_path(&data_dir, 1);
+        dump_snapshot(&snapshot_path, None, &mut out).await.unwrap();
+        let lines = read_lines(out);
+        assert_eq!(lines[0]["header"]["lastIndex"], 2);
+        assert_eq!(lines[1]["key"], "job");
+        assert_eq!(lines[1]["value"], 10);
+
+        let mut out = Vec::new();
+        let param = LogQueryParam {
+            start: None,
+            limit: 2,
+            tail: true,
+        };
+        assert_eq!(dump_logs(&data_dir, &param, &mut out).await.unwrap(), 2);
+        let lines = read_lines(out);
+        assert_eq!(lines[0]["index"], 6);
+        assert_eq!(lines[1]["index"], 7);
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn user_value_without_password_hash() {
+        let user = UserInfoDo {
+            username: "admin".into(),
+            password_hash: "hash".into(),
+            ..Default::default()
+        };
+        let mut buf = Vec::new();
+        Writer::new(&mut buf).write_message(&user).unwrap();
+        let v = value_to_json(USER_TABLE_NAME.as_str(), &buf).unwrap();
+        assert_eq!(v["username"], "admin");
+        assert!(v.get("password_hash").is_none());
+    }
+}
@@ -160,6 +160,7 @@ pub struct LogRange {
     pub split_off_index: u64,
     pub is_close: bool,
     pub mark_remove: bool,
+    pub compress_type: u32,
 }
 
 impl<'a> MessageRead<'a> for LogRange {
@@ -174,6 +175,7 @@ impl<'a> MessageRead<'a> for LogRange {
                 Ok(40) => msg.split_off_index = r.read_uint64(bytes)?,
                 Ok(48) => msg.is_close = r.read_bool(bytes)?,
                 Ok(56) => msg.mark_remove = r.read_bool(bytes)?,
+                Ok(64) => msg.compress_type = r.read_uint32(bytes)?,
                 Ok(t) => { r.read_unknown(bytes, t)?; }
                 Err(e) => return Err(e),
             }
@@ -192,6 +194,7 @@ impl MessageWrite for LogRange {
         + if self.split_off_index == 0u64 { 0 } else { 1 + sizeof_varint(*(&self.split_off_index) as u64) }
         + if self.is_close == false { 0 } else { 1 + sizeof_varint(*(&self.is_close) as u64) }
         + if self.mark_remove == false { 0 } else { 1 + sizeof_varint(*(&self.mark_remove) as u64) }
+        + if self.compress_type == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.compress_type) as u64) }
     }
 
     fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
@@ -202,6 +205,7 @@ impl MessageWrite for LogRange {
         if self.split_off_index != 0u64 { w.write_with_tag(40, |w| w.write_uint64(*&self.split_off_index))?; }
         if self.is_close != false { w.write_with_tag(48, |w| w.write_bool(*&self.is_close))?; }
         if self.mark_remove != false { w.write_with_tag(56, |w| w.write_bool(*&self.mark_remove))?; }
+        if self.compress_type != 0u32 { w.write_with_tag(64, |w| w.write_uint32(*&self.compress_type))?; }
         Ok(())
     }
 }
@@ -350,3 +354,43 @@ impl<'a> MessageWrite for RaftIndex<'a> {
     }
 }
 
+
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Debug, Default, PartialEq, Clone)]
+pub struct BackupItem<'a> {
+    pub item_type: u32,
+    pub data: Cow<'a, [u8]>,
+    pub checksum: u32,
+}
+
+impl<'a> MessageRead<'a> for BackupItem<'a> {
+    fn from_reader(r: &mut BytesReader, bytes: &'a [u8]) -> Result<Self> {
+        let mut msg = Self::default();
+        while !r.is_eof() {
+            match r.next_tag(bytes) {
+                Ok(8) => msg.item_type = r.read_uint32(bytes)?,
+                Ok(18) => msg.data = r.read_bytes(bytes).map(Cow::Borrowed)?,
+                Ok(24) => msg.checksum = r.read_uint32(bytes)?,
+                Ok(t) => { r.read_unknown(bytes, t)?; }
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(msg)
+    }
+}
+
+impl<'a> MessageWrite for BackupItem<'a> {
+    fn get_size(&self) -> usize {
+        0
+        + if self.item_type == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.item_type) as u64) }
+        + if self.data == Cow::Borrowed(b"") { 0 } else { 1 + sizeof_len((&self.data).len()) }
+        + if self.checksum == 0u32 { 0 } else { 1 + sizeof_varint(*(&self.checksum) as u64) }
+    }
+
+    fn write_message<W: WriterBackend>(&self, w: &mut Writer<W>) -> Result<()> {
+        if self.item_type != 0u32 { w.write_with_tag(8, |w| w.write_uint32(*&self.item_type))?; }
+        if self.data != Cow::Borrowed(b"") { w.write_with_tag(18, |w| w.write_bytes(&**&self.data))?; }
+        if self.checksum != 0u32 { w.write_with_tag(24, |w| w.write_uint32(*&self.checksum))?; }
+        Ok(())
+    }
+}
@@ -1,6 +1,7 @@
 #![allow(deprecated)]
 use self::model::LogRecordDto;
 use crate::app::model::{AppManagerRaftReq, AppManagerRaftResult};
+use crate::audit::model::actor_model::{AuditManagerRaftReq, AuditManagerRaftResult};
 use crate::cache::actor_model::{CacheManagerRaftReq, CacheManagerRaftResult};
 use crate::job::model::actor_model::{JobManagerRaftReq, JobManagerRaftResult};
 use crate::namespace::model::actor_model::{NamespaceManagerRaftReq, NamespaceManagerRaftResult};
@@ -12,7 +13,10 @@ use async_raft_ext::{AppData, AppDataResponse};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 
+pub mod backup;
+pub mod compress;
 pub mod core;
+pub mod inspect;
 pub mod log;
 pub mod model;
 pub mod raftapply;
@@ -23,6 +27,35 @@ pub mod raftsnapshot;
 
 pub type NodeId = u64;
 
+/// raft日志内容编码版本：首字节为1表示MessagePack；旧版本json格式首字节为'{'或'"'
+pub const LOG_VALUE_MSGPACK_V1: u8 = 1;
+
+/// raft日志内容写入格式；滚动升级期间旧版本节点只能读取json，全部节点升级后再开启MessagePack
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LogValueFormat {
+    #[default]
+    Json,
+    MsgpackV1,
+}
+
+impl LogValueFormat {
+    pub fn new(msgpack_enable: bool) -> Self {
+        if msgpack_enable {
+            Self::MsgpackV1
+        } else {
+            Self::Json
+        }
+    }
+
+    /// 按已有日志内容判断格式
+    pub fn from_value(value: &[u8]) -> Self {
+        match value.first() {
+            Some(&LOG_VALUE_MSGPACK_V1) => Self::MsgpackV1,
+            _ => Self::Json,
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum ClientRequest {
     NodeAddr { id: u64, addr: Arc<String> },
@@ -34,6 +67,7 @@ pub enum ClientRequest {
     CacheReq { req: CacheManagerRaftReq },
     UserReq { req: UserManagerRaftReq },
     NamespaceReq { req: NamespaceManagerRaftReq },
+    AuditReq { req: AuditManagerRaftReq },
 }
 
 impl AppData for ClientRequest {}
@@ -68,6 +102,9 @@ pub enum ClientResponse {
     NamespaceResp {
         resp: NamespaceManagerRaftResult,
     },
+    AuditResp {
+        resp: AuditManagerRaftResult,
+    },
 }
 
 impl Default for ClientResponse {
@@ -88,7 +125,7 @@ pub struct StoreUtils;
 
 impl StoreUtils {
     pub fn log_record_to_entry(record: LogRecordDto) -> anyhow::Result<Entry<ClientRequest>> {
-        let payload: EntryPayload<ClientRequest> = serde_json::from_slice(&record.value)?;
+        let payload = Self::decode_payload(&record.value)?;
         let entry = Entry {
             term: record.term,
             index: record.index,
@@ -97,8 +134,11 @@ impl StoreUtils {
         Ok(entry)
     }
 
-    pub fn entry_to_record(entry: &Entry<ClientRequest>) -> anyhow::Result<LogRecordDto> {
-        let value = serde_json::to_vec(&entry.payload)?;
+    pub fn entry_to_record(
+        entry: &Entry<ClientRequest>,
+        format: LogValueFormat,
+    ) -> anyhow::Result<LogRecordDto> {
+        let value = Self::encode_payload(&entry.payload, format)?;
         let record = LogRecordDto {
             index: entry.index,
             term: entry.term,
@@ -106,4 +146,82 @@ impl StoreUtils {
         };
         Ok(record)
     }
+
+    pub fn encode_payload(
+        payload: &EntryPayload<ClientRequest>,
+        format: LogValueFormat,
+    ) -> anyhow::Result<Vec<u8>> {
+        match format {
+            LogValueFormat::Json => Ok(serde_json::to_vec(payload)?),
+            LogValueFormat::MsgpackV1 => {
+                let mut value = vec![LOG_VALUE_MSGPACK_V1];
+                //按字段名编码，兼容字段增减
+                rmp_serde::encode::write_named(&mut value, payload)?;
+                Ok(value)
+            }
+        }
+    }
+
+    pub fn decode_payload(value: &[u8]) -> anyhow::Result<EntryPayload<ClientRequest>> {
+        match LogValueFormat::from_value(value) {
+            LogValueFormat::MsgpackV1 => Ok(rmp_serde::from_slice(&value[1..])?),
+            //兼容旧版本json格式日志
+            LogValueFormat::Json => Ok(serde_json::from_slice(value)?),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::job::model::actor_model::JobManagerRaftReq;
+    use crate::task::model::task::JobTaskInfo;
+
+    fn build_entry() -> Entry<ClientRequest> {
+        let task = JobTaskInfo {
+            task_id: 1,
+            job_id: 2,
+            instance_addr: Arc::new("127.0.0.1:9999".to_owned()),
+            ..Default::default()
+        };
+        Entry {
+            term: 1,
+            index: 3,
+            payload: EntryPayload::Normal(async_raft_ext::raft::EntryNormal {
+                data: ClientRequest::JobReq {
+                    req: JobManagerRaftReq::UpdateTaskList(vec![Arc::new(task)]),
+                },
+            }),
+        }
+    }
+
+    fn task_of(entry: Entry<ClientRequest>) -> Arc<JobTaskInfo> {
+        match entry.payload {
+            EntryPayload::Normal(v) => match v.data {
+                ClientRequest::JobReq {
+                    req: JobManagerRaftReq::UpdateTaskList(mut list),
+                } => list.pop().unwrap(),
+                _ => panic!("unexpected request"),
+            },
+            _ => panic!("unexpected payload"),
+        }
+    }
+
+    #[test]
+    fn log_payload_encode() {
+        let entry = build_entry();
+        let record = StoreUtils::entry_to_record(&entry, LogValueFormat::MsgpackV1).unwrap();
+        assert_eq!(record.value[0], LOG_VALUE_MSGPACK_V1);
+        let json_len = serde_json::to_vec(&entry.payload).unwrap().len();
+        assert!(record.value.len() < json_len);
+        let task = task_of(StoreUtils::log_record_to_entry(record).unwrap());
+        assert_eq!(task.job_id, 2);
+        assert_eq!(task.instance_addr.as_str(), "127.0.0.1:9999");
+
+        // 默认写json，旧版本节点可读取
+        let record = StoreUtils::entry_to_record(&entry, LogValueFormat::default()).unwrap();
+        assert_eq!(record.value, serde_json::to_vec(&entry.payload).unwrap());
+        let task = task_of(StoreUtils::log_record_to_entry(record).unwrap());
+        assert_eq!(task.task_id, 1);
+    }
 }
@@ -254,6 +254,7 @@ impl StateApplyManager {
         snapshot_manager: Addr<RaftSnapshotManager>,
         data_wrap: Arc<RaftDataHandler>,
         last_index: u64,
+        for_backup: bool,
     ) -> anyhow::Result<(SnapshotHeaderDto, Arc<String>, u64)> {
         //1. get last applied log
         let last_log = match log_manager
@@ -292,10 +293,12 @@ impl StateApplyManager {
             member_after_consensus: member_ship.member_after_consensus,
             node_addrs: member_ship.node_addrs,
         };
-        let (writer, snapshot_id, path) = match snapshot_manager
-            .send(RaftSnapshotRequest::NewSnapshot(header.clone()))
-            .await??
-        {
+        let snapshot_request = if for_backup {
+            RaftSnapshotRequest::NewBackupSnapshot(header.clone())
+        } else {
+            RaftSnapshotRequest::NewSnapshot(header.clone())
+        };
+        let (writer, snapshot_id, path) = match snapshot_manager.send(snapshot_request).await?? {
             RaftSnapshotResponse::NewSnapshot(writer, id, path) => (writer, id, path),
             _ => return Err(anyhow::anyhow!("RaftSnapshotResponse is error")),
         };
@@ -303,6 +306,13 @@ impl StateApplyManager {
         data_wrap.build_snapshot(writer.clone()).await?;
 
         //5. flush to file
+        if for_backup {
+            //备份镜像需要马上读取，等待落盘完成
+            writer
+                .send(super::raftsnapshot::SnapshotWriterRequest::Sync)
+                .await??;
+            return Ok((header, path, snapshot_id));
+        }
         writer
             .send(super::raftsnapshot::SnapshotWriterRequest::Flush)
             .await??;
@@ -358,6 +368,8 @@ pub enum StateApplyRequest {
 #[rtype(result = "anyhow::Result<StateApplyResponse>")]
 pub enum StateApplyAsyncRequest {
     BuildSnapshot,
+    /// 构建备份镜像，不影响raft镜像
+    BuildBackupSnapshot,
     ApplyRequest(ApplyRequestDto),
 }
 
@@ -430,7 +442,8 @@ impl Handler<StateApplyAsyncRequest> for StateApplyManager {
         let snapshot_manager = self.snapshot_manager.clone().unwrap();
         let data_wrap = self.data_wrap.clone().unwrap();
         match &msg {
-            StateApplyAsyncRequest::BuildSnapshot => {}
+            StateApplyAsyncRequest::BuildSnapshot | StateApplyAsyncRequest::BuildBackupSnapshot => {
+            }
             StateApplyAsyncRequest::ApplyRequest(req) => {
                 self.last_applied_log = req.index;
             }
@@ -445,6 +458,19 @@ impl Handler<StateApplyAsyncRequest> for StateApplyManager {
                         snapshot_manager,
                         data_wrap,
                         last_index,
+                        false,
+                    )
+                    .await?;
+                    Ok(StateApplyResponse::Snapshot(header, path, snapshot_id))
+                }
+                StateApplyAsyncRequest::BuildBackupSnapshot => {
+                    let (header, path, snapshot_id) = Self::do_build_snapshot(
+                        log_manager,
+                        index_manager,
+                        snapshot_manager,
+                        data_wrap,
+                        last_index,
+                        true,
                     )
                     .await?;
                     Ok(StateApplyResponse::Snapshot(header, path, snapshot_id))
@@ -1,9 +1,11 @@
 use crate::app::core::AppManager;
+use crate::audit::core::AuditManager;
 use crate::cache::core::CacheManager;
 use crate::common::constant::{
-    APP_INFO_TABLE_NAME, CACHE_TABLE_NAME, JOB_TABLE_NAME, JOB_TASK_HISTORY_TABLE_NAME,
-    JOB_TASK_RUNNING_TABLE_NAME, JOB_TASK_TABLE_NAME, NAMESPACE_TABLE_NAME, SEQUENCE_TABLE_NAME,
-    USER_TABLE_NAME,
+    APP_INFO_TABLE_NAME, AUDIT_TABLE_NAME, CACHE_TABLE_NAME, JOB_GLUE_TABLE_NAME, JOB_TABLE_NAME,
+    JOB_TASK_HISTORY_TABLE_NAME, JOB_TASK_RUNNING_TABLE_NAME, JOB_TASK_TABLE_NAME,
+    JOB_VERSION_TABLE_NAME, NAMESPACE_TABLE_NAME, SCHEDULE_PARTITION_TABLE_NAME,
+    SCHEDULE_PAUSE_TABLE_NAME, SEQUENCE_TABLE_NAME, USER_TABLE_NAME,
 };
 use crate::job::core::JobManager;
 use crate::namespace::core::NamespaceManager;
@@ -26,6 +28,7 @@ pub struct RaftDataHandler {
     pub cache_manager: Addr<CacheManager>,
     pub user_manager: Addr<UserManager>,
     pub namespace_manager: Addr<NamespaceManager>,
+    pub audit_manager: Addr<AuditManager>,
 }
 
 impl RaftDataHandler {
@@ -51,6 +54,9 @@ impl RaftDataHandler {
             .send(RaftApplyDataRequest::BuildSnapshot(writer.clone()))
             .await??;
         self.namespace_manager
+            .send(RaftApplyDataRequest::BuildSnapshot(writer.clone()))
+            .await??;
+        self.audit_manager
             .send(RaftApplyDataRequest::BuildSnapshot(writer))
             .await??;
         Ok(())
@@ -68,14 +74,19 @@ impl RaftDataHandler {
                 self.app_manager.send(req).await??;
             }
             ref tree
-                if *tree == JOB_TABLE_NAME.as_str() || *tree == JOB_TASK_TABLE_NAME.as_str() =>
+                if *tree == JOB_TABLE_NAME.as_str()
+                    || *tree == JOB_TASK_TABLE_NAME.as_str()
+                    || *tree == JOB_VERSION_TABLE_NAME.as_str()
+                    || *tree == JOB_GLUE_TABLE_NAME.as_str() =>
             {
                 let req = RaftApplyDataRequest::LoadSnapshotRecord(record);
                 self.job_manager.send(req).await??;
             }
             ref tree
                 if *tree == JOB_TASK_RUNNING_TABLE_NAME.as_str()
-                    || *tree == JOB_TASK_HISTORY_TABLE_NAME.as_str() =>
+                    || *tree == JOB_TASK_HISTORY_TABLE_NAME.as_str()
+                    || *tree == SCHEDULE_PARTITION_TABLE_NAME.as_str()
+                    || *tree == SCHEDULE_PAUSE_TABLE_NAME.as_str() =>
             {
                 let req = RaftApplyDataRequest::LoadSnapshotRecord(record);
                 self.schedule_manager.send(req).await??;
@@ -92,6 +103,10 @@ impl RaftDataHandler {
                 let req = RaftApplyDataRequest::LoadSnapshotRecord(record);
                 self.namespace_manager.send(req).await??;
             }
+            tree if tree == AUDIT_TABLE_NAME.as_str() => {
+                let req = RaftApplyDataRequest::LoadSnapshotRecord(record);
+                self.audit_manager.send(req).await??;
+            }
             _ => {
                 log::warn!(
                     "RaftDataHandler|load_snapshot|ignore_data|tree={}",
@@ -118,6 +133,8 @@ impl RaftDataHandler {
             .do_send(RaftApplyDataRequest::LoadCompleted);
         self.namespace_manager
             .do_send(RaftApplyDataRequest::LoadCompleted);
+        self.audit_manager
+            .do_send(RaftApplyDataRequest::LoadCompleted);
         Ok(())
     }
 
@@ -165,6 +182,9 @@ impl RaftDataHandler {
             ClientRequest::NamespaceReq { req } => {
                 self.namespace_manager.send(req).await.ok();
             }
+            ClientRequest::AuditReq { req } => {
+                self.audit_manager.send(req).await.ok();
+            }
         }
         Ok(())
     }
@@ -217,6 +237,10 @@ impl RaftDataHandler {
                 let r = self.namespace_manager.send(req).await??;
                 Ok(ClientResponse::NamespaceResp { resp: r })
             }
+            ClientRequest::AuditReq { req } => {
+                let r = self.audit_manager.send(req).await??;
+                Ok(ClientResponse::AuditResp { resp: r })
+            }
         }
     }
 
@@ -258,6 +282,9 @@ impl RaftDataHandler {
             ClientRequest::NamespaceReq { req } => {
                 self.namespace_manager.do_send(req);
             }
+            ClientRequest::AuditReq { req } => {
+                self.audit_manager.do_send(req);
+            }
         }
         Ok(())
     }
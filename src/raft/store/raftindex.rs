@@ -122,7 +122,7 @@ impl Drop for RaftIndexManager {
 }
 
 impl RaftIndexManager {
-    fn try_lock(base_path: &str) -> anyhow::Result<std::fs::File> {
+    pub(crate) fn try_lock(base_path: &str) -> anyhow::Result<std::fs::File> {
         let path = Path::new(base_path)
             .join("db_lock")
             .to_string_lossy()
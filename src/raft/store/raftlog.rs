@@ -1,5 +1,7 @@
 #![allow(clippy::suspicious_open_options)]
+use std::collections::{HashSet, VecDeque};
 use std::fmt::{Display, Formatter};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::time::Duration;
 use std::{
     io::{Cursor, SeekFrom},
@@ -17,10 +19,12 @@ use tokio::{
 };
 
 use super::{
+    compress::{compress_file, decompress_file, LogCompressType},
     log::{LogRange, LogRecord},
     model::{LogIndexInfo, LogRecordLoader, RaftIndexDto},
 };
 use crate::{
+    common::datetime_utils::now_millis,
     common::protobuf_utils::{
         inner_sizeof_varint, read_varint64_offset, write_varint64, FileMessageReader,
         MessageBufReader,
@@ -34,6 +38,10 @@ use super::{
 };
 
 const LOG_DATA_BUF_SIZE: u64 = 1024 * 1024;
+const LOG_ACTOR_IDLE_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+const LOG_ACTOR_IDLE_TIMEOUT: u64 = 60 * 1000;
+const TMP_LOG_FILE_MARK: &str = ".reading.";
+static TMP_LOG_FILE_SEQ: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Debug, Default, Clone)]
 struct InnerIdxDto {
@@ -195,7 +203,7 @@ impl LogInnerManager {
         Ok(this)
     }
 
-    async fn flush_log(&mut self) -> anyhow::Result<()> {
+    pub(crate) async fn flush_log(&mut self) -> anyhow::Result<()> {
         let end_index = self.get_end_index();
         if self.last_flush_index < end_index {
             self.data_file.flush().await?;
@@ -633,12 +641,69 @@ impl LogInnerManager {
     }
 }
 
+/// 只读访问日志文件，压缩的日志在阻塞线程中解压到系统临时目录，drop时删除
+pub(crate) struct ReadOnlyLogFile {
+    pub path: String,
+    is_tmp: bool,
+}
+
+impl ReadOnlyLogFile {
+    pub async fn open(base_path: &str, log_range: &LogRange) -> anyhow::Result<Self> {
+        let log_path = RaftLogManager::get_log_path(base_path, log_range);
+        let compress_type = LogCompressType::from_data(log_range.compress_type)?;
+        if compress_type.is_none() || Path::new(&log_path).exists() {
+            return Ok(Self {
+                path: log_path,
+                is_tmp: false,
+            });
+        }
+        let compress_path =
+            RaftLogManager::get_log_compress_path(base_path, log_range.id, compress_type);
+        let tmp_path = std::env::temp_dir()
+            .join(format!(
+                "ratchjob_log_{}_{}_{}",
+                std::process::id(),
+                log_range.id,
+                TMP_LOG_FILE_SEQ.fetch_add(1, Ordering::Relaxed)
+            ))
+            .to_string_lossy()
+            .into_owned();
+        let dst = tmp_path.clone();
+        tokio::task::spawn_blocking(move || {
+            decompress_file(Path::new(&compress_path), Path::new(&dst), compress_type)
+        })
+        .await??;
+        Ok(Self {
+            path: tmp_path,
+            is_tmp: true,
+        })
+    }
+}
+
+impl Drop for ReadOnlyLogFile {
+    fn drop(&mut self) {
+        if self.is_tmp {
+            std::fs::remove_file(&self.path).ok();
+        }
+    }
+}
+
+/// 压缩日志文件的来源，日志actor启动时在阻塞线程中解压
+#[derive(Debug, Clone)]
+pub struct LogFileSource {
+    pub compress_path: String,
+    pub compress_type: LogCompressType,
+    /// 只读时解压到临时文件，actor关闭后删除；否则解压到日志文件并删除压缩文件
+    pub is_tmp: bool,
+}
+
 /// 一个日志文件对应一个RaftLogActor
 pub struct RaftLogActor {
     path: String,
     start_index: u64,
     pre_term: u64,
     split_off_index: u64,
+    source: Option<LogFileSource>,
     sender: Option<LogRequestSenderType>,
 }
 
@@ -649,10 +714,16 @@ impl RaftLogActor {
             start_index,
             pre_term,
             split_off_index,
+            source: None,
             sender: None,
         }
     }
 
+    pub fn with_source(mut self, source: Option<LogFileSource>) -> Self {
+        self.source = source;
+        self
+    }
+
     fn init(&mut self, ctx: &mut Context<Self>) {
         self.receive_req(ctx);
     }
@@ -662,30 +733,75 @@ impl RaftLogActor {
         let start_index = self.start_index.to_owned();
         let pre_term = self.pre_term.to_owned();
         let split_off_index = self.split_off_index.to_owned();
-        let (tx, mut rx) = tokio::sync::mpsc::channel(10);
+        let source = self.source.clone();
+        let (tx, rx) = tokio::sync::mpsc::channel(10);
         self.sender = Some(tx);
         async move {
-            let mut inner =
-                LogInnerManager::init(log_path, start_index, pre_term, split_off_index).await?;
-            while let Some(Some(req)) = rx.recv().await {
-                if req
-                    .sender
-                    .send(inner.handle_request(req.request).await)
-                    .is_err()
-                {
-                    log::error!("RaftLogActor receive_req, send response error");
-                    break;
+            let tmp_path = match &source {
+                Some(source) => {
+                    Self::decompress_source(source.clone(), log_path.clone()).await?;
+                    if source.is_tmp {
+                        Some(log_path.clone())
+                    } else {
+                        None
+                    }
                 }
+                None => None,
+            };
+            let r =
+                Self::handle_requests(log_path, start_index, pre_term, split_off_index, rx).await;
+            if let Some(tmp_path) = tmp_path {
+                tokio::fs::remove_file(tmp_path).await.ok();
             }
             log::info!("RaftLogActor receive close");
-            Ok(())
+            r
         }
         .into_actor(self)
-        .map(|_r: anyhow::Result<()>, _act, ctx| {
+        .map(|r: anyhow::Result<()>, act, ctx| {
+            if let Err(e) = r {
+                log::error!("RaftLogActor error,{},{}", &act.path, e);
+            }
             ctx.stop();
         })
         .spawn(ctx);
     }
+
+    async fn decompress_source(source: LogFileSource, log_path: String) -> anyhow::Result<()> {
+        tokio::task::spawn_blocking(move || {
+            decompress_file(
+                Path::new(&source.compress_path),
+                Path::new(&log_path),
+                source.compress_type,
+            )?;
+            if !source.is_tmp {
+                std::fs::remove_file(&source.compress_path).ok();
+            }
+            Ok(())
+        })
+        .await?
+    }
+
+    async fn handle_requests(
+        log_path: String,
+        start_index: u64,
+        pre_term: u64,
+        split_off_index: u64,
+        mut rx: tokio::sync::mpsc::Receiver<Option<RaftLogRequestWrap>>,
+    ) -> anyhow::Result<()> {
+        let mut inner =
+            LogInnerManager::init(log_path, start_index, pre_term, split_off_index).await?;
+        while let Some(Some(req)) = rx.recv().await {
+            if req
+                .sender
+                .send(inner.handle_request(req.request).await)
+                .is_err()
+            {
+                log::error!("RaftLogActor receive_req, send response error");
+                break;
+            }
+        }
+        Ok(())
+    }
 }
 
 impl Actor for RaftLogActor {
@@ -790,6 +906,7 @@ impl Handler<RaftLogCmd> for RaftLogActor {
 pub struct LogRangeWrap {
     log_range: LogRange,
     log_actor: Option<Addr<RaftLogActor>>,
+    last_access_time: u64,
 }
 
 impl LogRangeWrap {
@@ -797,7 +914,18 @@ impl LogRangeWrap {
         Self {
             log_range,
             log_actor: None,
+            last_access_time: 0,
+        }
+    }
+
+    fn get_or_create_log_actor(&mut self, base_path: &str) -> Addr<RaftLogActor> {
+        self.last_access_time = now_millis();
+        if let Some(log_actor) = &self.log_actor {
+            return log_actor.clone();
         }
+        let log_actor = RaftLogManager::create_log_actor(base_path, &self.log_range, false);
+        self.log_actor = Some(log_actor.clone());
+        log_actor
     }
 
     pub fn get_log_range_end_index(&self) -> u64 {
@@ -811,6 +939,14 @@ impl LogRangeWrap {
 
 //const READY_TO_LOAD_TIME_OUT: u64 = 10 * 60 * 1000;
 
+#[derive(Debug, Clone, Default)]
+pub struct RaftLogOption {
+    /// 已封存日志文件的压缩方式
+    pub compress_type: LogCompressType,
+    /// 最新快照之前至少保留的日志条数，更早的日志文件会被删除
+    pub retain_log_size: u64,
+}
+
 #[bean(inject)]
 #[derive(Default)]
 pub struct RaftLogManager {
@@ -823,13 +959,20 @@ pub struct RaftLogManager {
     index_manager: Option<Addr<RaftIndexManager>>,
     //log_cache: BTreeMap<u64, Entry<ClientRequest>>,
     //last_ready_to_load_time: u64,
-    pre_ready_snapshot_pointer: Option<LogRecordDto>,
-    last_ready_snapshot_pointer: Option<LogRecordDto>,
+    //待生效的快照指针，用于保留快照之前的日志
+    snapshot_pointers: VecDeque<LogRecordDto>,
+    //压缩中的日志文件id
+    compressing_ids: HashSet<u64>,
+    option: RaftLogOption,
     is_init: bool,
 }
 
 impl RaftLogManager {
-    pub fn new(base_path: Arc<String>, index_manager: Option<Addr<RaftIndexManager>>) -> Self {
+    pub fn new(
+        base_path: Arc<String>,
+        index_manager: Option<Addr<RaftIndexManager>>,
+        option: RaftLogOption,
+    ) -> Self {
         Self {
             base_path,
             current_log_actor: None,
@@ -839,8 +982,9 @@ impl RaftLogManager {
             index_manager,
             //log_cache: BTreeMap::default(),
             //last_ready_to_load_time: 0,
-            pre_ready_snapshot_pointer: None,
-            last_ready_snapshot_pointer: None,
+            snapshot_pointers: VecDeque::new(),
+            compressing_ids: HashSet::new(),
+            option,
             is_init: false,
         }
     }
@@ -879,6 +1023,9 @@ impl RaftLogManager {
                 ctx.run_interval(Duration::from_millis(500), |a, _| {
                     a.send_flush();
                 });
+                ctx.run_interval(LOG_ACTOR_IDLE_CHECK_INTERVAL, |a, _| {
+                    a.close_idle_log_actors();
+                });
                 act.is_init = true;
             } else {
                 log::error!("load_index_info is error");
@@ -887,7 +1034,7 @@ impl RaftLogManager {
         .wait(ctx);
     }
 
-    fn build_log_actor(&mut self, _ctx: &mut Context<Self>) {
+    fn build_log_actor(&mut self, ctx: &mut Context<Self>) {
         if let Some(raft_index) = &self.index_info {
             self.logs = raft_index
                 .logs
@@ -899,6 +1046,7 @@ impl RaftLogManager {
             log::warn!("raft index logs is empty!");
             return;
         }
+        Self::remove_tmp_log_files(&self.base_path);
         let start_index = if let Some(v) = self.index_info.as_ref().unwrap().snapshots.last() {
             v.end_index + 1
         } else {
@@ -907,7 +1055,7 @@ impl RaftLogManager {
         for item in self.logs.iter_mut().rev() {
             let log_end_index = item.get_log_range_end_index();
             if log_end_index > start_index {
-                let log_actor_addr = Self::create_log_actor(&self.base_path, &item.log_range);
+                let log_actor_addr = item.get_or_create_log_actor(&self.base_path);
                 /*
                 let load_reqeust = RaftLogRequest::Load {
                     start: item.log_range.start_index,
@@ -923,6 +1071,22 @@ impl RaftLogManager {
         }
         let last_log_range = self.logs.last_mut().unwrap();
         self.current_log_actor.clone_from(&last_log_range.log_actor);
+        //启动后压缩快照之前未压缩的封存日志，快照之后的日志启动时需要加载
+        let base_path = self.base_path.clone();
+        let sealed_logs: Vec<LogRange> = self
+            .logs
+            .iter()
+            .filter(|e| {
+                e.log_actor.is_none()
+                    && e.log_range.is_close
+                    && e.log_range.compress_type == 0
+                    && Path::new(&Self::get_log_path(&base_path, &e.log_range)).exists()
+            })
+            .map(|e| e.log_range.clone())
+            .collect();
+        for log_range in sealed_logs {
+            self.compress_log(ctx, log_range, None);
+        }
     }
 
     fn load_record(
@@ -934,13 +1098,7 @@ impl RaftLogManager {
     ) {
         for item in &mut self.logs {
             if start < item.get_log_range_end_index() || end >= item.log_range.start_index {
-                let log_actor = if let Some(log_actor) = item.log_actor.as_ref() {
-                    log_actor.clone()
-                } else {
-                    let log_actor_addr = Self::create_log_actor(&self.base_path, &item.log_range);
-                    item.log_actor = Some(log_actor_addr.clone());
-                    log_actor_addr
-                };
+                let log_actor = item.get_or_create_log_actor(&self.base_path);
                 log_actor.do_send(RaftLogRequest::Load {
                     start,
                     end,
@@ -954,13 +1112,7 @@ impl RaftLogManager {
         let mut list = vec![];
         for item in &mut self.logs {
             if start < item.get_log_range_end_index() || end >= item.log_range.start_index {
-                let log_actor = if let Some(log_actor) = item.log_actor.as_ref() {
-                    log_actor.clone()
-                } else {
-                    let log_actor_addr = Self::create_log_actor(&self.base_path, &item.log_range);
-                    item.log_actor = Some(log_actor_addr.clone());
-                    log_actor_addr
-                };
+                let log_actor = item.get_or_create_log_actor(&self.base_path);
                 list.push(log_actor);
             }
         }
@@ -996,13 +1148,7 @@ impl RaftLogManager {
         let mut actor_logs = vec![];
         for item in &mut self.logs {
             if start < item.get_log_range_end_index() || end >= item.log_range.start_index {
-                let log_actor = if let Some(log_actor) = item.log_actor.as_ref() {
-                    log_actor.clone()
-                } else {
-                    let log_actor_addr = Self::create_log_actor(&self.base_path, &item.log_range);
-                    item.log_actor = Some(log_actor_addr.clone());
-                    log_actor_addr
-                };
+                let log_actor = item.get_or_create_log_actor(&self.base_path);
                 actor_logs.push(log_actor);
             }
         }
@@ -1024,11 +1170,13 @@ impl RaftLogManager {
         rlist
     }
 
-    fn switch_new_log(&mut self, _ctx: &mut Context<Self>, next_index: u64, last_term: u64) {
+    fn switch_new_log(&mut self, ctx: &mut Context<Self>, next_index: u64, last_term: u64) {
+        let mut sealed_log = None;
         let next_log_id = {
             if let Some(last_log) = self.logs.last_mut() {
                 last_log.log_range.is_close = true;
                 last_log.log_range.record_count = next_index - last_log.log_range.start_index;
+                sealed_log = Some(last_log.clone());
                 last_log.log_range.id + 1
             } else {
                 1
@@ -1042,17 +1190,102 @@ impl RaftLogManager {
             record_count: 0,
             is_close: false,
             mark_remove: false,
+            compress_type: 0,
         };
         let mut save_logs: Vec<LogRange> = self.logs.iter().map(|e| e.log_range.clone()).collect();
         save_logs.push(new_log_range.clone());
         let index_request = RaftIndexRequest::SaveLogs(save_logs);
         self.index_manager.as_ref().unwrap().do_send(index_request);
-        let log_actor_addr = Self::create_log_actor(&self.base_path, &new_log_range);
+        let log_actor_addr = Self::create_log_actor(&self.base_path, &new_log_range, true);
         self.logs.push(LogRangeWrap {
             log_range: new_log_range,
             log_actor: Some(log_actor_addr.clone()),
+            last_access_time: now_millis(),
         });
         self.current_log_actor = Some(log_actor_addr);
+        if let Some(sealed_log) = sealed_log {
+            self.compress_log(ctx, sealed_log.log_range, sealed_log.log_actor);
+        }
+    }
+
+    fn save_logs(&self) {
+        let save_logs = self.logs.iter().map(|e| e.log_range.clone()).collect();
+        let index_request = RaftIndexRequest::SaveLogs(save_logs);
+        self.index_manager.as_ref().unwrap().do_send(index_request);
+    }
+
+    /// 压缩已封存的日志文件，压缩完成后删除原文件
+    fn compress_log(
+        &mut self,
+        ctx: &mut Context<Self>,
+        log_range: LogRange,
+        log_actor: Option<Addr<RaftLogActor>>,
+    ) {
+        let compress_type = self.option.compress_type;
+        if compress_type.is_none()
+            || log_range.compress_type != 0
+            || !self.compressing_ids.insert(log_range.id)
+        {
+            return;
+        }
+        let id = log_range.id;
+        let log_path = Self::get_log_path(&self.base_path, &log_range);
+        let compress_path = Self::get_log_compress_path(&self.base_path, id, compress_type);
+        async move {
+            if let Some(log_actor) = log_actor {
+                log_actor.send(RaftLogRequest::Flush).await??;
+            }
+            let len = tokio::task::spawn_blocking(move || {
+                compress_file(
+                    Path::new(&log_path),
+                    Path::new(&compress_path),
+                    compress_type,
+                )
+            })
+            .await??;
+            Ok(len)
+        }
+        .into_actor(self)
+        .map(move |r: anyhow::Result<u64>, act, _ctx| {
+            act.compress_log_complete(id, compress_type, r);
+        })
+        .spawn(ctx);
+    }
+
+    fn compress_log_complete(
+        &mut self,
+        id: u64,
+        compress_type: LogCompressType,
+        r: anyhow::Result<u64>,
+    ) {
+        //压缩期间日志文件被删除或截断时放弃本次压缩结果
+        let is_valid = self.compressing_ids.remove(&id);
+        let is_current = self.logs.last().map(|e| e.log_range.id) == Some(id);
+        let compress_path = Self::get_log_compress_path(&self.base_path, id, compress_type);
+        let len = match r {
+            Ok(len) => len,
+            Err(e) => {
+                log::error!("compress log error,id:{},{}", id, e);
+                std::fs::remove_file(compress_path).ok();
+                return;
+            }
+        };
+        let item = self
+            .logs
+            .iter_mut()
+            .find(|e| e.log_range.id == id && e.log_range.is_close);
+        if let (true, false, Some(item)) = (is_valid, is_current, item) {
+            item.log_range.compress_type = compress_type.get_data();
+            if let Some(log_actor) = item.log_actor.take() {
+                log_actor.do_send(RaftLogCmd::Close);
+            }
+            let log_path = Self::get_log_path(&self.base_path, &item.log_range);
+            std::fs::remove_file(log_path).ok();
+            log::info!("compress log complete,id:{},compress len:{}", id, len);
+            self.save_logs();
+        } else {
+            std::fs::remove_file(compress_path).ok();
+        }
     }
 
     fn write(&mut self, ctx: &mut Context<Self>, record: LogRecordDto, can_rewrite: bool) {
@@ -1136,15 +1369,24 @@ impl RaftLogManager {
     fn strip_log_to_index(&mut self, _ctx: &mut Context<Self>, end_index: u64) {
         log::info!("strip_log_to_index end_index:{}", end_index);
         let mut pop_count = 0;
+        let mut compress_changed = false;
         for item in &mut self.logs {
             if end_index < item.get_log_range_end_index() {
-                let log_actor = if let Some(log_actor) = item.log_actor.as_ref() {
-                    log_actor.clone()
-                } else {
-                    let log_actor_addr = Self::create_log_actor(&self.base_path, &item.log_range);
-                    item.log_actor = Some(log_actor_addr.clone());
-                    log_actor_addr
-                };
+                //截断后日志文件会被修改，不再使用压缩文件，解压回日志文件后由日志actor删除压缩文件
+                self.compressing_ids.remove(&item.log_range.id);
+                if item.log_range.compress_type != 0 {
+                    if let Some(log_actor) = item.log_actor.take() {
+                        log_actor.do_send(RaftLogCmd::Close);
+                    }
+                    item.log_actor = Some(Self::create_log_actor(
+                        &self.base_path,
+                        &item.log_range,
+                        true,
+                    ));
+                    item.log_range.compress_type = 0;
+                    compress_changed = true;
+                }
+                let log_actor = item.get_or_create_log_actor(&self.base_path);
                 log_actor.do_send(RaftLogRequest::StripLogToIndex(end_index));
                 let is_remove = end_index < item.log_range.start_index;
                 if is_remove {
@@ -1158,17 +1400,12 @@ impl RaftLogManager {
             let log_count = self.logs.len() - pop_count;
             self.logs = self.logs[..log_count].to_vec();
             if let Some(last_log) = self.logs.last_mut() {
-                let log_actor = if let Some(log_actor) = &last_log.log_actor {
-                    log_actor.clone()
-                } else {
-                    let log_actor_addr =
-                        Self::create_log_actor(&self.base_path, &last_log.log_range);
-                    last_log.log_actor = Some(log_actor_addr.clone());
-                    log_actor_addr
-                };
-                self.current_log_actor = Some(log_actor);
+                self.current_log_actor = Some(last_log.get_or_create_log_actor(&self.base_path));
             }
         }
+        if compress_changed {
+            self.save_logs();
+        }
     }
 
     fn split_off(&mut self, _ctx: &mut Context<Self>, split_off_index: u64) {
@@ -1180,8 +1417,8 @@ impl RaftLogManager {
                     log_actor.do_send(RaftLogCmd::Close);
                 }
                 //remove file
-                let path = Self::get_log_path(&self.base_path, &item.log_range);
-                std::fs::remove_file(path).ok();
+                self.compressing_ids.remove(&item.log_range.id);
+                Self::remove_log_file(&self.base_path, &item.log_range);
                 i += 1;
             } else if split_off_index > item.log_range.split_off_index {
                 item.log_range.split_off_index = split_off_index;
@@ -1228,26 +1465,28 @@ impl RaftLogManager {
                 record_count: 1,
                 is_close: true,
                 mark_remove: false,
+                compress_type: 0,
             };
             let mut save_logs: Vec<LogRange> =
                 self.logs.iter().map(|e| e.log_range.clone()).collect();
             save_logs.insert(0, pointer_log_range.clone());
             let index_request = RaftIndexRequest::SaveLogs(save_logs);
             self.index_manager.as_ref().unwrap().do_send(index_request);
-            let log_actor_addr = Self::create_log_actor(&self.base_path, &pointer_log_range);
+            let log_actor_addr = Self::create_log_actor(&self.base_path, &pointer_log_range, true);
             log_actor_addr.do_send(RaftLogRequest::Write(snapshot_pointer));
             self.logs.insert(
                 0,
                 LogRangeWrap {
                     log_range: pointer_log_range,
                     log_actor: Some(log_actor_addr),
+                    last_access_time: now_millis(),
                 },
             );
         }
     }
 
     ///
-    /// 保险起见先保留最后两个snapshot对应的日志信息;
+    /// 保险起见最新snapshot之前至少保留retain_log_size条日志;
     ///
     fn begin_ready_to_load(&mut self, ctx: &mut Context<Self>, snapshot_pointer: LogRecordDto) {
         log::info!(
@@ -1255,13 +1494,17 @@ impl RaftLogManager {
             snapshot_pointer.index,
             snapshot_pointer.term
         );
-        if let Some(pre_pointer) = self.pre_ready_snapshot_pointer.take() {
-            self.last_ready_snapshot_pointer = Some(pre_pointer);
-            self.pre_ready_snapshot_pointer = Some(snapshot_pointer);
-        } else {
-            self.pre_ready_snapshot_pointer = Some(snapshot_pointer);
+        let last_index = snapshot_pointer.index;
+        self.snapshot_pointers.push_back(snapshot_pointer);
+        let mut ready_pointer = None;
+        while let Some(pointer) = self.snapshot_pointers.front() {
+            if pointer.index + self.option.retain_log_size <= last_index {
+                ready_pointer = self.snapshot_pointers.pop_front();
+            } else {
+                break;
+            }
         }
-        if let Some(snapshot_pointer) = self.last_ready_snapshot_pointer.take() {
+        if let Some(snapshot_pointer) = ready_pointer {
             self.save_new_snapshot_pointer(ctx, snapshot_pointer);
         }
     }
@@ -1290,21 +1533,131 @@ impl RaftLogManager {
         }
     }
 
-    fn get_log_path(base_path: &str, log_range: &LogRange) -> String {
+    pub(crate) fn get_log_path(base_path: &str, log_range: &LogRange) -> String {
         Path::new(base_path)
             .join(format!("log_{}", log_range.id))
             .to_string_lossy()
             .into_owned()
     }
 
-    fn create_log_actor(base_path: &str, log_range: &LogRange) -> Addr<RaftLogActor> {
+    pub(crate) fn get_log_compress_path(
+        base_path: &str,
+        id: u64,
+        compress_type: LogCompressType,
+    ) -> String {
+        Path::new(base_path)
+            .join(format!("log_{}{}", id, compress_type.get_file_suffix()))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// 查找日志对应的压缩文件；压缩类型为0但日志文件不存在时，为截断前解压中断留下的压缩文件
+    fn find_compress_file(base_path: &str, log_range: &LogRange) -> Option<LogFileSource> {
+        let types = match LogCompressType::from_data(log_range.compress_type) {
+            Ok(LogCompressType::None) => vec![LogCompressType::Zstd, LogCompressType::Lz4],
+            Ok(v) => vec![v],
+            Err(e) => {
+                log::error!("log compress type error,id:{},{}", log_range.id, e);
+                return None;
+            }
+        };
+        types.into_iter().find_map(|compress_type| {
+            let compress_path = Self::get_log_compress_path(base_path, log_range.id, compress_type);
+            if Path::new(&compress_path).exists() {
+                Some(LogFileSource {
+                    compress_path,
+                    compress_type,
+                    is_tmp: log_range.compress_type != 0,
+                })
+            } else {
+                None
+            }
+        })
+    }
+
+    fn get_tmp_log_path(base_path: &str, log_range: &LogRange) -> String {
+        let seq = TMP_LOG_FILE_SEQ.fetch_add(1, Ordering::Relaxed);
+        Path::new(base_path)
+            .join(format!("log_{}{}{}", log_range.id, TMP_LOG_FILE_MARK, seq))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    /// 清理上次运行未删除的临时解压文件
+    fn remove_tmp_log_files(base_path: &str) {
+        let Ok(dir) = std::fs::read_dir(base_path) else {
+            return;
+        };
+        for entry in dir.flatten() {
+            if entry
+                .file_name()
+                .to_string_lossy()
+                .contains(TMP_LOG_FILE_MARK)
+            {
+                std::fs::remove_file(entry.path()).ok();
+            }
+        }
+    }
+
+    /// 关闭长时间未访问的压缩日志actor，释放临时解压文件
+    fn close_idle_log_actors(&mut self) {
+        let Some(last_id) = self.logs.last().map(|e| e.log_range.id) else {
+            return;
+        };
+        let idle_time = now_millis().saturating_sub(LOG_ACTOR_IDLE_TIMEOUT);
+        for item in &mut self.logs {
+            if item.log_range.id != last_id
+                && item.log_range.compress_type != 0
+                && item.last_access_time < idle_time
+            {
+                if let Some(log_actor) = item.log_actor.take() {
+                    log_actor.do_send(RaftLogCmd::Close);
+                }
+            }
+        }
+    }
+
+    fn remove_log_file(base_path: &str, log_range: &LogRange) {
+        std::fs::remove_file(Self::get_log_path(base_path, log_range)).ok();
+        if let Ok(compress_type) = LogCompressType::from_data(log_range.compress_type) {
+            if !compress_type.is_none() {
+                let compress_path =
+                    Self::get_log_compress_path(base_path, log_range.id, compress_type);
+                std::fs::remove_file(compress_path).ok();
+            }
+        }
+    }
+
+    /// 压缩的日志只读时解压到临时文件，需要写入时解压回日志文件
+    fn create_log_actor(
+        base_path: &str,
+        log_range: &LogRange,
+        writable: bool,
+    ) -> Addr<RaftLogActor> {
         let log_path = Self::get_log_path(base_path, log_range);
+        let source = if Path::new(&log_path).exists() {
+            None
+        } else {
+            Self::find_compress_file(base_path, log_range)
+        };
+        let (log_path, source) = match source {
+            Some(mut source) => {
+                source.is_tmp = source.is_tmp && !writable;
+                if source.is_tmp {
+                    (Self::get_tmp_log_path(base_path, log_range), Some(source))
+                } else {
+                    (log_path, Some(source))
+                }
+            }
+            None => (log_path, None),
+        };
         RaftLogActor::new(
             log_path,
             log_range.start_index,
             log_range.pre_term,
             log_range.split_off_index,
         )
+        .with_source(source)
         .start()
     }
 }
@@ -1413,6 +1766,8 @@ impl Handler<RaftLogManagerRequest> for RaftLogManager {
                 Ok(RaftLogResponse::None)
             }
             RaftLogManagerRequest::InstallSnapshotPointerLog(snapshot_pointer) => {
+                self.snapshot_pointers
+                    .retain(|e| e.index > snapshot_pointer.index);
                 self.save_new_snapshot_pointer(ctx, snapshot_pointer);
                 Ok(RaftLogResponse::None)
             }
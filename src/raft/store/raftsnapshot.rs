@@ -9,6 +9,7 @@ use tokio::{
     io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt},
 };
 
+use crate::common::datetime_utils::now_millis;
 use crate::common::protobuf_utils::MessageBufReader;
 
 use super::{
@@ -57,6 +58,13 @@ impl SnapshotWriter {
         self.file.flush().await?;
         Ok(())
     }
+
+    /// 刷新并落盘
+    pub async fn sync(&mut self) -> anyhow::Result<()> {
+        self.file.flush().await?;
+        self.file.sync_data().await?;
+        Ok(())
+    }
 }
 
 ///
@@ -127,6 +135,26 @@ impl SnapshotWriterActor {
         })
         .wait(ctx);
     }
+
+    /// 落盘完成后通过sender通知
+    fn sync(&mut self, ctx: &mut Context<Self>, sender: tokio::sync::oneshot::Sender<bool>) {
+        let mut writer = self.inner_writer.take().unwrap();
+        async move {
+            writer.sync().await?;
+            Ok(writer)
+        }
+        .into_actor(self)
+        .map(move |v: anyhow::Result<SnapshotWriter>, act, ctx| {
+            if let Ok(v) = v {
+                act.inner_writer = Some(v);
+                sender.send(true).ok();
+            } else {
+                sender.send(false).ok();
+                ctx.stop()
+            }
+        })
+        .wait(ctx);
+    }
 }
 
 impl Actor for SnapshotWriterActor {
@@ -142,6 +170,8 @@ impl Actor for SnapshotWriterActor {
 pub enum SnapshotWriterRequest {
     Record(SnapshotRecordDto),
     Flush,
+    /// 刷新并落盘,完成后才返回
+    Sync,
 }
 
 pub enum SnapshotWriterResponse {
@@ -150,17 +180,31 @@ pub enum SnapshotWriterResponse {
 }
 
 impl Handler<SnapshotWriterRequest> for SnapshotWriterActor {
-    type Result = anyhow::Result<SnapshotWriterResponse>;
+    type Result = ResponseActFuture<Self, anyhow::Result<SnapshotWriterResponse>>;
 
     fn handle(&mut self, msg: SnapshotWriterRequest, ctx: &mut Self::Context) -> Self::Result {
+        let path = self.path.clone();
         match msg {
             SnapshotWriterRequest::Record(record) => {
                 self.write(ctx, record);
-                Ok(SnapshotWriterResponse::None)
+                Box::pin(fut::ready(Ok(SnapshotWriterResponse::None)))
             }
             SnapshotWriterRequest::Flush => {
                 self.flush(ctx);
-                Ok(SnapshotWriterResponse::Path(self.path.clone()))
+                Box::pin(fut::ready(Ok(SnapshotWriterResponse::Path(path))))
+            }
+            SnapshotWriterRequest::Sync => {
+                let (tx, rx) = tokio::sync::oneshot::channel();
+                self.sync(ctx, tx);
+                let fut = async move {
+                    if rx.await.unwrap_or(false) {
+                        Ok(SnapshotWriterResponse::Path(path))
+                    } else {
+                        Err(anyhow::anyhow!("snapshot sync error"))
+                    }
+                }
+                .into_actor(self);
+                Box::pin(fut)
             }
         }
     }
@@ -328,13 +372,21 @@ impl RaftSnapshotManager {
         Ok(next_id)
     }
 
-    fn get_snapshot_path(base_path: &str, id: u64) -> String {
+    pub(crate) fn get_snapshot_path(base_path: &str, id: u64) -> String {
         Path::new(base_path)
             .join(format!("snapshot_{}", id))
             .to_string_lossy()
             .into_owned()
     }
 
+    /// 备份用的镜像路径，不加入镜像列表
+    fn get_backup_snapshot_path(base_path: &str) -> String {
+        Path::new(base_path)
+            .join(format!("backup_snapshot_{}", now_millis()))
+            .to_string_lossy()
+            .into_owned()
+    }
+
     fn new_writer(
         &mut self,
         _ctx: &mut Context<Self>,
@@ -445,6 +497,7 @@ pub enum RaftSnapshotRequest {
     GetLastSnapshot,
     NewSnapshot(SnapshotHeaderDto),
     NewSnapshotForLoad,
+    NewBackupSnapshot(SnapshotHeaderDto),
     CompleteSnapshot(SnapshotRange),
     InstallSnapshot { end_index: u64, snapshot_id: u64 },
 }
@@ -477,6 +530,11 @@ impl Handler<RaftSnapshotRequest> for RaftSnapshotManager {
                 let writer = self.new_writer(ctx, header, path.clone());
                 Ok(RaftSnapshotResponse::NewSnapshot(writer, next_id, path))
             }
+            RaftSnapshotRequest::NewBackupSnapshot(header) => {
+                let path = Arc::new(Self::get_backup_snapshot_path(&self.base_path));
+                let writer = self.new_writer(ctx, header, path.clone());
+                Ok(RaftSnapshotResponse::NewSnapshot(writer, 0, path))
+            }
             RaftSnapshotRequest::NewSnapshotForLoad => {
                 let next_id = self.get_next_id()?;
                 let path = Self::get_snapshot_path(&self.base_path, next_id);
@@ -1,5 +1,4 @@
 use crate::job::model::actor_model::JobManagerRaftReq;
-use crate::openapi::xxljob::model::server_request::CallbackParam;
 use crate::raft::cluster::route::RaftRequestRoute;
 use crate::raft::store::ClientRequest;
 use crate::schedule::model::actor_model::ScheduleManagerRaftReq;
@@ -163,13 +162,15 @@ impl Inject for BatchCallManager {
 #[derive(Debug, Message)]
 #[rtype(result = "anyhow::Result<()>")]
 pub enum BatchCallManagerReq {
-    Callback(Vec<CallbackParam>),
+    Callback(Vec<TaskCallBackParam>),
 }
 
 #[derive(Debug, Message)]
 #[rtype(result = "anyhow::Result<()>")]
 pub enum BatchUpdateTaskManagerReq {
     UpdateTask(Arc<JobTaskInfo>),
+    /// 同一批次内按顺序更新
+    UpdateTasks(Vec<Arc<JobTaskInfo>>),
 }
 
 impl Handler<BatchCallManagerReq> for BatchCallManager {
@@ -181,9 +182,7 @@ impl Handler<BatchCallManagerReq> for BatchCallManager {
             BatchCallManagerReq::Callback(params) => {
                 let (tx, rx) = tokio::sync::oneshot::channel();
                 if let Some(callback_cache) = self.callback_cache.as_mut() {
-                    for param in params {
-                        callback_cache.params.push(param.into());
-                    }
+                    callback_cache.params.extend(params);
                     callback_cache.senders.push(tx);
                     count = callback_cache.senders.len();
                 }
@@ -222,6 +221,14 @@ impl Handler<BatchUpdateTaskManagerReq> for BatchCallManager {
                     }
                 }
             }
+            BatchUpdateTaskManagerReq::UpdateTasks(tasks) => {
+                if let Some(task_cache) = self.task_cache.as_mut() {
+                    task_cache.extend(tasks);
+                    if task_cache.len() >= trigger_batch_max_count {
+                        self.update_tasks(ctx);
+                    }
+                }
+            }
         }
         Ok(())
     }
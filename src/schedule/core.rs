@@ -1,15 +1,17 @@
 use crate::common::byte_utils::id_to_bin;
 use crate::common::constant::{
-    EMPTY_ARC_STR, ERR_MSG_JOB_DISABLE, ERR_MSG_TASK_TIMEOUT, JOB_TASK_HISTORY_TABLE_NAME,
-    JOB_TASK_RUNNING_TABLE_NAME,
+    EMPTY_ARC_STR, ERR_MSG_JOB_DISABLE, ERR_MSG_TASK_CANCELLED, ERR_MSG_TASK_TIMEOUT,
+    JOB_TASK_HISTORY_TABLE_NAME, JOB_TASK_RUNNING_TABLE_NAME, SCHEDULE_PARTITION_TABLE_NAME,
+    SCHEDULE_PAUSE_TABLE_NAME,
 };
 use crate::common::datetime_utils::{
     get_datetime_by_second, get_local_offset, now_millis, now_millis_i64, now_second_u32,
 };
-use crate::common::pb::data_object::JobTaskDo;
+use crate::common::pb::data_object::{JobTaskDo, SchedulePartitionDo, SchedulePauseDo};
 use crate::job::model::actor_model::JobManagerRaftReq;
 use crate::job::model::enum_type::ScheduleType;
 use crate::job::model::job::{JobInfo, JobTaskLogQueryParam};
+use crate::job::model::retry_policy::{JobRetryPolicy, RetryFailType};
 use crate::metrics::core::MetricsManager;
 use crate::metrics::metrics_key::MetricsKey;
 use crate::metrics::model::{MetricsItem, MetricsRecord, MetricsRequest};
@@ -24,9 +26,11 @@ use crate::schedule::model::actor_model::{
     ScheduleManagerRaftReq, ScheduleManagerRaftResult, ScheduleManagerReq, ScheduleManagerResult,
 };
 use crate::schedule::model::finish_mark::FinishMarkGroup;
+use crate::schedule::model::partition::{PartitionRing, SchedulePartitionInfo};
+use crate::schedule::model::pause::{SchedulePauseGroup, SchedulePauseInfo, SchedulePauseKey};
 use crate::schedule::model::{DelayFinishTasks, JobRunState, RedoInfo, RedoType, TriggerInfo};
 use crate::task::core::TaskManager;
-use crate::task::model::actor_model::{RedoTaskItem, TaskManagerReq, TriggerItem};
+use crate::task::model::actor_model::{KillTaskItem, RedoTaskItem, TaskManagerReq, TriggerItem};
 use crate::task::model::enum_type::TaskStatusType;
 use crate::task::model::task::{JobTaskInfo, TaskCallBackParam, UpdateTaskMetricsInfo};
 use actix::prelude::*;
@@ -62,6 +66,21 @@ pub struct ScheduleManager {
     running_heartbeat: bool,
     default_timeout_second: u32,
     trigger_batch_max_count: usize,
+    local_node_id: u64,
+    /// 开启后按任务id把调度分摊到集群各节点，否则只由主节点调度
+    partition_enable: bool,
+    partition_info: SchedulePartitionInfo,
+    partition_ring: PartitionRing,
+    /// 集群有效成员，只在主节点用于分配分区
+    cluster_members: Vec<u64>,
+    /// 本节点当前是否已加载调度集合
+    schedule_active: bool,
+    /// 调度暂停配置
+    pause_group: SchedulePauseGroup,
+    /// 暂停期间错过触发、恢复后需补执行的任务
+    expiring_pause_keys: HashSet<SchedulePauseKey>,
+    /// raft数据(快照与日志)是否已加载完成,加载期间重放的暂停恢复不补执行
+    data_load_completed: bool,
 }
 
 impl Actor for ScheduleManager {
@@ -88,7 +107,7 @@ impl Inject for ScheduleManager {
 }
 
 impl ScheduleManager {
-    pub fn new(offset_seconds: Option<i32>) -> Self {
+    pub fn new(offset_seconds: Option<i32>, local_node_id: u64, partition_enable: bool) -> Self {
         let fixed_offset = if let Some(offset_value) = offset_seconds {
             FixedOffset::east_opt(offset_value).unwrap_or(get_local_offset())
         } else {
@@ -114,12 +133,37 @@ impl ScheduleManager {
             running_heartbeat: false,
             default_timeout_second: 24 * 60 * 60, // 默认24小时
             trigger_batch_max_count: 1000,
+            local_node_id,
+            partition_enable,
+            partition_info: SchedulePartitionInfo::default(),
+            partition_ring: PartitionRing::default(),
+            cluster_members: vec![],
+            schedule_active: false,
+            pause_group: SchedulePauseGroup::default(),
+            expiring_pause_keys: HashSet::new(),
+            data_load_completed: false,
         }
     }
 
+    /// 本节点是否负责该任务的调度
+    fn is_local_owner(&self, job_id: u64) -> bool {
+        if !self.partition_enable || self.partition_ring.is_empty() {
+            return self.local_is_master;
+        }
+        self.partition_ring.get_node(job_id) == Some(self.local_node_id)
+    }
+
+    /// 本节点是否需要参与调度
+    fn can_schedule(&self) -> bool {
+        if !self.partition_enable || self.partition_ring.is_empty() {
+            return self.local_is_master;
+        }
+        self.partition_ring.contains_node(self.local_node_id)
+    }
+
     fn active_job(&mut self, job_id: u64, time: u32, version: u32) {
-        // 前期如果不是leader节点，则不执行调度任务
-        if !self.local_is_master || time == 0 {
+        // 不归属本节点的任务不执行调度
+        if time == 0 || !self.is_local_owner(job_id) {
             return;
         }
         self.active_time_set
@@ -127,7 +171,12 @@ impl ScheduleManager {
     }
 
     fn active_retry_task(&mut self, task_id: u64, time: u32, redo_type: RedoType) {
-        if !self.local_is_master {
+        let is_owner = if let Some(task) = self.running_task.get(&task_id) {
+            self.is_local_owner(task.job_id)
+        } else {
+            self.local_is_master
+        };
+        if !is_owner {
             return;
         }
         self.redo_set
@@ -202,6 +251,10 @@ impl ScheduleManager {
                         log::info!("job version change ignore,id:{}", &item.job_id);
                         continue;
                     }
+                    if !self.is_local_owner(item.job_id) {
+                        // 分区已迁移到其它节点
+                        continue;
+                    }
                     /*
                     log::info!(
                         "prepare job,id:{},run_mode:{:?},handler_name:{}",
@@ -210,7 +263,11 @@ impl ScheduleManager {
                         &job.source_job.handle_name
                     );
                     */
-                    trigger_list.push(TriggerItem::new(item.trigger_time, job.source_job.clone()));
+                    // 暂停期间不触发,只推进下次触发时间;恢复时按暂停配置补执行
+                    if self.pause_group.find(&job.source_job, seconds).is_none() {
+                        trigger_list
+                            .push(TriggerItem::new(item.trigger_time, job.source_job.clone()));
+                    }
                     let next_trigger_time = job.calculate_next_trigger_time(&date_time);
                     if next_trigger_time > 0 {
                         self.active_job(item.job_id, next_trigger_time, job.version);
@@ -238,12 +295,168 @@ impl ScheduleManager {
         }
     }
 
+    /// 根据暂停配置推算本节点负责的任务在暂停期间错过的触发,恢复后补执行
+    fn trigger_pause_missed_jobs(&mut self, info: &SchedulePauseInfo, now: u32) {
+        if !self.data_load_completed {
+            return;
+        }
+        let end_time = if info.resume_time > 0 {
+            info.resume_time.min(now)
+        } else {
+            now
+        };
+        let key = info.key();
+        let running_jobs: HashSet<u64> = self.running_task.values().map(|v| v.job_id).collect();
+        let mut trigger_list = Vec::new();
+        let mut delay_jobs = Vec::new();
+        for job in self.job_run_state.values() {
+            if !key.is_match(&job.source_job)
+                || !self.is_local_owner(job.id)
+                || self.pause_group.find(&job.source_job, now).is_some()
+            {
+                continue;
+            }
+            if job.schedule_type == ScheduleType::Delay {
+                // 延迟任务在暂停期间到期时不会再触发,恢复后重新开始计时
+                if !running_jobs.contains(&job.id)
+                    && job.last_finish_time + job.delay_second < end_time
+                {
+                    delay_jobs.push((job.id, job.version));
+                }
+            } else if info.execute_past_due(&job.source_job)
+                && job.has_trigger_between(info.get_start_time(), end_time, &self.fixed_offset)
+            {
+                trigger_list.push(TriggerItem::new(now, job.source_job.clone()));
+            }
+        }
+        for (job_id, version) in delay_jobs {
+            self.active_job(job_id, now, version);
+        }
+        if trigger_list.is_empty() {
+            return;
+        }
+        log::info!(
+            "ScheduleManager|trigger past due jobs after pause,scope:{},count:{}",
+            key.to_key_string(),
+            trigger_list.len()
+        );
+        if let Some(task_manager) = &self.task_manager {
+            task_manager.do_send(TaskManagerReq::TriggerTaskList(trigger_list));
+        }
+    }
+
+    /// 到达自动恢复时间的暂停配置通过raft移除,各节点应用时补执行错过的触发
+    fn expire_pauses(&mut self, now: u32, ctx: &mut Context<Self>) {
+        let raft_request_route = match self.raft_request_route.clone() {
+            Some(v) if self.data_load_completed => v,
+            _ => return,
+        };
+        for info in self.pause_group.expired_list(now) {
+            let key = info.key();
+            if self.expiring_pause_keys.contains(&key) {
+                continue;
+            }
+            self.expiring_pause_keys.insert(key.clone());
+            let raft_request_route = raft_request_route.clone();
+            let req = ScheduleManagerRaftReq::ExpirePause(key.clone(), info.resume_time);
+            async move {
+                raft_request_route
+                    .request(ClientRequest::ScheduleReq { req })
+                    .await
+            }
+            .into_actor(self)
+            .map(move |res, act, _ctx| {
+                if let Err(err) = res {
+                    log::error!("ScheduleManager|expire pause error,{}", err);
+                    act.expiring_pause_keys.remove(&key);
+                }
+            })
+            .spawn(ctx);
+        }
+    }
+
+    /// 已调度的任务按当前调度状态计算,未调度(如已禁用)的任务按配置计算
+    fn query_next_trigger_times(&self, job: Arc<JobInfo>, count: usize) -> Vec<u32> {
+        let now_datetime =
+            if let Some(v) = get_datetime_by_second(now_second_u32(), &self.fixed_offset) {
+                v
+            } else {
+                return vec![];
+            };
+        if let Some(job_run_state) = self.job_run_state.get(&job.id) {
+            job_run_state.next_trigger_times(&now_datetime, count)
+        } else {
+            JobRunState::new(job).next_trigger_times(&now_datetime, count)
+        }
+    }
+
+    fn update_pause(&mut self, mut info: SchedulePauseInfo) {
+        log::info!(
+            "ScheduleManager|pause schedule,scope:{},resume_time:{},past_due_strategy:{},operator:{}",
+            info.key().to_key_string(),
+            info.resume_time,
+            info.past_due_strategy.to_str(),
+            &info.operator
+        );
+        let now = now_second_u32();
+        let key = info.key();
+        self.expiring_pause_keys.remove(&key);
+        if let Some(old) = self.pause_group.remove(&key) {
+            if old.is_active(now) {
+                // 重复设置时保留原暂停开始时间
+                info.start_time = info.get_start_time().min(old.get_start_time());
+            } else {
+                self.trigger_pause_missed_jobs(&old, now);
+            }
+        }
+        self.pause_group.update(info);
+    }
+
+    fn remove_pause(&mut self, key: &SchedulePauseKey) {
+        log::info!(
+            "ScheduleManager|resume schedule,scope:{}",
+            key.to_key_string()
+        );
+        self.expiring_pause_keys.remove(key);
+        if let Some(info) = self.pause_group.remove(key) {
+            self.trigger_pause_missed_jobs(&info, now_second_u32());
+        }
+    }
+
+    /// 自动恢复,配置已被修改时忽略
+    fn expire_pause(&mut self, key: &SchedulePauseKey, resume_time: u32) {
+        if self
+            .pause_group
+            .get(key)
+            .is_some_and(|v| v.resume_time > 0 && v.resume_time == resume_time)
+        {
+            self.remove_pause(key);
+        } else {
+            self.expiring_pause_keys.remove(key);
+        }
+    }
+
+    fn get_job_retry_policy(&self, job_id: u64) -> Option<Arc<JobRetryPolicy>> {
+        self.job_run_state
+            .get(&job_id)
+            .and_then(|v| v.source_job.retry_policy.clone())
+    }
+
+    /// 失败的任务是否需要重试,重试策略可限定只重试部分失败类型
+    fn can_retry_task(task: &JobTaskInfo, retry_policy: Option<&JobRetryPolicy>) -> bool {
+        task.can_retry() && retry_policy.is_none_or(|v| v.can_retry_on(&task.get_fail_type()))
+    }
+
     fn trigger_redo_job(&mut self, seconds: u32, ctx: &mut Context<Self>) {
         let mut retry_items = Vec::new();
         let mut tasks = Vec::new();
+        let mut kill_items = Vec::new();
         for redo_info in self.redo_set.timeout(seconds as u64) {
             let task_id = redo_info.task_id;
             if let Some(old_task) = self.running_task.get(&task_id) {
+                if !self.is_local_owner(old_task.job_id) {
+                    continue;
+                }
                 match &redo_info.redo_type {
                     RedoType::Retry => {
                         if !old_task.can_retry() {
@@ -265,6 +478,11 @@ impl ScheduleManager {
                     .job_run_state
                     .get(&old_task.job_id)
                     .map(|e| e.source_job.clone());
+                if redo_info.redo_type == RedoType::Timeout {
+                    if let Some(item) = KillTaskItem::new_by_task(old_task, job.as_ref()) {
+                        kill_items.push(item);
+                    }
+                }
                 #[cfg(feature = "debug")]
                 log::info!(
                     "ScheduleManager|redo task,id:{},{:?},job is none:{}",
@@ -279,16 +497,30 @@ impl ScheduleManager {
             return;
         }
         log::info!("ScheduleManager|redo task count:{}", tasks.len());
+        if !kill_items.is_empty() {
+            if let Some(task_manager) = self.task_manager.as_ref() {
+                task_manager.do_send(TaskManagerReq::KillTaskList(kill_items));
+            }
+        }
         let mut finish_params: Vec<TaskCallBackParam> = Vec::new();
+        let mut timeout_tasks = Vec::new();
         let now = now_millis_i64();
         for (mut task, redo_type, mut job) in tasks {
             let fail_reason = match redo_type {
                 RedoType::Retry | RedoType::Timeout => {
-                    if task.can_retry() {
+                    let retry_on_timeout = redo_type != RedoType::Timeout
+                        || job
+                            .as_ref()
+                            .and_then(|v| v.retry_policy.as_ref())
+                            .is_none_or(|v| v.can_retry_on(&RetryFailType::Timeout));
+                    if task.can_retry() && retry_on_timeout {
                         task.push_next_try();
                         self.running_task
                             .insert(task.task_id, Arc::new(task.clone()));
                         EMPTY_ARC_STR.clone()
+                    } else if redo_type == RedoType::Timeout {
+                        timeout_tasks.push(task);
+                        continue;
                     } else {
                         job = None;
                         ERR_MSG_TASK_TIMEOUT.clone()
@@ -315,6 +547,8 @@ impl ScheduleManager {
                     task_date_time: now,
                     success: false,
                     handle_msg: Some(handle_msg),
+                    result: None,
+                    instance_addr: None,
                 };
                 finish_params.push(callback_param);
             }
@@ -323,15 +557,141 @@ impl ScheduleManager {
             task_manager.do_send(TaskManagerReq::RedoTaskList(retry_items));
         }
         self.task_callback(finish_params, ctx).ok();
+        self.finish_timeout_tasks(timeout_tasks, ctx);
+    }
+
+    /// 超时且不再重试的任务记录为超时状态
+    fn finish_timeout_tasks(&mut self, tasks: Vec<JobTaskInfo>, ctx: &mut Context<Self>) {
+        if tasks.is_empty() {
+            return;
+        }
+        let mut list = Vec::with_capacity(tasks.len());
+        let mut metrics_info = UpdateTaskMetricsInfo::default();
+        let now = now_second_u32();
+        for mut task in tasks {
+            if self.running_task.remove(&task.task_id).is_some() {
+                metrics_info.fail_count += 1;
+                metrics_info.timeout_count += 1;
+            }
+            task.status = TaskStatusType::Timeout;
+            task.fail_type = Some(RetryFailType::Timeout);
+            task.finish_time = now;
+            task.callback_message = ERR_MSG_TASK_TIMEOUT.clone();
+            list.push(Arc::new(task));
+        }
+        let mut metrics_request = vec![];
+        Self::append_update_metrics_request(&metrics_info, &mut metrics_request);
+        if !metrics_request.is_empty() {
+            self.do_send_metrics_request(MetricsRequest::BatchRecord(metrics_request));
+        }
+        if let Some(raft_request_route) = self.raft_request_route.clone() {
+            Self::notify_update_task(raft_request_route, list)
+                .into_actor(self)
+                .map(|_, _, _| {})
+                .spawn(ctx);
+        }
+    }
+
+    /// 应用取消任务的raft请求,各节点移除待执行或执行中的任务;
+    /// 只由任务归属节点通知执行器终止并更新任务记录
+    fn cancel_task(
+        &mut self,
+        task_id: u64,
+        operator: Arc<String>,
+        cancel_time: u32,
+        ctx: &mut Context<Self>,
+    ) -> Option<Arc<JobTaskInfo>> {
+        let old_task = self.running_task.remove(&task_id)?;
+        let mut task = old_task.as_ref().clone();
+        let mut metrics_info = UpdateTaskMetricsInfo::default();
+        let is_owner = self.data_load_completed && self.is_local_owner(task.job_id);
+        if task.status == TaskStatusType::Running {
+            let job = self
+                .job_run_state
+                .get(&task.job_id)
+                .map(|e| e.source_job.clone());
+            if is_owner {
+                if let Some(item) = KillTaskItem::new_by_task(&task, job.as_ref()) {
+                    if let Some(task_manager) = self.task_manager.as_ref() {
+                        task_manager.do_send(TaskManagerReq::KillTaskList(vec![item]));
+                    }
+                }
+            }
+            task.status = TaskStatusType::Killed;
+            metrics_info.fail_count += 1;
+            metrics_info.kill_count += 1;
+        } else {
+            task.status = TaskStatusType::Cancelled;
+            metrics_info.cancel_count += 1;
+        }
+        task.finish_time = cancel_time;
+        task.callback_message = if operator.is_empty() {
+            ERR_MSG_TASK_CANCELLED.clone()
+        } else {
+            Arc::new(format!(
+                "{} by {}",
+                ERR_MSG_TASK_CANCELLED.as_str(),
+                operator
+            ))
+        };
+        log::info!(
+            "ScheduleManager|cancel task,task_id:{},status:{},operator:{}",
+            task_id,
+            task.status.to_str(),
+            &operator
+        );
+        let mut metrics_request = vec![];
+        Self::append_update_metrics_request(&metrics_info, &mut metrics_request);
+        self.do_send_metrics_request(MetricsRequest::BatchRecord(metrics_request));
+        let task = Arc::new(task);
+        if is_owner {
+            if let Some(raft_request_route) = self.raft_request_route.clone() {
+                Self::notify_update_task(raft_request_route, vec![task.clone()])
+                    .into_actor(self)
+                    .map(|_, _, _| {})
+                    .spawn(ctx);
+            }
+        }
+        Some(task)
+    }
+
+    /// 来自之前执行实例(如被超时终止)的回调,不修改重试中任务的状态;
+    /// 只依据raft同步的任务状态判断,各节点应用回调时结果一致;
+    /// 没有来源地址(HTTP回调)或重试到同一实例时无法区分,按正常回调处理
+    fn is_previous_try_callback(&self, param: &TaskCallBackParam) -> bool {
+        let addr = match param.instance_addr.as_ref() {
+            Some(v) => v,
+            None => return false,
+        };
+        let is_previous = self.running_task.get(&param.task_id).is_some_and(|task| {
+            task.retry_count > 0
+                && &task.instance_addr != addr
+                && task.try_logs.iter().any(|v| &v.addr == addr)
+        });
+        if !is_previous {
+            return false;
+        }
+        log::warn!(
+            "ignore callback of previous try task,task_id:{},success:{},msg:{}",
+            param.task_id,
+            param.success,
+            param
+                .handle_msg
+                .as_ref()
+                .map(|v| v.as_str())
+                .unwrap_or_default()
+        );
+        true
     }
 
     fn heartbeat(&mut self, ctx: &mut Context<Self>) {
-        // 前期只支持主节点发起调度
-        if !self.local_is_master {
+        // 未开启分区时只由主节点发起调度
+        if !self.can_schedule() {
             self.running_heartbeat = false;
             return;
         }
         let now = now_second_u32();
+        self.expire_pauses(now, ctx);
         self.trigger_job(now);
         self.trigger_redo_job(now, ctx);
         self.switch_finish_mark(now);
@@ -353,6 +713,9 @@ impl ScheduleManager {
         let mut metrics_info = UpdateTaskMetricsInfo::default();
         let mut metrics_request = vec![];
         for param in params {
+            if self.is_previous_try_callback(&param) {
+                continue;
+            }
             let update_time = param.task_date_time;
             let update_second = (update_time / 1000) as u32;
             if let Some(task_instance) = self.running_task.remove(&param.task_id) {
@@ -379,10 +742,26 @@ impl ScheduleManager {
                     task_instance.status = TaskStatusType::Success;
                 } else {
                     task_instance.status = TaskStatusType::Fail;
+                    task_instance.fail_type = Some(RetryFailType::ExecutorFail);
                     if let Some(msg) = param.handle_msg {
                         task_instance.callback_message = msg;
                     }
                 }
+                if let Some(result) = param.result.filter(|v| !v.is_empty()) {
+                    match TaskCallBackParam::check_result(&result) {
+                        Ok(_) => task_instance.result = result,
+                        Err(err) => {
+                            log::warn!("task result is invalid,task_id:{},{}", param.task_id, err);
+                            //保留执行器的回调信息,结果校验错误追加在后面
+                            task_instance.callback_message =
+                                if task_instance.callback_message.is_empty() {
+                                    Arc::new(err.to_string())
+                                } else {
+                                    Arc::new(format!("{};{}", &task_instance.callback_message, err))
+                                };
+                        }
+                    }
+                }
                 list.push(Arc::new(task_instance));
             } else {
                 self.finish_mark_group
@@ -490,18 +869,46 @@ impl ScheduleManager {
                     }
                 }
             }
-            TaskStatusType::Fail => {
-                if task_log.can_retry() {
+            TaskStatusType::Timeout => {
+                finish_job_id = Some(task_log.job_id);
+                if let Some(_v) = self.running_task.remove(&task_log.task_id) {
+                    if task_log.finish_time >= self.app_start_second {
+                        metrics_info.fail_count += 1;
+                        metrics_info.timeout_count += 1;
+                    }
+                }
+            }
+            TaskStatusType::Cancelled | TaskStatusType::Killed => {
+                finish_job_id = Some(task_log.job_id);
+                if let Some(_v) = self.running_task.remove(&task_log.task_id) {
+                    if task_log.finish_time >= self.app_start_second {
+                        if task_log.status == TaskStatusType::Killed {
+                            metrics_info.fail_count += 1;
+                            metrics_info.kill_count += 1;
+                        } else {
+                            metrics_info.cancel_count += 1;
+                        }
+                    }
+                }
+            }
+            TaskStatusType::Fail | TaskStatusType::Skipped => {
+                let retry_policy = self.get_job_retry_policy(task_log.job_id);
+                if Self::can_retry_task(&task_log, retry_policy.as_deref()) {
                     self.running_task.insert(task_log.task_id, task_log.clone());
                     self.active_retry_task(
                         task_log.task_id,
-                        now_second_u32() + task_log.get_retry_interval(),
+                        now_second_u32()
+                            + task_log.get_policy_retry_interval(retry_policy.as_deref()),
                         RedoType::Retry,
                     );
                 } else {
                     finish_job_id = Some(task_log.job_id);
-                    if let Some(_v) = self.running_task.remove(&task_log.task_id) {
-                        if task_log.finish_time >= self.app_start_second {
+                    let is_running = self.running_task.remove(&task_log.task_id).is_some();
+                    if task_log.finish_time >= self.app_start_second {
+                        // 未执行的任务不会进入运行列表
+                        if task_log.status == TaskStatusType::Skipped {
+                            metrics_info.skip_count += 1;
+                        } else if is_running {
                             metrics_info.fail_count += 1;
                         }
                     }
@@ -573,6 +980,9 @@ impl ScheduleManager {
                     continue;
                 }
             }
+            if !query_param.match_status(&task_log.status) {
+                continue;
+            }
             if index >= query_param.offset && index < end_index {
                 rlist.push(task_log.clone());
             }
@@ -614,6 +1024,39 @@ impl ScheduleManager {
             };
             writer.do_send(SnapshotWriterRequest::Record(record));
         }
+        //调度分区
+        if !self.partition_info.is_empty() {
+            let mut buf = Vec::new();
+            {
+                let mut writer = Writer::new(&mut buf);
+                let value_do = self.partition_info.to_do();
+                writer.write_message(&value_do)?;
+            }
+            let record = SnapshotRecordDto {
+                tree: SCHEDULE_PARTITION_TABLE_NAME.clone(),
+                key: id_to_bin(0),
+                value: buf,
+                op_type: 0,
+            };
+            writer.do_send(SnapshotWriterRequest::Record(record));
+        }
+        //调度暂停
+        //包含已到恢复时间但还未移除的配置,恢复时需据此补执行
+        for info in self.pause_group.list() {
+            let mut buf = Vec::new();
+            {
+                let mut writer = Writer::new(&mut buf);
+                let value_do = info.to_do();
+                writer.write_message(&value_do)?;
+            }
+            let record = SnapshotRecordDto {
+                tree: SCHEDULE_PAUSE_TABLE_NAME.clone(),
+                key: info.key().to_key_string().into_bytes(),
+                value: buf,
+                op_type: 0,
+            };
+            writer.do_send(SnapshotWriterRequest::Record(record));
+        }
         Ok(())
     }
 
@@ -629,41 +1072,124 @@ impl ScheduleManager {
             let value_do: JobTaskDo = reader.read_message(&record.value)?;
             let value: Arc<JobTaskInfo> = Arc::new(value_do.into());
             self.running_task.insert(value.task_id, value);
+        } else if record.tree.as_str() == SCHEDULE_PARTITION_TABLE_NAME.as_str() {
+            let mut reader = BytesReader::from_bytes(&record.value);
+            let value_do: SchedulePartitionDo = reader.read_message(&record.value)?;
+            self.set_partition(value_do.into());
+        } else if record.tree.as_str() == SCHEDULE_PAUSE_TABLE_NAME.as_str() {
+            let mut reader = BytesReader::from_bytes(&record.value);
+            let value_do: SchedulePauseDo = reader.read_message(&record.value)?;
+            self.pause_group.update(value_do.into());
         }
         Ok(())
     }
 
     fn load_completed(&mut self, _ctx: &mut Context<Self>) -> anyhow::Result<()> {
+        self.data_load_completed = true;
         Ok(())
     }
 
     fn update_vote(&mut self, vote_info: VoteInfo, local_is_master: bool, ctx: &mut Context<Self>) {
         if self.last_vote_info.term < vote_info.term {
-            let last_local_is_master = self.local_is_master;
             self.last_vote_info = vote_info;
             self.local_is_master = local_is_master;
-            if !last_local_is_master && local_is_master {
-                self.init_run_job();
-                if !self.running_heartbeat {
-                    self.running_heartbeat = true;
-                    self.heartbeat(ctx);
-                }
-            }
-            if !local_is_master {
-                // 从节点清理任务
-                self.active_time_set.clear();
-                self.redo_set.clear();
+            if self.can_schedule() != self.schedule_active {
+                self.reset_schedule(None, ctx);
             }
+            self.check_partition(ctx);
+        }
+    }
+
+    /// 调度归属变化后，重建本节点的调度集合
+    fn reset_schedule(&mut self, start_second: Option<u32>, ctx: &mut Context<Self>) {
+        self.active_time_set.clear();
+        self.redo_set.clear();
+        self.schedule_active = self.can_schedule();
+        if !self.schedule_active {
+            return;
+        }
+        if let Some(start_second) = start_second {
+            self.init_run_job_from(start_second);
+        } else {
+            self.init_run_job();
+        }
+        if !self.running_heartbeat {
+            self.running_heartbeat = true;
+            self.heartbeat(ctx);
+        }
+    }
+
+    fn set_partition(&mut self, partition_info: SchedulePartitionInfo) {
+        self.partition_ring = PartitionRing::new(&partition_info.node_ids);
+        self.partition_info = partition_info;
+    }
+
+    fn update_partition(&mut self, partition_info: SchedulePartitionInfo, ctx: &mut Context<Self>) {
+        log::info!(
+            "ScheduleManager|update partition,version:{},nodes:{:?}",
+            partition_info.version,
+            &partition_info.node_ids
+        );
+        self.set_partition(partition_info);
+        if self.schedule_active || self.can_schedule() {
+            // 当前秒已由原归属节点触发，从下一秒开始计算
+            self.reset_schedule(Some(now_second_u32()), ctx);
         }
     }
 
+    fn update_members(&mut self, members: Vec<u64>, ctx: &mut Context<Self>) {
+        self.cluster_members = members;
+        self.check_partition(ctx);
+    }
+
+    /// 主节点按有效成员重新分配分区，通过raft同步到各节点
+    fn check_partition(&mut self, ctx: &mut Context<Self>) {
+        if !self.partition_enable || !self.local_is_master || self.cluster_members.is_empty() {
+            return;
+        }
+        if self.partition_info.same_nodes(&self.cluster_members) {
+            return;
+        }
+        let partition_info = SchedulePartitionInfo::new(
+            self.partition_info.version + 1,
+            self.cluster_members.clone(),
+        );
+        if let Some(raft_request_route) = self.raft_request_route.clone() {
+            Self::notify_update_partition(raft_request_route, partition_info)
+                .into_actor(self)
+                .map(|res, _, _| {
+                    if let Err(e) = res {
+                        log::error!("ScheduleManager|update partition error,{}", e);
+                    }
+                })
+                .spawn(ctx);
+        }
+    }
+
+    async fn notify_update_partition(
+        raft_request_route: Arc<RaftRequestRoute>,
+        partition_info: SchedulePartitionInfo,
+    ) -> anyhow::Result<()> {
+        raft_request_route
+            .request(ClientRequest::ScheduleReq {
+                req: ScheduleManagerRaftReq::UpdatePartition(partition_info),
+            })
+            .await?;
+        Ok(())
+    }
+
     fn init_run_job(&mut self) {
-        // 初始化任务调度
         let now = now_second_u32();
         let start_second = std::cmp::min(
             std::cmp::max(self.last_trigger_time, self.app_start_second),
             now - 1,
         );
+        self.init_run_job_from(start_second);
+    }
+
+    fn init_run_job_from(&mut self, start_second: u32) {
+        // 初始化任务调度
+        let now = now_second_u32();
         let mut active_jobs: Vec<(u64, u32, u32)> = Vec::new();
         let mut delay_job_ids = HashSet::new();
         let now_datetime_option = if let Some(now_datetime) =
@@ -703,11 +1229,12 @@ impl ScheduleManager {
                     retry_list.push((task.task_id, task.trigger_time + timeout, RedoType::Timeout));
                     running_jobs.insert(task.job_id);
                 }
-                TaskStatusType::Fail => {
-                    if task.can_retry() {
+                TaskStatusType::Fail | TaskStatusType::Skipped => {
+                    let retry_policy = self.get_job_retry_policy(task.job_id);
+                    if Self::can_retry_task(task, retry_policy.as_deref()) {
                         retry_list.push((
                             task.task_id,
-                            now + task.get_retry_interval(),
+                            now + task.get_policy_retry_interval(retry_policy.as_deref()),
                             RedoType::Retry,
                         ));
                         running_jobs.insert(task.job_id);
@@ -766,6 +1293,38 @@ impl ScheduleManager {
                 MetricsRecord::CounterInc(metrics_info.success_count),
             ));
         }
+        if metrics_info.timeout_count > 0 {
+            metrics_request.push(MetricsItem::new(
+                MetricsKey::TaskTimeoutSize,
+                MetricsRecord::CounterInc(metrics_info.timeout_count),
+            ));
+        }
+        if metrics_info.kill_count > 0 {
+            metrics_request.push(MetricsItem::new(
+                MetricsKey::TaskKillSize,
+                MetricsRecord::CounterInc(metrics_info.kill_count),
+            ));
+        }
+        // 跳过、取消不计入失败数
+        let other_finish_count = metrics_info.skip_count + metrics_info.cancel_count;
+        if metrics_info.skip_count > 0 {
+            metrics_request.push(MetricsItem::new(
+                MetricsKey::TaskSkipSize,
+                MetricsRecord::CounterInc(metrics_info.skip_count),
+            ));
+        }
+        if metrics_info.cancel_count > 0 {
+            metrics_request.push(MetricsItem::new(
+                MetricsKey::TaskCancelSize,
+                MetricsRecord::CounterInc(metrics_info.cancel_count),
+            ));
+        }
+        if other_finish_count > 0 {
+            metrics_request.push(MetricsItem::new(
+                MetricsKey::TaskFinishTotalCount,
+                MetricsRecord::CounterInc(other_finish_count),
+            ));
+        }
         if metrics_info.fail_count > 0 {
             metrics_request.push(MetricsItem::new(
                 MetricsKey::TaskFailSize,
@@ -788,7 +1347,7 @@ impl ScheduleManager {
 impl Handler<ScheduleManagerReq> for ScheduleManager {
     type Result = anyhow::Result<ScheduleManagerResult>;
 
-    fn handle(&mut self, msg: ScheduleManagerReq, _ctx: &mut Context<Self>) -> Self::Result {
+    fn handle(&mut self, msg: ScheduleManagerReq, ctx: &mut Context<Self>) -> Self::Result {
         match msg {
             ScheduleManagerReq::UpdateJob(job) => {
                 //log::info!("ScheduleManagerReq::UpdateJob,job_id:{}", &job.id);
@@ -810,6 +1369,29 @@ impl Handler<ScheduleManagerReq> for ScheduleManager {
                 let (total, list) = self.query_latest_history_task_logs(&param);
                 return Ok(ScheduleManagerResult::JobTaskLogPageInfo(total, list));
             }
+            ScheduleManagerReq::UpdateMembers(members) => {
+                self.update_members(members, ctx);
+            }
+            ScheduleManagerReq::QueryPartition => {
+                return Ok(ScheduleManagerResult::PartitionInfo(
+                    self.partition_info.clone(),
+                ));
+            }
+            ScheduleManagerReq::GetRunningTask(task_id) => {
+                return Ok(ScheduleManagerResult::TaskInfo(
+                    self.running_task.get(&task_id).cloned(),
+                ));
+            }
+            ScheduleManagerReq::QueryNextTriggerTimes(job, count) => {
+                return Ok(ScheduleManagerResult::TriggerTimes(
+                    self.query_next_trigger_times(job, count),
+                ));
+            }
+            ScheduleManagerReq::QueryPauseList => {
+                return Ok(ScheduleManagerResult::PauseList(
+                    self.pause_group.active_list(now_second_u32()),
+                ));
+            }
         }
         Ok(ScheduleManagerResult::None)
     }
@@ -823,6 +1405,26 @@ impl Handler<ScheduleManagerRaftReq> for ScheduleManager {
             ScheduleManagerRaftReq::TaskCallBacks(params) => {
                 self.task_callback(params, ctx)?;
             }
+            ScheduleManagerRaftReq::UpdatePartition(partition_info) => {
+                self.update_partition(partition_info, ctx);
+            }
+            ScheduleManagerRaftReq::UpdatePause(info) => {
+                self.update_pause(info);
+            }
+            ScheduleManagerRaftReq::RemovePause(key) => {
+                self.remove_pause(&key);
+            }
+            ScheduleManagerRaftReq::ExpirePause(key, resume_time) => {
+                self.expire_pause(&key, resume_time);
+            }
+            ScheduleManagerRaftReq::CancelTask(task_id, operator, cancel_time) => {
+                return Ok(ScheduleManagerRaftResult::TaskInfo(self.cancel_task(
+                    task_id,
+                    operator,
+                    cancel_time,
+                    ctx,
+                )));
+            }
         }
         Ok(ScheduleManagerRaftResult::None)
     }
@@ -15,6 +15,9 @@ impl JobTaskLogGroup {
 
     pub fn update_task_log(&mut self, new_task_log: Arc<JobTaskInfo>, limit_count: usize) {
         if let Some(task_log) = self.task_log_map.get_mut(&new_task_log.task_id) {
+            if task_log.status.is_closed() && task_log.status != new_task_log.status {
+                return;
+            }
             *task_log = new_task_log;
         } else {
             self.task_log_map.insert(new_task_log.task_id, new_task_log);
@@ -0,0 +1,120 @@
+use crate::audit::model::audit::{AUDIT_ACTION_PAUSE, AUDIT_ACTION_RESUME, AUDIT_TARGET_SCHEDULE};
+use crate::audit::recorder::AuditRecorder;
+use crate::common::datetime_utils::now_second_u32;
+use crate::common::share_data::ShareData;
+use crate::job::batch::AuditRecordBuilder;
+use crate::raft::store::{ClientRequest, ClientResponse};
+use crate::schedule::model::actor_model::{
+    ScheduleManagerRaftReq, ScheduleManagerRaftResult, ScheduleManagerReq, ScheduleManagerResult,
+};
+use crate::schedule::model::pause::{SchedulePauseInfo, SchedulePauseKey, SchedulePauseParam};
+use crate::task::model::task::JobTaskInfo;
+use actix_web::HttpRequest;
+use std::sync::Arc;
+
+pub async fn query_pause_list(
+    share_data: &ShareData,
+) -> anyhow::Result<Vec<Arc<SchedulePauseInfo>>> {
+    if let ScheduleManagerResult::PauseList(list) = share_data
+        .schedule_manager
+        .send(ScheduleManagerReq::QueryPauseList)
+        .await??
+    {
+        Ok(list)
+    } else {
+        Err(anyhow::anyhow!("query pause list result type error!"))
+    }
+}
+
+async fn get_pause_info(
+    share_data: &ShareData,
+    key: &SchedulePauseKey,
+) -> anyhow::Result<Option<Arc<SchedulePauseInfo>>> {
+    Ok(query_pause_list(share_data)
+        .await?
+        .into_iter()
+        .find(|v| v.namespace == key.namespace && v.app_name == key.app_name))
+}
+
+///
+/// 暂停调度,通过raft同步到各节点
+/// 只影响定时触发,不修改任务的enable,手动触发与失败重试不受影响
+pub async fn pause_schedule(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    param: &SchedulePauseParam,
+    operator: Arc<String>,
+    audit_builder: AuditRecordBuilder,
+) -> anyhow::Result<SchedulePauseInfo> {
+    let info = param.to_info(operator)?;
+    let key = info.key();
+    let mut record = audit_builder(
+        req,
+        AUDIT_ACTION_PAUSE,
+        AUDIT_TARGET_SCHEDULE,
+        key.to_key_string(),
+    );
+    record.namespace = key.namespace.clone();
+    record.set_before(get_pause_info(share_data, &key).await?.as_deref());
+    record.set_after(Some(&info));
+    share_data
+        .raft_request_route
+        .request(ClientRequest::ScheduleReq {
+            req: ScheduleManagerRaftReq::UpdatePause(info.clone()),
+        })
+        .await?;
+    AuditRecorder::submit(share_data, record).await;
+    Ok(info)
+}
+
+/// 恢复调度,返回被移除的暂停配置
+pub async fn resume_schedule(
+    req: &HttpRequest,
+    share_data: &ShareData,
+    param: &SchedulePauseParam,
+    audit_builder: AuditRecordBuilder,
+) -> anyhow::Result<Arc<SchedulePauseInfo>> {
+    let key = param.key()?;
+    let info = get_pause_info(share_data, &key)
+        .await?
+        .ok_or_else(|| anyhow::anyhow!("schedule {} is not paused", key.to_key_string()))?;
+    let mut record = audit_builder(
+        req,
+        AUDIT_ACTION_RESUME,
+        AUDIT_TARGET_SCHEDULE,
+        key.to_key_string(),
+    );
+    record.namespace = key.namespace.clone();
+    record.set_before(Some(info.as_ref()));
+    share_data
+        .raft_request_route
+        .request(ClientRequest::ScheduleReq {
+            req: ScheduleManagerRaftReq::RemovePause(key),
+        })
+        .await?;
+    AuditRecorder::submit(share_data, record).await;
+    Ok(info)
+}
+
+/// 取消待执行或执行中的任务,通过raft同步到各节点
+pub async fn cancel_task(
+    share_data: &ShareData,
+    task_id: u64,
+    operator: Arc<String>,
+) -> anyhow::Result<Arc<JobTaskInfo>> {
+    let resp = share_data
+        .raft_request_route
+        .request(ClientRequest::ScheduleReq {
+            req: ScheduleManagerRaftReq::CancelTask(task_id, operator, now_second_u32()),
+        })
+        .await?;
+    match resp {
+        ClientResponse::ScheduleResp {
+            resp: ScheduleManagerRaftResult::TaskInfo(Some(task)),
+        } => Ok(task),
+        _ => Err(anyhow::anyhow!(
+            "task {} is not pending or running",
+            task_id
+        )),
+    }
+}
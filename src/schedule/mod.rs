@@ -1,5 +1,6 @@
 pub mod batch_call;
 pub mod core;
 pub mod job_task;
+pub mod maintenance;
 pub mod metrics;
 pub mod model;
@@ -1,4 +1,6 @@
 use crate::job::model::job::{JobInfo, JobTaskLogQueryParam};
+use crate::schedule::model::partition::SchedulePartitionInfo;
+use crate::schedule::model::pause::{SchedulePauseInfo, SchedulePauseKey};
 use crate::schedule::model::DelayFinishTasks;
 use crate::task::model::task::{JobTaskInfo, TaskCallBackParam};
 use actix::Message;
@@ -14,10 +16,23 @@ pub enum ScheduleManagerReq {
     DelayFinishTasks(DelayFinishTasks),
     UpdateTaskList(Vec<Arc<JobTaskInfo>>),
     QueryJobTaskLog(JobTaskLogQueryParam),
+    /// 集群有效成员变更，主节点据此重新分配调度分区
+    UpdateMembers(Vec<u64>),
+    QueryPartition,
+    /// 查询待执行或执行中的任务
+    GetRunningTask(u64),
+    /// 查询生效中的调度暂停配置
+    QueryPauseList,
+    /// 查询任务后续N次触发时间(已应用时间窗口)
+    QueryNextTriggerTimes(Arc<JobInfo>, usize),
 }
 
 pub enum ScheduleManagerResult {
     JobTaskLogPageInfo(usize, Vec<Arc<JobTaskInfo>>),
+    PartitionInfo(SchedulePartitionInfo),
+    TaskInfo(Option<Arc<JobTaskInfo>>),
+    PauseList(Vec<Arc<SchedulePauseInfo>>),
+    TriggerTimes(Vec<u32>),
     None,
 }
 
@@ -25,9 +40,19 @@ pub enum ScheduleManagerResult {
 #[rtype(result = "anyhow::Result<ScheduleManagerRaftResult>")]
 pub enum ScheduleManagerRaftReq {
     TaskCallBacks(Vec<TaskCallBackParam>),
+    UpdatePartition(SchedulePartitionInfo),
+    /// 暂停调度,相同范围重复设置时覆盖
+    UpdatePause(SchedulePauseInfo),
+    /// 恢复调度
+    RemovePause(SchedulePauseKey),
+    /// 到达自动恢复时间,参数为暂停范围与恢复时间
+    ExpirePause(SchedulePauseKey, u32),
+    /// 取消待执行或执行中的任务,参数为任务id、操作人与取消时间
+    CancelTask(u64, Arc<String>, u32),
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum ScheduleManagerRaftResult {
+    TaskInfo(Option<Arc<JobTaskInfo>>),
     None,
 }
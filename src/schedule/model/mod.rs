@@ -1,10 +1,15 @@
 pub mod actor_model;
 pub mod finish_mark;
+pub mod partition;
+pub mod pause;
+pub mod preview;
 
 use crate::common::cron_utils::CronUtil;
+use crate::common::datetime_utils::get_datetime_by_second;
 use crate::job::model::enum_type::ScheduleType;
 use crate::job::model::job::JobInfo;
-use chrono::{DateTime, TimeZone};
+use crate::job::model::time_window::JobTimeWindow;
+use chrono::{DateTime, FixedOffset, Offset, TimeZone};
 use cron::Schedule;
 use std::str::FromStr;
 use std::sync::Arc;
@@ -26,11 +31,16 @@ pub struct JobRunState {
     pub source_job: Arc<JobInfo>,
     /// 已标记下次触发任务
     pub marked_delay_trigger: bool,
+    pub allow_time_window: Arc<String>,
+    pub forbid_time_window: Arc<String>,
+    /// 时间窗口,未设置时为空
+    pub time_window: Option<JobTimeWindow>,
 }
 
 impl JobRunState {
     pub fn new(source_job: Arc<JobInfo>) -> Self {
         let cron_schedule = Schedule::from_str(source_job.cron_value.as_str()).ok();
+        let time_window = Self::build_time_window(&source_job);
         JobRunState {
             id: source_job.id,
             schedule_type: source_job.schedule_type.clone(),
@@ -44,15 +54,39 @@ impl JobRunState {
             next_active: false,
             version: 0,
             route_value: 0,
+            allow_time_window: source_job.allow_time_window.clone(),
+            forbid_time_window: source_job.forbid_time_window.clone(),
+            time_window,
             source_job,
             marked_delay_trigger: false,
         }
     }
+
+    fn build_time_window(source_job: &JobInfo) -> Option<JobTimeWindow> {
+        match JobTimeWindow::new(
+            &source_job.allow_time_window,
+            &source_job.forbid_time_window,
+        ) {
+            Ok(v) => Some(v).filter(|v| !v.is_empty()),
+            Err(err) => {
+                // 保存时已校验,这里只可能是旧版本数据,不限制时间窗口
+                log::error!(
+                    "job time window is invalid and ignored,job id:{},{}",
+                    source_job.id,
+                    err
+                );
+                None
+            }
+        }
+    }
+
     pub fn calculate_first_trigger_time<T: TimeZone>(&self, datetime: &DateTime<T>) -> u32 {
         match self.schedule_type {
             ScheduleType::Delay => {
                 let timestamp_seconds = datetime.timestamp() as u32;
-                std::cmp::max(self.last_finish_time + self.delay_second, timestamp_seconds)
+                let trigger_time =
+                    std::cmp::max(self.last_finish_time + self.delay_second, timestamp_seconds);
+                self.apply_time_window(trigger_time, datetime)
             }
             ScheduleType::None => 0,
             _ => self.calculate_next_trigger_time(datetime),
@@ -74,6 +108,14 @@ impl JobRunState {
             change_schedule = true;
             self.interval_second = source_job.interval_second;
         }
+        if self.allow_time_window != source_job.allow_time_window
+            || self.forbid_time_window != source_job.forbid_time_window
+        {
+            change_schedule = true;
+            self.allow_time_window = source_job.allow_time_window.clone();
+            self.forbid_time_window = source_job.forbid_time_window.clone();
+            self.time_window = Self::build_time_window(&source_job);
+        }
         if self.delay_second != source_job.delay_second {
             change_schedule = true;
             self.delay_second = source_job.delay_second;
@@ -97,6 +139,68 @@ impl JobRunState {
     }
 
     pub fn calculate_next_trigger_time<T: TimeZone>(&self, datetime: &DateTime<T>) -> u32 {
+        let trigger_time = self.calculate_schedule_trigger_time(datetime);
+        self.apply_time_window(trigger_time, datetime)
+    }
+
+    /// 触发时间不在时间窗口内时顺延:cron任务取窗口内的下一个cron时间,间隔与延迟任务在窗口开始时触发
+    fn apply_time_window<T: TimeZone>(&self, trigger_time: u32, datetime: &DateTime<T>) -> u32 {
+        let time_window = match self.time_window.as_ref() {
+            Some(v) if trigger_time > 0 => v,
+            _ => return trigger_time,
+        };
+        let offset = datetime.offset().fix();
+        if self.schedule_type != ScheduleType::Cron {
+            return time_window.next_permitted_time(trigger_time, &offset);
+        }
+        match self.cron_schedule.as_ref() {
+            Some(cron_schedule) => {
+                time_window.next_permitted_cron_time(cron_schedule, trigger_time, &offset)
+            }
+            None => 0,
+        }
+    }
+
+    /// [start_time,end_time)内是否有调度触发时间,用于推算暂停期间错过的触发
+    pub fn has_trigger_between(
+        &self,
+        start_time: u32,
+        end_time: u32,
+        offset: &FixedOffset,
+    ) -> bool {
+        if start_time == 0 || start_time >= end_time {
+            return false;
+        }
+        match get_datetime_by_second(start_time - 1, offset) {
+            Some(datetime) => {
+                let trigger_time = self.calculate_next_trigger_time(&datetime);
+                trigger_time > 0 && trigger_time < end_time
+            }
+            None => false,
+        }
+    }
+
+    /// 从指定时间开始计算后续的触发时间,延迟任务只计算首次触发
+    pub fn next_trigger_times(&self, datetime: &DateTime<FixedOffset>, count: usize) -> Vec<u32> {
+        let mut state = self.clone();
+        let mut list = Vec::with_capacity(count);
+        let mut trigger_time = state.calculate_first_trigger_time(datetime);
+        while trigger_time > 0 && list.len() < count {
+            list.push(trigger_time);
+            if state.schedule_type == ScheduleType::Delay {
+                break;
+            }
+            state.pre_trigger_time = trigger_time;
+            if let Some(next_datetime) = get_datetime_by_second(trigger_time, datetime.offset()) {
+                trigger_time = state.calculate_next_trigger_time(&next_datetime);
+            } else {
+                break;
+            }
+        }
+        list
+    }
+
+    fn calculate_schedule_trigger_time<T: TimeZone>(&self, datetime: &DateTime<T>) -> u32 {
         let mut result = 0;
         let timestamp_seconds = datetime.timestamp() as u32;
         match self.schedule_type {
@@ -195,3 +299,59 @@ impl DelayFinishTasks {
         self.success_tasks.is_empty() && self.fail_tasks.is_empty()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::datetime_utils::get_datetime_by_second;
+
+    fn build_state(job: JobInfo) -> JobRunState {
+        JobRunState::new(Arc::new(job))
+    }
+
+    #[test]
+    fn next_trigger_times_with_time_window() {
+        let offset = FixedOffset::east_opt(8 * 3600).unwrap();
+        // 2024-01-01 00:00:00 +08:00 为周一
+        let monday = 1704038400u32;
+        let hour = 3600u32;
+        let state = build_state(JobInfo {
+            schedule_type: ScheduleType::Cron,
+            cron_value: Arc::new("0 0/30 * * * *".to_owned()),
+            allow_time_window: Arc::new("MON-FRI 09:00-18:00".to_owned()),
+            ..Default::default()
+        });
+        let start = get_datetime_by_second(monday + 17 * hour + 40 * 60, &offset).unwrap();
+        assert_eq!(
+            state.next_trigger_times(&start, 2),
+            vec![monday + 33 * hour, monday + 33 * hour + 30 * 60]
+        );
+        let state = build_state(JobInfo {
+            schedule_type: ScheduleType::Interval,
+            interval_second: hour,
+            forbid_time_window: Arc::new("09:00-18:00".to_owned()),
+            ..Default::default()
+        });
+        let start = get_datetime_by_second(monday + 8 * hour + 30 * 60, &offset).unwrap();
+        assert_eq!(
+            state.next_trigger_times(&start, 3),
+            vec![monday + 18 * hour, monday + 19 * hour, monday + 20 * hour]
+        );
+    }
+
+    #[test]
+    fn trigger_between_pause_time() {
+        let offset = FixedOffset::east_opt(8 * 3600).unwrap();
+        let monday = 1704038400u32;
+        let hour = 3600u32;
+        let state = build_state(JobInfo {
+            schedule_type: ScheduleType::Cron,
+            cron_value: Arc::new("0 0 3 * * *".to_owned()),
+            ..Default::default()
+        });
+        assert!(state.has_trigger_between(monday + 2 * hour, monday + 4 * hour, &offset));
+        assert!(state.has_trigger_between(monday + 3 * hour, monday + 4 * hour, &offset));
+        assert!(!state.has_trigger_between(monday + 2 * hour, monday + 3 * hour, &offset));
+        assert!(!state.has_trigger_between(monday + 4 * hour, monday + 26 * hour, &offset));
+    }
+}
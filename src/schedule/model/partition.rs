@@ -0,0 +1,124 @@
+use crate::common::hash_utils::get_stable_hash_value;
+use crate::common::pb::data_object::SchedulePartitionDo;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+
+/// 每个节点在哈希环上的虚拟节点数
+const VIRTUAL_NODE_COUNT: u32 = 64;
+
+/// 调度分区信息，由主节点根据集群成员分配，通过raft同步到各节点
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePartitionInfo {
+    pub version: u64,
+    pub node_ids: Vec<u64>,
+}
+
+impl SchedulePartitionInfo {
+    pub fn new(version: u64, mut node_ids: Vec<u64>) -> Self {
+        node_ids.sort_unstable();
+        node_ids.dedup();
+        Self { version, node_ids }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.node_ids.is_empty()
+    }
+
+    pub fn same_nodes(&self, node_ids: &[u64]) -> bool {
+        let mut node_ids = node_ids.to_vec();
+        node_ids.sort_unstable();
+        node_ids.dedup();
+        self.node_ids == node_ids
+    }
+
+    pub fn to_do(&self) -> SchedulePartitionDo {
+        SchedulePartitionDo {
+            version: self.version,
+            node_ids: self.node_ids.clone(),
+        }
+    }
+}
+
+impl From<SchedulePartitionDo> for SchedulePartitionInfo {
+    fn from(value: SchedulePartitionDo) -> Self {
+        Self::new(value.version, value.node_ids)
+    }
+}
+
+/// 按任务id一致性哈希到节点，节点变更时只迁移少量任务
+/// 使用crc32保证不同版本编译的节点计算结果一致
+#[derive(Clone, Debug, Default)]
+pub struct PartitionRing {
+    ring: BTreeMap<u64, u64>,
+}
+
+impl PartitionRing {
+    pub fn new(node_ids: &[u64]) -> Self {
+        let mut ring = BTreeMap::new();
+        for node_id in node_ids {
+            for i in 0..VIRTUAL_NODE_COUNT {
+                ring.insert(get_stable_hash_value(&[*node_id, i as u64]), *node_id);
+            }
+        }
+        Self { ring }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ring.is_empty()
+    }
+
+    pub fn get_node(&self, job_id: u64) -> Option<u64> {
+        let hash = get_stable_hash_value(&[job_id]);
+        self.ring
+            .range(hash..)
+            .next()
+            .or_else(|| self.ring.iter().next())
+            .map(|(_, node_id)| *node_id)
+    }
+
+    pub fn contains_node(&self, node_id: u64) -> bool {
+        self.ring.values().any(|v| *v == node_id)
+    }
+
+    pub fn node_ids(&self) -> HashSet<u64> {
+        self.ring.values().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PartitionRing;
+    use std::collections::HashMap;
+
+    #[test]
+    fn ring_balance() {
+        let ring = PartitionRing::new(&[1, 2, 3]);
+        let mut counts: HashMap<u64, usize> = HashMap::new();
+        for job_id in 0..3000u64 {
+            *counts.entry(ring.get_node(job_id).unwrap()).or_default() += 1;
+        }
+        assert_eq!(counts.len(), 3);
+        for count in counts.values() {
+            assert!(*count > 500, "unbalanced:{:?}", &counts);
+        }
+    }
+
+    #[test]
+    fn ring_rebalance_move_part() {
+        let old_ring = PartitionRing::new(&[1, 2, 3]);
+        let new_ring = PartitionRing::new(&[1, 2, 3, 4]);
+        let mut moved = 0;
+        for job_id in 0..3000u64 {
+            let old_node = old_ring.get_node(job_id).unwrap();
+            let new_node = new_ring.get_node(job_id).unwrap();
+            if old_node != new_node {
+                // 新增节点只会从其它节点接管任务
+                assert_eq!(new_node, 4);
+                moved += 1;
+            }
+        }
+        assert!(moved > 0 && moved < 1500);
+        assert!(PartitionRing::new(&[]).get_node(1).is_none());
+    }
+}
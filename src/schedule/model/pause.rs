@@ -0,0 +1,305 @@
+use crate::common::constant::EMPTY_ARC_STR;
+use crate::common::datetime_utils::{now_millis, now_second_u32};
+use crate::common::pb::data_object::SchedulePauseDo;
+use crate::job::model::enum_type::PastDueStrategy;
+use crate::job::model::job::JobInfo;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+///
+/// 调度暂停范围
+/// namespace为空表示全局暂停,app_name为空表示暂停整个命名空间
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePauseKey {
+    pub namespace: Arc<String>,
+    pub app_name: Arc<String>,
+}
+
+impl SchedulePauseKey {
+    pub fn new(namespace: Arc<String>, app_name: Arc<String>) -> Self {
+        Self {
+            namespace,
+            app_name,
+        }
+    }
+
+    pub fn global() -> Self {
+        Self::new(EMPTY_ARC_STR.clone(), EMPTY_ARC_STR.clone())
+    }
+
+    pub fn is_global(&self) -> bool {
+        self.namespace.is_empty()
+    }
+
+    /// 暂停范围是否包含该任务
+    pub fn is_match(&self, job: &JobInfo) -> bool {
+        self.is_global()
+            || (self.namespace == job.namespace
+                && (self.app_name.is_empty() || self.app_name == job.app_name))
+    }
+
+    pub fn check_valid(&self) -> anyhow::Result<()> {
+        if self.namespace.is_empty() && !self.app_name.is_empty() {
+            return Err(anyhow::anyhow!("namespace is empty but app_name is set!"));
+        }
+        Ok(())
+    }
+
+    /// 全局为`*`,命名空间为`namespace`,应用为`namespace@@app_name`
+    pub fn to_key_string(&self) -> String {
+        if self.is_global() {
+            "*".to_owned()
+        } else if self.app_name.is_empty() {
+            self.namespace.as_ref().to_owned()
+        } else {
+            format!("{}@@{}", &self.namespace, &self.app_name)
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePauseInfo {
+    pub namespace: Arc<String>,
+    pub app_name: Arc<String>,
+    /// 自动恢复时间(秒),0表示需手动恢复
+    pub resume_time: u32,
+    /// 暂停期间错过的触发: DEFAULT按任务自身配置,IGNORE忽略,EXECUTE恢复后补执行一次
+    pub past_due_strategy: PastDueStrategy,
+    pub reason: Arc<String>,
+    pub operator: Arc<String>,
+    pub update_time: u64,
+    /// 暂停开始时间(秒),用于恢复时推算错过的触发
+    #[serde(default)]
+    pub start_time: u32,
+}
+
+impl SchedulePauseInfo {
+    pub fn key(&self) -> SchedulePauseKey {
+        SchedulePauseKey::new(self.namespace.clone(), self.app_name.clone())
+    }
+
+    /// 暂停开始时间,兼容没有记录开始时间的旧数据
+    pub fn get_start_time(&self) -> u32 {
+        if self.start_time > 0 {
+            self.start_time
+        } else {
+            (self.update_time / 1000) as u32
+        }
+    }
+
+    pub fn is_active(&self, now_second: u32) -> bool {
+        self.resume_time == 0 || now_second < self.resume_time
+    }
+
+    /// 恢复后是否补执行暂停期间错过的触发
+    pub fn execute_past_due(&self, job: &JobInfo) -> bool {
+        match self.past_due_strategy {
+            PastDueStrategy::Execute => true,
+            PastDueStrategy::Ignore => false,
+            PastDueStrategy::Default => job.past_due_strategy == PastDueStrategy::Execute,
+        }
+    }
+
+    pub fn to_do(&self) -> SchedulePauseDo<'_> {
+        SchedulePauseDo {
+            namespace: Cow::Borrowed(&self.namespace),
+            app_name: Cow::Borrowed(&self.app_name),
+            resume_time: self.resume_time,
+            past_due_strategy: Cow::Borrowed(self.past_due_strategy.to_str()),
+            reason: Cow::Borrowed(&self.reason),
+            operator: Cow::Borrowed(&self.operator),
+            update_time: self.update_time,
+            start_time: self.start_time,
+        }
+    }
+}
+
+impl<'a> From<SchedulePauseDo<'a>> for SchedulePauseInfo {
+    fn from(value: SchedulePauseDo<'a>) -> Self {
+        Self {
+            namespace: Arc::new(value.namespace.to_string()),
+            app_name: Arc::new(value.app_name.to_string()),
+            resume_time: value.resume_time,
+            past_due_strategy: PastDueStrategy::from_str(&value.past_due_strategy),
+            reason: Arc::new(value.reason.to_string()),
+            operator: Arc::new(value.operator.to_string()),
+            update_time: value.update_time,
+            start_time: value.start_time,
+        }
+    }
+}
+
+/// 暂停或恢复调度的请求参数,恢复时只使用namespace与app_name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePauseParam {
+    pub namespace: Option<Arc<String>>,
+    pub app_name: Option<Arc<String>>,
+    pub resume_time: Option<u32>,
+    pub past_due_strategy: Option<PastDueStrategy>,
+    pub reason: Option<Arc<String>>,
+}
+
+impl SchedulePauseParam {
+    pub fn key(&self) -> anyhow::Result<SchedulePauseKey> {
+        let key = SchedulePauseKey::new(
+            self.namespace.clone().unwrap_or(EMPTY_ARC_STR.clone()),
+            self.app_name.clone().unwrap_or(EMPTY_ARC_STR.clone()),
+        );
+        key.check_valid()?;
+        Ok(key)
+    }
+
+    pub fn to_info(&self, operator: Arc<String>) -> anyhow::Result<SchedulePauseInfo> {
+        let key = self.key()?;
+        let resume_time = self.resume_time.unwrap_or_default();
+        if resume_time > 0 && resume_time <= now_second_u32() {
+            return Err(anyhow::anyhow!("resume_time is before now!"));
+        }
+        let now = now_millis();
+        Ok(SchedulePauseInfo {
+            namespace: key.namespace,
+            app_name: key.app_name,
+            resume_time,
+            past_due_strategy: self.past_due_strategy.clone().unwrap_or_default(),
+            reason: self.reason.clone().unwrap_or(EMPTY_ARC_STR.clone()),
+            operator,
+            update_time: now,
+            start_time: (now / 1000) as u32,
+        })
+    }
+}
+
+/// 生效中的调度暂停配置,不修改任务自身的enable
+#[derive(Clone, Debug, Default)]
+pub struct SchedulePauseGroup {
+    items: HashMap<SchedulePauseKey, Arc<SchedulePauseInfo>>,
+}
+
+impl SchedulePauseGroup {
+    pub fn update(&mut self, info: SchedulePauseInfo) {
+        self.items.insert(info.key(), Arc::new(info));
+    }
+
+    pub fn remove(&mut self, key: &SchedulePauseKey) -> Option<Arc<SchedulePauseInfo>> {
+        self.items.remove(key)
+    }
+
+    pub fn get(&self, key: &SchedulePauseKey) -> Option<&Arc<SchedulePauseInfo>> {
+        self.items.get(key)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// 查找作用于该任务的暂停配置,按全局、命名空间、应用的顺序匹配
+    pub fn find(&self, job: &JobInfo, now_second: u32) -> Option<&Arc<SchedulePauseInfo>> {
+        if self.items.is_empty() {
+            return None;
+        }
+        let keys = [
+            SchedulePauseKey::global(),
+            SchedulePauseKey::new(job.namespace.clone(), EMPTY_ARC_STR.clone()),
+            SchedulePauseKey::new(job.namespace.clone(), job.app_name.clone()),
+        ];
+        keys.iter()
+            .filter_map(|key| self.items.get(key))
+            .find(|v| v.is_active(now_second))
+    }
+
+    /// 全部配置,包含已到恢复时间但还未移除的
+    pub fn list(&self) -> Vec<Arc<SchedulePauseInfo>> {
+        self.items.values().cloned().collect()
+    }
+
+    /// 已到自动恢复时间的配置
+    pub fn expired_list(&self, now_second: u32) -> Vec<Arc<SchedulePauseInfo>> {
+        self.items
+            .values()
+            .filter(|v| !v.is_active(now_second))
+            .cloned()
+            .collect()
+    }
+
+    pub fn active_list(&self, now_second: u32) -> Vec<Arc<SchedulePauseInfo>> {
+        let mut list: Vec<Arc<SchedulePauseInfo>> = self
+            .items
+            .values()
+            .filter(|v| v.is_active(now_second))
+            .cloned()
+            .collect();
+        list.sort_by(|a, b| (&a.namespace, &a.app_name).cmp(&(&b.namespace, &b.app_name)));
+        list
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build_job(namespace: &str, app_name: &str) -> JobInfo {
+        JobInfo {
+            namespace: Arc::new(namespace.to_owned()),
+            app_name: Arc::new(app_name.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    fn build_pause(namespace: &str, app_name: &str, resume_time: u32) -> SchedulePauseInfo {
+        SchedulePauseInfo {
+            namespace: Arc::new(namespace.to_owned()),
+            app_name: Arc::new(app_name.to_owned()),
+            resume_time,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn pause_group_find() {
+        let now = 1000;
+        let job = build_job("dev", "app1");
+        let other_job = build_job("test", "app1");
+        let mut group = SchedulePauseGroup::default();
+        assert!(group.find(&job, now).is_none());
+        group.update(build_pause("dev", "app1", 0));
+        assert!(group.find(&job, now).is_some());
+        assert!(group.find(&other_job, now).is_none());
+        group.remove(&SchedulePauseKey::new(
+            Arc::new("dev".to_owned()),
+            Arc::new("app1".to_owned()),
+        ));
+        group.update(build_pause("dev", "", now + 10));
+        assert!(group.find(&job, now).is_some());
+        // 到达自动恢复时间
+        assert!(group.find(&job, now + 10).is_none());
+        group.update(build_pause("", "", 0));
+        assert!(group.find(&other_job, now).is_some());
+        assert_eq!(group.active_list(now + 10).len(), 1);
+        assert_eq!(group.expired_list(now + 10).len(), 1);
+        assert_eq!(group.list().len(), 2);
+        group.remove(&SchedulePauseKey::new(
+            Arc::new("dev".to_owned()),
+            EMPTY_ARC_STR.clone(),
+        ));
+        assert!(
+            SchedulePauseKey::new(Arc::new("dev".to_owned()), EMPTY_ARC_STR.clone()).is_match(&job)
+        );
+        assert!(
+            !SchedulePauseKey::new(Arc::new("dev".to_owned()), Arc::new("app2".to_owned()))
+                .is_match(&job)
+        );
+        group.remove(&SchedulePauseKey::global());
+        assert!(group.is_empty());
+        assert!(SchedulePauseParam {
+            app_name: Some(Arc::new("app1".to_owned())),
+            ..Default::default()
+        }
+        .key()
+        .is_err());
+    }
+}
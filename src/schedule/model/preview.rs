@@ -0,0 +1,193 @@
+use crate::common::datetime_utils::{get_datetime_by_second, now_second_u32, parse_fixed_offset};
+use crate::job::model::enum_type::ScheduleType;
+use crate::job::model::job::JobInfo;
+use crate::job::model::schedule_describe::ScheduleDescription;
+use crate::job::model::time_window::JobTimeWindow;
+use crate::schedule::model::JobRunState;
+use chrono::FixedOffset;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+
+const PREVIEW_DEFAULT_COUNT: usize = 10;
+const PREVIEW_MAX_COUNT: usize = 100;
+const PREVIEW_TIME_FMT: &str = "%Y-%m-%d %H:%M:%S%:z";
+
+/// 调度预览参数,time_zone为空时使用服务的调度时区
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePreviewParam {
+    pub schedule_type: Option<ScheduleType>,
+    pub cron_value: Option<Arc<String>>,
+    pub interval_second: Option<u32>,
+    pub delay_second: Option<u32>,
+    pub allow_time_window: Option<Arc<String>>,
+    pub forbid_time_window: Option<Arc<String>>,
+    pub time_zone: Option<String>,
+    /// 开始计算的时间(秒),默认当前时间
+    pub start_time: Option<u32>,
+    pub count: Option<usize>,
+}
+
+/// 校验错误,position为字段内从1开始的字符列,0表示无具体位置
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScheduleValidError {
+    pub field: String,
+    pub position: usize,
+    pub value: String,
+    pub message: String,
+}
+
+impl ScheduleValidError {
+    fn new(field: &str, message: String) -> Self {
+        Self {
+            field: field.to_owned(),
+            message,
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SchedulePreviewResult {
+    pub valid: bool,
+    pub time_zone: String,
+    pub trigger_times: Vec<u32>,
+    pub trigger_time_texts: Vec<String>,
+    pub description: Option<ScheduleDescription>,
+    pub errors: Vec<ScheduleValidError>,
+}
+
+impl SchedulePreviewParam {
+    fn to_job(&self) -> JobInfo {
+        JobInfo {
+            schedule_type: self.schedule_type.clone().unwrap_or_default(),
+            cron_value: self.cron_value.clone().unwrap_or_default(),
+            interval_second: self.interval_second.unwrap_or_default(),
+            delay_second: self.delay_second.unwrap_or_default(),
+            allow_time_window: self.allow_time_window.clone().unwrap_or_default(),
+            forbid_time_window: self.forbid_time_window.clone().unwrap_or_default(),
+            ..Default::default()
+        }
+    }
+
+    fn describe(
+        &self,
+        job: &JobInfo,
+        errors: &mut Vec<ScheduleValidError>,
+    ) -> Option<ScheduleDescription> {
+        let description = match job.schedule_type {
+            ScheduleType::Cron => match ScheduleDescription::cron(&job.cron_value) {
+                Ok(v) => v,
+                Err(e) => {
+                    errors.push(ScheduleValidError {
+                        field: "cronValue".to_owned(),
+                        position: e.position,
+                        value: e.value.clone(),
+                        message: e.to_string(),
+                    });
+                    return None;
+                }
+            },
+            ScheduleType::Interval => {
+                if job.interval_second == 0 {
+                    errors.push(ScheduleValidError::new(
+                        "intervalSecond",
+                        "interval_second eq 0,it is invalid!".to_owned(),
+                    ));
+                    return None;
+                }
+                ScheduleDescription::interval(job.interval_second)
+            }
+            ScheduleType::Delay => ScheduleDescription::delay(job.delay_second),
+            ScheduleType::None => ScheduleDescription::none(),
+        };
+        Some(description.with_time_window(&job.allow_time_window, &job.forbid_time_window))
+    }
+
+    ///
+    /// 校验调度配置并按JobRunState的规则计算后续触发时间
+    /// 延迟任务以开始时间作为上次执行结束时间,只计算首次触发
+    pub fn preview(&self, default_offset: &FixedOffset) -> SchedulePreviewResult {
+        let mut errors = vec![];
+        let offset = match self.time_zone.as_deref().filter(|v| !v.trim().is_empty()) {
+            Some(v) => match parse_fixed_offset(v) {
+                Ok(offset) => offset,
+                Err(e) => {
+                    errors.push(ScheduleValidError::new("timeZone", e.to_string()));
+                    *default_offset
+                }
+            },
+            None => *default_offset,
+        };
+        let job = self.to_job();
+        let description = self.describe(&job, &mut errors);
+        if let Err(e) = JobTimeWindow::new(&job.allow_time_window, &job.forbid_time_window) {
+            errors.push(ScheduleValidError::new("timeWindow", e.to_string()));
+        }
+        let mut result = SchedulePreviewResult {
+            valid: errors.is_empty(),
+            time_zone: offset.to_string(),
+            description,
+            errors,
+            ..Default::default()
+        };
+        if !result.valid {
+            return result;
+        }
+        let start_time = self.start_time.unwrap_or_else(now_second_u32);
+        let count = self
+            .count
+            .unwrap_or(PREVIEW_DEFAULT_COUNT)
+            .clamp(1, PREVIEW_MAX_COUNT);
+        let mut state = JobRunState::new(Arc::new(job));
+        state.last_finish_time = start_time;
+        if let Some(datetime) = get_datetime_by_second(start_time, &offset) {
+            result.trigger_times = state.next_trigger_times(&datetime, count);
+        }
+        result.trigger_time_texts = result
+            .trigger_times
+            .iter()
+            .filter_map(|v| get_datetime_by_second(*v, &offset))
+            .map(|v| v.format(PREVIEW_TIME_FMT).to_string())
+            .collect();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn preview_schedule() {
+        // 2024-01-01 00:00:00 +08:00 为周一
+        let monday = 1704038400u32;
+        let param = SchedulePreviewParam {
+            schedule_type: Some(ScheduleType::Cron),
+            cron_value: Some(Arc::new("0 30 9 * * MON-FRI".to_owned())),
+            time_zone: Some("+08:00".to_owned()),
+            start_time: Some(monday),
+            count: Some(2),
+            ..Default::default()
+        };
+        let result = param.preview(&FixedOffset::east_opt(0).unwrap());
+        assert!(result.valid);
+        assert_eq!(
+            result.trigger_times,
+            vec![monday + 9 * 3600 + 1800, monday + 33 * 3600 + 1800]
+        );
+        assert_eq!(result.trigger_time_texts[0], "2024-01-01 09:30:00+08:00");
+        let param = SchedulePreviewParam {
+            cron_value: Some(Arc::new("0 30 9 * * MON-FRX".to_owned())),
+            ..param
+        };
+        let result = param.preview(&FixedOffset::east_opt(0).unwrap());
+        assert!(!result.valid && result.trigger_times.is_empty());
+        assert_eq!(
+            (result.errors[0].field.as_str(), result.errors[0].position),
+            ("cronValue", 12)
+        );
+    }
+}
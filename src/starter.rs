@@ -1,8 +1,10 @@
 use crate::app::core::AppManager;
+use crate::audit::core::AuditManager;
 use crate::cache::core::CacheManager;
 use crate::common::actor_utils::{create_actor_at_thread, create_actor_at_thread2};
 use crate::common::app_config::AppConfig;
 use crate::common::share_data::ShareData;
+use crate::executor::core::ExecutorStreamManager;
 use crate::grpc::handler::RAFT_ROUTE_REQUEST;
 use crate::grpc::payload_utils::PayloadUtils;
 use crate::job::core::JobManager;
@@ -13,13 +15,14 @@ use crate::raft::cluster::node_manager::ClusterNodeManager;
 use crate::raft::cluster::route::{RaftAddrRouter, RaftRequestRoute};
 use crate::raft::network::core::RaftRouter;
 use crate::raft::network::factory::{RaftClusterRequestSender, RaftConnectionFactory};
+use crate::raft::store::compress::LogCompressType;
 use crate::raft::store::core::Store;
 use crate::raft::store::raftapply::StateApplyManager;
 use crate::raft::store::raftdata::RaftDataHandler;
 use crate::raft::store::raftindex::RaftIndexManager;
-use crate::raft::store::raftlog::RaftLogManager;
+use crate::raft::store::raftlog::{RaftLogManager, RaftLogOption};
 use crate::raft::store::raftsnapshot::RaftSnapshotManager;
-use crate::raft::store::ClientRequest;
+use crate::raft::store::{ClientRequest, LogValueFormat};
 use crate::raft::RatchRaft;
 use crate::schedule::batch_call::BatchCallManager;
 use crate::schedule::core::ScheduleManager;
@@ -60,6 +63,8 @@ pub async fn config_factory(app_config: Arc<AppConfig>) -> anyhow::Result<Factor
     ));
     let schedule_manager = create_actor_at_thread(ScheduleManager::new(
         app_config.gmt_fixed_offset_hours.map(|v| v * 60 * 60),
+        app_config.raft_node_id,
+        app_config.schedule_partition_enable,
     ));
     factory.register(BeanDefinition::actor_with_inject_from_obj(
         schedule_manager.clone(),
@@ -75,12 +80,20 @@ pub async fn config_factory(app_config: Arc<AppConfig>) -> anyhow::Result<Factor
     factory.register(BeanDefinition::actor_from_obj(
         TaskHistoryManager::new().start(),
     ));
+    factory.register(BeanDefinition::actor_with_inject_from_obj(
+        ExecutorStreamManager::new(app_config.clone()).start(),
+    ));
     let sequence_db_addr = SequenceDbManager::new().start();
     factory.register(BeanDefinition::actor_from_obj(sequence_db_addr.clone()));
 
     // raft begin
     let index_manager = RaftIndexManager::new(base_path.clone()).start();
-    let log_manager = RaftLogManager::new(base_path.clone(), Some(index_manager.clone()));
+    let log_option = RaftLogOption {
+        compress_type: LogCompressType::from_name(&app_config.raft_log_compress),
+        retain_log_size: app_config.raft_log_retain_size,
+    };
+    let log_manager =
+        RaftLogManager::new(base_path.clone(), Some(index_manager.clone()), log_option);
     let log_manager = create_actor_at_thread(log_manager);
     let snapshot_manager = RaftSnapshotManager::new(base_path.clone(), Some(index_manager.clone()));
     let apply_manager = StateApplyManager::new();
@@ -105,6 +118,7 @@ pub async fn config_factory(app_config: Arc<AppConfig>) -> anyhow::Result<Factor
         snapshot_manager,
         log_manager,
         apply_manager,
+        LogValueFormat::new(app_config.raft_log_msgpack_enable),
     ));
     factory.register(BeanDefinition::from_obj(store.clone()));
     let conn_factory = RaftConnectionFactory::new(60).start();
@@ -125,6 +139,10 @@ pub async fn config_factory(app_config: Arc<AppConfig>) -> anyhow::Result<Factor
     factory.register(BeanDefinition::actor_with_inject_from_obj(
         namespace_manager.clone(),
     ));
+    let audit_manager = AuditManager::new(app_config.clone()).start();
+    factory.register(BeanDefinition::actor_with_inject_from_obj(
+        audit_manager.clone(),
+    ));
     let raft_data_wrap = Arc::new(RaftDataHandler {
         sequence_db: sequence_db_addr,
         app_manager,
@@ -133,6 +151,7 @@ pub async fn config_factory(app_config: Arc<AppConfig>) -> anyhow::Result<Factor
         cache_manager,
         user_manager,
         namespace_manager,
+        audit_manager,
     });
     factory.register(BeanDefinition::from_obj(raft_data_wrap.clone()));
     let raft = build_raft(&app_config, store.clone(), cluster_sender.clone()).await?;
@@ -216,6 +235,8 @@ pub fn build_share_data(factory_data: FactoryData) -> anyhow::Result<Arc<ShareDa
         task_history_manager: factory_data.get_actor().unwrap(),
         metrics_manager: factory_data.get_actor().unwrap(),
         namespace_manager: factory_data.get_actor().unwrap(),
+        audit_manager: factory_data.get_actor().unwrap(),
+        executor_stream_manager: factory_data.get_actor().unwrap(),
         raft: factory_data.get_bean().unwrap(),
         raft_store: factory_data.get_bean().unwrap(),
         raft_request_route: factory_data.get_bean().unwrap(),
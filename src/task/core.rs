@@ -1,13 +1,16 @@
 use crate::app::model::AppKey;
 use crate::common::app_config::AppConfig;
 use crate::common::constant::{
-    ERR_MSG_JOB_DISABLE, ERR_MSG_NOT_FOUND_APP_INSTANCE_ADDR, SEQ_TASK_ID,
+    ERR_MSG_HTTP_CONFIG_EMPTY, ERR_MSG_JOB_DISABLE, ERR_MSG_NOT_FOUND_APP_INSTANCE_ADDR,
+    SEQ_TASK_ID,
 };
 use crate::common::datetime_utils::now_second_u32;
 use crate::common::get_app_version;
 use crate::job::core::JobManager;
-use crate::job::model::actor_model::JobManagerRaftReq;
+use crate::job::model::actor_model::{JobManagerRaftReq, JobManagerReq, JobManagerResult};
+use crate::job::model::enum_type::JobRunMode;
 use crate::job::model::job::JobInfo;
+use crate::job::model::retry_policy::RetryFailType;
 use crate::metrics::core::MetricsManager;
 use crate::metrics::metrics_key::MetricsKey;
 use crate::metrics::model::{MetricsItem, MetricsRecord, MetricsRequest};
@@ -21,7 +24,8 @@ use crate::task::model::actor_model::{
 };
 use crate::task::model::app_instance::{AppInstanceStateGroup, InstanceAddrSelectResult};
 use crate::task::model::enum_type::TaskStatusType;
-use crate::task::model::request_model::JobRunParam;
+use crate::task::model::param_template::TriggerParamContext;
+use crate::task::model::request_model::{HttpTaskRequest, JobRunParam};
 use crate::task::model::task::{JobTaskInfo, TaskWrap};
 use crate::task::model::task_request::{TaskRequestCmd, TaskRequestResult};
 use crate::task::request_actor::TaskRequestActor;
@@ -117,6 +121,7 @@ impl TaskManager {
                 let raft_request_route = act.raft_request_route.clone().unwrap();
                 let task_request_actor = act.task_request_actor.clone().unwrap();
                 let task_request_parallel = act.task_request_parallel;
+                let job_manager = act.job_manager.clone();
                 async move {
                     let count = notify_task_list.len() + task_list.len();
                     Self::notify_update_task(&raft_request_route, notify_task_list).await?;
@@ -125,6 +130,7 @@ impl TaskManager {
                         task_request_parallel,
                         raft_request_route,
                         task_request_actor,
+                        job_manager,
                     )
                     .await?;
                     Ok(count)
@@ -169,6 +175,7 @@ impl TaskManager {
         let _xxl_request_header = self.xxl_request_header.clone();
         let task_request_parallel = self.task_request_parallel;
         let task_request_actor = self.task_request_actor.clone().unwrap();
+        let job_manager = self.job_manager.clone();
         async move {
             Self::notify_update_task(&raft_request_route, notify_task_list).await?;
             Self::run_task_list(
@@ -176,6 +183,7 @@ impl TaskManager {
                 task_request_parallel,
                 raft_request_route,
                 task_request_actor,
+                job_manager,
             )
             .await?;
             Ok(())
@@ -251,6 +259,10 @@ impl TaskManager {
         let now_second = now_second_u32();
         for (mut task, job_info, trigger_source) in tasks {
             task.execution_time = now_second;
+            if job_info.run_mode == JobRunMode::Http {
+                task_list.push(Self::build_http_task_wrap(task, job_info, trigger_source));
+                continue;
+            }
             let app_key = job_info.build_app_key();
             if let Some(app_instance_group) = self.app_instance_group.get_mut(&app_key) {
                 let select = if trigger_source.fix_addr.is_empty() {
@@ -259,7 +271,8 @@ impl TaskManager {
                     InstanceAddrSelectResult::Fixed(trigger_source.fix_addr.clone())
                 };
                 if let &InstanceAddrSelectResult::Empty = &select {
-                    task.status = TaskStatusType::Fail;
+                    task.status = TaskStatusType::Skipped;
+                    task.fail_type = Some(RetryFailType::CallError);
                     task.finish_time = now_second;
                     task.trigger_message = ERR_MSG_NOT_FOUND_APP_INSTANCE_ADDR.clone();
                     ignore_task_list.push(Arc::new(task));
@@ -274,7 +287,8 @@ impl TaskManager {
                     task_list.push(wrap);
                 }
             } else {
-                task.status = TaskStatusType::Fail;
+                task.status = TaskStatusType::Skipped;
+                task.fail_type = Some(RetryFailType::CallError);
                 task.finish_time = now_second;
                 task.trigger_message = ERR_MSG_NOT_FOUND_APP_INSTANCE_ADDR.clone();
                 ignore_task_list.push(Arc::new(task));
@@ -296,6 +310,7 @@ impl TaskManager {
                 v
             } else {
                 task.status = TaskStatusType::Fail;
+                task.fail_type = Some(RetryFailType::CallError);
                 task.retry_count = task.try_times;
                 task.finish_time = now_second;
                 if item.fail_reason.is_empty() {
@@ -307,12 +322,34 @@ impl TaskManager {
                 continue;
             };
             task.execution_time = now_second;
+            if job_info.run_mode == JobRunMode::Http {
+                task_list.push(Self::build_http_task_wrap(
+                    task,
+                    job_info,
+                    Default::default(),
+                ));
+                continue;
+            }
             let app_key = job_info.build_app_key();
             if let Some(app_instance_group) = self.app_instance_group.get_mut(&app_key) {
-                let select =
-                    app_instance_group.select_instance(&job_info.router_strategy, job_info.id);
+                //按重试策略排除已尝试过的实例
+                let exclude_addrs = if job_info
+                    .retry_policy
+                    .as_ref()
+                    .is_some_and(|v| v.exclude_tried_instance)
+                {
+                    task.get_tried_addrs()
+                } else {
+                    vec![]
+                };
+                let select = app_instance_group.select_retry_instance(
+                    &job_info.router_strategy,
+                    job_info.id,
+                    &exclude_addrs,
+                );
                 if let &InstanceAddrSelectResult::Empty = &select {
-                    task.status = TaskStatusType::Fail;
+                    task.status = TaskStatusType::Skipped;
+                    task.fail_type = Some(RetryFailType::CallError);
                     task.finish_time = now_second;
                     task.trigger_message = ERR_MSG_NOT_FOUND_APP_INSTANCE_ADDR.clone();
                     ignore_task_list.push(Arc::new(task));
@@ -327,7 +364,8 @@ impl TaskManager {
                     task_list.push(wrap);
                 }
             } else {
-                task.status = TaskStatusType::Fail;
+                task.status = TaskStatusType::Skipped;
+                task.fail_type = Some(RetryFailType::CallError);
                 task.finish_time = now_second;
                 task.trigger_message = ERR_MSG_NOT_FOUND_APP_INSTANCE_ADDR.clone();
                 ignore_task_list.push(Arc::new(task));
@@ -336,22 +374,67 @@ impl TaskManager {
         (task_list, ignore_task_list)
     }
 
+    /// HTTP任务由调度中心直接请求,不需要选择执行器实例
+    fn build_http_task_wrap(
+        task: JobTaskInfo,
+        job_info: Arc<JobInfo>,
+        trigger_source: TriggerSourceInfo,
+    ) -> TaskWrap {
+        TaskWrap {
+            task,
+            job_info,
+            select_result: InstanceAddrSelectResult::Empty,
+            app_addrs: Default::default(),
+            trigger_source,
+        }
+    }
+
     async fn run_task_list(
         task_wrap_list: Vec<TaskWrap>,
         task_request_parallel: usize,
         raft_request_route: Arc<RaftRequestRoute>,
         task_request_actor: Addr<TaskRequestActor>,
+        job_manager: Option<Addr<JobManager>>,
     ) -> anyhow::Result<()> {
         let mut task_list = Vec::with_capacity(task_wrap_list.len());
         let mut index = 0;
+        let last_results = Self::query_last_results(job_manager, &task_wrap_list).await;
         for task_wrap in task_wrap_list {
             index += 1;
             let mut task_info = task_wrap.task;
             let mut param = JobRunParam::from_job_info(task_info.task_id, &task_wrap.job_info);
             param.log_date_time = Some(task_info.trigger_time as u64 * 1000);
+            param.last_result = last_results.get(&task_info.job_id).cloned();
+            if task_wrap.job_info.trigger_param.contains("${") {
+                let context = TriggerParamContext::new(&task_wrap.job_info, &task_info);
+                param.executor_params =
+                    Some(Arc::new(context.render(&task_wrap.job_info.trigger_param)));
+            }
             if index >= task_request_parallel {
                 index = 0;
             }
+            if task_wrap.job_info.run_mode == JobRunMode::Http {
+                if let Some(request) = HttpTaskRequest::build(&task_wrap.job_info, &task_info) {
+                    task_info.instance_addr = request.url.clone();
+                    let cmd = TaskRequestCmd::RunHttpTask(request, task_info);
+                    if index == 0 {
+                        if let Ok(Ok(TaskRequestResult::RunningCount(wait_count))) =
+                            task_request_actor.send(cmd).await
+                        {
+                            index = wait_count;
+                        }
+                    } else {
+                        task_request_actor.do_send(cmd);
+                    }
+                } else {
+                    task_info.status = TaskStatusType::Fail;
+                    task_info.fail_type = Some(RetryFailType::CallError);
+                    task_info.finish_time = now_second_u32();
+                    task_info.trigger_message = ERR_MSG_HTTP_CONFIG_EMPTY.clone();
+                    task_list.push(Arc::new(task_info));
+                }
+                continue;
+            }
             match task_wrap.select_result {
                 InstanceAddrSelectResult::Fixed(addr) => {
                     task_info.instance_addr = addr.clone();
@@ -405,6 +488,25 @@ impl TaskManager {
         Ok(())
     }
 
+    /// 查询任务上次运行的结果,传给本次运行
+    async fn query_last_results(
+        job_manager: Option<Addr<JobManager>>,
+        task_wrap_list: &[TaskWrap],
+    ) -> HashMap<u64, Arc<String>> {
+        let job_manager = match job_manager {
+            Some(v) => v,
+            None => return HashMap::new(),
+        };
+        let job_ids = task_wrap_list.iter().map(|v| v.task.job_id).collect();
+        match job_manager
+            .send(JobManagerReq::QueryJobLastResults(job_ids))
+            .await
+        {
+            Ok(Ok(JobManagerResult::JobLastResults(map))) => map,
+            _ => HashMap::new(),
+        }
+    }
+
     /*
     async fn try_run_task(
         instance_addr: Arc<String>,
@@ -520,6 +622,13 @@ impl Handler<TaskManagerReq> for TaskManager {
             TaskManagerReq::RedoTaskList(retry_list) => {
                 self.redo_task_list(retry_list, ctx)?;
             }
+            TaskManagerReq::KillTaskList(kill_list) => {
+                if let Some(task_request_actor) = self.task_request_actor.as_ref() {
+                    for item in kill_list {
+                        task_request_actor.do_send(TaskRequestCmd::KillTask(item));
+                    }
+                }
+            }
         }
         Ok(TaskManagerResult::None)
     }
@@ -1,5 +1,6 @@
 use crate::app::model::{AppInstanceKey, AppKey};
 use crate::common::constant::{EMPTY_ARC_STR, TRIGGER_FROM_SYSTEM};
+use crate::job::model::enum_type::JobRunMode;
 use crate::job::model::job::{JobInfo, JobTaskLogQueryParam};
 use crate::task::model::task::JobTaskInfo;
 use actix::Message;
@@ -79,6 +80,28 @@ pub struct RedoTaskItem {
     pub fail_reason: Arc<String>,
 }
 
+/// 通知执行器终止任务
+#[derive(Debug, Clone)]
+pub struct KillTaskItem {
+    pub instance_addr: Arc<String>,
+    pub job_id: u64,
+    pub task_id: u64,
+}
+
+impl KillTaskItem {
+    /// 超时或手动终止的任务需要通知执行器终止;HTTP任务没有执行器,不需要处理
+    pub fn new_by_task(task: &JobTaskInfo, job: Option<&Arc<JobInfo>>) -> Option<Self> {
+        if task.instance_addr.is_empty() || job.is_none_or(|v| v.run_mode == JobRunMode::Http) {
+            return None;
+        }
+        Some(KillTaskItem {
+            instance_addr: task.instance_addr.clone(),
+            job_id: task.job_id,
+            task_id: task.task_id,
+        })
+    }
+}
+
 #[derive(Debug, Message)]
 #[rtype(result = "anyhow::Result<TaskManagerResult>")]
 pub enum TaskManagerReq {
@@ -88,6 +111,7 @@ pub enum TaskManagerReq {
     RemoveAppInstances(Vec<AppInstanceKey>),
     TriggerTaskList(Vec<TriggerItem>),
     RedoTaskList(Vec<RedoTaskItem>),
+    KillTaskList(Vec<KillTaskItem>),
 }
 
 pub enum TaskManagerResult {
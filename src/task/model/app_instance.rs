@@ -85,32 +85,61 @@ impl AppInstanceStateGroup {
             return InstanceAddrSelectResult::Empty;
         }
         //TODO 过滤掉不可用的实例后再做选择
+        if let RouterStrategy::ShardingBroadcast = router {
+            return InstanceAddrSelectResult::ALL(self.instance_keys.clone());
+        }
+        let instance_keys = self.instance_keys.clone();
+        self.select_from(&instance_keys, router, job_id)
+    }
 
-        match router {
-            RouterStrategy::First => {
-                InstanceAddrSelectResult::Selected(self.instance_keys.first().unwrap().clone())
-            }
-            RouterStrategy::Last => {
-                InstanceAddrSelectResult::Selected(self.instance_keys.last().unwrap().clone())
-            }
+    /// 重试时选择实例,排除已尝试过的实例;全部实例都已尝试过时按原路由策略选择
+    pub fn select_retry_instance(
+        &mut self,
+        router: &RouterStrategy,
+        job_id: u64,
+        exclude_addrs: &[Arc<String>],
+    ) -> InstanceAddrSelectResult {
+        if exclude_addrs.is_empty() || router == &RouterStrategy::ShardingBroadcast {
+            return self.select_instance(router, job_id);
+        }
+        let instance_keys: Vec<Arc<String>> = self
+            .instance_keys
+            .iter()
+            .filter(|v| !exclude_addrs.contains(v))
+            .cloned()
+            .collect();
+        if instance_keys.is_empty() {
+            return self.select_instance(router, job_id);
+        }
+        self.select_from(&instance_keys, router, job_id)
+    }
+
+    fn select_from(
+        &mut self,
+        instance_keys: &[Arc<String>],
+        router: &RouterStrategy,
+        job_id: u64,
+    ) -> InstanceAddrSelectResult {
+        let selected = match router {
+            RouterStrategy::First => instance_keys.first().unwrap().clone(),
+            RouterStrategy::Last => instance_keys.last().unwrap().clone(),
             RouterStrategy::RoundRobin => {
-                let index = self.round_robin_index % self.instance_keys.len();
+                let index = self.round_robin_index % instance_keys.len();
                 self.round_robin_index += 1;
-                InstanceAddrSelectResult::Selected(self.instance_keys[index].clone())
+                instance_keys[index].clone()
             }
             RouterStrategy::Random => {
                 let mut rng = rand::thread_rng();
-                let selected = self.instance_keys.choose(&mut rng).unwrap();
-                InstanceAddrSelectResult::Selected(selected.clone())
+                instance_keys.choose(&mut rng).unwrap().clone()
             }
             RouterStrategy::ConsistentHash => {
                 let hash = get_hash_value(&job_id) as usize;
-                let selected = self.instance_keys[hash % self.instance_keys.len()].clone();
-                InstanceAddrSelectResult::Selected(selected)
+                instance_keys[hash % instance_keys.len()].clone()
             }
             RouterStrategy::ShardingBroadcast => {
-                InstanceAddrSelectResult::ALL(self.instance_keys.clone())
+                return InstanceAddrSelectResult::ALL(Arc::new(instance_keys.to_vec()));
             }
-        }
+        };
+        InstanceAddrSelectResult::Selected(selected)
     }
 }
@@ -7,6 +7,14 @@ pub enum TaskStatusType {
     Running,
     Fail,
     Success,
+    /// 执行超时,调度中心已通知执行器终止任务
+    Timeout,
+    /// 没有可用的执行器实例,未执行
+    Skipped,
+    /// 未开始执行或等待重试时被取消
+    Cancelled,
+    /// 执行中被手动终止
+    Killed,
 }
 
 impl Default for TaskStatusType {
@@ -22,6 +30,10 @@ impl TaskStatusType {
             "RUNNING" => TaskStatusType::Running,
             "FAIL" => TaskStatusType::Fail,
             "SUCCESS" => TaskStatusType::Success,
+            "TIMEOUT" => TaskStatusType::Timeout,
+            "SKIPPED" => TaskStatusType::Skipped,
+            "CANCELLED" => TaskStatusType::Cancelled,
+            "KILLED" => TaskStatusType::Killed,
             _ => TaskStatusType::Init,
         }
     }
@@ -32,6 +44,10 @@ impl TaskStatusType {
             TaskStatusType::Running => "RUNNING",
             TaskStatusType::Fail => "FAIL",
             TaskStatusType::Success => "SUCCESS",
+            TaskStatusType::Timeout => "TIMEOUT",
+            TaskStatusType::Skipped => "SKIPPED",
+            TaskStatusType::Cancelled => "CANCELLED",
+            TaskStatusType::Killed => "KILLED",
         }
     }
 
@@ -41,6 +57,10 @@ impl TaskStatusType {
             TaskStatusType::Running => false,
             TaskStatusType::Fail => true,
             TaskStatusType::Success => false,
+            TaskStatusType::Timeout => true,
+            TaskStatusType::Skipped => false,
+            TaskStatusType::Cancelled => false,
+            TaskStatusType::Killed => true,
         }
     }
 
@@ -50,6 +70,10 @@ impl TaskStatusType {
             TaskStatusType::Running => false,
             TaskStatusType::Fail => true,
             TaskStatusType::Success => true,
+            TaskStatusType::Timeout => true,
+            TaskStatusType::Skipped => true,
+            TaskStatusType::Cancelled => true,
+            TaskStatusType::Killed => true,
         }
     }
 
@@ -59,6 +83,23 @@ impl TaskStatusType {
             TaskStatusType::Running => true,
             TaskStatusType::Fail => false,
             TaskStatusType::Success => false,
+            TaskStatusType::Timeout => false,
+            TaskStatusType::Skipped => false,
+            TaskStatusType::Cancelled => false,
+            TaskStatusType::Killed => false,
         }
     }
+
+    /// 由调度中心结束的状态,之后执行器的回调不再修改任务状态
+    pub fn is_closed(&self) -> bool {
+        matches!(
+            self,
+            TaskStatusType::Timeout | TaskStatusType::Cancelled | TaskStatusType::Killed
+        )
+    }
+
+    /// 失败或未执行,可按重试策略重试
+    pub fn can_retry(&self) -> bool {
+        matches!(self, TaskStatusType::Fail | TaskStatusType::Skipped)
+    }
 }
@@ -1,6 +1,7 @@
 pub mod actor_model;
 pub mod app_instance;
 pub mod enum_type;
+pub mod param_template;
 pub mod request_model;
 pub mod task;
 pub mod task_history;
@@ -0,0 +1,182 @@
+use crate::common::datetime_utils::{get_datetime_by_second, get_local_offset};
+use crate::common::string_utils::StringUtils;
+use crate::job::model::job::JobInfo;
+use crate::task::model::task::JobTaskInfo;
+use chrono::{DateTime, Duration, FixedOffset, Months};
+use std::sync::Arc;
+
+const SCHEDULE_TIME_VAR: &str = "scheduleTime";
+const DEFAULT_TIME_FORMAT: &str = "yyyy-MM-dd HH:mm:ss";
+
+///
+/// 触发参数模板变量
+/// 时间按任务的调度时间计算(不是当前时间),重试、补偿运行时取值与原调度一致
+/// 支持: `${jobId}`,`${taskId}`,`${namespace}`,`${appName}`,
+/// `${scheduleTime}`,`${scheduleTime:yyyy-MM-dd}`,`${scheduleTime-1d}`,`${scheduleTime-1M+2h:yyyyMMddHH}`
+pub struct TriggerParamContext {
+    pub schedule_time: u32,
+    pub job_id: u64,
+    pub task_id: u64,
+    pub namespace: Arc<String>,
+    pub app_name: Arc<String>,
+}
+
+impl TriggerParamContext {
+    pub fn new(job_info: &JobInfo, task_info: &JobTaskInfo) -> Self {
+        Self {
+            schedule_time: task_info.trigger_time,
+            job_id: job_info.id,
+            task_id: task_info.task_id,
+            namespace: job_info.namespace.clone(),
+            app_name: job_info.app_name.clone(),
+        }
+    }
+
+    pub fn render(&self, template: &str) -> String {
+        if !template.contains("${") {
+            return template.to_owned();
+        }
+        StringUtils::render_template(template, |name| self.resolve(name))
+    }
+
+    /// 解析单个变量,不支持的变量返回None
+    pub fn resolve(&self, name: &str) -> Option<String> {
+        match name {
+            "jobId" => Some(self.job_id.to_string()),
+            "taskId" => Some(self.task_id.to_string()),
+            "namespace" => Some(self.namespace.to_string()),
+            "appName" => Some(self.app_name.to_string()),
+            _ => name
+                .strip_prefix(SCHEDULE_TIME_VAR)
+                .and_then(|expr| self.resolve_schedule_time(expr)),
+        }
+    }
+
+    fn resolve_schedule_time(&self, expr: &str) -> Option<String> {
+        let (offset_expr, format) = match expr.split_once(':') {
+            Some((offset_expr, format)) => (offset_expr, format),
+            None => (expr, DEFAULT_TIME_FORMAT),
+        };
+        if format.is_empty() {
+            return None;
+        }
+        let time = get_datetime_by_second(self.schedule_time, &get_local_offset())?;
+        let time = apply_offsets(time, offset_expr)?;
+        Some(time.format(&to_chrono_format(format)).to_string())
+    }
+}
+
+/// 依次应用时间偏移,如 `-1d`、`+2h-30m`;单位: s,m,h,d,w,M(月),y
+fn apply_offsets(mut time: DateTime<FixedOffset>, expr: &str) -> Option<DateTime<FixedOffset>> {
+    let mut rest = expr.trim();
+    while !rest.is_empty() {
+        let negative = match rest.as_bytes()[0] {
+            b'+' => false,
+            b'-' => true,
+            _ => return None,
+        };
+        rest = &rest[1..];
+        let digit_len = rest.bytes().take_while(|v| v.is_ascii_digit()).count();
+        if digit_len == 0 || digit_len >= rest.len() {
+            return None;
+        }
+        let value: u32 = rest[..digit_len].parse().ok()?;
+        //单位可能是多字节字符,按字符截取
+        let unit = rest[digit_len..].chars().next()?;
+        rest = &rest[digit_len + unit.len_utf8()..];
+        time = match unit {
+            'M' | 'y' => {
+                let months = if unit == 'y' {
+                    Months::new(value.checked_mul(12)?)
+                } else {
+                    Months::new(value)
+                };
+                if negative {
+                    time.checked_sub_months(months)?
+                } else {
+                    time.checked_add_months(months)?
+                }
+            }
+            _ => {
+                let seconds = value as i64
+                    * match unit {
+                        's' => 1,
+                        'm' => 60,
+                        'h' => 3600,
+                        'd' => 86400,
+                        'w' => 7 * 86400,
+                        _ => return None,
+                    };
+                let duration = Duration::seconds(if negative { -seconds } else { seconds });
+                time.checked_add_signed(duration)?
+            }
+        };
+    }
+    Some(time)
+}
+
+/// 把java风格的时间格式(yyyy-MM-dd HH:mm:ss.SSS)转为chrono格式
+fn to_chrono_format(format: &str) -> String {
+    const TOKENS: [(&str, &str); 8] = [
+        ("yyyy", "%Y"),
+        ("SSS", "%3f"),
+        ("yy", "%y"),
+        ("MM", "%m"),
+        ("dd", "%d"),
+        ("HH", "%H"),
+        ("mm", "%M"),
+        ("ss", "%S"),
+    ];
+    let mut result = String::with_capacity(format.len() + 8);
+    let mut rest = format;
+    'outer: while !rest.is_empty() {
+        for (token, value) in TOKENS.iter() {
+            if let Some(v) = rest.strip_prefix(token) {
+                result.push_str(value);
+                rest = v;
+                continue 'outer;
+            }
+        }
+        let c = rest.chars().next().unwrap_or_default();
+        if c == '%' {
+            result.push_str("%%");
+        } else {
+            result.push(c);
+        }
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_trigger_param() {
+        let offset_second = get_local_offset().local_minus_utc() as i64;
+        // 2025-03-01 08:30:15 本地时间
+        let schedule_time = (1740817815 - offset_second) as u32;
+        let context = TriggerParamContext {
+            schedule_time,
+            job_id: 3,
+            task_id: 100,
+            namespace: Arc::new("dev".to_owned()),
+            app_name: Arc::new("demo".to_owned()),
+        };
+        assert_eq!(
+            context.render("date=${scheduleTime:yyyy-MM-dd},id=${jobId}/${taskId}@${namespace}"),
+            "date=2025-03-01,id=3/100@dev"
+        );
+        assert_eq!(context.render("${scheduleTime-1d:yyyyMMdd}"), "20250228");
+        assert_eq!(context.render("${scheduleTime-1d}"), "2025-02-28 08:30:15");
+        assert_eq!(
+            context.render("${scheduleTime-1M+2h-30m:yyyy-MM-dd HH:mm}"),
+            "2025-02-01 10:00"
+        );
+        assert_eq!(context.render("${scheduleTime:HH%}"), "08%");
+        assert_eq!(context.render("${scheduleTime-1x}"), "${scheduleTime-1x}");
+        assert_eq!(context.render("${scheduleTime-1日}"), "${scheduleTime-1日}");
+        assert_eq!(context.render("${unknown}"), "${unknown}");
+    }
+}
@@ -1,7 +1,15 @@
+use crate::common::string_utils::StringUtils;
+use crate::job::model::http_config::JobHttpConfig;
 use crate::job::model::job::JobInfo;
+use crate::task::model::param_template::TriggerParamContext;
+use crate::task::model::task::JobTaskInfo;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::sync::Arc;
 
+/// HTTP任务未设置超时时间时的默认值
+const HTTP_TASK_DEFAULT_TIMEOUT_SECOND: u32 = 60;
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct JobRunParam {
@@ -18,10 +26,29 @@ pub struct JobRunParam {
     pub glue_update_time: Option<u64>,
     pub broadcast_index: Option<u64>,
     pub broadcast_total: Option<u64>,
+    /// 同一任务上次运行成功返回的结果(json)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_result: Option<Arc<String>>,
+}
+
+/// xxl-job执行器终止任务参数
+#[derive(Debug, Serialize, Deserialize, Default, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct JobKillParam {
+    pub job_id: u64,
 }
 
 impl JobRunParam {
     pub fn from_job_info(log_id: u64, job_info: &Arc<JobInfo>) -> Self {
+        //兼容旧数据:未单独设置脚本时仍使用trigger_param
+        let (glue_source, glue_update_time) = if job_info.glue_source.is_empty() {
+            (
+                job_info.trigger_param.clone(),
+                job_info.last_modified_millis,
+            )
+        } else {
+            (job_info.glue_source.clone(), job_info.glue_update_time)
+        };
         Self {
             job_id: job_info.id,
             log_id,
@@ -31,14 +58,59 @@ impl JobRunParam {
             executor_timeout: Some(job_info.timeout_second as i32),
             log_date_time: Some(job_info.last_modified_millis),
             glue_type: Some(job_info.run_mode.to_str().to_string()),
-            glue_source: Some(job_info.trigger_param.clone()),
-            glue_update_time: Some(job_info.last_modified_millis),
+            glue_source: Some(glue_source),
+            glue_update_time: Some(glue_update_time),
             broadcast_index: Some(0),
             broadcast_total: Some(0),
+            last_result: None,
         }
     }
 }
 
+/// 渲染模板变量后的HTTP任务请求
+#[derive(Debug, Clone, Default)]
+pub struct HttpTaskRequest {
+    pub method: String,
+    pub url: Arc<String>,
+    pub headers: HashMap<String, String>,
+    pub body: Vec<u8>,
+    pub timeout_millis: u64,
+    pub config: Arc<JobHttpConfig>,
+}
+
+impl HttpTaskRequest {
+    pub fn build(job_info: &JobInfo, task_info: &JobTaskInfo) -> Option<Self> {
+        let config = job_info.http_config.clone()?;
+        let context = TriggerParamContext::new(job_info, task_info);
+        let resolver = |name: &str| match name {
+            "triggerParam" => Some(context.render(&job_info.trigger_param)),
+            "triggerTime" => Some(task_info.trigger_time.to_string()),
+            "retryCount" => Some(task_info.retry_count.to_string()),
+            _ => context.resolve(name),
+        };
+        let headers = config
+            .headers
+            .iter()
+            .map(|(k, v)| (k.to_owned(), StringUtils::render_template(v, resolver)))
+            .collect();
+        let timeout_second = if config.timeout_second > 0 {
+            config.timeout_second
+        } else if job_info.timeout_second > 0 {
+            job_info.timeout_second
+        } else {
+            HTTP_TASK_DEFAULT_TIMEOUT_SECOND
+        };
+        Some(Self {
+            method: config.get_method(),
+            url: Arc::new(StringUtils::render_template(&config.url, resolver)),
+            headers,
+            body: StringUtils::render_template(&config.body, resolver).into_bytes(),
+            timeout_millis: timeout_second as u64 * 1000,
+            config,
+        })
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct JobLogParam {
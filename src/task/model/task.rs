@@ -1,6 +1,7 @@
 use crate::common::constant::EMPTY_ARC_STR;
 use crate::common::pb::data_object::{JobTaskDo, TaskTryLogDo};
 use crate::job::model::job::JobInfo;
+use crate::job::model::retry_policy::{JobRetryPolicy, RetryFailType};
 use crate::task::model::actor_model::{TriggerItem, TriggerSourceInfo, TriggerSourceType};
 use crate::task::model::app_instance::InstanceAddrSelectResult;
 use crate::task::model::enum_type::TaskStatusType;
@@ -8,6 +9,9 @@ use serde::{Deserialize, Serialize};
 use std::borrow::Cow;
 use std::sync::Arc;
 
+/// 任务结果最大长度
+pub const TASK_RESULT_MAX_LENGTH: usize = 64 * 1024;
+
 #[derive(Debug, Default, Clone, Deserialize, Serialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TaskTryLog {
@@ -41,6 +45,12 @@ pub struct JobTaskInfo {
     pub namespace: Arc<String>,
     #[serde(default)]
     pub app_name: Arc<String>,
+    /// 执行器返回的结构化结果(json)
+    #[serde(default)]
+    pub result: Arc<String>,
+    /// 失败类型,失败时记录,用于判断重试策略是否重试
+    #[serde(default)]
+    pub fail_type: Option<RetryFailType>,
 }
 
 impl JobTaskInfo {
@@ -69,6 +79,8 @@ impl JobTaskInfo {
             trigger_user,
             namespace: trigger_item.job_info.namespace.clone(),
             app_name: trigger_item.job_info.app_name.clone(),
+            result: EMPTY_ARC_STR.clone(),
+            fail_type: None,
         }
     }
 
@@ -84,6 +96,8 @@ impl JobTaskInfo {
         });
         self.execution_time = 0;
         self.instance_addr = EMPTY_ARC_STR.clone();
+        self.callback_message = EMPTY_ARC_STR.clone();
+        self.fail_type = None;
         self.status = TaskStatusType::Running;
     }
 
@@ -96,6 +110,30 @@ impl JobTaskInfo {
         }
     }
 
+    /// 按任务的重试策略计算下一次重试的间隔
+    pub fn get_policy_retry_interval(&self, policy: Option<&JobRetryPolicy>) -> u32 {
+        match policy {
+            Some(policy) => {
+                policy.get_retry_interval(self.get_retry_interval(), self.retry_count + 1)
+            }
+            None => self.get_retry_interval(),
+        }
+    }
+
+    /// 失败类型,未记录的(旧版本数据)按调用失败处理
+    pub fn get_fail_type(&self) -> RetryFailType {
+        self.fail_type.clone().unwrap_or(RetryFailType::CallError)
+    }
+
+    /// 已尝试过的实例地址
+    pub fn get_tried_addrs(&self) -> Vec<Arc<String>> {
+        self.try_logs
+            .iter()
+            .filter(|v| !v.addr.is_empty())
+            .map(|v| v.addr.clone())
+            .collect()
+    }
+
     pub fn get_timeout_second(&self, default_value: u32) -> u32 {
         if self.timeout_second > 0 {
             self.timeout_second
@@ -132,6 +170,8 @@ impl JobTaskInfo {
             trigger_user: Cow::Borrowed(&self.trigger_user),
             namespace: Cow::Borrowed(&self.namespace),
             app_name: Cow::Borrowed(&self.app_name),
+            result: Cow::Borrowed(&self.result),
+            fail_type: Cow::Borrowed(self.fail_type.as_ref().map_or("", |v| v.to_str())),
         }
     }
 }
@@ -165,6 +205,8 @@ impl<'a> From<JobTaskDo<'a>> for JobTaskInfo {
             trigger_user: Arc::new(task_do.trigger_user.to_string()),
             namespace: Arc::new(task_do.namespace.to_string()),
             app_name: Arc::new(task_do.app_name.to_string()),
+            result: Arc::new(task_do.result.to_string()),
+            fail_type: RetryFailType::from_name(&task_do.fail_type),
         }
     }
 }
@@ -176,6 +218,27 @@ pub struct TaskCallBackParam {
     pub task_date_time: i64,
     pub success: bool,
     pub handle_msg: Option<Arc<String>>,
+    #[serde(default)]
+    pub result: Option<Arc<String>>,
+    /// 回调来源的执行器实例地址,只有执行器长连接回调时有值
+    #[serde(default)]
+    pub instance_addr: Option<Arc<String>>,
+}
+
+impl TaskCallBackParam {
+    /// 校验任务结果:需为json且不超过长度限制
+    pub fn check_result(result: &str) -> anyhow::Result<()> {
+        if result.len() > TASK_RESULT_MAX_LENGTH {
+            return Err(anyhow::anyhow!(
+                "task result length {} exceeds the limit {}",
+                result.len(),
+                TASK_RESULT_MAX_LENGTH
+            ));
+        }
+        serde_json::from_str::<serde_json::Value>(result)
+            .map_err(|e| anyhow::anyhow!("task result is not valid json:{}", e))?;
+        Ok(())
+    }
 }
 
 pub struct TaskWrap {
@@ -190,11 +253,54 @@ pub struct TaskWrap {
 pub struct UpdateTaskMetricsInfo {
     pub success_count: u64,
     pub fail_count: u64,
+    /// 超时的任务数,同时计入失败数
+    pub timeout_count: u64,
+    /// 没有可用实例未执行的任务数
+    pub skip_count: u64,
+    /// 取消的任务数
+    pub cancel_count: u64,
+    /// 手动终止的任务数,同时计入失败数
+    pub kill_count: u64,
 }
 
 impl UpdateTaskMetricsInfo {
     pub fn add(&mut self, task_info: &UpdateTaskMetricsInfo) {
         self.success_count += task_info.success_count;
         self.fail_count += task_info.fail_count;
+        self.timeout_count += task_info.timeout_count;
+        self.skip_count += task_info.skip_count;
+        self.cancel_count += task_info.cancel_count;
+        self.kill_count += task_info.kill_count;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn task_result_check() {
+        assert!(TaskCallBackParam::check_result(r#"{"offset":100}"#).is_ok());
+        assert!(TaskCallBackParam::check_result("offset=100").is_err());
+        let long_value = format!(r#""{}""#, "a".repeat(TASK_RESULT_MAX_LENGTH));
+        assert!(TaskCallBackParam::check_result(&long_value).is_err());
+    }
+
+    #[test]
+    fn task_fail_type() {
+        let mut task = JobTaskInfo {
+            try_times: 1,
+            callback_message: Arc::new("fail".to_owned()),
+            fail_type: Some(RetryFailType::Timeout),
+            ..Default::default()
+        };
+        let task_do = task.to_do();
+        assert_eq!(task_do.fail_type, "TIMEOUT");
+        assert_eq!(
+            JobTaskInfo::from(task_do).get_fail_type(),
+            RetryFailType::Timeout
+        );
+        task.push_next_try();
+        assert!(task.fail_type.is_none());
     }
 }
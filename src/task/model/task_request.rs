@@ -1,4 +1,5 @@
-use crate::task::model::request_model::JobRunParam;
+use crate::task::model::actor_model::KillTaskItem;
+use crate::task::model::request_model::{HttpTaskRequest, JobRunParam};
 use crate::task::model::task::JobTaskInfo;
 use actix::Message;
 use std::sync::Arc;
@@ -8,6 +9,8 @@ use std::sync::Arc;
 pub enum TaskRequestCmd {
     RunTask(Arc<String>, JobRunParam, JobTaskInfo),
     RunBroadcastTask(Arc<Vec<Arc<String>>>, JobRunParam),
+    RunHttpTask(HttpTaskRequest, JobTaskInfo),
+    KillTask(KillTaskItem),
 }
 
 impl TaskRequestCmd {
@@ -15,6 +18,8 @@ impl TaskRequestCmd {
         match self {
             TaskRequestCmd::RunTask(_, _, task) => Some(task),
             TaskRequestCmd::RunBroadcastTask(_, _) => None,
+            TaskRequestCmd::RunHttpTask(_, task) => Some(task),
+            TaskRequestCmd::KillTask(_) => None,
         }
     }
 }
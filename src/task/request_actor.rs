@@ -1,12 +1,16 @@
 use crate::common::app_config::AppConfig;
 use crate::common::datetime_utils::now_second_u32;
 use crate::common::get_app_version;
+use crate::executor::core::ExecutorStreamManager;
+use crate::executor::model::{ExecutorAddr, ExecutorKillParam, ExecutorStreamReq};
+use crate::job::model::retry_policy::RetryFailType;
 use crate::schedule::batch_call::{BatchCallManager, BatchUpdateTaskManagerReq};
+use crate::task::model::actor_model::KillTaskItem;
 use crate::task::model::enum_type::TaskStatusType;
-use crate::task::model::request_model::JobRunParam;
+use crate::task::model::request_model::{JobKillParam, JobRunParam};
 use crate::task::model::task::JobTaskInfo;
 use crate::task::model::task_request::{TaskRequestCmd, TaskRequestResult};
-use crate::task::request_client::XxlClient;
+use crate::task::request_client::{HttpTaskClient, XxlClient};
 use actix::prelude::*;
 use bean_factory::{bean, BeanFactory, FactoryData, Inject};
 use std::collections::HashMap;
@@ -18,7 +22,9 @@ pub struct TaskRequestActor {
     client: reqwest::Client,
     xxl_request_header: HashMap<String, String>,
     batch_call_manager: Option<Addr<BatchCallManager>>,
+    executor_stream_manager: Option<Addr<ExecutorStreamManager>>,
     request_semaphore: Arc<tokio::sync::Semaphore>,
+    http_request_semaphore: Arc<tokio::sync::Semaphore>,
     pub(crate) running_count: usize,
 }
 
@@ -41,7 +47,11 @@ impl TaskRequestActor {
             client,
             xxl_request_header,
             batch_call_manager: None,
+            executor_stream_manager: None,
             request_semaphore: Arc::new(tokio::sync::Semaphore::new(config.task_request_parallel)),
+            http_request_semaphore: Arc::new(tokio::sync::Semaphore::new(
+                config.task_http_request_parallel,
+            )),
             running_count: 0,
         }
     }
@@ -51,7 +61,8 @@ impl TaskRequestActor {
         xxl_request_header: HashMap<String, String>,
         client: reqwest::Client,
         semaphore: Arc<tokio::sync::Semaphore>,
-    ) -> anyhow::Result<(anyhow::Result<()>, Option<JobTaskInfo>)> {
+        executor_stream_manager: Option<Addr<ExecutorStreamManager>>,
+    ) -> anyhow::Result<(anyhow::Result<Option<(bool, String)>>, Option<JobTaskInfo>)> {
         let permit = match semaphore.acquire_owned().await {
             Ok(permit) => permit,
             Err(err) => {
@@ -60,20 +71,58 @@ impl TaskRequestActor {
         };
         match msg {
             TaskRequestCmd::RunTask(addr, param, task) => {
-                let r = Self::do_run_task(&addr, &param, &client, &xxl_request_header).await;
+                let r = Self::do_run_task(
+                    &addr,
+                    &param,
+                    &client,
+                    &xxl_request_header,
+                    &executor_stream_manager,
+                )
+                .await;
                 drop(permit);
-                Ok((r, Some(task)))
+                Ok((r.map(|_| None), Some(task)))
+            }
+            TaskRequestCmd::RunHttpTask(request, task) => {
+                let r = HttpTaskClient::run(&client, &request).await;
+                drop(permit);
+                Ok((r.map(Some), Some(task)))
+            }
+            TaskRequestCmd::KillTask(item) => {
+                let r = Self::do_kill_task(
+                    &item,
+                    &client,
+                    &xxl_request_header,
+                    &executor_stream_manager,
+                )
+                .await;
+                drop(permit);
+                if let Err(err) = r {
+                    log::warn!(
+                        "kill task error,task_id:{},addr:{},{}",
+                        item.task_id,
+                        &item.instance_addr,
+                        err
+                    );
+                }
+                Ok((Ok(None), None))
             }
             TaskRequestCmd::RunBroadcastTask(addrs, param) => {
                 let mut r = Ok(());
                 for addr in addrs.iter() {
-                    let t = Self::do_run_task(addr, &param, &client, &xxl_request_header).await;
+                    let t = Self::do_run_task(
+                        addr,
+                        &param,
+                        &client,
+                        &xxl_request_header,
+                        &executor_stream_manager,
+                    )
+                    .await;
                     if t.is_err() {
                         r = t;
                     }
                 }
                 drop(permit);
-                Ok((r, None))
+                Ok((r.map(|_| None), None))
             }
         }
     }
@@ -83,11 +132,55 @@ impl TaskRequestActor {
         param: &JobRunParam,
         client: &reqwest::Client,
         xxl_request_header: &HashMap<String, String>,
+        executor_stream_manager: &Option<Addr<ExecutorStreamManager>>,
     ) -> anyhow::Result<()> {
+        if ExecutorAddr::is_executor_addr(instance_addr) {
+            //gRPC执行器通过连接下发
+            let manager = executor_stream_manager
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("executor_stream_manager is none"))?;
+            manager
+                .send(ExecutorStreamReq::RunTask(
+                    instance_addr.clone(),
+                    param.clone(),
+                ))
+                .await??;
+            return Ok(());
+        }
         let xxl_client = XxlClient::new(&client, &xxl_request_header, instance_addr);
         xxl_client.run_job(param).await?;
         Ok(())
     }
+
+    async fn do_kill_task(
+        item: &KillTaskItem,
+        client: &reqwest::Client,
+        xxl_request_header: &HashMap<String, String>,
+        executor_stream_manager: &Option<Addr<ExecutorStreamManager>>,
+    ) -> anyhow::Result<()> {
+        if ExecutorAddr::is_executor_addr(&item.instance_addr) {
+            let manager = executor_stream_manager
+                .as_ref()
+                .ok_or_else(|| anyhow::anyhow!("executor_stream_manager is none"))?;
+            let param = ExecutorKillParam {
+                job_id: item.job_id,
+                task_id: item.task_id,
+            };
+            manager
+                .send(ExecutorStreamReq::KillTask(
+                    item.instance_addr.clone(),
+                    param,
+                ))
+                .await??;
+            return Ok(());
+        }
+        let xxl_client = XxlClient::new(client, xxl_request_header, &item.instance_addr);
+        xxl_client
+            .kill_job(&JobKillParam {
+                job_id: item.job_id,
+            })
+            .await
+    }
 }
 
 impl Actor for TaskRequestActor {
@@ -106,6 +199,7 @@ impl Inject for TaskRequestActor {
         _ctx: &mut Self::Context,
     ) {
         self.batch_call_manager = factory_data.get_actor();
+        self.executor_stream_manager = factory_data.get_actor();
     }
 }
 
@@ -116,36 +210,63 @@ impl Handler<TaskRequestCmd> for TaskRequestActor {
         self.running_count += 1;
         let client = self.client.clone();
         let xxl_request_header = self.xxl_request_header.clone();
-        let semaphore = self.request_semaphore.clone();
-        let fut = Self::async_run_task(msg, xxl_request_header, client, semaphore)
-            .into_actor(self)
-            .map(|res, act, _ctx| {
-                act.running_count -= 1;
-                match res {
-                    Ok((r, task_info)) => {
-                        if let Some(mut task_info) = task_info {
-                            match r {
-                                Ok(_) => {
-                                    task_info.status = TaskStatusType::Running;
-                                }
-                                Err(err) => {
-                                    log::error!("run task error:{}", &err);
+        //HTTP任务需要等待请求完成，使用单独的并行限制
+        let semaphore = if let TaskRequestCmd::RunHttpTask(_, _) = &msg {
+            self.http_request_semaphore.clone()
+        } else {
+            self.request_semaphore.clone()
+        };
+        let executor_stream_manager = self.executor_stream_manager.clone();
+        let fut = Self::async_run_task(
+            msg,
+            xxl_request_header,
+            client,
+            semaphore,
+            executor_stream_manager,
+        )
+        .into_actor(self)
+        .map(|res, act, _ctx| {
+            act.running_count -= 1;
+            match res {
+                Ok((r, task_info)) => {
+                    if let Some(mut task_info) = task_info {
+                        let mut update_list = vec![];
+                        match r {
+                            Ok(None) => {
+                                task_info.status = TaskStatusType::Running;
+                            }
+                            Ok(Some((success, message))) => {
+                                //HTTP任务同步执行,先记录运行中再记录结果,保持与执行器回调一致的状态流转
+                                task_info.status = TaskStatusType::Running;
+                                update_list.push(Arc::new(task_info.clone()));
+                                if success {
+                                    task_info.status = TaskStatusType::Success;
+                                } else {
                                     task_info.status = TaskStatusType::Fail;
-                                    task_info.trigger_message = Arc::new(err.to_string());
-                                    task_info.finish_time = now_second_u32();
+                                    task_info.fail_type = Some(RetryFailType::ExecutorFail);
                                 }
-                            };
-                            if let Some(raft_request_route) = act.batch_call_manager.as_ref() {
-                                raft_request_route.do_send(BatchUpdateTaskManagerReq::UpdateTask(
-                                    Arc::new(task_info),
-                                ));
+                                task_info.callback_message = Arc::new(message);
+                                task_info.finish_time = now_second_u32();
                             }
+                            Err(err) => {
+                                log::error!("run task error:{}", &err);
+                                task_info.status = TaskStatusType::Fail;
+                                task_info.fail_type = Some(RetryFailType::CallError);
+                                task_info.trigger_message = Arc::new(err.to_string());
+                                task_info.finish_time = now_second_u32();
+                            }
+                        };
+                        update_list.push(Arc::new(task_info));
+                        if let Some(raft_request_route) = act.batch_call_manager.as_ref() {
+                            raft_request_route
+                                .do_send(BatchUpdateTaskManagerReq::UpdateTasks(update_list));
                         }
-                        Ok(TaskRequestResult::RunningCount(act.running_count))
                     }
-                    Err(err) => Err(err),
+                    Ok(TaskRequestResult::RunningCount(act.running_count))
                 }
-            });
+                Err(err) => Err(err),
+            }
+        });
         Box::pin(fut)
     }
 }
@@ -1,6 +1,6 @@
 use crate::common::http_utils::{HttpUtils, ResponseWrap};
 use crate::openapi::xxljob::model::XxlApiResult;
-use crate::task::model::request_model::JobRunParam;
+use crate::task::model::request_model::{HttpTaskRequest, JobKillParam, JobRunParam};
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -41,6 +41,11 @@ impl<'a> XxlClient<'a> {
         }
     }
 
+    pub async fn kill_job(&self, param: &JobKillParam) -> anyhow::Result<()> {
+        let body = serde_json::to_vec(param)?;
+        self.request(body, "kill").await
+    }
+
     async fn request(&self, body: Vec<u8>, sub_url: &str) -> anyhow::Result<()> {
         let mut _registry_success = false;
         let url = if self.is_addr_end_bias {
@@ -77,3 +82,33 @@ impl<'a> XxlClient<'a> {
         Ok(v)
     }
 }
+
+/// 回调信息中保留的响应内容长度
+const HTTP_TASK_BODY_EXCERPT_LEN: usize = 512;
+/// 响应内容只用于生成摘要，最多读取的字节数
+const HTTP_TASK_BODY_READ_LIMIT: usize = HTTP_TASK_BODY_EXCERPT_LEN * 4;
+
+pub struct HttpTaskClient;
+
+impl HttpTaskClient {
+    /// 执行HTTP任务,返回是否成功及响应摘要
+    pub async fn run(
+        client: &reqwest::Client,
+        request: &HttpTaskRequest,
+    ) -> anyhow::Result<(bool, String)> {
+        let resp = HttpUtils::request_limit_body(
+            client,
+            &request.method,
+            &request.url,
+            request.body.clone(),
+            Some(&request.headers),
+            Some(request.timeout_millis),
+            HTTP_TASK_BODY_READ_LIMIT,
+        )
+        .await?;
+        let success = request.config.is_success_status(resp.status);
+        let body = resp.get_lossy_string_body();
+        let excerpt: String = body.chars().take(HTTP_TASK_BODY_EXCERPT_LEN).collect();
+        Ok((success, format!("status:{},body:{}", resp.status, excerpt)))
+    }
+}
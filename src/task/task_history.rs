@@ -49,6 +49,9 @@ impl TaskHistoryManager {
                     continue;
                 }
             }
+            if !query_param.match_status(&task_log.status) {
+                continue;
+            }
             if index >= query_param.offset && index < end_index {
                 rlist.push(task_log.clone());
             }
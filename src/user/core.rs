@@ -9,7 +9,8 @@ use crate::raft::store::raftapply::{RaftApplyDataRequest, RaftApplyDataResponse}
 use crate::raft::store::raftsnapshot::{SnapshotWriterActor, SnapshotWriterRequest};
 use crate::raft::store::ClientRequest;
 use crate::user::actor_model::{UserManagerRaftReq, UserManagerRaftResult, UserManagerReq};
-use crate::user::model::{QueryUserPageParam, UserDto, UserInfo};
+use crate::user::ldap::{LdapClient, LdapConfig};
+use crate::user::model::{QueryUserPageParam, UserDto, UserInfo, USER_SOURCE_LDAP};
 use crate::user::permission::USER_ROLE_MANAGER;
 use crate::user::{build_password_hash, verify_password_hash};
 use actix::prelude::*;
@@ -26,6 +27,7 @@ pub struct UserManager {
     local_is_master: bool,
     data_load_completed: bool,
     app_config: Option<Arc<AppConfig>>,
+    ldap_syncing: bool,
 }
 
 impl UserManager {
@@ -36,6 +38,7 @@ impl UserManager {
             local_is_master: false,
             data_load_completed: false,
             app_config: None,
+            ldap_syncing: false,
         }
     }
 
@@ -57,6 +60,10 @@ impl UserManager {
 
     fn check_user(&self, name: Arc<String>, password: String) -> anyhow::Result<(bool, UserInfo)> {
         if let Some(user) = self.data.get(&name) {
+            //LDAP、OIDC等外部来源用户只能通过对应方式认证
+            if !user.is_local_user() {
+                return Ok((false, user.clone()));
+            }
             if let Ok(true) = verify_password_hash(&password, &user.password_hash) {
                 Ok((true, user.clone()))
             } else {
@@ -161,6 +168,7 @@ impl UserManager {
             extend_info: None,
             namespace_privilege: None,
             app_privilege: None,
+            source: None,
         };
         let raft_router = self.raft_router.clone().unwrap();
         async move {
@@ -181,6 +189,78 @@ impl UserManager {
         })
         .spawn(ctx);
     }
+
+    fn start_ldap_sync(&mut self, ctx: &mut Context<Self>) {
+        let interval = if let Some(config) = self
+            .app_config
+            .as_ref()
+            .and_then(|v| LdapConfig::new(v.as_ref()))
+        {
+            config.sync_interval_second
+        } else {
+            return;
+        };
+        if interval == 0 {
+            return;
+        }
+        ctx.run_interval(Duration::from_secs(interval), |act, ctx| {
+            act.sync_ldap_users(ctx);
+        });
+    }
+
+    /// 主节点定时从LDAP同步用户的角色及命名空间权限
+    fn sync_ldap_users(&mut self, ctx: &mut Context<Self>) {
+        if self.ldap_syncing
+            || !self.local_is_master
+            || !self.data_load_completed
+            || self.raft_router.is_none()
+        {
+            return;
+        }
+        let config = if let Some(v) = self
+            .app_config
+            .as_ref()
+            .and_then(|v| LdapConfig::new(v.as_ref()))
+        {
+            Arc::new(v)
+        } else {
+            return;
+        };
+        let users: Vec<UserInfo> = self
+            .data
+            .values()
+            .filter(|v| v.get_source() == USER_SOURCE_LDAP)
+            .cloned()
+            .collect();
+        if users.is_empty() {
+            return;
+        }
+        self.ldap_syncing = true;
+        let raft_router = self.raft_router.clone().unwrap();
+        async move {
+            let client = LdapClient::new(config);
+            let updates = client.build_sync_updates(users, now_millis_i64()).await?;
+            let count = updates.len();
+            for user_dto in updates {
+                let req = UserManagerRaftReq::UpdateUser(user_dto);
+                raft_router.request(ClientRequest::UserReq { req }).await?;
+            }
+            Ok(count)
+        }
+        .into_actor(self)
+        .map(|res: anyhow::Result<usize>, act, _ctx| {
+            act.ldap_syncing = false;
+            match res {
+                Ok(count) => {
+                    if count > 0 {
+                        log::info!("sync ldap users finish, update count:{}", count);
+                    }
+                }
+                Err(err) => log::warn!("sync ldap users error:{}", err),
+            }
+        })
+        .spawn(ctx);
+    }
 }
 
 impl Actor for UserManager {
@@ -198,10 +278,11 @@ impl Inject for UserManager {
         &mut self,
         factory_data: FactoryData,
         _factory: BeanFactory,
-        _ctx: &mut Self::Context,
+        ctx: &mut Self::Context,
     ) {
         self.raft_router = factory_data.get_bean();
         self.app_config = factory_data.get_bean();
+        self.start_ldap_sync(ctx);
     }
 }
 
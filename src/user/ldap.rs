@@ -0,0 +1,447 @@
+use crate::common::app_config::AppConfig;
+use crate::common::model::privilege::PrivilegeGroupOptionParam;
+use crate::user::model::{UserDto, UserInfo, USER_SOURCE_LDAP};
+use crate::user::permission::UserRoleHelper;
+use ldap3::{Ldap, LdapConnAsync, LdapConnSettings, Scope, SearchEntry};
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const LDAP_DN_KEY: &str = "ldapDn";
+const LDAP_RC_INVALID_CREDENTIALS: u32 = 49;
+const ALL_NAMESPACE: &str = "*";
+
+/// LDAP认证配置，url为空时不开启
+#[derive(Debug, Clone, Default)]
+pub struct LdapConfig {
+    pub url: String,
+    pub bind_dn: String,
+    pub bind_password: String,
+    pub base_dn: String,
+    /// 查询用户的过滤条件，{username}会替换为登录用户名
+    pub user_filter: String,
+    /// 作为本地用户名的属性，使用目录中的值而不是登录时输入的用户名
+    pub username_attr: String,
+    pub nickname_attr: String,
+    pub group_attr: String,
+    /// 组名 -> 角色
+    pub role_mapping: Vec<(String, Arc<String>)>,
+    /// 组名 -> 命名空间列表；为空表示不限制命名空间
+    pub namespace_mapping: Vec<(String, Vec<Arc<String>>)>,
+    pub default_role: Option<Arc<String>>,
+    pub sync_interval_second: u64,
+}
+
+impl LdapConfig {
+    pub fn new(app_config: &AppConfig) -> Option<Self> {
+        if app_config.ldap_url.is_empty() {
+            return None;
+        }
+        Some(Self {
+            url: app_config.ldap_url.clone(),
+            bind_dn: app_config.ldap_bind_dn.clone(),
+            bind_password: app_config.ldap_bind_password.clone(),
+            base_dn: app_config.ldap_base_dn.clone(),
+            user_filter: app_config.ldap_user_filter.clone(),
+            username_attr: app_config.ldap_username_attr.clone(),
+            nickname_attr: app_config.ldap_nickname_attr.clone(),
+            group_attr: app_config.ldap_group_attr.clone(),
+            role_mapping: UserRoleHelper::parse_role_mapping(&app_config.ldap_role_mapping)
+                .into_iter()
+                .map(|(group, role)| (group.to_lowercase(), role))
+                .collect(),
+            namespace_mapping: Self::parse_namespace_mapping(&app_config.ldap_namespace_mapping),
+            default_role: UserRoleHelper::parse_role_name(&app_config.ldap_default_role),
+            sync_interval_second: app_config.ldap_sync_interval_second,
+        })
+    }
+
+    /// 格式: group:ns1|ns2,group:* ; *表示全部命名空间
+    pub fn parse_namespace_mapping(value: &str) -> Vec<(String, Vec<Arc<String>>)> {
+        let mut list = vec![];
+        for item in value.split(',') {
+            if let Some((group, namespaces)) = item.rsplit_once(':') {
+                let namespaces = namespaces
+                    .split('|')
+                    .map(|v| v.trim())
+                    .filter(|v| !v.is_empty())
+                    .map(|v| Arc::new(v.to_owned()))
+                    .collect();
+                list.push((group.trim().to_lowercase(), namespaces));
+            }
+        }
+        list
+    }
+}
+
+/// LDAP中查到的用户信息
+#[derive(Debug, Clone, Default)]
+pub struct LdapUserEntry {
+    pub dn: String,
+    pub username: String,
+    pub nickname: String,
+    /// 组名(取组dn的第一段值)，已转小写
+    pub groups: Vec<String>,
+}
+
+impl LdapUserEntry {
+    fn from_search_entry(config: &LdapConfig, entry: SearchEntry) -> anyhow::Result<Self> {
+        let mut username = String::new();
+        let mut nickname = String::new();
+        let mut groups = vec![];
+        for (key, values) in entry.attrs {
+            if key.eq_ignore_ascii_case(&config.username_attr) {
+                username = values.first().cloned().unwrap_or_default();
+            }
+            if key.eq_ignore_ascii_case(&config.nickname_attr) {
+                nickname = values.into_iter().next().unwrap_or_default();
+            } else if key.eq_ignore_ascii_case(&config.group_attr) {
+                groups = values.iter().map(|v| get_group_name(v)).collect();
+            }
+        }
+        if username.is_empty() {
+            return Err(anyhow::anyhow!(
+                "ldap entry {} has no attribute {}",
+                &entry.dn,
+                &config.username_attr
+            ));
+        }
+        Ok(Self {
+            dn: entry.dn,
+            username,
+            nickname,
+            groups,
+        })
+    }
+}
+
+/// cn=admins,ou=groups,dc=example,dc=com -> admins
+fn get_group_name(value: &str) -> String {
+    let first = value.split(',').next().unwrap_or_default();
+    let name = first.split_once('=').map(|(_, v)| v).unwrap_or(first);
+    name.trim().to_lowercase()
+}
+
+pub enum LdapAuthResult {
+    Success(LdapUserEntry),
+    UserNotFound,
+    InvalidPassword,
+}
+
+pub struct LdapClient {
+    config: Arc<LdapConfig>,
+}
+
+impl LdapClient {
+    pub fn new(config: Arc<LdapConfig>) -> Self {
+        Self { config }
+    }
+
+    async fn connect(&self) -> anyhow::Result<Ldap> {
+        let settings = LdapConnSettings::new().set_conn_timeout(Duration::from_secs(5));
+        let (conn, ldap) = LdapConnAsync::with_settings(settings, &self.config.url).await?;
+        ldap3::drive!(conn);
+        Ok(ldap)
+    }
+
+    /// 使用服务账号绑定，没有配置时匿名查询
+    async fn service_bind(&self, ldap: &mut Ldap) -> anyhow::Result<()> {
+        if !self.config.bind_dn.is_empty() {
+            ldap.simple_bind(&self.config.bind_dn, &self.config.bind_password)
+                .await?
+                .success()?;
+        }
+        Ok(())
+    }
+
+    async fn search_user(
+        &self,
+        ldap: &mut Ldap,
+        username: &str,
+    ) -> anyhow::Result<Option<LdapUserEntry>> {
+        let filter = self
+            .config
+            .user_filter
+            .replace("{username}", &ldap3::ldap_escape(username));
+        let attrs = vec![
+            self.config.username_attr.as_str(),
+            self.config.nickname_attr.as_str(),
+            self.config.group_attr.as_str(),
+        ];
+        let (mut entries, _) = ldap
+            .search(&self.config.base_dn, Scope::Subtree, &filter, attrs)
+            .await?
+            .success()?;
+        if entries.len() > 1 {
+            return Err(anyhow::anyhow!(
+                "ldap found multiple entries for user {}",
+                username
+            ));
+        }
+        entries
+            .pop()
+            .map(|entry| {
+                LdapUserEntry::from_search_entry(&self.config, SearchEntry::construct(entry))
+            })
+            .transpose()
+    }
+
+    pub async fn authenticate(
+        &self,
+        username: &str,
+        password: &str,
+    ) -> anyhow::Result<LdapAuthResult> {
+        // 空密码在LDAP中是匿名绑定，直接拒绝
+        if password.is_empty() {
+            return Ok(LdapAuthResult::InvalidPassword);
+        }
+        let mut ldap = self.connect().await?;
+        self.service_bind(&mut ldap).await?;
+        let entry = match self.search_user(&mut ldap, username).await? {
+            Some(v) => v,
+            None => {
+                ldap.unbind().await.ok();
+                return Ok(LdapAuthResult::UserNotFound);
+            }
+        };
+        let res = ldap.simple_bind(&entry.dn, password).await?;
+        ldap.unbind().await.ok();
+        if res.rc == LDAP_RC_INVALID_CREDENTIALS {
+            return Ok(LdapAuthResult::InvalidPassword);
+        }
+        res.success()?;
+        Ok(LdapAuthResult::Success(entry))
+    }
+
+    /// 重新查询LDAP用户的组信息，返回需要更新的用户
+    pub async fn build_sync_updates(
+        &self,
+        users: Vec<UserInfo>,
+        now: i64,
+    ) -> anyhow::Result<Vec<UserDto>> {
+        let mut ldap = self.connect().await?;
+        self.service_bind(&mut ldap).await?;
+        let mut updates = vec![];
+        for user in users {
+            let entry = self.search_user(&mut ldap, &user.username).await?;
+            let dto = match entry {
+                Some(entry) if entry.username == user.username.as_str() => {
+                    build_user_dto(&self.config, &entry, Some(&user), now)
+                        .unwrap_or_else(|_| revoke_user_dto(&user, now))
+                }
+                // LDAP中已删除或用户名已变更的用户收回权限
+                _ => revoke_user_dto(&user, now),
+            };
+            if is_privilege_changed(&user, &dto) {
+                updates.push(dto);
+            }
+        }
+        ldap.unbind().await.ok();
+        Ok(updates)
+    }
+}
+
+fn build_namespace_privilege(
+    config: &LdapConfig,
+    groups: &[String],
+) -> PrivilegeGroupOptionParam<Arc<String>> {
+    if config.namespace_mapping.is_empty() {
+        return PrivilegeGroupOptionParam {
+            whitelist_is_all: Some(true),
+            ..Default::default()
+        };
+    }
+    let mut namespaces = HashSet::new();
+    for (group, list) in &config.namespace_mapping {
+        if !groups.contains(group) {
+            continue;
+        }
+        for namespace in list {
+            if namespace.as_str() == ALL_NAMESPACE {
+                return PrivilegeGroupOptionParam {
+                    whitelist_is_all: Some(true),
+                    ..Default::default()
+                };
+            }
+            namespaces.insert(namespace.clone());
+        }
+    }
+    PrivilegeGroupOptionParam {
+        whitelist_is_all: Some(false),
+        whitelist: Some(Arc::new(namespaces)),
+        ..Default::default()
+    }
+}
+
+/// 根据LDAP组生成需要新增或更新的用户信息;本地账号同名时不处理
+pub fn build_user_dto(
+    config: &LdapConfig,
+    entry: &LdapUserEntry,
+    exist_user: Option<&UserInfo>,
+    now: i64,
+) -> anyhow::Result<UserDto> {
+    if let Some(user) = exist_user {
+        if user.get_source() != USER_SOURCE_LDAP {
+            return Err(anyhow::anyhow!(
+                "the {} user {} already exists",
+                user.get_source(),
+                &entry.username
+            ));
+        }
+    }
+    let mut roles: Vec<Arc<String>> = vec![];
+    for (group, role) in &config.role_mapping {
+        if entry.groups.contains(group) && !roles.contains(role) {
+            roles.push(role.clone());
+        }
+    }
+    if roles.is_empty() {
+        if let Some(role) = &config.default_role {
+            roles.push(role.clone());
+        } else {
+            return Err(anyhow::anyhow!("ldap user {} has no role", &entry.username));
+        }
+    }
+    let nickname = if entry.nickname.is_empty() {
+        entry.username.clone()
+    } else {
+        entry.nickname.clone()
+    };
+    let mut extend_info = exist_user
+        .map(|v| v.extend_info.clone())
+        .unwrap_or_default();
+    extend_info.insert(LDAP_DN_KEY.to_owned(), entry.dn.clone());
+    let is_new = exist_user.is_none();
+    Ok(UserDto {
+        username: Arc::new(entry.username.clone()),
+        nickname: Some(nickname),
+        gmt_create: if is_new { Some(now) } else { None },
+        gmt_modified: Some(now),
+        enable: if is_new { Some(true) } else { None },
+        roles: Some(roles),
+        extend_info: Some(extend_info),
+        namespace_privilege: Some(build_namespace_privilege(config, &entry.groups)),
+        source: Some(Arc::new(USER_SOURCE_LDAP.to_owned())),
+        ..Default::default()
+    })
+}
+
+fn revoke_user_dto(user: &UserInfo, now: i64) -> UserDto {
+    UserDto {
+        username: user.username.clone(),
+        gmt_modified: Some(now),
+        roles: Some(vec![]),
+        namespace_privilege: Some(PrivilegeGroupOptionParam {
+            whitelist_is_all: Some(false),
+            whitelist: Some(Arc::new(HashSet::new())),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+fn is_privilege_changed(user: &UserInfo, dto: &UserDto) -> bool {
+    if let Some(roles) = &dto.roles {
+        if roles != &user.roles {
+            return true;
+        }
+    }
+    if let Some(nickname) = &dto.nickname {
+        if nickname != &user.nickname {
+            return true;
+        }
+    }
+    if let Some(privilege) = &dto.namespace_privilege {
+        let mut namespace_privilege = user.namespace_privilege.clone();
+        namespace_privilege.update(privilege.clone());
+        if namespace_privilege.whitelist_is_all != user.namespace_privilege.whitelist_is_all
+            || (!namespace_privilege.whitelist_is_all
+                && namespace_privilege.whitelist != user.namespace_privilege.whitelist)
+        {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::permission::{USER_ROLE_DEVELOPER, USER_ROLE_MANAGER, USER_ROLE_VISITOR};
+    use std::collections::HashMap;
+
+    fn build_config() -> LdapConfig {
+        let app_config = AppConfig {
+            ldap_url: "ldap://127.0.0.1:389".to_owned(),
+            ldap_username_attr: "uid".to_owned(),
+            ldap_nickname_attr: "cn".to_owned(),
+            ldap_group_attr: "memberOf".to_owned(),
+            ldap_role_mapping: "Admins:manager,devs:developer".to_owned(),
+            ldap_namespace_mapping: "devs:dev|test,admins:*".to_owned(),
+            ldap_default_role: "visitor".to_owned(),
+            ..Default::default()
+        };
+        LdapConfig::new(&app_config).unwrap()
+    }
+
+    #[test]
+    fn ldap_group_mapping() {
+        let config = build_config();
+        let mut search_entry = SearchEntry {
+            dn: "uid=bob,ou=people,dc=example,dc=com".to_owned(),
+            attrs: HashMap::from([
+                ("CN".to_owned(), vec!["Bob".to_owned()]),
+                (
+                    "memberof".to_owned(),
+                    vec!["cn=devs,ou=groups,dc=example,dc=com".to_owned()],
+                ),
+            ]),
+            bin_attrs: HashMap::new(),
+        };
+        assert!(LdapUserEntry::from_search_entry(&config, search_entry.clone()).is_err());
+        // 用户名取目录中的uid，而不是登录时输入的值
+        search_entry
+            .attrs
+            .insert("uid".to_owned(), vec!["bob".to_owned()]);
+        let entry = LdapUserEntry::from_search_entry(&config, search_entry).unwrap();
+        assert_eq!(entry.username, "bob");
+        assert_eq!(entry.groups, vec!["devs".to_owned()]);
+        let user = build_user_dto(&config, &entry, None, 1).unwrap();
+        assert_eq!(user.nickname.as_deref(), Some("Bob"));
+        assert_eq!(user.roles, Some(vec![USER_ROLE_DEVELOPER.clone()]));
+        let namespace = user.namespace_privilege.clone().unwrap();
+        assert_eq!(namespace.whitelist_is_all, Some(false));
+        assert_eq!(namespace.whitelist.unwrap().len(), 2);
+
+        // 组变更后同步角色和命名空间
+        let user_info: UserInfo = user.into();
+        assert_eq!(user_info.get_source(), USER_SOURCE_LDAP);
+        assert!(user_info.password_hash.is_empty());
+        let mut admin_entry = entry.clone();
+        admin_entry.groups = vec!["admins".to_owned()];
+        let update = build_user_dto(&config, &admin_entry, Some(&user_info), 2).unwrap();
+        assert!(is_privilege_changed(&user_info, &update));
+        assert_eq!(update.roles, Some(vec![USER_ROLE_MANAGER.clone()]));
+        assert_eq!(
+            update.namespace_privilege.unwrap().whitelist_is_all,
+            Some(true)
+        );
+        let same = build_user_dto(&config, &entry, Some(&user_info), 2).unwrap();
+        assert!(!is_privilege_changed(&user_info, &same));
+        assert!(is_privilege_changed(
+            &user_info,
+            &revoke_user_dto(&user_info, 2)
+        ));
+
+        // 没有映射的组使用默认角色，本地同名账号不处理
+        let mut other_entry = entry.clone();
+        other_entry.groups = vec![];
+        let other = build_user_dto(&config, &other_entry, None, 1).unwrap();
+        assert_eq!(other.roles, Some(vec![USER_ROLE_VISITOR.clone()]));
+        let local_user = UserInfo {
+            username: Arc::new("bob".to_owned()),
+            ..Default::default()
+        };
+        assert!(build_user_dto(&config, &entry, Some(&local_user), 2).is_err());
+    }
+}
@@ -1,6 +1,8 @@
 pub mod actor_model;
 pub mod core;
+pub mod ldap;
 pub mod model;
+pub mod oidc;
 pub mod permission;
 
 pub(crate) fn build_password_hash(password: &str) -> anyhow::Result<String> {
@@ -8,6 +10,10 @@ pub(crate) fn build_password_hash(password: &str) -> anyhow::Result<String> {
 }
 
 pub(crate) fn verify_password_hash(password: &str, password_hash: &str) -> anyhow::Result<bool> {
+    //外部来源用户没有本地密码
+    if password_hash.is_empty() {
+        return Ok(false);
+    }
     Ok(bcrypt::verify(password, password_hash)?)
 }
 
@@ -23,3 +29,14 @@ pub(crate) fn verify_password_hash_option(
     }
 }
 */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_empty_password_hash() {
+        assert!(!verify_password_hash("", "").unwrap());
+        assert!(!verify_password_hash("admin", "").unwrap());
+    }
+}
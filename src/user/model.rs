@@ -6,6 +6,11 @@ use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+/// 用户来源
+pub const USER_SOURCE_LOCAL: &str = "local";
+pub const USER_SOURCE_LDAP: &str = "ldap";
+pub const USER_SOURCE_OIDC: &str = "oidc";
+
 #[derive(Clone, Debug, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct UserInfo {
@@ -19,18 +24,35 @@ pub struct UserInfo {
     pub extend_info: HashMap<String, String>,
     pub namespace_privilege: PrivilegeGroup<Arc<String>>,
     pub app_privilege: PrivilegeGroup<Arc<String>>,
+    /// 用户来源，空表示本地用户
+    pub source: Arc<String>,
 }
 
 impl UserInfo {
+    pub fn get_source(&self) -> &str {
+        if self.source.is_empty() {
+            USER_SOURCE_LOCAL
+        } else {
+            &self.source
+        }
+    }
+
+    pub fn is_local_user(&self) -> bool {
+        self.get_source() == USER_SOURCE_LOCAL
+    }
+
     pub fn update(&mut self, record: UserDto) {
         if let Some(nickname) = record.nickname {
             self.nickname = nickname;
         }
-        if let Some(password) = record.password {
-            self.password_hash = build_password_hash(&password).unwrap_or_default();
-        }
-        if let Some(password_hash) = record.password_hash {
-            self.password_hash = password_hash;
+        // 外部来源的用户不设置本地密码
+        if self.is_local_user() {
+            if let Some(password) = record.password {
+                self.password_hash = build_password_hash(&password).unwrap_or_default();
+            }
+            if let Some(password_hash) = record.password_hash {
+                self.password_hash = password_hash;
+            }
         }
         if let Some(gmt_create) = record.gmt_create {
             self.gmt_create = gmt_create;
@@ -75,6 +97,7 @@ impl UserInfo {
                 .collect(),
             namespace_privilege: Some(self.namespace_privilege.to_do()),
             app_privilege: Some(self.app_privilege.to_do()),
+            source: Cow::Borrowed(self.source.as_str()),
         }
     }
 }
@@ -103,6 +126,7 @@ impl<'a> From<UserInfoDo<'a>> for UserInfo {
                 .map(|pg| pg.into())
                 .unwrap_or_default(),
             app_privilege: record.app_privilege.map(|pg| pg.into()).unwrap_or_default(),
+            source: Arc::new(record.source.to_string()),
         }
     }
 }
@@ -122,11 +146,16 @@ pub struct UserDto {
     pub extend_info: Option<HashMap<String, String>>,
     pub namespace_privilege: Option<PrivilegeGroupOptionParam<Arc<String>>>,
     pub app_privilege: Option<PrivilegeGroupOptionParam<Arc<String>>>,
+    /// 只在新增用户时生效
+    pub source: Option<Arc<String>>,
 }
 
 impl From<UserDto> for UserInfo {
     fn from(record: UserDto) -> Self {
-        let password_hash = if record.password_hash.is_none() {
+        let source = record.source.unwrap_or_default();
+        let password_hash = if !source.is_empty() && source.as_str() != USER_SOURCE_LOCAL {
+            "".to_string()
+        } else if record.password_hash.is_none() {
             if let Some(p) = record.password.as_ref() {
                 build_password_hash(p).unwrap_or_default()
             } else {
@@ -150,6 +179,7 @@ impl From<UserDto> for UserInfo {
             extend_info: record.extend_info.unwrap_or_default(),
             namespace_privilege,
             app_privilege,
+            source,
         }
     }
 }
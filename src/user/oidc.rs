@@ -0,0 +1,666 @@
+use crate::common::app_config::AppConfig;
+use crate::common::model::privilege::PrivilegeGroupOptionParam;
+use crate::user::model::{UserDto, UserInfo, USER_SOURCE_OIDC};
+use crate::user::permission::UserRoleHelper;
+use base64::{engine::general_purpose, Engine};
+use ring::signature;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+pub const OIDC_SUBJECT_KEY: &str = "oidcSubject";
+
+pub type OidcClaims = Map<String, Value>;
+
+/// OIDC单点登录配置，issuer或client_id为空时不开启
+#[derive(Debug, Clone, Default)]
+pub struct OidcConfig {
+    pub issuer: String,
+    pub client_id: String,
+    pub client_secret: String,
+    pub redirect_uri: String,
+    pub scopes: String,
+    pub username_claim: String,
+    pub role_claim: String,
+    /// claim值 -> 角色
+    pub role_mapping: Vec<(String, Arc<String>)>,
+    /// 没有匹配到角色时使用的角色，为空则拒绝登录
+    pub default_role: Option<Arc<String>>,
+    /// 为空表示不限制命名空间，否则只授权claim中的命名空间
+    pub namespace_claim: String,
+}
+
+impl OidcConfig {
+    pub fn new(app_config: &AppConfig) -> Option<Self> {
+        if app_config.oidc_issuer.is_empty() || app_config.oidc_client_id.is_empty() {
+            return None;
+        }
+        Some(Self {
+            issuer: app_config.oidc_issuer.clone(),
+            client_id: app_config.oidc_client_id.clone(),
+            client_secret: app_config.oidc_client_secret.clone(),
+            redirect_uri: app_config.oidc_redirect_uri.clone(),
+            scopes: app_config.oidc_scopes.clone(),
+            username_claim: app_config.oidc_username_claim.clone(),
+            role_claim: app_config.oidc_role_claim.clone(),
+            role_mapping: UserRoleHelper::parse_role_mapping(&app_config.oidc_role_mapping),
+            default_role: UserRoleHelper::parse_role_name(&app_config.oidc_default_role),
+            namespace_claim: app_config.oidc_namespace_claim.clone(),
+        })
+    }
+
+    fn get_issuer(&self) -> &str {
+        self.issuer.trim_end_matches('/')
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct OidcProviderMetadata {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub userinfo_endpoint: Option<String>,
+    #[serde(default)]
+    pub jwks_uri: String,
+}
+
+/// 签名公钥,支持RSA与EC(P-256、P-384)
+#[derive(Debug, Clone, Deserialize, Default)]
+pub struct OidcJwk {
+    #[serde(default)]
+    pub kty: String,
+    pub kid: Option<String>,
+    #[serde(default)]
+    pub n: String,
+    #[serde(default)]
+    pub e: String,
+    #[serde(default)]
+    pub crv: String,
+    #[serde(default)]
+    pub x: String,
+    #[serde(default)]
+    pub y: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OidcJwks {
+    keys: Vec<OidcJwk>,
+}
+
+#[derive(Debug, Deserialize)]
+struct IdTokenHeader {
+    alg: String,
+    kid: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OidcTokenResponse {
+    access_token: Option<String>,
+    id_token: String,
+}
+
+#[derive(Serialize)]
+struct AuthorizeParam<'a> {
+    response_type: &'a str,
+    client_id: &'a str,
+    redirect_uri: &'a str,
+    scope: &'a str,
+    state: &'a str,
+    nonce: &'a str,
+}
+
+pub struct OidcClient {
+    config: Arc<OidcConfig>,
+    client: reqwest::Client,
+}
+
+impl OidcClient {
+    pub fn new(config: Arc<OidcConfig>) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(10))
+            .build()
+            .unwrap_or_default();
+        Self { config, client }
+    }
+
+    pub async fn discover(&self) -> anyhow::Result<OidcProviderMetadata> {
+        check_secure_url(&self.config.issuer)?;
+        let url = format!(
+            "{}/.well-known/openid-configuration",
+            self.config.get_issuer()
+        );
+        let metadata: OidcProviderMetadata = self
+            .client
+            .get(&url)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        if metadata.issuer.trim_end_matches('/') != self.config.get_issuer() {
+            return Err(anyhow::anyhow!(
+                "oidc issuer mismatch, expect {}, got {}",
+                &self.config.issuer,
+                &metadata.issuer
+            ));
+        }
+        check_secure_url(&metadata.authorization_endpoint)?;
+        check_secure_url(&metadata.token_endpoint)?;
+        check_secure_url(&metadata.jwks_uri)?;
+        if let Some(userinfo_endpoint) = &metadata.userinfo_endpoint {
+            check_secure_url(userinfo_endpoint)?;
+        }
+        Ok(metadata)
+    }
+
+    pub fn build_authorize_url(
+        &self,
+        metadata: &OidcProviderMetadata,
+        state: &str,
+        nonce: &str,
+    ) -> anyhow::Result<String> {
+        let param = AuthorizeParam {
+            response_type: "code",
+            client_id: &self.config.client_id,
+            redirect_uri: &self.config.redirect_uri,
+            scope: &self.config.scopes,
+            state,
+            nonce,
+        };
+        let query = serde_urlencoded::to_string(param)?;
+        let sep = if metadata.authorization_endpoint.contains('?') {
+            '&'
+        } else {
+            '?'
+        };
+        Ok(format!(
+            "{}{}{}",
+            &metadata.authorization_endpoint, sep, query
+        ))
+    }
+
+    /// 用授权码换取id_token并校验，返回合并userinfo后的claims
+    pub async fn exchange_code(
+        &self,
+        metadata: &OidcProviderMetadata,
+        code: &str,
+        nonce: &str,
+    ) -> anyhow::Result<OidcClaims> {
+        let form = [
+            ("grant_type", "authorization_code"),
+            ("code", code),
+            ("redirect_uri", &self.config.redirect_uri),
+            ("client_id", &self.config.client_id),
+            ("client_secret", &self.config.client_secret),
+        ];
+        let token: OidcTokenResponse = self
+            .client
+            .post(&metadata.token_endpoint)
+            .form(&form)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        let jwks: OidcJwks = self
+            .client
+            .get(&metadata.jwks_uri)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+        verify_id_token_signature(&token.id_token, &jwks.keys)?;
+        let mut claims = decode_id_token_claims(&token.id_token)?;
+        self.check_id_token_claims(&claims, nonce)?;
+        if let (Some(userinfo_endpoint), Some(access_token)) =
+            (&metadata.userinfo_endpoint, &token.access_token)
+        {
+            let userinfo: OidcClaims = self
+                .client
+                .get(userinfo_endpoint)
+                .bearer_auth(access_token)
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            if userinfo.get("sub") != claims.get("sub") {
+                return Err(anyhow::anyhow!("oidc userinfo sub mismatch"));
+            }
+            for (k, v) in userinfo {
+                claims.entry(k).or_insert(v);
+            }
+        }
+        Ok(claims)
+    }
+
+    fn check_id_token_claims(&self, claims: &OidcClaims, nonce: &str) -> anyhow::Result<()> {
+        let issuer = claims
+            .get("iss")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        if issuer.trim_end_matches('/') != self.config.get_issuer() {
+            return Err(anyhow::anyhow!("id_token iss mismatch:{}", issuer));
+        }
+        if !get_claim_values(claims, "aud").contains(&self.config.client_id) {
+            return Err(anyhow::anyhow!("id_token aud mismatch"));
+        }
+        let exp = claims
+            .get("exp")
+            .and_then(|v| v.as_i64())
+            .unwrap_or_default();
+        if exp < chrono::Utc::now().timestamp() {
+            return Err(anyhow::anyhow!("id_token is expired"));
+        }
+        if claims.get("nonce").and_then(|v| v.as_str()) != Some(nonce) {
+            return Err(anyhow::anyhow!("id_token nonce mismatch"));
+        }
+        if claims.get("sub").and_then(|v| v.as_str()).is_none() {
+            return Err(anyhow::anyhow!("id_token sub is empty"));
+        }
+        Ok(())
+    }
+}
+
+/// OIDC服务地址必须使用https，只有本机回环地址允许http
+fn check_secure_url(url: &str) -> anyhow::Result<()> {
+    let url = reqwest::Url::parse(url)?;
+    let host = url.host_str().unwrap_or_default();
+    let is_loopback = host == "localhost"
+        || host
+            .trim_start_matches('[')
+            .trim_end_matches(']')
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|v| v.is_loopback());
+    if url.scheme() == "https" || (url.scheme() == "http" && is_loopback) {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("oidc url must use https:{}", url))
+    }
+}
+
+fn decode_base64_url(value: &str) -> anyhow::Result<Vec<u8>> {
+    Ok(general_purpose::URL_SAFE_NO_PAD.decode(value.trim_end_matches('='))?)
+}
+
+/// 用OIDC服务jwks中的公钥校验id_token签名;header中有kid时只使用对应的公钥
+pub fn verify_id_token_signature(id_token: &str, keys: &[OidcJwk]) -> anyhow::Result<()> {
+    let parts: Vec<&str> = id_token.split('.').collect();
+    if parts.len() != 3 {
+        return Err(anyhow::anyhow!("id_token format error"));
+    }
+    let header: IdTokenHeader = serde_json::from_slice(&decode_base64_url(parts[0])?)?;
+    let message = &id_token[..parts[0].len() + 1 + parts[1].len()];
+    let sig = decode_base64_url(parts[2])?;
+    for key in keys {
+        if header.kid.is_some() && key.kid != header.kid {
+            continue;
+        }
+        let verified = match (header.alg.as_str(), key.kty.as_str()) {
+            ("RS256" | "RS384" | "RS512", "RSA") => {
+                let params = match header.alg.as_str() {
+                    "RS256" => &signature::RSA_PKCS1_2048_8192_SHA256,
+                    "RS384" => &signature::RSA_PKCS1_2048_8192_SHA384,
+                    _ => &signature::RSA_PKCS1_2048_8192_SHA512,
+                };
+                let n = decode_base64_url(&key.n)?;
+                let e = decode_base64_url(&key.e)?;
+                signature::RsaPublicKeyComponents { n: &n, e: &e }
+                    .verify(params, message.as_bytes(), &sig)
+                    .is_ok()
+            }
+            ("ES256" | "ES384", "EC") => {
+                let params = match (header.alg.as_str(), key.crv.as_str()) {
+                    ("ES256", "P-256") => &signature::ECDSA_P256_SHA256_FIXED,
+                    ("ES384", "P-384") => &signature::ECDSA_P384_SHA384_FIXED,
+                    _ => continue,
+                };
+                let mut point = vec![0x04];
+                point.extend(decode_base64_url(&key.x)?);
+                point.extend(decode_base64_url(&key.y)?);
+                signature::UnparsedPublicKey::new(params, point)
+                    .verify(message.as_bytes(), &sig)
+                    .is_ok()
+            }
+            ("RS256" | "RS384" | "RS512" | "ES256" | "ES384", _) => continue,
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "id_token alg is not supported:{}",
+                    &header.alg
+                ))
+            }
+        };
+        if verified {
+            return Ok(());
+        }
+    }
+    Err(anyhow::anyhow!("id_token signature is invalid"))
+}
+
+pub fn decode_id_token_claims(id_token: &str) -> anyhow::Result<OidcClaims> {
+    let payload = id_token
+        .split('.')
+        .nth(1)
+        .ok_or_else(|| anyhow::anyhow!("id_token format error"))?;
+    Ok(serde_json::from_slice(&decode_base64_url(payload)?)?)
+}
+
+/// claim值支持字符串或字符串数组
+fn get_claim_values(claims: &OidcClaims, name: &str) -> Vec<String> {
+    match claims.get(name) {
+        Some(Value::String(v)) => vec![v.to_owned()],
+        Some(Value::Array(list)) => list
+            .iter()
+            .filter_map(|v| v.as_str().map(|v| v.to_owned()))
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// 用户名取配置的claim，没有时使用sub
+pub fn get_username(config: &OidcConfig, claims: &OidcClaims) -> anyhow::Result<String> {
+    let username = get_claim_values(claims, &config.username_claim)
+        .into_iter()
+        .next()
+        .or_else(|| get_claim_values(claims, "sub").into_iter().next())
+        .unwrap_or_default();
+    if username.is_empty() {
+        return Err(anyhow::anyhow!("oidc username is empty"));
+    }
+    Ok(username)
+}
+
+/// 根据claims生成需要新增或更新的用户信息;本地账号同名时不允许通过单点登录覆盖,
+/// 已有的单点登录用户需sub一致
+pub fn build_user_dto(
+    config: &OidcConfig,
+    claims: &OidcClaims,
+    exist_user: Option<&UserInfo>,
+    now: i64,
+) -> anyhow::Result<UserDto> {
+    let subject = claims
+        .get("sub")
+        .and_then(|v| v.as_str())
+        .unwrap_or_default()
+        .to_owned();
+    let username = get_username(config, claims)?;
+    if let Some(user) = exist_user {
+        if user.get_source() != USER_SOURCE_OIDC {
+            return Err(anyhow::anyhow!(
+                "the local user {} already exists",
+                &username
+            ));
+        }
+        if subject.is_empty() || user.extend_info.get(OIDC_SUBJECT_KEY) != Some(&subject) {
+            return Err(anyhow::anyhow!(
+                "the oidc user {} subject mismatch",
+                &username
+            ));
+        }
+    }
+    let claim_values = get_claim_values(claims, &config.role_claim);
+    let mut roles: Vec<Arc<String>> = vec![];
+    for (claim_value, role) in &config.role_mapping {
+        if claim_values.contains(claim_value) && !roles.contains(role) {
+            roles.push(role.clone());
+        }
+    }
+    if roles.is_empty() {
+        if let Some(role) = &config.default_role {
+            roles.push(role.clone());
+        } else {
+            return Err(anyhow::anyhow!("oidc user {} has no role", &username));
+        }
+    }
+    let namespace_privilege = if config.namespace_claim.is_empty() {
+        PrivilegeGroupOptionParam {
+            whitelist_is_all: Some(true),
+            ..Default::default()
+        }
+    } else {
+        let namespaces: HashSet<Arc<String>> = get_claim_values(claims, &config.namespace_claim)
+            .into_iter()
+            .map(Arc::new)
+            .collect();
+        PrivilegeGroupOptionParam {
+            whitelist_is_all: Some(false),
+            whitelist: Some(Arc::new(namespaces)),
+            ..Default::default()
+        }
+    };
+    let nickname = get_claim_values(claims, "name")
+        .into_iter()
+        .next()
+        .unwrap_or_else(|| username.clone());
+    let mut extend_info = HashMap::new();
+    extend_info.insert(OIDC_SUBJECT_KEY.to_owned(), subject);
+    let is_new = exist_user.is_none();
+    Ok(UserDto {
+        username: Arc::new(username),
+        nickname: Some(nickname),
+        gmt_create: if is_new { Some(now) } else { None },
+        gmt_modified: Some(now),
+        enable: if is_new { Some(true) } else { None },
+        roles: Some(roles),
+        extend_info: Some(extend_info),
+        source: Some(Arc::new(USER_SOURCE_OIDC.to_owned())),
+        namespace_privilege: Some(namespace_privilege),
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user::permission::{USER_ROLE_DEVELOPER, USER_ROLE_MANAGER};
+    use actix_web::{web, App, HttpResponse, HttpServer};
+    use ring::rand::SystemRandom;
+    use ring::signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_FIXED_SIGNING};
+
+    const CLIENT_ID: &str = "ratchjob";
+
+    fn encode_part(v: &Value) -> String {
+        general_purpose::URL_SAFE_NO_PAD.encode(serde_json::to_vec(v).unwrap())
+    }
+
+    fn new_key_pair() -> Arc<EcdsaKeyPair> {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, &rng).unwrap();
+        Arc::new(
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_FIXED_SIGNING, pkcs8.as_ref(), &rng)
+                .unwrap(),
+        )
+    }
+
+    fn to_jwk(key_pair: &EcdsaKeyPair, kid: &str) -> Value {
+        let point = key_pair.public_key().as_ref();
+        serde_json::json!({
+            "kty": "EC",
+            "kid": kid,
+            "crv": "P-256",
+            "x": general_purpose::URL_SAFE_NO_PAD.encode(&point[1..33]),
+            "y": general_purpose::URL_SAFE_NO_PAD.encode(&point[33..]),
+        })
+    }
+
+    fn sign_id_token(key_pair: &EcdsaKeyPair, kid: &str, claims: &Value) -> String {
+        let message = format!(
+            "{}.{}",
+            encode_part(&serde_json::json!({"alg":"ES256","kid":kid})),
+            encode_part(claims)
+        );
+        let sig = key_pair
+            .sign(&SystemRandom::new(), message.as_bytes())
+            .unwrap();
+        format!(
+            "{}.{}",
+            message,
+            general_purpose::URL_SAFE_NO_PAD.encode(sig.as_ref())
+        )
+    }
+
+    /// 本地模拟的OIDC服务，授权码直接当作nonce使用
+    async fn start_mock_provider() -> String {
+        let key_pair = new_key_pair();
+        let server = HttpServer::new(move || {
+            let jwk = to_jwk(&key_pair, "k1");
+            let key_pair = key_pair.clone();
+            App::new()
+                .route(
+                    "/.well-known/openid-configuration",
+                    web::get().to(|req: actix_web::HttpRequest| async move {
+                        let issuer = format!("http://{}", req.connection_info().host());
+                        HttpResponse::Ok().json(serde_json::json!({
+                            "issuer": &issuer,
+                            "authorization_endpoint": format!("{}/authorize", &issuer),
+                            "token_endpoint": format!("{}/token", &issuer),
+                            "userinfo_endpoint": format!("{}/userinfo", &issuer),
+                            "jwks_uri": format!("{}/jwks", &issuer),
+                        }))
+                    }),
+                )
+                .route(
+                    "/jwks",
+                    web::get().to(move || {
+                        let jwks = serde_json::json!({ "keys": [jwk.clone()] });
+                        async move { HttpResponse::Ok().json(jwks) }
+                    }),
+                )
+                .route(
+                    "/token",
+                    web::post().to(
+                        move |req: actix_web::HttpRequest,
+                              web::Form(form): web::Form<HashMap<String, String>>| {
+                            let key_pair = key_pair.clone();
+                            async move {
+                            let issuer = format!("http://{}", req.connection_info().host());
+                            let claims = serde_json::json!({
+                                "iss": issuer,
+                                "sub": "u-1001",
+                                "aud": [CLIENT_ID],
+                                "exp": chrono::Utc::now().timestamp() + 300,
+                                "nonce": form.get("code").cloned().unwrap_or_default(),
+                                "preferred_username": "alice",
+                                "name": "Alice",
+                            });
+                            let id_token = sign_id_token(&key_pair, "k1", &claims);
+                            HttpResponse::Ok().json(serde_json::json!({
+                                "access_token": "at-1",
+                                "id_token": id_token,
+                            }))
+                            }
+                        },
+                    ),
+                )
+                .route(
+                    "/userinfo",
+                    web::get().to(|| async {
+                        HttpResponse::Ok().json(serde_json::json!({
+                            "sub": "u-1001",
+                            "groups": ["job-admin", "job-dev"],
+                            "namespaces": "dev",
+                        }))
+                    }),
+                )
+        })
+        .workers(1)
+        .bind("127.0.0.1:0")
+        .unwrap();
+        let addr = server.addrs()[0];
+        actix_rt::spawn(server.run());
+        format!("http://{}", addr)
+    }
+
+    #[actix_rt::test]
+    async fn oidc_login_with_mock_provider() {
+        let issuer = start_mock_provider().await;
+        let config = OidcConfig {
+            issuer: issuer.clone(),
+            client_id: CLIENT_ID.to_owned(),
+            redirect_uri: "http://127.0.0.1/callback".to_owned(),
+            scopes: "openid".to_owned(),
+            username_claim: "preferred_username".to_owned(),
+            role_claim: "groups".to_owned(),
+            role_mapping: UserRoleHelper::parse_role_mapping("job-admin:manager,job-dev:1,x:bad"),
+            namespace_claim: "namespaces".to_owned(),
+            ..Default::default()
+        };
+        let client = OidcClient::new(Arc::new(config.clone()));
+        let metadata = client.discover().await.unwrap();
+        let url = client.build_authorize_url(&metadata, "s1", "n1").unwrap();
+        let insecure_client = OidcClient::new(Arc::new(OidcConfig {
+            issuer: "http://sso.example.com".to_owned(),
+            ..config.clone()
+        }));
+        assert!(insecure_client.discover().await.is_err());
+        assert!(url.starts_with(&format!("{}/authorize?response_type=code", &issuer)));
+        assert!(url.contains("state=s1") && url.contains("nonce=n1"));
+
+        let claims = client.exchange_code(&metadata, "n1", "n1").await.unwrap();
+        assert!(client.exchange_code(&metadata, "n2", "n1").await.is_err());
+        let user = build_user_dto(&config, &claims, None, 1).unwrap();
+        assert_eq!(user.username.as_str(), "alice");
+        assert_eq!(user.nickname.as_deref(), Some("Alice"));
+        assert_eq!(
+            user.roles,
+            Some(vec![USER_ROLE_MANAGER.clone(), USER_ROLE_DEVELOPER.clone()])
+        );
+        let namespace = user.namespace_privilege.clone().unwrap();
+        assert_eq!(namespace.whitelist_is_all, Some(false));
+        assert!(namespace
+            .whitelist
+            .unwrap()
+            .contains(&Arc::new("dev".to_owned())));
+
+        // 同名本地账号不允许被覆盖
+        let local_user = UserInfo {
+            username: Arc::new("alice".to_owned()),
+            ..Default::default()
+        };
+        assert!(build_user_dto(&config, &claims, Some(&local_user), 2).is_err());
+        let oidc_user: UserInfo = user.into();
+        let update = build_user_dto(&config, &claims, Some(&oidc_user), 2).unwrap();
+        assert!(update.gmt_create.is_none() && update.enable.is_none());
+        // 同名的单点登录用户sub不一致或缺失时拒绝
+        let mut other_claims = claims.clone();
+        other_claims.insert("sub".to_owned(), Value::String("u-2002".to_owned()));
+        assert!(build_user_dto(&config, &other_claims, Some(&oidc_user), 2).is_err());
+        let mut no_subject_user = oidc_user.clone();
+        no_subject_user.extend_info.clear();
+        assert!(build_user_dto(&config, &claims, Some(&no_subject_user), 2).is_err());
+    }
+
+    #[test]
+    fn verify_id_token_signature_with_jwks() {
+        let key_pair = new_key_pair();
+        let jwk: OidcJwk = serde_json::from_value(to_jwk(&key_pair, "k1")).unwrap();
+        let other: OidcJwk = serde_json::from_value(to_jwk(&new_key_pair(), "k2")).unwrap();
+        let claims = serde_json::json!({"sub": "u-1001"});
+        let id_token = sign_id_token(&key_pair, "k1", &claims);
+        assert!(verify_id_token_signature(&id_token, &[other.clone(), jwk.clone()]).is_ok());
+        // kid不匹配或公钥不同
+        assert!(verify_id_token_signature(&id_token, &[other.clone()]).is_err());
+        let mut wrong_kid = other;
+        wrong_kid.kid = Some("k1".to_owned());
+        assert!(verify_id_token_signature(&id_token, &[wrong_kid]).is_err());
+        // 篡改内容
+        let parts: Vec<&str> = id_token.split('.').collect();
+        let forged = format!(
+            "{}.{}.{}",
+            parts[0],
+            encode_part(&serde_json::json!({"sub": "admin"})),
+            parts[2]
+        );
+        assert!(verify_id_token_signature(&forged, &[jwk.clone()]).is_err());
+        // 不接受无签名的token
+        let unsigned = format!(
+            "{}.{}.",
+            encode_part(&serde_json::json!({"alg": "none"})),
+            encode_part(&claims)
+        );
+        assert!(verify_id_token_signature(&unsigned, &[jwk]).is_err());
+    }
+}
@@ -158,6 +158,13 @@ lazy_static::lazy_static! {
         R::Path("/ratchjob/api/console/v1/cluster/cluster_node_list",HTTP_METHOD_GET),
     ]);
 
+    static ref M_CLUSTER_MANAGE: ModuleResource = ModuleResource::new(vec![
+        //WebResource
+        R::WebResource("CLUSTER_BACKUP"),
+        //path
+        R::Path("/ratchjob/api/console/v1/cluster/backup",HTTP_METHOD_ALL),
+    ]);
+
     static ref M_APP_VISITOR: ModuleResource = ModuleResource::new(vec![
         //WebResource
         R::WebResource("/manage/app"),
@@ -216,7 +223,15 @@ lazy_static::lazy_static! {
         R::Path("/ratchjob/api/console/v1/job/list",HTTP_METHOD_GET),
         R::Path("/ratchjob/api/console/v1/job/info",HTTP_METHOD_GET),
         R::Path("/ratchjob/api/console/v1/job/task/list",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/task/executor-log",HTTP_METHOD_GET),
         R::Path("/ratchjob/api/console/v1/job/task/latest-history",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/version/list",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/version/diff",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/glue/list",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/trigger-param/preview",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/schedule/pause/list",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/trigger-time/next",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/schedule/preview",HTTP_METHOD_GET),
     ]);
 
     static ref M_JOB_MANAGER: ModuleResource = ModuleResource::new(vec![
@@ -230,11 +245,24 @@ lazy_static::lazy_static! {
         R::Path("/ratchjob/api/console/v1/job/list",HTTP_METHOD_GET),
         R::Path("/ratchjob/api/console/v1/job/info",HTTP_METHOD_GET),
         R::Path("/ratchjob/api/console/v1/job/task/list",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/task/executor-log",HTTP_METHOD_GET),
         R::Path("/ratchjob/api/console/v1/job/task/latest-history",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/version/list",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/version/diff",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/glue/list",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/trigger-param/preview",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/schedule/pause/list",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/job/trigger-time/next",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/schedule/preview",HTTP_METHOD_GET),
         R::Path("/ratchjob/api/console/v1/job/create",HTTP_METHOD_ALL),
         R::Path("/ratchjob/api/console/v1/job/update",HTTP_METHOD_ALL),
         R::Path("/ratchjob/api/console/v1/job/remove",HTTP_METHOD_ALL),
         R::Path("/ratchjob/api/console/v1/job/trigger",HTTP_METHOD_ALL),
+        R::Path("/ratchjob/api/console/v1/job/version/rollback",HTTP_METHOD_ALL),
+        R::Path("/ratchjob/api/console/v1/job/task/cancel",HTTP_METHOD_ALL),
+        R::Path("/ratchjob/api/console/v1/job/batch",HTTP_METHOD_ALL),
+        R::Path("/ratchjob/api/console/v1/schedule/pause",HTTP_METHOD_ALL),
+        R::Path("/ratchjob/api/console/v1/schedule/resume",HTTP_METHOD_ALL),
     ]);
 
 
@@ -252,6 +280,15 @@ lazy_static::lazy_static! {
 
     ]);
 
+    static ref M_AUDIT_MANAGE: ModuleResource = ModuleResource::new(vec![
+        //WebResource
+        R::WebResource("/manage/audit"),
+        //path
+        R::Path("/ratchjob/manage/audit",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/audit/list",HTTP_METHOD_GET),
+        R::Path("/ratchjob/api/console/v1/audit/export",HTTP_METHOD_GET),
+    ]);
+
     static ref M_METRICS_VISITOR: ModuleResource = ModuleResource::new(vec![
         //WebResource
         R::WebResource("/manage/appmonitor"),
@@ -289,7 +326,9 @@ lazy_static::lazy_static! {
         &M_CLUSTER_VISITOR,
         &M_METRICS_VISITOR,
         &M_USER_MANAGE,
+        &M_CLUSTER_MANAGE,
         &M_NAMESPACE_VISITOR,
+        &M_AUDIT_MANAGE,
     ]));
 
 }
@@ -415,6 +454,31 @@ impl UserRoleHelper {
         Arc::new(role_value.to_owned())
     }
 
+    /// 支持角色名(manager/developer/visitor)或角色值(0/1/2)
+    pub fn parse_role_name(name: &str) -> Option<Arc<String>> {
+        match name.trim().to_lowercase().as_str() {
+            "manager" | MANAGER_VALUE => Some(USER_ROLE_MANAGER.clone()),
+            "developer" | DEVELOPER_VALUE => Some(USER_ROLE_DEVELOPER.clone()),
+            "visitor" | VISITOR_VALUE => Some(USER_ROLE_VISITOR.clone()),
+            _ => None,
+        }
+    }
+
+    /// 外部用户组到角色的映射，格式: value:role,value:role
+    pub fn parse_role_mapping(value: &str) -> Vec<(String, Arc<String>)> {
+        let mut list = vec![];
+        for item in value.split(',') {
+            if let Some((key, role)) = item.rsplit_once(':') {
+                if let Some(role) = Self::parse_role_name(role) {
+                    list.push((key.trim().to_owned(), role));
+                } else {
+                    log::warn!("role mapping ignore invalid role:{}", item);
+                }
+            }
+        }
+        list
+    }
+
     pub fn match_url_by_base(path: &str, method: &str) -> bool {
         M_BASE.match_url(path, method)
     }